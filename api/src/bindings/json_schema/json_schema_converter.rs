@@ -0,0 +1,706 @@
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::iter;
+use syn::{
+    Attribute, Expr, ExprLit, Field, Fields, FieldsNamed, File, GenericArgument, Generics, Ident,
+    Item, ItemEnum, ItemImpl, ItemStruct, Lit, LitStr, Meta, MetaNameValue, PathArguments, Type,
+    Variant,
+};
+
+pub trait Hook {
+    /// Return the JSON Schema file name (without extension) that corresponds to the given Rust
+    /// crate name. Return `None` if the type should not be treated as foreign type.
+    ///
+    /// Called for each referenced Rust type that's addressed with an explicit module path. It will
+    /// pass the first segment of that path.
+    fn translate_crate_name(&self, rust_crate_ident: &str) -> Option<&'static str> {
+        let _ = rust_crate_ident;
+        None
+    }
+}
+
+/// Generates a JSON Schema document from Rust code containing serde-annotated types.
+///
+/// All deserializable, non-generic structs and enums end up in `$defs`. Generic types are inlined
+/// wherever they are used.
+pub struct JsonSchemaFile<'a, H> {
+    context: ConvContext<'a, H>,
+}
+
+struct ConvContext<'a, H> {
+    file: &'a File,
+    hook: &'a H,
+    foreign_files: &'a [File],
+}
+
+impl<'a, H> Clone for ConvContext<'a, H> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, H> Copy for ConvContext<'a, H> {}
+
+/// Maps generic type parameters to the concrete types with which they are used.
+type Substitutions<'a> = HashMap<&'a Ident, &'a Type>;
+
+impl<'a, H: Hook> JsonSchemaFile<'a, H> {
+    pub fn new(file: &'a File, hook: &'a H, foreign_files: &'a [File]) -> Self {
+        Self {
+            context: ConvContext {
+                file,
+                hook,
+                foreign_files,
+            },
+        }
+    }
+
+    /// Builds the complete schema document, accepting any of the given root types.
+    pub fn to_schema(&self, title: &str, root_types: &[&str]) -> Value {
+        let defs: Map<String, Value> = self
+            .context
+            .file
+            .items
+            .iter()
+            .filter(|item| self.context.is_deserializable(item))
+            .filter_map(|item| {
+                let (ident, generics) = match item {
+                    Item::Struct(s) => (&s.ident, &s.generics),
+                    Item::Enum(e) => (&e.ident, &e.generics),
+                    _ => return None,
+                };
+                if !generics.params.is_empty() {
+                    return None;
+                }
+                let schema = self.context.item_schema(item, &Substitutions::new());
+                Some((ident.to_string(), schema))
+            })
+            .collect();
+        let roots: Vec<_> = root_types
+            .iter()
+            .map(|t| json!({ "$ref": format!("#/$defs/{t}") }))
+            .collect();
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": title,
+            "anyOf": roots,
+            "$defs": defs,
+        })
+    }
+}
+
+impl<'a, H: Hook> ConvContext<'a, H> {
+    fn find_item_by_ident(&self, needle: &Ident) -> Option<&'a Item> {
+        iter::once(self.file)
+            .chain(self.foreign_files.iter())
+            .flat_map(|file| file.items.iter())
+            .find(|it| match it {
+                Item::Struct(ItemStruct { ident, .. }) | Item::Enum(ItemEnum { ident, .. }) => {
+                    ident == needle
+                }
+                _ => false,
+            })
+    }
+
+    fn is_local_item(&self, needle: &Ident) -> bool {
+        self.file.items.iter().any(|it| match it {
+            Item::Struct(ItemStruct { ident, .. }) | Item::Enum(ItemEnum { ident, .. }) => {
+                ident == needle
+            }
+            _ => false,
+        })
+    }
+
+    /// Returns `true` if the item derives `Deserialize` or implements it manually.
+    fn is_deserializable(&self, item: &Item) -> bool {
+        let (ident, attrs) = match item {
+            Item::Struct(s) => (&s.ident, &s.attrs),
+            Item::Enum(e) => (&e.ident, &e.attrs),
+            _ => return false,
+        };
+        let derives_deserialize = attributes_where_ident(attrs, "derive").any(|a| {
+            let mut found = false;
+            let _ = a.parse_nested_meta(|meta| {
+                if meta
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|s| s.ident == "Deserialize")
+                {
+                    found = true;
+                }
+                Ok(())
+            });
+            found
+        });
+        derives_deserialize || self.implements_deserialize(ident)
+    }
+
+    fn implements_deserialize(&self, ident: &Ident) -> bool {
+        self.file.items.iter().any(|it| {
+            let Item::Impl(ItemImpl {
+                trait_: Some((_, trait_path, _)),
+                self_ty,
+                ..
+            }) = it
+            else {
+                return false;
+            };
+            trait_path
+                .segments
+                .last()
+                .is_some_and(|s| s.ident == "Deserialize")
+                && type_ident(self_ty) == Some(ident)
+        })
+    }
+
+    fn item_schema(&self, item: &'a Item, subst: &Substitutions<'a>) -> Value {
+        let (attrs, schema) = match item {
+            Item::Struct(s) => (&s.attrs, self.struct_schema(s, subst)),
+            Item::Enum(e) => (&e.attrs, self.enum_schema(e, subst)),
+            _ => return json!({}),
+        };
+        with_description(schema, attrs)
+    }
+
+    fn struct_schema(&self, item: &'a ItemStruct, subst: &Substitutions<'a>) -> Value {
+        let container_args = SerdeArgs::from_attributes(&item.attrs);
+        if let Some(proxy) = &container_args.from {
+            // #[serde(try_from = "...")] or #[serde(from = "...")]
+            let proxy_ident: Ident = syn::parse_str(proxy).expect("invalid proxy type");
+            if !self.is_local_item(&proxy_ident) {
+                return json!({});
+            }
+            return json!({ "$ref": format!("#/$defs/{proxy_ident}") });
+        }
+        match &item.fields {
+            Fields::Named(fields) => {
+                let mut object = ObjectSchema::default();
+                self.add_named_fields(&mut object, fields, subst, container_args.default);
+                object.into_value()
+            }
+            Fields::Unnamed(fields) => {
+                let field = fields
+                    .unnamed
+                    .first()
+                    .expect("empty tuple structs not supported");
+                let item_schema = self.type_schema(&field.ty, subst);
+                if fields.unnamed.len() == 1 {
+                    item_schema
+                } else {
+                    let len = fields.unnamed.len();
+                    json!({
+                        "type": "array",
+                        "items": item_schema,
+                        "minItems": len,
+                        "maxItems": len,
+                    })
+                }
+            }
+            Fields::Unit => json!({ "type": "null" }),
+        }
+    }
+
+    fn enum_schema(&self, item: &'a ItemEnum, subst: &Substitutions<'a>) -> Value {
+        let args = SerdeArgs::from_attributes(&item.attrs);
+        let variants = item
+            .variants
+            .iter()
+            .filter(|v| !SerdeArgs::from_attributes(&v.attrs).skip);
+        match (&args.tag, args.untagged) {
+            (Some(tag), _) => {
+                // Internally tagged
+                let schemas = variants
+                    .flat_map(|v| self.tagged_variant_schemas(v, tag, subst))
+                    .collect();
+                json!({ "oneOf": Value::Array(schemas) })
+            }
+            (None, true) => {
+                // Untagged
+                let schemas = variants
+                    .map(|v| with_description(self.untagged_variant_schema(v, subst), &v.attrs))
+                    .collect();
+                json!({ "anyOf": Value::Array(schemas) })
+            }
+            (None, false) => {
+                let variants: Vec<_> = variants.collect();
+                if variants.iter().all(|v| matches!(v.fields, Fields::Unit)) {
+                    // Primitive enum, serialized as string
+                    let names: Vec<_> = variants.iter().flat_map(|v| variant_names(v)).collect();
+                    json!({ "type": "string", "enum": names })
+                } else {
+                    // Externally tagged
+                    let schemas = variants
+                        .iter()
+                        .map(|v| {
+                            let schema = self.externally_tagged_variant_schema(v, subst);
+                            with_description(schema, &v.attrs)
+                        })
+                        .collect();
+                    json!({ "oneOf": Value::Array(schemas) })
+                }
+            }
+        }
+    }
+
+    /// Usually returns just one schema, but more if the variant wraps an untagged enum.
+    fn tagged_variant_schemas(
+        &self,
+        variant: &'a Variant,
+        tag: &str,
+        subst: &Substitutions<'a>,
+    ) -> Vec<Value> {
+        let new_object = || {
+            let mut object = ObjectSchema::default();
+            object.add_property(tag.to_string(), tag_schema(variant), true);
+            object
+        };
+        let objects = match &variant.fields {
+            Fields::Unit => vec![new_object()],
+            Fields::Named(fields) => {
+                let mut object = new_object();
+                self.add_named_fields(&mut object, fields, subst, false);
+                vec![object]
+            }
+            Fields::Unnamed(fields) => {
+                assert_eq!(
+                    fields.unnamed.len(),
+                    1,
+                    "enum tuple variants with more than one value not supported: {}",
+                    variant.ident
+                );
+                // Serde inlines the fields of the referenced type
+                let field = fields.unnamed.first().unwrap();
+                let (ty, subst) = self.resolve_wrapped_type(&field.ty, subst);
+                let referenced_item = type_ident(ty).and_then(|i| self.find_item_by_ident(i));
+                match referenced_item {
+                    Some(Item::Struct(
+                        s @ ItemStruct {
+                            fields: Fields::Named(fields_named),
+                            ..
+                        },
+                    )) => {
+                        let subst = generic_substitutions(&s.generics, ty, &subst);
+                        let container_default = SerdeArgs::from_attributes(&s.attrs).default;
+                        let mut object = new_object();
+                        self.add_named_fields(&mut object, fields_named, &subst, container_default);
+                        vec![object]
+                    }
+                    Some(Item::Enum(ItemEnum { variants, .. })) => {
+                        // The referenced item must be an untagged enum, e.g. BookmarkRef
+                        variants
+                            .iter()
+                            .filter_map(|v| {
+                                let Fields::Named(fields_named) = &v.fields else {
+                                    return None;
+                                };
+                                let mut object = new_object();
+                                self.add_named_fields(&mut object, fields_named, &subst, false);
+                                Some(object)
+                            })
+                            .collect()
+                    }
+                    _ => vec![new_object()],
+                }
+            }
+        };
+        objects
+            .into_iter()
+            .map(|o| with_description(o.into_value(), &variant.attrs))
+            .collect()
+    }
+
+    fn untagged_variant_schema(&self, variant: &'a Variant, subst: &Substitutions<'a>) -> Value {
+        match &variant.fields {
+            Fields::Unit => json!({ "type": "null" }),
+            Fields::Named(fields) => {
+                let mut object = ObjectSchema::default();
+                self.add_named_fields(&mut object, fields, subst, false);
+                object.into_value()
+            }
+            Fields::Unnamed(fields) => {
+                let field = fields.unnamed.first().expect("empty tuple variant");
+                self.type_schema(&field.ty, subst)
+            }
+        }
+    }
+
+    fn externally_tagged_variant_schema(
+        &self,
+        variant: &'a Variant,
+        subst: &Substitutions<'a>,
+    ) -> Value {
+        let content = match &variant.fields {
+            Fields::Unit => return tag_schema(variant),
+            Fields::Named(_) | Fields::Unnamed(_) => self.untagged_variant_schema(variant, subst),
+        };
+        let mut object = ObjectSchema::default();
+        for name in variant_names(variant) {
+            object.add_property(name, content.clone(), false);
+        }
+        let mut value = object.into_value();
+        value["minProperties"] = json!(1);
+        value["maxProperties"] = json!(1);
+        value
+    }
+
+    fn add_named_fields(
+        &self,
+        object: &mut ObjectSchema,
+        fields: &'a FieldsNamed,
+        subst: &Substitutions<'a>,
+        container_default: bool,
+    ) {
+        for field in &fields.named {
+            self.add_named_field(object, field, subst, container_default);
+        }
+    }
+
+    fn add_named_field(
+        &self,
+        object: &mut ObjectSchema,
+        field: &'a Field,
+        subst: &Substitutions<'a>,
+        container_default: bool,
+    ) {
+        let args = SerdeArgs::from_attributes(&field.attrs);
+        if args.skip {
+            return;
+        }
+        if args.flatten {
+            self.add_flattened_field(object, field, subst);
+            return;
+        }
+        let ident = field
+            .ident
+            .as_ref()
+            .expect("no tuple struct field expected");
+        let name = args.rename.clone().unwrap_or_else(|| ident.to_string());
+        let (ty, is_optional) = unwrap_option(&field.ty);
+        let required = !is_optional && !args.default && !container_default;
+        let schema = with_description(self.type_schema(ty, subst), &field.attrs);
+        for alias in &args.aliases {
+            let description = format!("Deprecated alias for `{name}`.");
+            let alias_schema = json!({ "description": description, "allOf": [schema.clone()] });
+            object.add_property(alias.clone(), alias_schema, false);
+        }
+        object.add_property(name, schema, required);
+    }
+
+    fn add_flattened_field(
+        &self,
+        object: &mut ObjectSchema,
+        field: &'a Field,
+        subst: &Substitutions<'a>,
+    ) {
+        let (ty, _) = unwrap_option(&field.ty);
+        let (ty, subst) = self.resolve_wrapped_type(ty, subst);
+        if let Some(value_type) = map_value_type(ty) {
+            // Flattened map, e.g. for collecting unknown properties
+            object.additional_properties = Some(self.type_schema(value_type, &subst));
+            return;
+        }
+        let referenced_item = type_ident(ty).and_then(|i| self.find_item_by_ident(i));
+        match referenced_item {
+            Some(Item::Struct(
+                s @ ItemStruct {
+                    fields: Fields::Named(fields_named),
+                    ..
+                },
+            )) => {
+                let subst = generic_substitutions(&s.generics, ty, &subst);
+                let container_default = SerdeArgs::from_attributes(&s.attrs).default;
+                self.add_named_fields(object, fields_named, &subst, container_default);
+            }
+            _ => panic!(
+                "flattened struct field type is not a struct: {:?}",
+                field.ident
+            ),
+        }
+    }
+
+    /// Resolves generic type parameters and removes `Box` wrappers.
+    fn resolve_wrapped_type(
+        &self,
+        ty: &'a Type,
+        subst: &Substitutions<'a>,
+    ) -> (&'a Type, Substitutions<'a>) {
+        if let Some(ident) = type_ident(ty) {
+            if let Some(substituted) = subst.get(ident) {
+                return self.resolve_wrapped_type(substituted, subst);
+            }
+            if ident == "Box" {
+                let inner = get_type_arg(ty, 0);
+                return self.resolve_wrapped_type(inner, subst);
+            }
+        }
+        (ty, subst.clone())
+    }
+
+    fn type_schema(&self, ty: &'a Type, subst: &Substitutions<'a>) -> Value {
+        let Type::Path(p) = ty else {
+            return json!({});
+        };
+        let el = p.path.segments.last().unwrap();
+        let ident = &el.ident;
+        if let Some(substituted) = subst.get(ident) {
+            return self.type_schema(substituted, subst);
+        }
+        match ident.to_string().as_str() {
+            "Option" | "Box" => self.type_schema(get_type_arg(ty, 0), subst),
+            "Vec" => {
+                json!({ "type": "array", "items": self.type_schema(get_type_arg(ty, 0), subst) })
+            }
+            "EnumSet" | "HashSet" | "BTreeSet" => json!({
+                "type": "array",
+                "items": self.type_schema(get_type_arg(ty, 0), subst),
+                "uniqueItems": true,
+            }),
+            "HashMap" | "BTreeMap" | "Map" => json!({
+                "type": "object",
+                "additionalProperties": self.type_schema(get_type_arg(ty, 1), subst),
+            }),
+            "u8" | "u16" | "u32" | "u64" | "usize" => json!({ "type": "integer", "minimum": 0 }),
+            "i8" | "i16" | "i32" | "i64" | "isize" => json!({ "type": "integer" }),
+            "NonZeroU32" => json!({ "type": "integer", "minimum": 1 }),
+            "f64" | "f32" | "Db" | "Bpm" | "Semitones" | "DurationInSeconds"
+            | "DurationInBeats" => {
+                json!({ "type": "number" })
+            }
+            "PathBuf" | "Utf8PathBuf" | "NaiveDateTime" | "Version" | "String" => {
+                json!({ "type": "string" })
+            }
+            "bool" => json!({ "type": "boolean" }),
+            // serde_json::Value
+            "Value" => json!({}),
+            _ => {
+                if p.path.segments.len() > 1 {
+                    let first_seg = p.path.segments.first().unwrap();
+                    if let Some(file_name) =
+                        self.hook.translate_crate_name(&first_seg.ident.to_string())
+                    {
+                        return json!({ "$ref": format!("{file_name}.schema.json#/$defs/{ident}") });
+                    }
+                }
+                if !self.is_local_item(ident) {
+                    // Type not known, allow anything
+                    return json!({});
+                }
+                let item = self.find_item_by_ident(ident).unwrap();
+                let generics = match item {
+                    Item::Struct(s) => &s.generics,
+                    Item::Enum(e) => &e.generics,
+                    _ => unreachable!(),
+                };
+                if generics.params.is_empty() {
+                    json!({ "$ref": format!("#/$defs/{ident}") })
+                } else {
+                    // Generic types are inlined
+                    let subst = generic_substitutions(generics, ty, subst);
+                    self.item_schema(item, &subst)
+                }
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct ObjectSchema {
+    properties: Map<String, Value>,
+    required: Vec<String>,
+    /// `None` means no additional properties allowed.
+    additional_properties: Option<Value>,
+}
+
+impl ObjectSchema {
+    fn add_property(&mut self, name: String, schema: Value, required: bool) {
+        if required {
+            self.required.push(name.clone());
+        }
+        self.properties.insert(name, schema);
+    }
+
+    fn into_value(self) -> Value {
+        let mut value = json!({
+            "type": "object",
+            "properties": self.properties,
+            "additionalProperties": self.additional_properties.unwrap_or(Value::Bool(false)),
+        });
+        if !self.required.is_empty() {
+            value["required"] = json!(self.required);
+        }
+        value
+    }
+}
+
+/// The serde attributes which are relevant for the schema.
+#[derive(Default)]
+struct SerdeArgs {
+    tag: Option<String>,
+    untagged: bool,
+    default: bool,
+    flatten: bool,
+    skip: bool,
+    rename: Option<String>,
+    aliases: Vec<String>,
+    /// Type given in `from` or `try_from`.
+    from: Option<String>,
+}
+
+impl SerdeArgs {
+    fn from_attributes(attributes: &[Attribute]) -> Self {
+        let mut args = Self::default();
+        for attr in attributes_where_ident(attributes, "serde") {
+            attr.parse_nested_meta(|meta| {
+                let key = meta
+                    .path
+                    .get_ident()
+                    .map(|i| i.to_string())
+                    .unwrap_or_default();
+                let string_value = || -> syn::Result<String> {
+                    let s: LitStr = meta.value()?.parse()?;
+                    Ok(s.value())
+                };
+                match key.as_str() {
+                    "tag" => args.tag = Some(string_value()?),
+                    "rename" => args.rename = Some(string_value()?),
+                    "alias" => args.aliases.push(string_value()?),
+                    "from" | "try_from" => args.from = Some(string_value()?),
+                    "untagged" => args.untagged = true,
+                    "flatten" => args.flatten = true,
+                    "skip" | "skip_deserializing" => args.skip = true,
+                    "default" => {
+                        args.default = true;
+                        if meta.input.peek(syn::Token![=]) {
+                            let _ = string_value()?;
+                        }
+                    }
+                    _ => {
+                        // Not relevant for the schema, e.g. skip_serializing_if
+                        if meta.input.peek(syn::Token![=]) {
+                            let _: Expr = meta.value()?.parse()?;
+                        } else if meta.input.peek(syn::token::Paren) {
+                            meta.parse_nested_meta(|_| Ok(()))?;
+                        }
+                    }
+                }
+                Ok(())
+            })
+            .unwrap_or_else(|e| panic!("couldn't parse serde attribute: {e}"));
+        }
+        args
+    }
+}
+
+/// Returns the name of the variant and all of its aliases.
+fn variant_names(variant: &Variant) -> Vec<String> {
+    let args = SerdeArgs::from_attributes(&variant.attrs);
+    let name = args.rename.unwrap_or_else(|| variant.ident.to_string());
+    iter::once(name).chain(args.aliases).collect()
+}
+
+fn tag_schema(variant: &Variant) -> Value {
+    let mut names = variant_names(variant);
+    if names.len() == 1 {
+        json!({ "const": names.remove(0) })
+    } else {
+        json!({ "enum": names })
+    }
+}
+
+fn with_description(mut schema: Value, attributes: &[Attribute]) -> Value {
+    let doc = doc_text(attributes);
+    if doc.is_empty() {
+        return schema;
+    }
+    if let Some(object) = schema.as_object_mut() {
+        if object.contains_key("$ref") {
+            // Siblings of $ref are allowed in draft 2020-12, but some editors ignore them
+            return json!({ "description": doc, "allOf": [schema] });
+        }
+        object.insert("description".to_string(), Value::String(doc));
+    }
+    schema
+}
+
+fn doc_text(attributes: &[Attribute]) -> String {
+    let lines: Vec<_> = attributes_where_ident(attributes, "doc")
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(MetaNameValue {
+                value:
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(s), ..
+                    }),
+                ..
+            }) => Some(s.value()),
+            _ => None,
+        })
+        .map(|line| line.strip_prefix(' ').map(str::to_string).unwrap_or(line))
+        .collect();
+    lines.join("\n").trim().to_string()
+}
+
+/// Returns the inner type and `true` if the given type is an `Option`.
+fn unwrap_option(ty: &Type) -> (&Type, bool) {
+    if type_ident(ty).is_some_and(|i| i == "Option") {
+        (get_type_arg(ty, 0), true)
+    } else {
+        (ty, false)
+    }
+}
+
+fn map_value_type(ty: &Type) -> Option<&Type> {
+    let ident = type_ident(ty)?;
+    if ident == "HashMap" || ident == "BTreeMap" || ident == "Map" {
+        Some(get_type_arg(ty, 1))
+    } else {
+        None
+    }
+}
+
+fn generic_substitutions<'a>(
+    generics: &'a Generics,
+    ty: &'a Type,
+    parent: &Substitutions<'a>,
+) -> Substitutions<'a> {
+    let mut subst = parent.clone();
+    for (i, param) in generics.type_params().enumerate() {
+        subst.insert(&param.ident, get_type_arg(ty, i));
+    }
+    subst
+}
+
+fn type_ident(ty: &Type) -> Option<&Ident> {
+    match ty {
+        Type::Path(p) => Some(&p.path.segments.last()?.ident),
+        _ => None,
+    }
+}
+
+fn get_type_arg(ty: &Type, n: usize) -> &Type {
+    let Type::Path(p) = ty else {
+        panic!("path type expected");
+    };
+    let PathArguments::AngleBracketed(args) = &p.path.segments.last().unwrap().arguments else {
+        panic!("angle-bracketed type argument expected");
+    };
+    let arg = args
+        .args
+        .iter()
+        .filter(|a| matches!(a, GenericArgument::Type(_)))
+        .nth(n)
+        .expect("type argument expected");
+    let GenericArgument::Type(ty) = arg else {
+        unreachable!()
+    };
+    ty
+}
+
+fn attributes_where_ident<'a>(
+    attributes: &'a [Attribute],
+    ident: &'a str,
+) -> impl Iterator<Item = &'a Attribute> + 'a {
+    attributes
+        .iter()
+        .filter(move |a| a.path().segments.last().is_some_and(|s| s.ident == ident))
+}
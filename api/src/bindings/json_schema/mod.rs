@@ -0,0 +1,90 @@
+use crate::bindings::json_schema::json_schema_converter::Hook;
+use std::fs;
+use std::path::PathBuf;
+
+mod json_schema_converter;
+
+/// Generates JSON Schema files which editors can use for completion and validation of presets
+/// written in JSON.
+#[test]
+pub fn export_json_schema() {
+    struct RealearnApiExportHook;
+    impl Hook for RealearnApiExportHook {
+        fn translate_crate_name(&self, rust_crate_ident: &str) -> Option<&'static str> {
+            match rust_crate_ident {
+                "playtime_api" => Some("playtime"),
+                _ => None,
+            }
+        }
+    }
+    export_json_schema_internal(
+        "realearn",
+        "ReaLearn preset or API object",
+        [
+            "src/persistence/mod.rs",
+            "src/persistence/compartment.rs",
+            "src/persistence/glue.rs",
+            "src/persistence/group.rs",
+            "src/persistence/mapping.rs",
+            "src/persistence/parameter.rs",
+            "src/persistence/source.rs",
+            "src/persistence/target.rs",
+        ],
+        &["Compartment", "ApiObject"],
+        &RealearnApiExportHook,
+        ["../playtime-api/src/persistence/mod.rs"],
+    );
+    struct PlaytimeApiExportHook;
+    impl Hook for PlaytimeApiExportHook {}
+    export_json_schema_internal(
+        "playtime",
+        "Playtime matrix",
+        ["../playtime-api/src/persistence/mod.rs"],
+        &["Matrix"],
+        &PlaytimeApiExportHook,
+        [],
+    );
+}
+
+fn export_json_schema_internal<'a>(
+    name: &str,
+    title: &str,
+    src_files: impl IntoIterator<Item = &'a str>,
+    root_types: &[&str],
+    hook: &impl Hook,
+    foreign_files: impl IntoIterator<Item = &'a str>,
+) {
+    let rust_codes: Vec<_> = src_files
+        .into_iter()
+        .map(|src_file| {
+            let code = fs::read_to_string(src_file).unwrap();
+            let filtered_code: Vec<_> = code
+                .lines()
+                .filter(|line| !line.starts_with("//!"))
+                .collect();
+            filtered_code.join("\n")
+        })
+        .collect();
+    let merged_rust_code = rust_codes.join("\n\n");
+    let rust_file = parse_rust_code(&merged_rust_code);
+    let foreign_rust_files: Vec<_> = foreign_files
+        .into_iter()
+        .map(|path| {
+            let code = fs::read_to_string(path).unwrap();
+            parse_rust_code(&code)
+        })
+        .collect();
+    let schema_file =
+        json_schema_converter::JsonSchemaFile::new(&rust_file, hook, &foreign_rust_files);
+    let mut schema = schema_file.to_schema(title, root_types);
+    schema["$comment"] =
+        "Attention: This file is generated from Rust code! Don't modify it directly!".into();
+    let dest_dir = PathBuf::from("../resources/api/json-schema");
+    fs::create_dir_all(&dest_dir).unwrap();
+    let json = serde_json::to_string_pretty(&schema).unwrap();
+    fs::write(dest_dir.join(format!("{name}.schema.json")), json + "\n").unwrap();
+}
+
+fn parse_rust_code(code: &str) -> syn::File {
+    syn::parse_file(code).expect("unable to parse Rust file")
+}
@@ -1 +1,2 @@
+mod json_schema;
 mod luau;
@@ -2,6 +2,11 @@ pub mod persistence;
 
 pub mod runtime;
 
+pub mod preset_data;
+
+#[cfg(feature = "lua")]
+pub mod lua;
+
 /// Bindings are generated as result of unit tests.
 #[cfg(test)]
 mod bindings;
@@ -1,4 +1,4 @@
-use crate::domain::{compile_and_execute, create_fresh_environment};
+use crate::lua::{compile_and_execute, create_fresh_environment};
 use anyhow::{bail, Context};
 use auto_impl::auto_impl;
 use camino::{Utf8Path, Utf8PathBuf};
@@ -274,4 +274,50 @@ impl LuaModuleFinder for FsDirLuaModuleFinder {
     }
 }
 
+/// Returns the root directory for resolving Lua modules required by the user preset at the given
+/// path (relative to the preset directory).
+///
+/// The subdirectory of the preset directory in which the preset resides serves as namespace and
+/// module root. Presets residing in the root of the preset directory can't use `require`. This is
+/// discouraged nowadays because it makes sharing presets more difficult (conflicting file names
+/// etc.).
+pub fn user_preset_lua_module_root(
+    preset_dir: &Utf8Path,
+    relative_preset_path: &Utf8Path,
+) -> Result<Utf8PathBuf, &'static str> {
+    let mut components = relative_preset_path.components();
+    let first_component = components.next().ok_or("preset path is empty")?;
+    if components.next().is_none() {
+        return Err(
+            r#"Using "require" in Lua presets is only supported if they are located in a subfolder of the main or controller preset folder."#,
+        );
+    }
+    Ok(preset_dir.join(first_component))
+}
+
 const LUA_PRESET_RUNTIME_NAME: &str = "preset_runtime";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn use_namespace_folder_as_lua_module_root() {
+        // Given
+        let preset_dir = Utf8Path::new("/presets/main");
+        // When
+        let root = user_preset_lua_module_root(preset_dir, Utf8Path::new("akai/sub/apc-mini"));
+        // Then
+        assert_eq!(root, Ok(Utf8PathBuf::from("/presets/main/akai")));
+    }
+
+    #[test]
+    fn disallow_require_for_presets_in_root_folder() {
+        // Given
+        let preset_dir = Utf8Path::new("/presets/main");
+        // When
+        let root = user_preset_lua_module_root(preset_dir, Utf8Path::new("apc-mini"));
+        // Then
+        assert!(root.is_err());
+    }
+}
@@ -0,0 +1,7 @@
+//! Sandboxed Lua execution shared by the plug-in and REAPER-independent tools such as the preset linter.
+
+mod lua_support;
+pub use lua_support::*;
+
+mod lua_module_container;
+pub use lua_module_container::*;
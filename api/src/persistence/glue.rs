@@ -53,6 +53,8 @@ pub struct Glue {
     pub feedback: Option<Feedback>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub feedback_value_table: Option<FeedbackValueTable>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feedback_color_table: Option<FeedbackColorTable>,
     //endregion
 }
 
@@ -73,6 +75,28 @@ pub struct ContinuousFeedbackValueTableContent {
     pub value: HashMap<String, f64>,
 }
 
+/// Maps the normalized feedback value to a color, overriding the feedback color.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum FeedbackColorTable {
+    /// Blends between the colors of the two stops surrounding the feedback value.
+    Gradient(FeedbackColorTableContent),
+    /// Uses the color of the highest stop reached by the feedback value.
+    Threshold(FeedbackColorTableContent),
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct FeedbackColorTableContent {
+    pub stops: Vec<FeedbackColorStop>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct FeedbackColorStop {
+    /// Normalized feedback value between 0.0 and 1.0.
+    pub value: f64,
+    pub color: RgbColor,
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Default, Serialize, Deserialize)]
 pub enum AbsoluteMode {
     #[default]
@@ -143,7 +167,7 @@ pub enum VirtualColor {
     Prop(PropColor),
 }
 
-#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct RgbColor(pub u8, pub u8, pub u8);
 
 #[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
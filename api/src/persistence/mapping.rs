@@ -43,10 +43,13 @@ pub struct Mapping {
     pub unprocessed: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
-#[derive(Eq, PartialEq, Default, Serialize, Deserialize)]
+#[derive(PartialEq, Default, Serialize, Deserialize)]
 pub struct LifecycleHook {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub send_midi_feedback: Option<Vec<SendMidiFeedbackAction>>,
+    /// Actions which are executed in the main thread, in the given order.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actions: Option<Vec<LifecycleAction>>,
 }
 
 #[derive(Eq, PartialEq, Serialize, Deserialize)]
@@ -55,6 +58,40 @@ pub enum SendMidiFeedbackAction {
     Raw { message: RawMidiMessage },
 }
 
+#[derive(PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum LifecycleAction {
+    /// Sends an OSC message.
+    SendOsc {
+        address: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        arguments: Option<Vec<OscArgValue>>,
+        /// Defaults to the feedback output.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        destination: Option<OscDestination>,
+    },
+    /// Sets a compartment parameter to the given normalized value (between 0.0 and 1.0).
+    SetParameter {
+        parameter: CompartmentParameterDescriptor,
+        value: f64,
+    },
+    /// Invokes a REAPER action of the main section.
+    InvokeAction { command: ReaperCommand },
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum OscArgValue {
+    Float { value: f32 },
+    Double { value: f64 },
+    Int { value: i32 },
+    Long { value: i64 },
+    Bool { value: bool },
+    String { value: String },
+    Nil,
+    Inf,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum RawMidiMessage {
@@ -76,6 +113,9 @@ pub enum ActivationCondition {
     Eel(EelActivationCondition),
     Expression(ExpressionActivationCondition),
     TargetValue(TargetValueActivationCondition),
+    And(CompositeActivationCondition),
+    Or(CompositeActivationCondition),
+    Not(CompositeActivationCondition),
 }
 
 #[derive(Eq, PartialEq, Default, Serialize, Deserialize)]
@@ -112,6 +152,16 @@ pub struct TargetValueActivationCondition {
     pub condition: String,
 }
 
+/// Combines multiple activation conditions.
+///
+/// - `And`: All conditions must be met.
+/// - `Or`: At least one condition must be met.
+/// - `Not`: None of the conditions must be met (so with one condition, it's a negation).
+#[derive(Eq, PartialEq, Serialize, Deserialize)]
+pub struct CompositeActivationCondition {
+    pub conditions: Vec<ActivationCondition>,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ParamRef {
@@ -16,6 +16,7 @@ pub enum Source {
     RealearnInstanceStart,
     RealearnCompartmentLoaded,
     Timer(TimerSource),
+    MusicalTimer(MusicalTimerSource),
     RealearnParameter(RealearnParameterSource),
     Speech,
     // MIDI
@@ -44,6 +45,10 @@ pub enum Source {
     Key(KeySource),
     // StreamDeck
     StreamDeck(StreamDeckSource),
+    // Evdev
+    Evdev(EvdevSource),
+    // Chord
+    Chord(ChordSource),
     // Virtual
     Virtual(VirtualSource),
 }
@@ -203,6 +208,10 @@ pub enum MidiScriptKind {
     #[serde(alias = "lua")]
     #[display(fmt = "Lua")]
     Lua,
+    /// Precompiled WebAssembly module, Base64-encoded.
+    #[serde(alias = "wasm")]
+    #[display(fmt = "WASM")]
+    Wasm,
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Serialize, Deserialize)]
@@ -305,6 +314,46 @@ pub struct TimerSource {
     pub duration: u64,
 }
 
+/// Fires on musical divisions, following the project's transport position and tempo map.
+#[derive(Default, PartialEq, Serialize, Deserialize)]
+pub struct MusicalTimerSource {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<MusicalTimerUnit>,
+    /// Number of units between two fires, e.g. 0.25 beats for 1/16 notes in a 4/4 measure.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval: Option<f64>,
+    /// Shifts the fire positions by the given number of units.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<f64>,
+    /// If `true`, the timer doesn't fire while the transport is stopped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub only_while_playing: Option<bool>,
+}
+
+/// Musical unit in which the interval of a musical timer is expressed
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Default,
+    Serialize,
+    Deserialize,
+    EnumIter,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+)]
+#[repr(usize)]
+pub enum MusicalTimerUnit {
+    #[default]
+    #[display(fmt = "Beats")]
+    Beat,
+    #[display(fmt = "Bars")]
+    Bar,
+}
+
 #[derive(Default, Eq, PartialEq, Serialize, Deserialize)]
 pub struct KeySource {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -318,6 +367,57 @@ pub struct StreamDeckSource {
     pub button_design: StreamDeckButtonDesign,
 }
 
+/// Linux input device event (gamepads, foot pedals, knob boxes, ...).
+#[derive(Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct EvdevSource {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_kind: Option<EvdevEventKind>,
+    /// Key, absolute axis or relative axis code as defined in `linux/input-event-codes.h`.
+    pub code: u16,
+}
+
+/// Kind of evdev event that an evdev source reacts to
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    Serialize,
+    Deserialize,
+    EnumIter,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+)]
+#[repr(usize)]
+pub enum EvdevEventKind {
+    /// Keys and buttons.
+    #[default]
+    #[display(fmt = "Key/button")]
+    Key,
+    /// Sticks, triggers, knobs with a fixed range.
+    #[display(fmt = "Absolute axis")]
+    AbsoluteAxis,
+    /// Wheels and endless knobs.
+    #[display(fmt = "Relative axis")]
+    RelativeAxis,
+}
+
+/// Fires when all member sources are pressed together within a short time window.
+///
+/// Member sources can be MIDI, OSC, keyboard, Stream Deck or evdev sources. When the chord fires,
+/// the individual presses of the members are suppressed.
+#[derive(Default, PartialEq, Serialize, Deserialize)]
+pub struct ChordSource {
+    pub members: Vec<Source>,
+    /// Maximum time between the first and the last member press in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub window_millis: Option<u64>,
+}
+
 #[derive(Clone, Eq, PartialEq, Hash, Debug, Default, Serialize, Deserialize)]
 pub struct StreamDeckButtonDesign {
     #[serde(default)]
@@ -41,6 +41,9 @@ pub enum LearnableTargetKind {
     AutomationModeOverride,
     ReaperAction,
     TransportAction,
+    TrackRecordInput,
+    TrackRecordMode,
+    TrackInputFxOnOffState,
     // Could be nice to add to the list of learnable targets
     // Seek,
     // TrackParentSendState,
@@ -102,6 +105,9 @@ pub enum Target {
     PlayRate(PlayRateTarget),
     Tempo(TempoTarget),
     GoToBookmark(GoToBookmarkTarget),
+    AddBookmark(AddBookmarkTarget),
+    RenameBookmark(RenameBookmarkTarget),
+    DeleteBookmark(DeleteBookmarkTarget),
     TrackArmState(TrackArmStateTarget),
     TrackParentSendState(TrackParentSendStateTarget),
     AllTrackFxOnOffState(AllTrackFxOnOffStateTarget),
@@ -111,6 +117,9 @@ pub enum Target {
     TrackSelectionState(TrackSelectionStateTarget),
     TrackAutomationMode(TrackAutomationModeTarget),
     TrackMonitoringMode(TrackMonitoringModeTarget),
+    TrackRecordInput(TrackRecordInputTarget),
+    TrackRecordMode(TrackRecordModeTarget),
+    TrackInputFxOnOffState(TrackInputFxOnOffStateTarget),
     TrackAutomationTouchState(TrackAutomationTouchStateTarget),
     TrackPan(TrackPanTarget),
     TrackWidth(TrackWidthTarget),
@@ -119,6 +128,8 @@ pub enum Target {
     TrackTool(TrackToolTarget),
     TrackVisibility(TrackVisibilityTarget),
     TrackSoloState(TrackSoloStateTarget),
+    TrackName(TrackNameTarget),
+    TrackColor(TrackColorTarget),
     #[serde(alias = "CycleThroughFx")]
     BrowseFxChain(BrowseFxChainTarget),
     FxOnOffState(FxOnOffStateTarget),
@@ -129,6 +140,7 @@ pub enum Target {
     #[serde(alias = "Fx")]
     FxTool(FxToolTarget),
     FxVisibility(FxVisibilityTarget),
+    FxName(FxNameTarget),
     FxParameterValue(FxParameterValueTarget),
     FxParameterAutomationTouchState(FxParameterAutomationTouchStateTarget),
     RouteAutomationMode(RouteAutomationModeTarget),
@@ -138,6 +150,12 @@ pub enum Target {
     RoutePan(RoutePanTarget),
     RouteVolume(RouteVolumeTarget),
     RouteTouchState(RouteTouchStateTarget),
+    ItemParameterValue(ItemParameterValueTarget),
+    TakeParameterValue(TakeParameterValueTarget),
+    BrowseTakes(BrowseTakesTarget),
+    EnvelopeValue(EnvelopeValueTarget),
+    BrowseEnvelopePoints(BrowseEnvelopePointsTarget),
+    EnvelopeArmState(EnvelopeArmStateTarget),
     #[serde(alias = "ClipTransportAction")]
     PlaytimeSlotTransportAction(PlaytimeSlotTransportActionTarget),
     #[serde(alias = "ClipColumnAction")]
@@ -165,6 +183,7 @@ pub enum Target {
     #[serde(alias = "LoadMappingSnapshots")]
     LoadMappingSnapshot(LoadMappingSnapshotTarget),
     TakeMappingSnapshot(TakeMappingSnapshotTarget),
+    MorphMappingSnapshots(MorphMappingSnapshotsTarget),
     #[serde(alias = "CycleThroughGroupMappings")]
     BrowseGroupMappings(BrowseGroupMappingsTarget),
     BrowsePotFilterItems(BrowsePotFilterItemsTarget),
@@ -361,6 +380,46 @@ pub struct GoToBookmarkTarget {
     pub seek_behavior: Option<SeekBehavior>,
 }
 
+#[derive(Eq, PartialEq, Default, Serialize, Deserialize)]
+pub struct AddBookmarkTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+    /// If `true`, adds a region spanning the time selection instead of a marker at the play
+    /// position.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<bool>,
+    /// Markers and regions within this many milliseconds of the desired position count as
+    /// already existing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tolerance: Option<u32>,
+}
+
+#[derive(Eq, PartialEq, Default, Serialize, Deserialize)]
+pub struct RenameBookmarkTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+    /// If not given, renames the marker nearest to the play position.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bookmark: Option<BookmarkDescriptor>,
+    /// Maximum distance in milliseconds between a marker and the play position for the marker to
+    /// count as being at the play position.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tolerance: Option<u32>,
+}
+
+#[derive(Eq, PartialEq, Default, Serialize, Deserialize)]
+pub struct DeleteBookmarkTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+    /// If not given, deletes the marker nearest to the play position.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bookmark: Option<BookmarkDescriptor>,
+    /// Maximum distance in milliseconds between a marker and the play position for the marker to
+    /// count as being at the play position.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tolerance: Option<u32>,
+}
+
 #[derive(Eq, PartialEq, Default, Serialize, Deserialize)]
 pub struct TrackArmStateTarget {
     #[serde(flatten)]
@@ -473,6 +532,38 @@ pub struct TrackMonitoringModeTarget {
     pub use_selection_ganging: Option<bool>,
 }
 
+#[derive(Eq, PartialEq, Serialize, Deserialize)]
+pub struct TrackRecordInputTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub track: Option<TrackDescriptor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclusivity: Option<TrackExclusivity>,
+    pub input: RecordInput,
+}
+
+#[derive(Eq, PartialEq, Serialize, Deserialize)]
+pub struct TrackRecordModeTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub track: Option<TrackDescriptor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclusivity: Option<TrackExclusivity>,
+    pub mode: RecordMode,
+}
+
+#[derive(Eq, PartialEq, Default, Serialize, Deserialize)]
+pub struct TrackInputFxOnOffStateTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub track: Option<TrackDescriptor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclusivity: Option<TrackExclusivity>,
+}
+
 #[derive(Eq, PartialEq, Serialize, Deserialize)]
 pub struct TrackAutomationTouchStateTarget {
     #[serde(flatten)]
@@ -716,6 +807,22 @@ pub struct TrackSoloStateTarget {
     pub use_selection_ganging: Option<bool>,
 }
 
+#[derive(Eq, PartialEq, Default, Serialize, Deserialize)]
+pub struct TrackNameTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub track: Option<TrackDescriptor>,
+}
+
+#[derive(Eq, PartialEq, Default, Serialize, Deserialize)]
+pub struct TrackColorTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub track: Option<TrackDescriptor>,
+}
+
 #[derive(Eq, PartialEq, Serialize, Deserialize)]
 pub struct BrowseFxChainTarget {
     #[serde(flatten)]
@@ -812,6 +919,45 @@ pub struct FxVisibilityTarget {
     pub display_kind: Option<FxDisplayKind>,
 }
 
+#[derive(Eq, PartialEq, Default, Serialize, Deserialize)]
+pub struct FxNameTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fx: Option<FxDescriptor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name_kind: Option<FxNameKind>,
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Debug,
+    Serialize,
+    Deserialize,
+    derive_more::Display,
+    strum::EnumIter,
+    num_enum::TryFromPrimitive,
+    num_enum::IntoPrimitive,
+)]
+#[repr(usize)]
+pub enum FxNameKind {
+    /// The custom name of the FX instance as shown in the FX chain.
+    #[display(fmt = "Alias")]
+    Alias,
+    /// The name of the active preset. Setting it activates the preset with that name.
+    #[display(fmt = "Preset")]
+    Preset,
+}
+
+impl Default for FxNameKind {
+    fn default() -> Self {
+        Self::Alias
+    }
+}
+
 #[derive(Eq, PartialEq, Serialize, Deserialize)]
 pub struct FxParameterValueTarget {
     #[serde(flatten)]
@@ -898,6 +1044,150 @@ pub struct RouteTouchStateTarget {
     pub touched_parameter: TouchedRouteParameter,
 }
 
+#[derive(Eq, PartialEq, Default, Serialize, Deserialize)]
+pub struct ItemParameterValueTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+    /// Track on which to look for the item. Ignored if the item is addressed as selected item.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub track: Option<TrackDescriptor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item: Option<ItemDescriptor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameter: Option<ItemParameter>,
+}
+
+#[derive(Eq, PartialEq, Default, Serialize, Deserialize)]
+pub struct TakeParameterValueTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+    /// Track on which to look for the item. Ignored if the item is addressed as selected item.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub track: Option<TrackDescriptor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item: Option<ItemDescriptor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub take: Option<TakeDescriptor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameter: Option<TakeParameter>,
+}
+
+#[derive(Eq, PartialEq, Default, Serialize, Deserialize)]
+pub struct BrowseTakesTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+    /// Track on which to look for the item. Ignored if the item is addressed as selected item.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub track: Option<TrackDescriptor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item: Option<ItemDescriptor>,
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    derive_more::Display,
+    strum::EnumIter,
+    num_enum::TryFromPrimitive,
+    num_enum::IntoPrimitive,
+)]
+#[repr(usize)]
+pub enum ItemParameter {
+    /// Item position. Can only be controlled relatively: Each increment moves the item by one beat.
+    #[default]
+    #[display(fmt = "Position")]
+    Position,
+    #[display(fmt = "Volume")]
+    Volume,
+    #[display(fmt = "Mute")]
+    Mute,
+    #[display(fmt = "Fade-in length")]
+    FadeInLength,
+    #[display(fmt = "Fade-out length")]
+    FadeOutLength,
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    derive_more::Display,
+    strum::EnumIter,
+    num_enum::TryFromPrimitive,
+    num_enum::IntoPrimitive,
+)]
+#[repr(usize)]
+pub enum TakeParameter {
+    #[default]
+    #[display(fmt = "Volume")]
+    Volume,
+    #[display(fmt = "Pitch")]
+    Pitch,
+    #[display(fmt = "Playrate")]
+    PlayRate,
+}
+
+#[derive(Eq, PartialEq, Serialize, Deserialize)]
+pub struct EnvelopeValueTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+    pub envelope: EnvelopeDescriptor,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<EnvelopePosition>,
+}
+
+#[derive(Eq, PartialEq, Serialize, Deserialize)]
+pub struct BrowseEnvelopePointsTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+    pub envelope: EnvelopeDescriptor,
+}
+
+#[derive(Eq, PartialEq, Serialize, Deserialize)]
+pub struct EnvelopeArmStateTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+    pub envelope: EnvelopeDescriptor,
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    derive_more::Display,
+    strum::EnumIter,
+    num_enum::TryFromPrimitive,
+    num_enum::IntoPrimitive,
+)]
+#[repr(usize)]
+pub enum EnvelopePosition {
+    /// Writes the envelope point at the edit cursor position. Hitting the target repeatedly
+    /// adjusts the same point.
+    #[default]
+    #[display(fmt = "Edit cursor")]
+    EditCursor,
+    /// Inserts an envelope point at the play position (or at the edit cursor position if not
+    /// playing).
+    #[display(fmt = "Play position")]
+    PlayPosition,
+}
+
 #[derive(Eq, PartialEq, Serialize, Deserialize)]
 pub struct PlaytimeSlotTransportActionTarget {
     #[serde(flatten)]
@@ -1136,6 +1426,22 @@ pub struct TakeMappingSnapshotTarget {
     pub snapshot: BackwardCompatibleMappingSnapshotDescForTake,
 }
 
+#[derive(Eq, PartialEq, Default, Serialize, Deserialize)]
+pub struct MorphMappingSnapshotsTarget {
+    #[serde(flatten)]
+    pub commons: TargetCommons,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active_mappings_only: Option<bool>,
+    /// Snapshot which is loaded at 0%.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snapshot_a: Option<String>,
+    /// Snapshot which is loaded at 100%.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snapshot_b: Option<String>,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum BackwardCompatibleMappingSnapshotDescForTake {
@@ -1474,6 +1780,91 @@ impl Default for MonitoringMode {
     }
 }
 
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum RecordInput {
+    None,
+    /// Mono audio input with the given 0-based input channel.
+    Mono {
+        index: u32,
+    },
+    /// Stereo audio input starting at the given 0-based input channel.
+    Stereo {
+        index: u32,
+    },
+    /// MIDI input. Omitting device or channel means "all".
+    Midi {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        device_id: Option<u8>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        channel: Option<u8>,
+    },
+}
+
+impl Default for RecordInput {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+/// Record mode of a track, in the order of REAPER's `I_RECMODE` values.
+#[derive(
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Debug,
+    Serialize,
+    Deserialize,
+    derive_more::Display,
+    strum::EnumIter,
+    num_enum::TryFromPrimitive,
+    num_enum::IntoPrimitive,
+)]
+#[repr(usize)]
+pub enum RecordMode {
+    #[display(fmt = "Input (audio or MIDI)")]
+    Input,
+    #[display(fmt = "Output (stereo)")]
+    StereoOutput,
+    #[display(fmt = "Disable (input monitoring only)")]
+    Disabled,
+    #[display(fmt = "Output (stereo, latency compensated)")]
+    StereoOutputLatencyCompensated,
+    #[display(fmt = "Output (MIDI)")]
+    MidiOutput,
+    #[display(fmt = "Output (mono)")]
+    MonoOutput,
+    #[display(fmt = "Output (mono, latency compensated)")]
+    MonoOutputLatencyCompensated,
+    #[display(fmt = "MIDI overdub")]
+    MidiOverdub,
+    #[display(fmt = "MIDI replace")]
+    MidiReplace,
+    #[display(fmt = "MIDI touch-replace")]
+    MidiTouchReplace,
+    #[display(fmt = "Output (multichannel)")]
+    MultichannelOutput,
+    #[display(fmt = "Output (multichannel, latency compensated)")]
+    MultichannelOutputLatencyCompensated,
+    #[display(fmt = "Input (force mono)")]
+    InputForceMono,
+    #[display(fmt = "Input (force stereo)")]
+    InputForceStereo,
+    #[display(fmt = "Input (force multichannel)")]
+    InputForceMultichannel,
+    #[display(fmt = "Input (force MIDI)")]
+    InputForceMidi,
+    #[display(fmt = "MIDI latch-replace")]
+    MidiLatchReplace,
+}
+
+impl Default for RecordMode {
+    fn default() -> Self {
+        Self::Input
+    }
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum TransportAction {
     PlayStop,
@@ -1799,6 +2190,162 @@ pub struct TrackDescriptorCommons {
     pub track_must_be_selected: Option<bool>,
 }
 
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(tag = "address")]
+pub enum ItemDescriptor {
+    /// Resolves to the first selected item in the project, no matter on which track it is.
+    Selected,
+    /// Resolves to the item on the track which is located at the edit cursor position.
+    UnderEditCursor,
+    /// Resolves to the item at the given position on the track (zero-based).
+    ByIndex { index: u32 },
+    /// Resolves to the item on the track whose position is the result of the given expression.
+    Dynamic { expression: String },
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    strum::EnumIter,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+)]
+#[repr(usize)]
+pub enum ItemDescriptorKind {
+    #[default]
+    #[display(fmt = "Selected")]
+    Selected,
+    #[display(fmt = "Under edit cursor")]
+    UnderEditCursor,
+    #[display(fmt = "At position")]
+    ByIndex,
+    #[display(fmt = "Dynamic")]
+    Dynamic,
+}
+
+impl Default for ItemDescriptor {
+    fn default() -> Self {
+        Self::Selected
+    }
+}
+
+impl ItemDescriptor {
+    pub fn from_kind(kind: ItemDescriptorKind) -> Self {
+        match kind {
+            ItemDescriptorKind::Selected => Self::Selected,
+            ItemDescriptorKind::UnderEditCursor => Self::UnderEditCursor,
+            ItemDescriptorKind::ByIndex => Self::ByIndex { index: 0 },
+            ItemDescriptorKind::Dynamic => Self::Dynamic {
+                expression: "".to_string(),
+            },
+        }
+    }
+
+    pub fn kind(&self) -> ItemDescriptorKind {
+        match self {
+            Self::Selected => ItemDescriptorKind::Selected,
+            Self::UnderEditCursor => ItemDescriptorKind::UnderEditCursor,
+            Self::ByIndex { .. } => ItemDescriptorKind::ByIndex,
+            Self::Dynamic { .. } => ItemDescriptorKind::Dynamic,
+        }
+    }
+
+    /// Returns whether the track setting is relevant for resolving the item.
+    pub fn uses_track(&self) -> bool {
+        !matches!(self, Self::Selected)
+    }
+}
+
+impl Display for ItemDescriptor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ItemDescriptor::Selected => f.write_str("Selected item"),
+            ItemDescriptor::UnderEditCursor => f.write_str("Item under edit cursor"),
+            ItemDescriptor::ByIndex { index } => write!(f, "Item #{}", index + 1),
+            ItemDescriptor::Dynamic { .. } => f.write_str("Dynamic item"),
+        }
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(tag = "address")]
+pub enum TakeDescriptor {
+    /// Resolves to the active take of the item.
+    Active,
+    /// Resolves to the take at the given position within the item (zero-based).
+    ByIndex { index: u32 },
+    /// Resolves to the take whose position is the result of the given expression.
+    Dynamic { expression: String },
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    strum::EnumIter,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+)]
+#[repr(usize)]
+pub enum TakeDescriptorKind {
+    #[default]
+    #[display(fmt = "Active")]
+    Active,
+    #[display(fmt = "At position")]
+    ByIndex,
+    #[display(fmt = "Dynamic")]
+    Dynamic,
+}
+
+impl Default for TakeDescriptor {
+    fn default() -> Self {
+        Self::Active
+    }
+}
+
+impl TakeDescriptor {
+    pub fn from_kind(kind: TakeDescriptorKind) -> Self {
+        match kind {
+            TakeDescriptorKind::Active => Self::Active,
+            TakeDescriptorKind::ByIndex => Self::ByIndex { index: 0 },
+            TakeDescriptorKind::Dynamic => Self::Dynamic {
+                expression: "".to_string(),
+            },
+        }
+    }
+
+    pub fn kind(&self) -> TakeDescriptorKind {
+        match self {
+            Self::Active => TakeDescriptorKind::Active,
+            Self::ByIndex { .. } => TakeDescriptorKind::ByIndex,
+            Self::Dynamic { .. } => TakeDescriptorKind::Dynamic,
+        }
+    }
+}
+
+impl Display for TakeDescriptor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TakeDescriptor::Active => f.write_str("Active take"),
+            TakeDescriptor::ByIndex { index } => write!(f, "Take #{}", index + 1),
+            TakeDescriptor::Dynamic { .. } => f.write_str("Dynamic take"),
+        }
+    }
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum FeedbackResolution {
     Beat,
@@ -2049,6 +2596,19 @@ pub enum FxParameterDescriptor {
     },
 }
 
+#[derive(Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "address")]
+pub enum EnvelopeDescriptor {
+    /// Track envelope with the given name, e.g. "Volume", "Pan" or "Mute".
+    Track {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        track: Option<TrackDescriptor>,
+        name: String,
+    },
+    /// Automation envelope of an FX parameter.
+    FxParameter { parameter: FxParameterDescriptor },
+}
+
 #[derive(Eq, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "address")]
 pub enum CompartmentParameterDescriptor {
@@ -2451,3 +3011,31 @@ pub enum TargetValue {
         value: u32,
     },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn item_descriptor_kind_round_trip() {
+        // Given
+        let kinds = ItemDescriptorKind::iter();
+        // When
+        // Then
+        for kind in kinds {
+            assert_eq!(ItemDescriptor::from_kind(kind).kind(), kind);
+        }
+    }
+
+    #[test]
+    fn take_descriptor_kind_round_trip() {
+        // Given
+        let kinds = TakeDescriptorKind::iter();
+        // When
+        // Then
+        for kind in kinds {
+            assert_eq!(TakeDescriptor::from_kind(kind).kind(), kind);
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! REAPER-independent access to presets saved in ReaLearn's internal data format.
+//!
+//! ReaLearn saves JSON presets in its internal data format, which is different from the API format
+//! (the one that Lua presets return). Converting it completely requires ReaLearn itself. Tools
+//! such as the preset linter only need the parts which identify mappings, groups and parameters
+//! and the way mappings are connected via virtual control elements, which is what this module
+//! provides.
+use crate::persistence::{
+    Compartment, Group, Mapping, Parameter, Source, Target, VirtualControlElementCharacter,
+    VirtualControlElementId, VirtualSource, VirtualTarget,
+};
+use crate::util::deserialize_null_default;
+use semver::Version;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Outline of a compartment preset in ReaLearn's internal data format.
+///
+/// Properties which are not relevant for the outline are ignored.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InternalCompartmentPresetData {
+    pub name: String,
+    #[serde(default, deserialize_with = "deserialize_null_default")]
+    pub version: Option<Version>,
+    #[serde(default, deserialize_with = "deserialize_null_default")]
+    pub groups: Vec<InternalGroupData>,
+    #[serde(default, deserialize_with = "deserialize_null_default")]
+    pub mappings: Vec<InternalMappingData>,
+    /// Parameter settings by parameter index.
+    #[serde(default, deserialize_with = "deserialize_null_default")]
+    pub parameters: HashMap<String, InternalParamData>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct InternalGroupData {
+    #[serde(default, deserialize_with = "deserialize_null_default", alias = "key")]
+    pub id: String,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InternalMappingData {
+    #[serde(default, deserialize_with = "deserialize_null_default", alias = "key")]
+    pub id: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_null_default")]
+    pub name: String,
+    /// Empty if the mapping is in the default group.
+    #[serde(default, deserialize_with = "deserialize_null_default")]
+    pub group_id: String,
+    #[serde(default, deserialize_with = "deserialize_null_default")]
+    pub source: InternalControlElementData,
+    #[serde(default, deserialize_with = "deserialize_null_default")]
+    pub target: InternalControlElementData,
+}
+
+/// The parts of a source or target which are relevant if it's virtual.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InternalControlElementData {
+    #[serde(default, deserialize_with = "deserialize_null_default")]
+    pub category: String,
+    #[serde(default, deserialize_with = "deserialize_null_default")]
+    pub control_element_type: VirtualControlElementCharacter,
+    #[serde(default, deserialize_with = "deserialize_null_default")]
+    pub control_element_index: Option<VirtualControlElementId>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct InternalParamData {
+    #[serde(default, deserialize_with = "deserialize_null_default")]
+    pub key: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_null_default")]
+    pub name: String,
+}
+
+impl InternalCompartmentPresetData {
+    /// Converts this outline into a compartment in the API format.
+    ///
+    /// The compartment only contains IDs, names, group assignments and virtual sources and
+    /// targets. Everything else is left at its default.
+    pub fn into_compartment_outline(self) -> Result<Compartment, &'static str> {
+        let mut parameters = self
+            .parameters
+            .into_iter()
+            .map(|(index, p)| {
+                let p = Parameter {
+                    index: index
+                        .parse()
+                        .map_err(|_| "parameter index is not a number")?,
+                    id: p.key,
+                    name: Some(p.name),
+                    ..Default::default()
+                };
+                Ok(p)
+            })
+            .collect::<Result<Vec<_>, &'static str>>()?;
+        parameters.sort_by_key(|p| p.index);
+        let groups = self
+            .groups
+            .into_iter()
+            .map(|g| Group {
+                id: Some(g.id),
+                ..Default::default()
+            })
+            .collect();
+        let mappings = self
+            .mappings
+            .into_iter()
+            .map(|m| Mapping {
+                id: m.id,
+                name: Some(m.name).filter(|n| !n.is_empty()),
+                group: Some(m.group_id).filter(|g| !g.is_empty()),
+                source: m.source.virtual_element().map(|(id, character)| {
+                    Source::Virtual(VirtualSource {
+                        id,
+                        character: Some(character),
+                    })
+                }),
+                target: m.target.virtual_element().map(|(id, character)| {
+                    Target::Virtual(VirtualTarget {
+                        id,
+                        character: Some(character),
+                        learnable: None,
+                    })
+                }),
+                ..Default::default()
+            })
+            .collect();
+        let compartment = Compartment {
+            parameters: Some(parameters),
+            groups: Some(groups),
+            mappings: Some(mappings),
+            ..Default::default()
+        };
+        Ok(compartment)
+    }
+}
+
+impl InternalControlElementData {
+    fn virtual_element(self) -> Option<(VirtualControlElementId, VirtualControlElementCharacter)> {
+        if self.category != "virtual" {
+            return None;
+        }
+        let id = self
+            .control_element_index
+            .unwrap_or(VirtualControlElementId::Indexed(0));
+        Some((id, self.control_element_type))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn outline_of_internal_preset() {
+        // Given
+        let raw = json!({
+            "version": "2.16.0",
+            "name": "My preset",
+            "groups": [{ "id": "faders", "name": "Faders" }],
+            "mappings": [
+                {
+                    "id": "play",
+                    "name": "Play",
+                    "groupId": "faders",
+                    "source": { "category": "virtual", "controlElementType": "Button", "controlElementIndex": "play" },
+                    "mode": {},
+                    "target": { "category": "reaper", "type": 0 }
+                },
+                {
+                    "source": { "category": "midi" },
+                    "mode": {},
+                    "target": { "category": "virtual", "controlElementIndex": 3 }
+                }
+            ],
+            "parameters": {
+                "1": { "key": "bank", "name": "Bank" },
+                "0": { "name": "Mode" }
+            }
+        });
+        let data: InternalCompartmentPresetData = serde_json::from_value(raw).unwrap();
+        // When
+        let compartment = data.into_compartment_outline().unwrap();
+        // Then
+        let groups = compartment.groups.unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].id.as_deref(), Some("faders"));
+        let params = compartment.parameters.unwrap();
+        assert_eq!(params[0].index, 0);
+        assert_eq!(params[1].id.as_deref(), Some("bank"));
+        let mappings = compartment.mappings.unwrap();
+        assert_eq!(mappings[0].id.as_deref(), Some("play"));
+        assert_eq!(mappings[0].group.as_deref(), Some("faders"));
+        assert!(matches!(
+            &mappings[0].source,
+            Some(Source::Virtual(VirtualSource {
+                id: VirtualControlElementId::Named(n),
+                character: Some(VirtualControlElementCharacter::Button),
+            })) if n == "play"
+        ));
+        assert!(mappings[0].target.is_none());
+        assert_eq!(mappings[1].group, None);
+        assert!(mappings[1].source.is_none());
+        assert!(matches!(
+            &mappings[1].target,
+            Some(Target::Virtual(VirtualTarget {
+                id: VirtualControlElementId::Indexed(3),
+                character: Some(VirtualControlElementCharacter::Multi),
+                ..
+            }))
+        ));
+    }
+}
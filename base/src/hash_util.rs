@@ -83,7 +83,9 @@ pub fn create_non_crypto_hash_builder() -> NonCryptoHashBuilder {
 
 /// This newtype should be used whenever it matters to keep a stable hash function, for example
 /// when the hashes are going to be persisted.
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, serde::Serialize, serde::Deserialize,
+)]
 pub struct PersistentHash(u128);
 
 impl PersistentHash {
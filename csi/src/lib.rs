@@ -14,18 +14,36 @@ use std::fmt::{Display, Formatter};
 
 mod parser;
 mod schema;
+mod serializer;
+mod widget_export;
+mod zone_conversion;
 
 pub use schema::*;
 
 pub enum CsiObject {
+    /// Content of a `.mst` file.
     Widgets(Vec<Widget>),
+    /// Content of a `.zon` file.
+    Zones(Vec<Zone>),
 }
 
 type CsiResult<T> = Result<T, Box<dyn Error>>;
 
 pub fn deserialize_csi_object_from_csi(text: &str) -> Result<CsiObject, Box<dyn Error>> {
-    let widgets = parser::mst_file_content(text)?;
-    Ok(CsiObject::Widgets(widgets))
+    if parser::is_zon_file_content(text) {
+        let zones = parser::zon_file_content(text)?;
+        Ok(CsiObject::Zones(zones))
+    } else {
+        let widgets = parser::mst_file_content(text)?;
+        Ok(CsiObject::Widgets(widgets))
+    }
+}
+
+pub fn serialize_csi_object_to_csi(object: &CsiObject) -> Result<String, Box<dyn Error>> {
+    match object {
+        CsiObject::Widgets(widgets) => Ok(serializer::mst_file_content(widgets)),
+        CsiObject::Zones(_) => Err("Writing CSI zone files is not supported".into()),
+    }
 }
 
 #[derive(Default)]
@@ -144,9 +162,28 @@ impl CsiObject {
                     value: Box::new(compartment),
                 })
             }
+            O::Zones(zones) => {
+                let compartment = zone_conversion::convert_zones(zones, &mut annotator);
+                ApiObject::MainCompartment(Envelope {
+                    version: None,
+                    value: Box::new(compartment),
+                })
+            }
         };
         Ok(annotator.build_result(api_object))
     }
+
+    /// Converts a controller compartment into CSI widgets.
+    pub fn try_from_api_object(
+        api_object: &ApiObject,
+    ) -> Result<AnnotatedResult<CsiObject>, Box<dyn Error>> {
+        let ApiObject::ControllerCompartment(Envelope { value, .. }) = api_object else {
+            return Err("Only controller compartments can be converted to CSI widgets".into());
+        };
+        let mut annotator = Annotator::new();
+        let widgets = widget_export::convert_compartment_to_widgets(value, &mut annotator);
+        Ok(annotator.build_result(CsiObject::Widgets(widgets)))
+    }
 }
 
 struct WidgetConvResult {
@@ -1,7 +1,9 @@
-use crate::schema::{Acceleration, Accelerations, Capability, Widget};
+use crate::schema::{
+    Acceleration, Accelerations, ActionAssignment, Capability, Navigator, Widget, Zone, ZoneEntry,
+};
 use helgoboss_midi::{RawShortMessage, ShortMessageFactory};
 use nom::branch::alt;
-use nom::bytes::complete::{tag, take_while1, take_while_m_n};
+use nom::bytes::complete::{is_not, tag, take_while1, take_while_m_n};
 use nom::character::complete::{multispace0, not_line_ending, space0, space1};
 use nom::combinator::{all_consuming, map, map_res, opt, verify};
 use nom::error::ParseError;
@@ -13,19 +15,145 @@ use std::convert::TryInto;
 type Res<'a, T> = IResult<&'a str, T>;
 
 pub fn mst_file_content(input: &str) -> Result<Vec<Widget>, String> {
+    let input_without_comments = strip_comments(input);
+    let (_, widgets) = all_consuming(widgets)(&input_without_comments).map_err(shorten_error)?;
+    Ok(widgets)
+}
+
+pub fn zon_file_content(input: &str) -> Result<Vec<Zone>, String> {
+    let input_without_comments = strip_comments(input);
+    let (_, zones) = all_consuming(zones)(&input_without_comments).map_err(shorten_error)?;
+    Ok(zones)
+}
+
+/// Returns `true` if the given file content looks like it contains zones (`.zon`) instead of
+/// widgets (`.mst`).
+pub fn is_zon_file_content(input: &str) -> bool {
+    input
+        .lines()
+        .map(|l| l.trim())
+        .find(|l| !l.is_empty() && !l.starts_with('/'))
+        .is_some_and(|l| l.starts_with("Zone"))
+}
+
+fn strip_comments(input: &str) -> String {
     let non_comment_lines: Vec<_> = input
         .lines()
         .filter(|l| !l.trim_start().starts_with('/'))
         .collect();
-    let input_without_comments = non_comment_lines.join("\n");
-    let (_, widgets) = all_consuming(widgets)(&input_without_comments).map_err(|e| {
-        let short_err = match e {
-            Err::Error(e) => Err::Error(nom::error::Error::new(&e.input[0..30], e.code)),
-            e => e,
-        };
-        short_err.to_string()
-    })?;
-    Ok(widgets)
+    non_comment_lines.join("\n")
+}
+
+fn shorten_error(e: Err<nom::error::Error<&str>>) -> String {
+    let short_err = match e {
+        Err::Error(e) => Err::Error(nom::error::Error::new(
+            &e.input[0..e.input.len().min(30)],
+            e.code,
+        )),
+        e => e,
+    };
+    short_err.to_string()
+}
+
+fn zones(input: &str) -> Res<Vec<Zone>> {
+    delimited(
+        multispace0,
+        separated_list0(space_with_at_least_one_line_ending, zone),
+        multispace0,
+    )(input)
+}
+
+fn zone(input: &str) -> Res<Zone> {
+    map(
+        tuple((
+            zone_begin,
+            space_with_at_least_one_line_ending,
+            zone_entries,
+            space_with_at_least_one_line_ending,
+            tag("ZoneEnd"),
+        )),
+        |((name, alias), _, entries, _, _)| Zone {
+            name: name.to_owned(),
+            alias: alias.map(|a| a.to_owned()),
+            entries,
+        },
+    )(input)
+}
+
+fn zone_begin(input: &str) -> Res<(&str, Option<&str>)> {
+    preceded(
+        tuple((tag("Zone"), space1)),
+        tuple((quoted_string, opt(preceded(space1, quoted_string)))),
+    )(input)
+}
+
+fn zone_entries(input: &str) -> Res<Vec<ZoneEntry>> {
+    separated_list0(space_with_at_least_one_line_ending, zone_entry)(input)
+}
+
+fn zone_entry(input: &str) -> Res<ZoneEntry> {
+    alt((zone_entry_included_zones, zone_entry_line))(input)
+}
+
+fn zone_entry_included_zones(input: &str) -> Res<ZoneEntry> {
+    map(
+        delimited(
+            tuple((tag("IncludedZones"), space_with_at_least_one_line_ending)),
+            separated_list0(space_with_at_least_one_line_ending, quoted_string),
+            tuple((space_with_at_least_one_line_ending, tag("IncludedZonesEnd"))),
+        ),
+        |names| ZoneEntry::IncludedZones(names.into_iter().map(|n| n.to_owned()).collect()),
+    )(input)
+}
+
+fn zone_entry_line(input: &str) -> Res<ZoneEntry> {
+    map(
+        verify(tokens, |tokens: &Vec<&str>| {
+            !matches!(tokens.first(), Some(&"ZoneEnd" | &"IncludedZonesEnd"))
+        }),
+        |tokens| match tokens.as_slice() {
+            [name] if name.ends_with("Navigator") => ZoneEntry::Navigator(navigator(name)),
+            [widget_spec, action, params @ ..] => {
+                let mut modifiers: Vec<_> = widget_spec.split('+').map(|m| m.to_owned()).collect();
+                let widget_name = modifiers.pop().unwrap_or_default();
+                let (widget_name, per_channel) = match widget_name.strip_suffix('|') {
+                    None => (widget_name, false),
+                    Some(n) => (n.to_owned(), true),
+                };
+                ZoneEntry::ActionAssignment(ActionAssignment {
+                    modifiers,
+                    widget_name,
+                    per_channel,
+                    action: (*action).to_owned(),
+                    params: params.iter().map(|p| (*p).to_owned()).collect(),
+                })
+            }
+            _ => ZoneEntry::Unknown(tokens.join(" ")),
+        },
+    )(input)
+}
+
+fn navigator(name: &str) -> Navigator {
+    match name {
+        "TrackNavigator" => Navigator::Track,
+        "SelectedTrackNavigator" => Navigator::SelectedTrack,
+        "MasterTrackNavigator" => Navigator::MasterTrack,
+        "FocusedFXNavigator" => Navigator::FocusedFx,
+        _ => Navigator::Unknown(name.to_owned()),
+    }
+}
+
+/// Whitespace-separated tokens within one line, where quoted strings count as one token.
+fn tokens(input: &str) -> Res<Vec<&str>> {
+    separated_list1(space1, alt((quoted_string, is_not(" \t\r\n\""))))(input)
+}
+
+fn quoted_string(input: &str) -> Res<&str> {
+    delimited(
+        char('"'),
+        map(opt(is_not("\"\r\n")), Option::unwrap_or_default),
+        char('"'),
+    )(input)
 }
 
 fn widgets(input: &str) -> Res<Vec<Widget>> {
@@ -438,6 +566,74 @@ WidgetEnd"
         );
     }
 
+    #[test]
+    fn parse_zone() {
+        assert_eq!(
+            zone(
+                "\
+Zone \"Track|1-8\" \"Tracks\"
+    TrackNavigator
+    IncludedZones
+        \"Buttons\"
+    IncludedZonesEnd
+    Shift+Fader|    TrackVolume
+  Rotary1 FXParam 3 \"Thresh\"
+    OnInitialization
+ZoneEnd"
+            ),
+            Ok((
+                "",
+                Zone {
+                    name: "Track|1-8".to_owned(),
+                    alias: Some("Tracks".to_owned()),
+                    entries: vec![
+                        ZoneEntry::Navigator(Navigator::Track),
+                        ZoneEntry::IncludedZones(vec!["Buttons".to_owned()]),
+                        ZoneEntry::ActionAssignment(ActionAssignment {
+                            modifiers: vec!["Shift".to_owned()],
+                            widget_name: "Fader".to_owned(),
+                            per_channel: true,
+                            action: "TrackVolume".to_owned(),
+                            params: vec![],
+                        }),
+                        ZoneEntry::ActionAssignment(ActionAssignment {
+                            modifiers: vec![],
+                            widget_name: "Rotary1".to_owned(),
+                            per_channel: false,
+                            action: "FXParam".to_owned(),
+                            params: vec!["3".to_owned(), "Thresh".to_owned()],
+                        }),
+                        ZoneEntry::Unknown("OnInitialization".to_owned()),
+                    ]
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_zon_file_content() {
+        // Given
+        let content = "\
+// Comment
+Zone \"Home\"
+    IncludedZones
+        \"Buttons\"
+    IncludedZonesEnd
+ZoneEnd
+
+Zone \"Buttons\"
+    Play Play
+ZoneEnd
+";
+        // When
+        let zones = zon_file_content(content).unwrap();
+        // Then
+        assert!(is_zon_file_content(content));
+        assert_eq!(zones.len(), 2);
+        assert_eq!(zones[1].name, "Buttons");
+        assert_eq!(zones[1].entries.len(), 1);
+    }
+
     #[test]
     fn parse_short_midi_msg() {
         assert_eq!(
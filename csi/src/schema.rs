@@ -1,5 +1,6 @@
 use derive_more::Display;
 use helgoboss_midi::RawShortMessage;
+use std::fmt::{Display, Formatter, Write};
 use std::ops::RangeInclusive;
 
 #[derive(Eq, PartialEq, Debug)]
@@ -72,3 +73,62 @@ pub enum Acceleration {
     Sequence(Vec<u8>),
     Range(RangeInclusive<u8>),
 }
+
+#[derive(Eq, PartialEq, Debug)]
+pub struct Zone {
+    /// Zone name, possibly containing a channel range such as in `Track|1-8`.
+    pub name: String,
+    pub alias: Option<String>,
+    pub entries: Vec<ZoneEntry>,
+}
+
+#[derive(Eq, PartialEq, Debug)]
+pub enum ZoneEntry {
+    /// References to other zones, possibly containing channel ranges.
+    IncludedZones(Vec<String>),
+    Navigator(Navigator),
+    ActionAssignment(ActionAssignment),
+    Unknown(String),
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Display)]
+pub enum Navigator {
+    #[display(fmt = "TrackNavigator")]
+    Track,
+    #[display(fmt = "SelectedTrackNavigator")]
+    SelectedTrack,
+    #[display(fmt = "MasterTrackNavigator")]
+    MasterTrack,
+    #[display(fmt = "FocusedFXNavigator")]
+    FocusedFx,
+    #[display(fmt = "{_0}")]
+    Unknown(String),
+}
+
+#[derive(Eq, PartialEq, Debug)]
+pub struct ActionAssignment {
+    pub modifiers: Vec<String>,
+    pub widget_name: String,
+    /// `true` if the widget name ended with `|`, which means that it's expanded to one widget
+    /// per channel.
+    pub per_channel: bool,
+    pub action: String,
+    pub params: Vec<String>,
+}
+
+impl Display for ActionAssignment {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        for m in &self.modifiers {
+            write!(f, "{m}+")?;
+        }
+        f.write_str(&self.widget_name)?;
+        if self.per_channel {
+            f.write_char('|')?;
+        }
+        write!(f, " {}", self.action)?;
+        for p in &self.params {
+            write!(f, " {p}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+use crate::schema::{Acceleration, Accelerations, Capability, Widget};
+use helgoboss_midi::{RawShortMessage, ShortMessage};
+use std::fmt::Write;
+
+/// Writes widgets in the format of CSI `.mst` files.
+pub fn mst_file_content(widgets: &[Widget]) -> String {
+    let mut out = String::new();
+    for (i, widget) in widgets.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        write_widget(&mut out, widget);
+    }
+    out
+}
+
+fn write_widget(out: &mut String, widget: &Widget) {
+    let _ = writeln!(out, "Widget {}", widget.name);
+    for capability in &widget.capabilities {
+        let _ = writeln!(out, "\t{}", capability_line(capability));
+    }
+    out.push_str("WidgetEnd\n");
+}
+
+fn capability_line(capability: &Capability) -> String {
+    use Capability as C;
+    match capability {
+        C::Press { press, release } => match release {
+            None => format!("{capability} {}", short_midi_msg(*press)),
+            Some(release) => format!(
+                "{capability} {} {}",
+                short_midi_msg(*press),
+                short_midi_msg(*release)
+            ),
+        },
+        C::FbTwoState { on: a, off: b }
+        | C::Touch {
+            touch: a,
+            release: b,
+        } => format!("{capability} {} {}", short_midi_msg(*a), short_midi_msg(*b)),
+        C::Encoder {
+            main,
+            accelerations,
+        } => match accelerations {
+            None => format!("{capability} {}", short_midi_msg(*main)),
+            Some(accelerations) => format!(
+                "{capability} {} {}",
+                short_midi_msg(*main),
+                accelerations_expression(accelerations)
+            ),
+        },
+        C::FbEncoder { max: msg }
+        | C::Toggle { on: msg }
+        | C::Fader14Bit { max: msg }
+        | C::FbFader14Bit { max: msg } => format!("{capability} {}", short_midi_msg(*msg)),
+        C::FbMcuDisplayLower { index }
+        | C::FbMcuDisplayUpper { index }
+        | C::FbMcuVuMeter { index } => format!("{capability} {index}"),
+        C::FbMcuTimeDisplay | C::Unknown(_) => capability.to_string(),
+    }
+}
+
+fn short_midi_msg(msg: RawShortMessage) -> String {
+    let (status_byte, data_byte_1, data_byte_2) = msg.to_bytes();
+    format!(
+        "{:02x} {:02x} {:02x}",
+        status_byte,
+        data_byte_1.get(),
+        data_byte_2.get()
+    )
+}
+
+fn accelerations_expression(accelerations: &Accelerations) -> String {
+    format!(
+        "[ < {} > {} ]",
+        acceleration_expression(&accelerations.decrements),
+        acceleration_expression(&accelerations.increments)
+    )
+}
+
+fn acceleration_expression(acceleration: &Acceleration) -> String {
+    match acceleration {
+        Acceleration::Sequence(values) => values
+            .iter()
+            .map(|v| format!("{v:02x}"))
+            .collect::<Vec<_>>()
+            .join(" "),
+        Acceleration::Range(range) => format!("{:02x}-{:02x}", range.start(), range.end()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    #[test]
+    fn roundtrip() {
+        // Given
+        let widgets = parser::mst_file_content(include_str!("test_data/test.mst")).unwrap();
+        // When
+        let content = mst_file_content(&widgets);
+        // Then
+        assert_eq!(parser::mst_file_content(&content).unwrap(), widgets);
+    }
+}
@@ -0,0 +1,296 @@
+//! Conversion of a ReaLearn controller compartment into CSI widgets (the content of `.mst` files).
+//!
+//! This is the reverse of the widget conversion in the crate root. Each mapping with a MIDI source
+//! and a virtual target contributes capabilities to the widget named after the virtual control
+//! element.
+use crate::schema::{Capability, Widget};
+use crate::{Annotator, CsiResult};
+use helgoboss_midi::{RawShortMessage, ShortMessageFactory};
+use helgobox_api::persistence::{
+    ButtonFilter, Compartment, MackieLcdSource, MackieSevenSegmentDisplayScope,
+    MackieSevenSegmentDisplaySource, Mapping, MidiControlChangeValueSource, MidiNoteVelocitySource,
+    MidiPitchBendChangeValueSource, Source, Target, VirtualControlElementCharacter,
+    VirtualControlElementId, VirtualTarget,
+};
+use std::convert::TryInto;
+
+pub fn convert_compartment_to_widgets(
+    compartment: &Compartment,
+    annotator: &mut Annotator,
+) -> Vec<Widget> {
+    let mut widgets: Vec<Widget> = vec![];
+    for (i, mapping) in compartment.mappings.iter().flatten().enumerate() {
+        let context = match &mapping.name {
+            Some(name) => format!("Mapping \"{name}\""),
+            None => format!("Mapping {}", i + 1),
+        };
+        annotator.with_context(context, |annotator| {
+            let (widget_name, capabilities) = match convert_mapping(mapping, annotator) {
+                Ok(res) => res,
+                Err(e) => {
+                    annotator.warn(e.to_string());
+                    return;
+                }
+            };
+            let widget = match widgets.iter().position(|w| w.name == widget_name) {
+                Some(i) => &mut widgets[i],
+                None => {
+                    widgets.push(Widget {
+                        name: widget_name,
+                        capabilities: vec![],
+                    });
+                    widgets.last_mut().unwrap()
+                }
+            };
+            for c in capabilities {
+                if !widget.capabilities.contains(&c) {
+                    widget.capabilities.push(c);
+                }
+            }
+        });
+    }
+    widgets
+}
+
+/// The kind of control element that the virtual target refers to, derived from the ID suffixes
+/// which the widget conversion uses.
+enum ElementKind {
+    Main,
+    Touch,
+    Push,
+}
+
+fn convert_mapping(
+    mapping: &Mapping,
+    annotator: &mut Annotator,
+) -> CsiResult<(String, Vec<Capability>)> {
+    if mapping.enabled == Some(false) {
+        return Err("Mapping is disabled.".into());
+    }
+    let Some(Target::Virtual(target)) = &mapping.target else {
+        return Err("Only mappings with virtual targets can be exported to CSI.".into());
+    };
+    let (widget_name, element_kind) = convert_virtual_target(target, annotator);
+    let control = mapping.control_enabled != Some(false);
+    let feedback = mapping.feedback_enabled != Some(false);
+    let glue = mapping.glue.as_ref();
+    let reverse = glue.and_then(|g| g.reverse).unwrap_or(false);
+    let press_only = glue.and_then(|g| g.button_filter) == Some(ButtonFilter::PressOnly);
+    let source = mapping.source.as_ref().ok_or("Mapping has no source.")?;
+    let mut capabilities = vec![];
+    match source {
+        Source::MidiNoteVelocity(MidiNoteVelocitySource {
+            channel,
+            key_number,
+            ..
+        }) => {
+            let channel = require_channel(*channel)?;
+            let key_number =
+                key_number.ok_or("Note sources without key number can't be exported to CSI.")?;
+            let mut on = short_msg(0x90 | channel, key_number, 0x7f)?;
+            let mut off = short_msg(0x90 | channel, key_number, 0x00)?;
+            if reverse {
+                std::mem::swap(&mut on, &mut off);
+            }
+            match element_kind {
+                ElementKind::Touch => {
+                    if control {
+                        capabilities.push(Capability::Touch {
+                            touch: on,
+                            release: off,
+                        });
+                    }
+                }
+                ElementKind::Push => {
+                    if control {
+                        capabilities.push(Capability::Toggle { on });
+                    }
+                }
+                ElementKind::Main => {
+                    if control {
+                        capabilities.push(Capability::Press {
+                            press: on,
+                            release: if press_only { None } else { Some(off) },
+                        });
+                    }
+                    if feedback {
+                        capabilities.push(Capability::FbTwoState { on, off });
+                    }
+                }
+            }
+        }
+        Source::MidiControlChangeValue(MidiControlChangeValueSource {
+            channel,
+            controller_number,
+            fourteen_bit,
+            ..
+        }) => {
+            if *fourteen_bit != Some(true) {
+                return Err("Only 14-bit CC sources can be exported to CSI.".into());
+            }
+            let channel = require_channel(*channel)?;
+            let controller_number = controller_number
+                .ok_or("CC sources without controller number can't be exported to CSI.")?;
+            let max = short_msg(0xb0 | channel, controller_number, 0x7f)?;
+            push_fader_14_bit_capabilities(&mut capabilities, max, control, feedback);
+        }
+        Source::MidiPitchBendChangeValue(MidiPitchBendChangeValueSource { channel, .. }) => {
+            let channel = require_channel(*channel)?;
+            let max = short_msg(0xe0 | channel, 0x7f, 0x7f)?;
+            push_fader_14_bit_capabilities(&mut capabilities, max, control, feedback);
+        }
+        Source::MackieLcd(MackieLcdSource {
+            extender_index,
+            channel,
+            line,
+        }) => {
+            if extender_index.is_some_and(|i| i > 0) {
+                return Err("Mackie LCD sources of extenders can't be exported to CSI.".into());
+            }
+            let index =
+                channel.ok_or("Mackie LCD sources without channel can't be exported to CSI.")?;
+            let capability = match line {
+                Some(0) => Capability::FbMcuDisplayUpper { index },
+                Some(1) => Capability::FbMcuDisplayLower { index },
+                _ => {
+                    return Err(
+                        "Only Mackie LCD sources with line 1 or 2 can be exported to CSI.".into(),
+                    )
+                }
+            };
+            if feedback {
+                capabilities.push(capability);
+            }
+        }
+        Source::MackieSevenSegmentDisplay(MackieSevenSegmentDisplaySource {
+            scope: Some(MackieSevenSegmentDisplayScope::Tc),
+        }) => {
+            if feedback {
+                capabilities.push(Capability::FbMcuTimeDisplay);
+            }
+        }
+        _ => {
+            return Err("This kind of source can't be exported to CSI. Supported are note velocity, 14-bit CC, pitch bend, Mackie LCD and Mackie time code display sources.".into());
+        }
+    }
+    if capabilities.is_empty() {
+        return Err("Mapping doesn't result in any CSI capability because neither control nor feedback is relevant for this kind of source.".into());
+    }
+    Ok((widget_name, capabilities))
+}
+
+fn push_fader_14_bit_capabilities(
+    capabilities: &mut Vec<Capability>,
+    max: RawShortMessage,
+    control: bool,
+    feedback: bool,
+) {
+    if control {
+        capabilities.push(Capability::Fader14Bit { max });
+    }
+    if feedback {
+        capabilities.push(Capability::FbFader14Bit { max });
+    }
+}
+
+fn convert_virtual_target(
+    target: &VirtualTarget,
+    annotator: &mut Annotator,
+) -> (String, ElementKind) {
+    let id = match &target.id {
+        VirtualControlElementId::Named(name) => name.clone(),
+        VirtualControlElementId::Indexed(i) => {
+            let prefix = match target.character {
+                Some(VirtualControlElementCharacter::Button) => "Button",
+                _ => "Multi",
+            };
+            format!("{prefix}{}", i + 1)
+        }
+    };
+    let (base_id, element_kind) = if let Some(base_id) = id.strip_suffix("/touch") {
+        (base_id, ElementKind::Touch)
+    } else if let Some(base_id) = id.strip_suffix("/push") {
+        (base_id, ElementKind::Push)
+    } else {
+        (id.as_str(), ElementKind::Main)
+    };
+    let widget_name: String = base_id
+        .chars()
+        .map(|ch| {
+            if ch.is_alphanumeric() || matches!(ch, '-' | '_') {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if widget_name != base_id {
+        annotator.info(format!("CSI doesn't allow special characters in widget names, therefore the control element ID \"{base_id}\" was converted to the widget name \"{widget_name}\"."));
+    }
+    (widget_name, element_kind)
+}
+
+fn require_channel(channel: Option<u8>) -> CsiResult<u8> {
+    let channel = channel.ok_or("Sources without channel (\"Any\") can't be exported to CSI.")?;
+    if channel > 15 {
+        return Err(format!("Invalid MIDI channel {channel}").into());
+    }
+    Ok(channel)
+}
+
+fn short_msg(status_byte: u8, data_byte_1: u8, data_byte_2: u8) -> CsiResult<RawShortMessage> {
+    let msg = RawShortMessage::from_bytes((
+        status_byte,
+        data_byte_1.try_into().map_err(|_| "data byte 1 too high")?,
+        data_byte_2.try_into().map_err(|_| "data byte 2 too high")?,
+    ))
+    .map_err(|_| "invalid short message")?;
+    Ok(msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::mst_file_content;
+    use crate::{AnnotatedResult, CsiObject};
+    use helgobox_api::persistence::ApiObject;
+
+    #[test]
+    fn round_trip_widgets() {
+        // Given
+        let original = "\
+Widget Play
+	Press 90 5e 7f 90 5e 00
+	FB_TwoState 90 5e 7f 90 5e 00
+WidgetEnd
+
+Widget Fader1
+	Fader14Bit e0 7f 7f
+	FB_Fader14Bit e0 7f 7f
+	Touch 90 68 7f 90 68 00
+WidgetEnd
+
+Widget DisplayUpper1
+	FB_MCUDisplayUpper 0
+WidgetEnd
+
+Widget TimeDisplay
+	FB_MCUTimeDisplay
+WidgetEnd
+";
+        let widgets = mst_file_content(original).unwrap();
+        // When
+        let AnnotatedResult {
+            value: api_object, ..
+        } = CsiObject::Widgets(widgets).try_into_api_object().unwrap();
+        assert!(matches!(api_object, ApiObject::ControllerCompartment(_)));
+        let AnnotatedResult { value, annotations } =
+            CsiObject::try_from_api_object(&api_object).unwrap();
+        // Then
+        assert!(annotations.is_empty());
+        let CsiObject::Widgets(exported_widgets) = value else {
+            panic!("widgets expected");
+        };
+        assert_eq!(exported_widgets, mst_file_content(original).unwrap());
+    }
+}
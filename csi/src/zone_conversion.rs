@@ -0,0 +1,823 @@
+//! Conversion of CSI zones (`.zon` files) into a ReaLearn main compartment.
+//!
+//! Widgets are referred to via the virtual control elements produced by the widget conversion. So
+//! the result is meant to be used together with a controller compartment converted from the
+//! corresponding `.mst` file.
+use crate::schema::{ActionAssignment, Navigator, Zone, ZoneEntry};
+use crate::{convert_widget_name_to_id, Annotator, CsiResult};
+use base::hash_util::NonCryptoHashSet;
+use helgobox_api::persistence::{
+    AbsoluteMode, ActivationCondition, BankActivationCondition, Compartment,
+    CompartmentParameterDescriptor, CompartmentParameterValueTarget, Feedback, FxChainDescriptor,
+    FxDescriptor, FxDescriptorCommons, FxParameterDescriptor, FxParameterValueTarget, Glue, Group,
+    Mapping, ModifierActivationCondition, ModifierState, ParamRef, Parameter, ReaperActionTarget,
+    ReaperCommand, Source, Target, TextFeedback, TrackArmStateTarget, TrackDescriptor,
+    TrackExclusivity, TrackFxChain, TrackMuteStateTarget, TrackPanTarget, TrackPeakTarget,
+    TrackSelectionStateTarget, TrackSoloStateTarget, TrackToolTarget, TrackVolumeTarget,
+    TrackWidthTarget, TransportAction, TransportActionTarget, VirtualControlElementCharacter,
+    VirtualControlElementId, VirtualSource,
+};
+use std::num::NonZeroU32;
+use std::ops::RangeInclusive;
+
+const HOME_ZONE_NAME: &str = "Home";
+/// Number of banks generated for zones with a track navigator.
+const TRACK_BANK_COUNT: u32 = 16;
+/// Assumed if a zone contains channel widgets (such as `Fader|`) but no channel range is given.
+const DEFAULT_CHANNEL_COUNT: u32 = 8;
+const TRACK_BANK_PARAM_INDEX: u32 = 0;
+/// ReaLearn modifier conditions consider at most two modifiers.
+const MAX_MODIFIER_COUNT: usize = 2;
+const SUPPORTED_MODIFIERS: [&str; 4] = ["Shift", "Option", "Control", "Alt"];
+const NO_MODIFIERS_GROUP_ID: &str = "no-modifiers";
+
+pub fn convert_zones(zones: Vec<Zone>, annotator: &mut Annotator) -> Compartment {
+    let mut converter = ZoneConverter::new(&zones, annotator);
+    match converter.find_zone(HOME_ZONE_NAME) {
+        Some(home_zone) => converter.convert_zone(home_zone, None, annotator),
+        None => {
+            annotator.warn(
+                "No \"Home\" zone found. Converting all zones as if they were included by it.",
+            );
+            for zone in zones.iter().filter(|z| !is_fx_zone(z)) {
+                if !converter.converted_zones.contains(zone.name.as_str()) {
+                    converter.convert_zone(zone, None, annotator);
+                }
+            }
+        }
+    }
+    for zone in &zones {
+        if converter.converted_zones.contains(zone.name.as_str()) {
+            continue;
+        }
+        if is_fx_zone(zone) {
+            converter.convert_zone(zone, None, annotator);
+        } else {
+            annotator.with_context(format!("Zone \"{}\"", zone.name), |annotator| {
+                annotator.info("Zone was ignored because it's neither included by the home zone nor an FX zone. Switching zones (GoZone) is not supported.");
+            });
+        }
+    }
+    converter.build_compartment()
+}
+
+struct ZoneConverter<'a> {
+    zones: &'a [Zone],
+    /// Supported modifiers in order of appearance. Each one gets its own compartment parameter.
+    modifiers: Vec<String>,
+    converted_zones: NonCryptoHashSet<&'a str>,
+    /// For detecting cyclic zone inclusions.
+    zone_stack: Vec<&'a str>,
+    uses_track_banks: bool,
+    groups: Vec<Group>,
+    mappings: Vec<Mapping>,
+}
+
+struct ZoneScope {
+    navigator: Option<Navigator>,
+    channels: Option<RangeInclusive<u32>>,
+    fx: Option<FxDescriptor>,
+}
+
+impl<'a> ZoneConverter<'a> {
+    fn new(zones: &'a [Zone], annotator: &mut Annotator) -> Self {
+        let mut modifiers: Vec<String> = vec![];
+        let modifier_candidates = zones
+            .iter()
+            .flat_map(|z| &z.entries)
+            .filter_map(|e| match e {
+                ZoneEntry::ActionAssignment(a) => Some(a),
+                _ => None,
+            })
+            .flat_map(|a| a.modifiers.iter().chain(std::iter::once(&a.action)));
+        for m in modifier_candidates {
+            if SUPPORTED_MODIFIERS.contains(&m.as_str()) && !modifiers.contains(m) {
+                modifiers.push(m.clone());
+            }
+        }
+        if modifiers.len() > MAX_MODIFIER_COUNT {
+            annotator.warn(format!("More than {MAX_MODIFIER_COUNT} different modifiers are used. ReaLearn modifier conditions can't consider more than {MAX_MODIFIER_COUNT} modifiers, therefore assignments without modifiers will stay active while modifiers are pressed."));
+        }
+        Self {
+            zones,
+            modifiers,
+            converted_zones: Default::default(),
+            zone_stack: vec![],
+            uses_track_banks: false,
+            groups: vec![],
+            mappings: vec![],
+        }
+    }
+
+    fn find_zone(&self, name: &str) -> Option<&'a Zone> {
+        self.zones
+            .iter()
+            .find(|z| split_channel_range(&z.name).0 == name)
+    }
+
+    fn convert_zone(
+        &mut self,
+        zone: &'a Zone,
+        included_channels: Option<RangeInclusive<u32>>,
+        annotator: &mut Annotator,
+    ) {
+        annotator.with_context(format!("Zone \"{}\"", zone.name), |annotator| {
+            let (base_name, header_channels) = split_channel_range(&zone.name);
+            if self.zone_stack.contains(&base_name) {
+                annotator.warn("Zone includes itself. Stopped following the inclusion.");
+                return;
+            }
+            if !self.converted_zones.insert(&zone.name) {
+                annotator.info("Zone is included more than once. Only the first inclusion was converted.");
+                return;
+            }
+            let navigator = zone.entries.iter().find_map(|e| match e {
+                ZoneEntry::Navigator(n) => Some(n),
+                _ => None,
+            });
+            if let Some(Navigator::Unknown(name)) = navigator {
+                annotator.warn(format!(
+                    "Navigator \"{name}\" is not supported. Using the selected track instead."
+                ));
+            }
+            let has_channel_widgets = zone.entries.iter().any(|e| {
+                matches!(e, ZoneEntry::ActionAssignment(a) if a.per_channel)
+            });
+            let channels = if has_channel_widgets {
+                let channels = included_channels.or(header_channels).unwrap_or_else(|| {
+                    annotator.warn(format!("Zone contains channel widgets but no channel range is given. Assuming {DEFAULT_CHANNEL_COUNT} channels."));
+                    1..=DEFAULT_CHANNEL_COUNT
+                });
+                Some(channels)
+            } else {
+                None
+            };
+            let fx = if is_fx_zone(zone) {
+                let fx = FxDescriptor::ByName {
+                    commons: FxDescriptorCommons {
+                        fx_must_have_focus: Some(true),
+                    },
+                    chain: FxChainDescriptor::Track {
+                        track: Some(TrackDescriptor::Selected {
+                            allow_multiple: None,
+                        }),
+                        chain: Some(TrackFxChain::Normal),
+                    },
+                    name: zone.name.clone(),
+                    allow_multiple: None,
+                };
+                Some(fx)
+            } else if navigator == Some(&Navigator::FocusedFx) {
+                Some(FxDescriptor::Focused)
+            } else {
+                None
+            };
+            let scope = ZoneScope {
+                navigator: navigator.cloned(),
+                channels,
+                fx,
+            };
+            self.zone_stack.push(base_name);
+            for entry in &zone.entries {
+                match entry {
+                    ZoneEntry::IncludedZones(zone_refs) => {
+                        for zone_ref in zone_refs {
+                            let (name, channels) = split_channel_range(zone_ref);
+                            match self.find_zone(name) {
+                                None => annotator
+                                    .warn(format!("Included zone \"{name}\" doesn't exist.")),
+                                Some(z) => self.convert_zone(z, channels, annotator),
+                            }
+                        }
+                    }
+                    ZoneEntry::Navigator(_) => {}
+                    ZoneEntry::ActionAssignment(a) => {
+                        annotator.with_context(format!("Assignment \"{a}\""), |annotator| {
+                            if let Err(e) = self.convert_assignment(a, &scope, annotator) {
+                                annotator.warn(e.to_string());
+                            }
+                        });
+                    }
+                    ZoneEntry::Unknown(line) => {
+                        annotator.info(format!("Unsupported line \"{line}\" was ignored."));
+                    }
+                }
+            }
+            self.zone_stack.pop();
+        });
+    }
+
+    fn convert_assignment(
+        &mut self,
+        assignment: &ActionAssignment,
+        scope: &ZoneScope,
+        annotator: &mut Annotator,
+    ) -> CsiResult<()> {
+        let Some(action) = self.convert_action(assignment, annotator)? else {
+            return Ok(());
+        };
+        if action.needs_fx() && scope.fx.is_none() {
+            return Err(
+                "FX actions are only supported in FX zones and zones with a focused FX navigator."
+                    .into(),
+            );
+        }
+        let group = self.modifier_group(&assignment.modifiers, &action, annotator)?;
+        let channels: Vec<Option<u32>> = match &scope.channels {
+            Some(channels) if assignment.per_channel => channels.clone().map(Some).collect(),
+            _ => vec![None],
+        };
+        let action_label = std::iter::once(&assignment.action)
+            .chain(&assignment.params)
+            .map(|s| s.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+        for channel in channels {
+            let widget_name = match channel {
+                None => assignment.widget_name.clone(),
+                Some(c) => format!("{}{c}", assignment.widget_name),
+            };
+            let widget_id = convert_widget_name_to_id(&widget_name, annotator)?;
+            let source = VirtualSource {
+                id: VirtualControlElementId::Named(widget_id),
+                character: Some(action.character()),
+            };
+            let modifier_prefix: String = assignment
+                .modifiers
+                .iter()
+                .map(|m| format!("{m}+"))
+                .collect();
+            let mapping_name = format!("{modifier_prefix}{widget_name} - {action_label}");
+            let track_banking = match (&scope.navigator, &scope.channels, channel) {
+                (Some(Navigator::Track), Some(channels), Some(c)) if action.is_track_related() => {
+                    let channel_count = channels.end() - channels.start() + 1;
+                    Some((channel_count, c - channels.start()))
+                }
+                _ => None,
+            };
+            if let Some((channel_count, channel_offset)) = track_banking {
+                self.uses_track_banks = true;
+                for bank_index in 0..TRACK_BANK_COUNT {
+                    let track = TrackDescriptor::ByIndex {
+                        commons: Default::default(),
+                        index: bank_index * channel_count + channel_offset,
+                        scope: None,
+                    };
+                    let condition = BankActivationCondition {
+                        parameter: ParamRef::Index(TRACK_BANK_PARAM_INDEX),
+                        bank_index,
+                    };
+                    let mapping = Mapping {
+                        name: Some(format!("{mapping_name} (bank {})", bank_index + 1)),
+                        group: group.clone(),
+                        activation_condition: Some(ActivationCondition::Bank(condition)),
+                        ..action.create_mapping(source.clone(), track, scope.fx.as_ref())
+                    };
+                    self.mappings.push(mapping);
+                }
+            } else {
+                let track = match &scope.navigator {
+                    Some(Navigator::MasterTrack) => TrackDescriptor::Master {
+                        commons: Default::default(),
+                    },
+                    _ => TrackDescriptor::Selected {
+                        allow_multiple: None,
+                    },
+                };
+                let mapping = Mapping {
+                    name: Some(mapping_name),
+                    group: group.clone(),
+                    ..action.create_mapping(source, track, scope.fx.as_ref())
+                };
+                self.mappings.push(mapping);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `None` if the action is deliberately empty.
+    fn convert_action(
+        &self,
+        assignment: &ActionAssignment,
+        annotator: &mut Annotator,
+    ) -> CsiResult<Option<ZoneAction>> {
+        use ZoneAction as A;
+        let first_param = assignment.params.first().map(|p| p.as_str());
+        let action = match assignment.action.as_str() {
+            "NoAction" => return Ok(None),
+            "Play" => A::Transport(TransportAction::PlayStop),
+            "Stop" => A::Transport(TransportAction::Stop),
+            "Pause" => A::Transport(TransportAction::Pause),
+            "Record" => A::Transport(TransportAction::Record),
+            "CycleTimeline" => A::Transport(TransportAction::Repeat),
+            "Reaper" => {
+                let command = first_param.ok_or("REAPER action without command ID")?;
+                let command = match command.parse() {
+                    Ok(id) => ReaperCommand::Id(id),
+                    Err(_) => ReaperCommand::Name(command.to_owned()),
+                };
+                A::ReaperAction(command)
+            }
+            "TrackVolume" => A::TrackVolume,
+            "TrackPan" => A::TrackPan,
+            "TrackPanWidth" => A::TrackWidth,
+            "TrackMute" => A::TrackMute,
+            "TrackSolo" => A::TrackSolo,
+            "TrackRecordArm" => A::TrackRecordArm,
+            "TrackSelect" => A::TrackSelect { exclusive: false },
+            "TrackUniqueSelect" => A::TrackSelect { exclusive: true },
+            "TrackOutputMeterAverageLR" => A::TrackPeak,
+            "TrackNameDisplay" => A::TrackNameDisplay,
+            "TrackVolumeDisplay" => A::TrackVolumeDisplay,
+            "TrackPanDisplay" => A::TrackPanDisplay,
+            "FXParam" => A::FxParam(parse_fx_param_index(first_param)?),
+            "FXParamNameDisplay" => A::FxParamNameDisplay(parse_fx_param_index(first_param)?),
+            "FXParamValueDisplay" => A::FxParamValueDisplay(parse_fx_param_index(first_param)?),
+            "TrackBank" => {
+                let amount: i32 = first_param
+                    .and_then(|p| p.parse().ok())
+                    .ok_or("TrackBank action without valid amount")?;
+                annotator.info(
+                    "TrackBank moves by one complete bank in ReaLearn, no matter the given amount.",
+                );
+                A::TrackBank {
+                    reverse: amount < 0,
+                }
+            }
+            name => {
+                if let Some(i) = self.modifiers.iter().position(|m| m == name) {
+                    A::Modifier(modifier_param_index(i))
+                } else {
+                    return Err(format!(
+                        "Action \"{name}\" is not supported by the CSI-to-ReaLearn conversion."
+                    )
+                    .into());
+                }
+            }
+        };
+        Ok(Some(action))
+    }
+
+    /// Returns the ID of the group which activates mappings according to the given modifiers.
+    fn modifier_group(
+        &mut self,
+        assignment_modifiers: &[String],
+        action: &ZoneAction,
+        annotator: &mut Annotator,
+    ) -> CsiResult<Option<String>> {
+        if matches!(action, ZoneAction::Modifier(_)) {
+            // Modifier widgets must stay active no matter which other modifiers are pressed.
+            if !assignment_modifiers.is_empty() {
+                annotator.info("Modifiers of modifier assignments are ignored.");
+            }
+            return Ok(None);
+        }
+        if let Some(m) = assignment_modifiers
+            .iter()
+            .find(|m| !SUPPORTED_MODIFIERS.contains(&m.as_str()))
+        {
+            return Err(format!(
+                "Modifier \"{m}\" is not supported by the CSI-to-ReaLearn conversion."
+            )
+            .into());
+        }
+        if assignment_modifiers.len() > MAX_MODIFIER_COUNT {
+            return Err(format!(
+                "Assignments with more than {MAX_MODIFIER_COUNT} modifiers are not supported."
+            )
+            .into());
+        }
+        let considers_all_modifiers = self.modifiers.len() <= MAX_MODIFIER_COUNT;
+        let modifier_states: Vec<_> = self
+            .modifiers
+            .iter()
+            .enumerate()
+            .filter_map(|(i, m)| {
+                let on = assignment_modifiers.contains(m);
+                if !on && !considers_all_modifiers {
+                    return None;
+                }
+                let state = ModifierState {
+                    parameter: ParamRef::Index(modifier_param_index(i)),
+                    on,
+                };
+                Some(state)
+            })
+            .collect();
+        if modifier_states.is_empty() {
+            return Ok(None);
+        }
+        let group_id = if assignment_modifiers.is_empty() {
+            NO_MODIFIERS_GROUP_ID.to_owned()
+        } else {
+            let mut sorted_modifiers = assignment_modifiers.to_vec();
+            sorted_modifiers.sort();
+            sorted_modifiers.join("+").to_lowercase()
+        };
+        if !self
+            .groups
+            .iter()
+            .any(|g| g.id.as_deref() == Some(group_id.as_str()))
+        {
+            let group_name = if assignment_modifiers.is_empty() {
+                "No modifiers".to_owned()
+            } else {
+                assignment_modifiers.join("+")
+            };
+            let condition = ModifierActivationCondition {
+                modifiers: Some(modifier_states),
+            };
+            let group = Group {
+                id: Some(group_id.clone()),
+                name: Some(group_name),
+                activation_condition: Some(ActivationCondition::Modifier(condition)),
+                ..Default::default()
+            };
+            self.groups.push(group);
+        }
+        Ok(Some(group_id))
+    }
+
+    fn build_compartment(self) -> Compartment {
+        let track_bank_param = if self.uses_track_banks {
+            let p = Parameter {
+                index: TRACK_BANK_PARAM_INDEX,
+                id: Some("track-bank".to_owned()),
+                name: Some("Track bank".to_owned()),
+                value_count: NonZeroU32::new(TRACK_BANK_COUNT),
+                value_labels: None,
+            };
+            Some(p)
+        } else {
+            None
+        };
+        let modifier_params = self.modifiers.iter().enumerate().map(|(i, m)| Parameter {
+            index: modifier_param_index(i),
+            id: Some(m.to_lowercase()),
+            name: Some(m.clone()),
+            ..Default::default()
+        });
+        let parameters: Vec<_> = track_bank_param
+            .into_iter()
+            .chain(modifier_params)
+            .collect();
+        Compartment {
+            parameters: Some(parameters).filter(|p| !p.is_empty()),
+            groups: Some(self.groups).filter(|g| !g.is_empty()),
+            mappings: Some(self.mappings),
+            ..Default::default()
+        }
+    }
+}
+
+enum ZoneAction {
+    Transport(TransportAction),
+    ReaperAction(ReaperCommand),
+    TrackVolume,
+    TrackPan,
+    TrackWidth,
+    TrackMute,
+    TrackSolo,
+    TrackRecordArm,
+    TrackSelect {
+        exclusive: bool,
+    },
+    TrackPeak,
+    TrackNameDisplay,
+    TrackVolumeDisplay,
+    TrackPanDisplay,
+    FxParam(u32),
+    FxParamNameDisplay(u32),
+    FxParamValueDisplay(u32),
+    TrackBank {
+        reverse: bool,
+    },
+    /// Contains the index of the compartment parameter which represents the modifier.
+    Modifier(u32),
+}
+
+impl ZoneAction {
+    fn character(&self) -> VirtualControlElementCharacter {
+        use ZoneAction as A;
+        match self {
+            A::Transport(_)
+            | A::ReaperAction(_)
+            | A::TrackMute
+            | A::TrackSolo
+            | A::TrackRecordArm
+            | A::TrackSelect { .. }
+            | A::TrackBank { .. }
+            | A::Modifier(_) => VirtualControlElementCharacter::Button,
+            _ => VirtualControlElementCharacter::Multi,
+        }
+    }
+
+    fn is_track_related(&self) -> bool {
+        use ZoneAction as A;
+        matches!(
+            self,
+            A::TrackVolume
+                | A::TrackPan
+                | A::TrackWidth
+                | A::TrackMute
+                | A::TrackSolo
+                | A::TrackRecordArm
+                | A::TrackSelect { .. }
+                | A::TrackPeak
+                | A::TrackNameDisplay
+                | A::TrackVolumeDisplay
+                | A::TrackPanDisplay
+        )
+    }
+
+    fn needs_fx(&self) -> bool {
+        use ZoneAction as A;
+        matches!(
+            self,
+            A::FxParam(_) | A::FxParamNameDisplay(_) | A::FxParamValueDisplay(_)
+        )
+    }
+
+    fn feedback_text_expression(&self) -> Option<&'static str> {
+        use ZoneAction as A;
+        match self {
+            A::TrackNameDisplay => Some("{{ target.track.name }}"),
+            A::FxParamNameDisplay(_) => Some("{{ target.fx_parameter.name }}"),
+            A::TrackVolumeDisplay | A::TrackPanDisplay | A::FxParamValueDisplay(_) => {
+                Some("{{ target.text_value }}")
+            }
+            _ => None,
+        }
+    }
+
+    fn create_mapping(
+        &self,
+        source: VirtualSource,
+        track: TrackDescriptor,
+        fx: Option<&FxDescriptor>,
+    ) -> Mapping {
+        let text_expression = self.feedback_text_expression();
+        let glue = if let Some(text_expression) = text_expression {
+            let feedback = TextFeedback {
+                text_expression: Some(text_expression.to_owned()),
+                ..Default::default()
+            };
+            Some(Glue {
+                feedback: Some(Feedback::Text(feedback)),
+                ..Default::default()
+            })
+        } else if let ZoneAction::TrackBank { reverse } = self {
+            Some(Glue {
+                absolute_mode: Some(AbsoluteMode::IncrementalButton),
+                reverse: Some(*reverse),
+                ..Default::default()
+            })
+        } else {
+            None
+        };
+        Mapping {
+            control_enabled: text_expression.map(|_| false),
+            source: Some(Source::Virtual(source)),
+            glue,
+            target: Some(self.create_target(track, fx)),
+            ..Default::default()
+        }
+    }
+
+    fn create_target(&self, track: TrackDescriptor, fx: Option<&FxDescriptor>) -> Target {
+        use ZoneAction as A;
+        let track = Some(track);
+        match self {
+            A::Transport(action) => Target::TransportAction(TransportActionTarget {
+                commons: Default::default(),
+                action: *action,
+            }),
+            A::ReaperAction(command) => Target::ReaperAction(ReaperActionTarget {
+                command: Some(command.clone()),
+                ..Default::default()
+            }),
+            A::TrackVolume | A::TrackVolumeDisplay => Target::TrackVolume(TrackVolumeTarget {
+                track,
+                ..Default::default()
+            }),
+            A::TrackPan | A::TrackPanDisplay => Target::TrackPan(TrackPanTarget {
+                track,
+                ..Default::default()
+            }),
+            A::TrackWidth => Target::TrackWidth(TrackWidthTarget {
+                track,
+                ..Default::default()
+            }),
+            A::TrackMute => Target::TrackMuteState(TrackMuteStateTarget {
+                track,
+                ..Default::default()
+            }),
+            A::TrackSolo => Target::TrackSoloState(TrackSoloStateTarget {
+                track,
+                ..Default::default()
+            }),
+            A::TrackRecordArm => Target::TrackArmState(TrackArmStateTarget {
+                track,
+                ..Default::default()
+            }),
+            A::TrackSelect { exclusive } => {
+                Target::TrackSelectionState(TrackSelectionStateTarget {
+                    track,
+                    exclusivity: exclusive.then_some(TrackExclusivity::WithinProject),
+                    ..Default::default()
+                })
+            }
+            A::TrackPeak => Target::TrackPeak(TrackPeakTarget {
+                track,
+                ..Default::default()
+            }),
+            A::TrackNameDisplay => Target::TrackTool(TrackToolTarget {
+                track,
+                ..Default::default()
+            }),
+            A::FxParam(index) | A::FxParamNameDisplay(index) | A::FxParamValueDisplay(index) => {
+                Target::FxParameterValue(FxParameterValueTarget {
+                    commons: Default::default(),
+                    parameter: FxParameterDescriptor::ByIndex {
+                        fx: fx.cloned(),
+                        index: *index,
+                    },
+                    poll_for_feedback: None,
+                    retrigger: None,
+                    real_time: None,
+                })
+            }
+            A::TrackBank { .. } => compartment_parameter_target(TRACK_BANK_PARAM_INDEX),
+            A::Modifier(param_index) => compartment_parameter_target(*param_index),
+        }
+    }
+}
+
+fn compartment_parameter_target(index: u32) -> Target {
+    Target::CompartmentParameterValue(CompartmentParameterValueTarget {
+        commons: Default::default(),
+        parameter: CompartmentParameterDescriptor::ById { index },
+    })
+}
+
+/// Modifier parameters come right after the track bank parameter.
+fn modifier_param_index(modifier_index: usize) -> u32 {
+    TRACK_BANK_PARAM_INDEX + 1 + modifier_index as u32
+}
+
+fn parse_fx_param_index(param: Option<&str>) -> CsiResult<u32> {
+    let index = param
+        .and_then(|p| p.parse().ok())
+        .ok_or("FX parameter action without valid parameter index")?;
+    Ok(index)
+}
+
+/// FX zones are named after the FX they apply to, e.g. `VST: ReaComp (Cockos)`.
+fn is_fx_zone(zone: &Zone) -> bool {
+    zone.name.split_once(':').is_some_and(|(prefix, _)| {
+        !prefix.is_empty() && prefix.chars().all(|ch| ch.is_ascii_alphanumeric())
+    })
+}
+
+/// Splits a zone name such as `Track|1-8` into base name and channel range.
+fn split_channel_range(zone_name: &str) -> (&str, Option<RangeInclusive<u32>>) {
+    let Some((base_name, range_expression)) = zone_name.split_once('|') else {
+        return (zone_name, None);
+    };
+    let range = range_expression
+        .split_once('-')
+        .and_then(|(start, end)| Some(start.trim().parse().ok()?..=end.trim().parse().ok()?))
+        .filter(|r: &RangeInclusive<u32>| *r.start() >= 1 && !r.is_empty());
+    (base_name, range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::zon_file_content;
+
+    #[test]
+    fn split_zone_name_with_channel_range() {
+        assert_eq!(split_channel_range("Track|1-8"), ("Track", Some(1..=8)));
+        assert_eq!(split_channel_range("Track|"), ("Track", None));
+        assert_eq!(split_channel_range("Buttons"), ("Buttons", None));
+    }
+
+    #[test]
+    fn convert_banked_tracks_and_modifiers() {
+        // Given
+        let zones = zon_file_content(
+            "\
+Zone \"Home\"
+    IncludedZones
+        \"Buttons\"
+        \"Track|1-2\"
+    IncludedZonesEnd
+ZoneEnd
+
+Zone \"Buttons\"
+    Shift Shift
+    Play Play
+    Shift+Play Stop
+    Left TrackBank -2
+    Track GoZone Track
+ZoneEnd
+
+Zone \"Track\"
+    TrackNavigator
+    Fader| TrackVolume
+ZoneEnd
+",
+        )
+        .unwrap();
+        let mut annotator = Annotator::new();
+        // When
+        let compartment = convert_zones(zones, &mut annotator);
+        // Then
+        let mappings = compartment.mappings.unwrap();
+        // Shift, Play, Shift+Play, Left + 2 channels * 16 banks
+        assert_eq!(mappings.len(), 4 + 2 * TRACK_BANK_COUNT as usize);
+        let parameters = compartment.parameters.unwrap();
+        assert_eq!(parameters.len(), 2);
+        assert_eq!(parameters[1].name.as_deref(), Some("Shift"));
+        let groups = compartment.groups.unwrap();
+        assert_eq!(groups.len(), 2);
+        let fader_2_bank_2 = mappings
+            .iter()
+            .find(|m| m.name.as_deref() == Some("Fader2 - TrackVolume (bank 2)"))
+            .unwrap();
+        assert!(matches!(
+            fader_2_bank_2.target,
+            Some(Target::TrackVolume(TrackVolumeTarget {
+                track: Some(TrackDescriptor::ByIndex { index: 3, .. }),
+                ..
+            }))
+        ));
+        assert!(matches!(
+            fader_2_bank_2.activation_condition,
+            Some(ActivationCondition::Bank(BankActivationCondition {
+                bank_index: 1,
+                ..
+            }))
+        ));
+        // GoZone is not supported
+        assert_eq!(annotator.build_result(()).annotations.len(), 2);
+    }
+
+    #[test]
+    fn convert_zones_included_more_than_once_only_once() {
+        // Given
+        let zones = zon_file_content(
+            "\
+Zone \"Home\"
+    IncludedZones
+        \"Left\"
+        \"Right\"
+        \"VST: ReaComp (Cockos)\"
+    IncludedZonesEnd
+ZoneEnd
+
+Zone \"Left\"
+    IncludedZones
+        \"Shared\"
+    IncludedZonesEnd
+    Stop Stop
+ZoneEnd
+
+Zone \"Right\"
+    IncludedZones
+        \"Shared\"
+    IncludedZonesEnd
+    Record Record
+ZoneEnd
+
+Zone \"Shared\"
+    Play Play
+ZoneEnd
+
+Zone \"VST: ReaComp (Cockos)\"
+    Rotary1 FXParam 0
+ZoneEnd
+",
+        )
+        .unwrap();
+        let mut annotator = Annotator::new();
+        // When
+        let compartment = convert_zones(zones, &mut annotator);
+        // Then
+        let mappings = compartment.mappings.unwrap();
+        let count = |name: &str| {
+            mappings
+                .iter()
+                .filter(|m| m.name.as_deref() == Some(name))
+                .count()
+        };
+        assert_eq!(mappings.len(), 4);
+        assert_eq!(count("Play - Play"), 1);
+        assert_eq!(count("Stop - Stop"), 1);
+        assert_eq!(count("Record - Record"), 1);
+        assert_eq!(count("Rotary1 - FXParam 0"), 1);
+        // Second inclusion of "Shared"
+        assert_eq!(annotator.build_result(()).annotations.len(), 1);
+    }
+}
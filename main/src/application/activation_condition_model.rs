@@ -3,7 +3,8 @@ use crate::application::{
     ModifierConditionModel, ProcessingRelevance,
 };
 use crate::domain::{
-    ActivationCondition, EelCondition, ExpressionCondition, ExpressionEvaluator, MappingId,
+    ActivationCondition, BooleanOperator, CompositeCondition, EelCondition, ExpressionCondition,
+    ExpressionEvaluator, MappingId,
 };
 
 #[allow(clippy::enum_variant_names)]
@@ -14,6 +15,8 @@ pub enum ActivationConditionCommand {
     SetBankCondition(BankConditionModel),
     SetScript(String),
     SetMappingId(Option<MappingId>),
+    SetOperator(BooleanOperator),
+    SetConditions(Vec<ActivationConditionModel>),
 }
 
 #[derive(Eq, PartialEq)]
@@ -24,6 +27,8 @@ pub enum ActivationConditionProp {
     BankCondition,
     Script,
     MappingId,
+    Operator,
+    Conditions,
 }
 
 impl GetProcessingRelevance for ActivationConditionProp {
@@ -40,6 +45,10 @@ pub struct ActivationConditionModel {
     bank_condition: BankConditionModel,
     script: String,
     mapping_id: Option<MappingId>,
+    /// Only relevant for composite conditions.
+    operator: BooleanOperator,
+    /// Operands of composite conditions. They can be composite conditions themselves.
+    conditions: Vec<ActivationConditionModel>,
 }
 
 impl Change<'_> for ActivationConditionModel {
@@ -78,6 +87,14 @@ impl Change<'_> for ActivationConditionModel {
                 self.mapping_id = v;
                 One(P::MappingId)
             }
+            C::SetOperator(v) => {
+                self.operator = v;
+                One(P::Operator)
+            }
+            C::SetConditions(v) => {
+                self.conditions = v;
+                One(P::Conditions)
+            }
         };
         Some(affected)
     }
@@ -108,6 +125,21 @@ impl ActivationConditionModel {
         self.mapping_id
     }
 
+    pub fn operator(&self) -> BooleanOperator {
+        self.operator
+    }
+
+    pub fn conditions(&self) -> &[ActivationConditionModel] {
+        &self.conditions
+    }
+
+    /// Short description of a composite condition, e.g. for displaying it in the UI.
+    pub fn composite_summary(&self) -> String {
+        let count = self.conditions.len();
+        let plural = if count == 1 { "" } else { "s" };
+        format!("{} of {count} condition{plural}", self.operator)
+    }
+
     pub fn create_activation_condition(&self) -> ActivationCondition {
         use ActivationType::*;
         match self.activation_type() {
@@ -138,6 +170,15 @@ impl ActivationConditionModel {
                 },
                 Err(_) => ActivationCondition::Always,
             },
+            Composite => {
+                let conditions = self
+                    .conditions
+                    .iter()
+                    .map(|c| c.create_activation_condition())
+                    .collect();
+                let condition = CompositeCondition::new(self.operator, conditions);
+                ActivationCondition::Composite(Box::new(condition))
+            }
         }
     }
 
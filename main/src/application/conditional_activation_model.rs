@@ -39,6 +39,9 @@ pub enum ActivationType {
     #[serde(rename = "target-value")]
     #[display(fmt = "When target value met")]
     TargetValue,
+    #[serde(rename = "composite")]
+    #[display(fmt = "When combination met")]
+    Composite,
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize, Default)]
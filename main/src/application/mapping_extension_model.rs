@@ -1,8 +1,12 @@
+use crate::application::build_action_from_smart_command_name;
 use crate::domain::{
-    parse_hex_string, DisplayAsPrettyHex, LifecycleMidiData, LifecycleMidiMessage, MappingExtension,
+    parse_hex_string, CompartmentParamIndex, DisplayAsPrettyHex, LifecycleAction,
+    LifecycleActionData, LifecycleMidiData, LifecycleMidiMessage, MappingExtension, OscDeviceId,
 };
 
-use helgoboss_learn::RawMidiEvent;
+use helgoboss_learn::{RawMidiEvent, UnitValue};
+use reaper_medium::SectionId;
+use rosc::{OscMessage, OscType};
 use serde::{Deserialize, Serialize};
 use serde_with::SerializeDisplay;
 use std::convert::TryFrom;
@@ -19,6 +23,9 @@ pub struct MappingExtensionModel {
 #[serde(default)]
 pub struct LifecycleModel {
     pub send_midi_feedback: Vec<LifecycleMidiMessageModel>,
+    /// Executed in the main thread, in the given order.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub actions: Vec<LifecycleActionModel>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -27,6 +34,40 @@ pub enum LifecycleMidiMessageModel {
     Raw(RawMidiMessage),
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LifecycleActionModel {
+    SendOsc {
+        address: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        args: Vec<OscArgValueModel>,
+        /// If `None`, the message is sent to the feedback output.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        device_id: Option<OscDeviceId>,
+    },
+    SetParameter {
+        index: CompartmentParamIndex,
+        value: f64,
+    },
+    InvokeAction {
+        /// Command ID (built-in actions) or command name (ReaScripts and custom actions).
+        command_name: String,
+    },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OscArgValueModel {
+    Float(f32),
+    Double(f64),
+    Int(i32),
+    Long(i64),
+    Bool(bool),
+    String(String),
+    Nil,
+    Inf,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum RawMidiMessage {
@@ -79,6 +120,52 @@ impl LifecycleMidiMessageModel {
     }
 }
 
+impl LifecycleActionModel {
+    pub fn create_lifecycle_action(&self) -> Result<LifecycleAction, &'static str> {
+        use LifecycleActionModel::*;
+        let action = match self {
+            SendOsc {
+                address,
+                args,
+                device_id,
+            } => LifecycleAction::SendOsc {
+                device_id: *device_id,
+                message: OscMessage {
+                    addr: address.clone(),
+                    args: args.iter().map(OscArgValueModel::to_osc_type).collect(),
+                },
+            },
+            SetParameter { index, value } => LifecycleAction::SetCompartmentParameter {
+                index: *index,
+                value: UnitValue::try_new(*value)
+                    .ok_or("parameter value must be between 0 and 1")?,
+            },
+            InvokeAction { command_name } => {
+                let action = build_action_from_smart_command_name(SectionId::new(0), command_name)
+                    .ok_or("invalid command ID")?;
+                LifecycleAction::InvokeAction(action)
+            }
+        };
+        Ok(action)
+    }
+}
+
+impl OscArgValueModel {
+    fn to_osc_type(&self) -> OscType {
+        use OscArgValueModel::*;
+        match self {
+            Float(v) => OscType::Float(*v),
+            Double(v) => OscType::Double(*v),
+            Int(v) => OscType::Int(*v),
+            Long(v) => OscType::Long(*v),
+            Bool(v) => OscType::Bool(*v),
+            String(v) => OscType::String(v.clone()),
+            Nil => OscType::Nil,
+            Inf => OscType::Inf,
+        }
+    }
+}
+
 impl MappingExtensionModel {
     pub fn create_mapping_extension(&self) -> Result<MappingExtension, &'static str> {
         fn convert_messages(
@@ -89,10 +176,122 @@ impl MappingExtensionModel {
                 .map(|m| m.create_lifecycle_midi_message())
                 .collect()
         }
-        let ext = MappingExtension::new(LifecycleMidiData {
-            activation_midi_messages: convert_messages(&self.on_activate.send_midi_feedback)?,
-            deactivation_midi_messages: convert_messages(&self.on_deactivate.send_midi_feedback)?,
-        });
+        let ext = MappingExtension::new(
+            LifecycleMidiData {
+                activation_midi_messages: convert_messages(&self.on_activate.send_midi_feedback)?,
+                deactivation_midi_messages: convert_messages(
+                    &self.on_deactivate.send_midi_feedback,
+                )?,
+            },
+            LifecycleActionData {
+                activation_actions: convert_lifecycle_actions(&self.on_activate.actions),
+                deactivation_actions: convert_lifecycle_actions(&self.on_deactivate.actions),
+            },
+        );
         Ok(ext)
     }
 }
+
+/// Converts the given lifecycle action models, skipping actions which can't be resolved.
+///
+/// One bad action (e.g. an unknown command name) shouldn't prevent the other actions and the
+/// lifecycle MIDI messages of the mapping from being executed.
+fn convert_lifecycle_actions(models: &[LifecycleActionModel]) -> Vec<LifecycleAction> {
+    models
+        .iter()
+        .filter_map(|m| match m.create_lifecycle_action() {
+            Ok(a) => Some(a),
+            Err(e) => {
+                tracing::warn!(msg = "Skipping invalid lifecycle action", ?m, %e);
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_valid_lifecycle_actions() {
+        // Given
+        let models = [send_osc_model(), set_parameter_model(0.5)];
+        // When
+        let actions = convert_lifecycle_actions(&models);
+        // Then
+        assert_eq!(actions.len(), 2);
+        assert!(matches!(
+            &actions[0],
+            LifecycleAction::SendOsc { device_id: None, message }
+                if message.addr == "/tablet/page" && message.args == vec![OscType::Int(2)]
+        ));
+        assert!(matches!(
+            actions[1],
+            LifecycleAction::SetCompartmentParameter { value, .. } if value.get() == 0.5
+        ));
+    }
+
+    #[test]
+    fn reject_invalid_lifecycle_action() {
+        // Given
+        let model = set_parameter_model(1.5);
+        // When
+        let result = model.create_lifecycle_action();
+        // Then
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn skip_invalid_lifecycle_actions() {
+        // Given
+        let models = [
+            set_parameter_model(1.5),
+            send_osc_model(),
+            set_parameter_model(-1.0),
+            set_parameter_model(1.0),
+        ];
+        // When
+        let actions = convert_lifecycle_actions(&models);
+        // Then
+        assert_eq!(actions.len(), 2);
+        assert!(matches!(actions[0], LifecycleAction::SendOsc { .. }));
+        assert!(matches!(
+            actions[1],
+            LifecycleAction::SetCompartmentParameter { value, .. } if value.get() == 1.0
+        ));
+    }
+
+    #[test]
+    fn keep_midi_messages_if_actions_are_invalid() {
+        // Given
+        let model = MappingExtensionModel {
+            on_activate: LifecycleModel {
+                send_midi_feedback: vec![LifecycleMidiMessageModel::Raw(
+                    RawMidiMessage::ByteArray(RawByteArrayMidiMessage(vec![0xb0, 0x07, 0x64])),
+                )],
+                actions: vec![set_parameter_model(1.5)],
+            },
+            on_deactivate: Default::default(),
+        };
+        // When
+        let result = model.create_mapping_extension();
+        // Then
+        assert!(result.is_ok());
+    }
+
+    fn send_osc_model() -> LifecycleActionModel {
+        LifecycleActionModel::SendOsc {
+            address: "/tablet/page".to_string(),
+            args: vec![OscArgValueModel::Int(2)],
+            device_id: None,
+        }
+    }
+
+    fn set_parameter_model(value: f64) -> LifecycleActionModel {
+        LifecycleActionModel::SetParameter {
+            index: CompartmentParamIndex::try_from(0).unwrap(),
+            value,
+        }
+    }
+}
@@ -604,7 +604,11 @@ impl MappingModel {
             options,
             self.extension_model
                 .create_mapping_extension()
-                .unwrap_or_default(),
+                .unwrap_or_else(|e| {
+                    tracing::warn!(msg = "Ignoring invalid lifecycle MIDI messages", %e);
+                    Default::default()
+                }),
+            self.mode_model.create_feedback_color_table(),
         )
     }
 }
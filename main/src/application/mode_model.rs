@@ -1,17 +1,20 @@
-use crate::domain::{Backbone, EelTransformation, LuaFeedbackScript, Mode};
+use crate::domain;
+use crate::domain::{
+    Backbone, EelTransformation, FeedbackColorStop, FeedbackColorTableKind, LuaFeedbackScript, Mode,
+};
 
 use helgoboss_learn::{
     check_mode_applicability, create_unit_value_interval, full_discrete_interval,
     full_unit_interval, AbsoluteMode, ButtonUsage, DetailedSourceCharacter, DiscreteIncrement,
     EncoderUsage, FeedbackProcessor, FeedbackType, FireMode, GroupInteraction, Interval,
-    ModeApplicabilityCheckInput, ModeParameter, ModeSettings, OutOfRangeBehavior, TakeoverMode,
-    UnitValue, ValueSequence, VirtualColor,
+    ModeApplicabilityCheckInput, ModeParameter, ModeSettings, OutOfRangeBehavior, RgbColor,
+    TakeoverMode, UnitValue, ValueSequence, VirtualColor,
 };
 
 use crate::application::{Affected, Change, GetProcessingRelevance, ProcessingRelevance};
 use crate::base::CloneAsDefault;
 use base::hash_util::clone_to_other_hash_map;
-use helgobox_api::persistence::FeedbackValueTable;
+use helgobox_api::persistence::{FeedbackColorTable, FeedbackValueTable};
 use std::time::Duration;
 
 pub enum ModeCommand {
@@ -51,6 +54,7 @@ pub enum ModeCommand {
     SetFeedbackColor(Option<VirtualColor>),
     SetFeedbackBackgroundColor(Option<VirtualColor>),
     SetFeedbackValueTable(Option<FeedbackValueTable>),
+    SetFeedbackColorTable(Option<FeedbackColorTable>),
     /// This doesn't reset the mode type, just all the values.
     ResetWithinType,
 }
@@ -83,6 +87,7 @@ pub enum ModeProp {
     FeedbackColor,
     FeedbackBackgroundColor,
     FeedbackValueTable,
+    FeedbackColorTable,
 }
 
 impl GetProcessingRelevance for ModeProp {
@@ -140,6 +145,7 @@ pub struct ModeModel {
     feedback_color: Option<VirtualColor>,
     feedback_background_color: Option<VirtualColor>,
     feedback_value_table: Option<FeedbackValueTable>,
+    feedback_color_table: Option<FeedbackColorTable>,
 }
 
 impl Default for ModeModel {
@@ -174,6 +180,7 @@ impl Default for ModeModel {
             feedback_color: Default::default(),
             feedback_background_color: Default::default(),
             feedback_value_table: None,
+            feedback_color_table: None,
         }
     }
 }
@@ -337,6 +344,10 @@ impl Change<'_> for ModeModel {
                 self.feedback_value_table = v;
                 One(P::FeedbackValueTable)
             }
+            C::SetFeedbackColorTable(v) => {
+                self.feedback_color_table = v;
+                One(P::FeedbackColorTable)
+            }
             C::ResetWithinType => {
                 *self = Default::default();
                 Multiple
@@ -365,6 +376,26 @@ impl ModeModel {
         self.feedback_value_table.as_ref()
     }
 
+    pub fn feedback_color_table(&self) -> Option<&FeedbackColorTable> {
+        self.feedback_color_table.as_ref()
+    }
+
+    pub fn create_feedback_color_table(&self) -> Option<domain::FeedbackColorTable> {
+        let (kind, content) = match self.feedback_color_table.as_ref()? {
+            FeedbackColorTable::Gradient(c) => (FeedbackColorTableKind::Gradient, c),
+            FeedbackColorTable::Threshold(c) => (FeedbackColorTableKind::Threshold, c),
+        };
+        let stops = content
+            .stops
+            .iter()
+            .map(|s| FeedbackColorStop {
+                value: UnitValue::new_clamped(s.value),
+                color: RgbColor::new(s.color.0, s.color.1, s.color.2),
+            })
+            .collect();
+        Some(domain::FeedbackColorTable::new(kind, stops))
+    }
+
     pub fn absolute_mode(&self) -> AbsoluteMode {
         self.absolute_mode
     }
@@ -3,10 +3,11 @@ use crate::application::{
 };
 use crate::base::CloneAsDefault;
 use crate::domain::{
-    Backbone, CompartmentKind, CompartmentParamIndex, CompoundMappingSource, EelMidiSourceScript,
-    ExtendedSourceCharacter, FlexibleMidiSourceScript, KeySource, Keystroke, LuaMidiSourceScript,
-    MidiSource, RealearnParameterSource, ReaperSource, SpeechSource, StreamDeckSource, TimerSource,
-    VirtualControlElement, VirtualControlElementId, VirtualSource,
+    Backbone, ChordSource, CompartmentKind, CompartmentParamIndex, CompoundMappingSource,
+    EelMidiSourceScript, EvdevSource, ExtendedSourceCharacter, FlexibleMidiSourceScript, KeySource,
+    Keystroke, LuaMidiSourceScript, MidiSource, MusicalTimerSource, RealearnParameterSource,
+    ReaperSource, SpeechSource, StreamDeckSource, TimerSource, VirtualControlElement,
+    VirtualControlElementId, VirtualSource, DEFAULT_CHORD_WINDOW, DEFAULT_MUSICAL_TIMER_INTERVAL,
 };
 use camino::{Utf8Path, Utf8PathBuf};
 use derive_more::Display;
@@ -18,8 +19,8 @@ use helgoboss_learn::{
 };
 use helgoboss_midi::{Channel, U14, U7};
 use helgobox_api::persistence::{
-    MidiScriptKind, StreamDeckButtonBackground, StreamDeckButtonDesign,
-    StreamDeckButtonFadingImageForeground, StreamDeckButtonForeground,
+    EvdevEventKind, MidiScriptKind, MusicalTimerUnit, StreamDeckButtonBackground,
+    StreamDeckButtonDesign, StreamDeckButtonFadingImageForeground, StreamDeckButtonForeground,
     StreamDeckButtonImageBackground, StreamDeckButtonSlidingImageForeground,
     VirtualControlElementCharacter,
 };
@@ -59,6 +60,10 @@ pub enum SourceCommand {
     SetOscFeedbackArgs(Vec<String>),
     SetReaperSourceType(ReaperSourceType),
     SetTimerMillis(u64),
+    SetMusicalTimerUnit(MusicalTimerUnit),
+    SetMusicalTimerInterval(f64),
+    SetMusicalTimerOffset(f64),
+    SetMusicalTimerOnlyWhilePlaying(bool),
     SetParameterIndex(CompartmentParamIndex),
     SetKeystroke(Option<Keystroke>),
     SetButtonIndex(u32),
@@ -67,8 +72,12 @@ pub enum SourceCommand {
     SetButtonForegroundType(StreamDeckButtonForegroundType),
     SetButtonForegroundImagePath(Utf8PathBuf),
     SetButtonStaticText(String),
+    SetEvdevEventKind(EvdevEventKind),
+    SetEvdevCode(u16),
     SetControlElementCharacter(VirtualControlElementCharacter),
     SetControlElementId(VirtualControlElementId),
+    SetChordMembers(Vec<SourceModel>),
+    SetChordWindowMillis(u64),
 }
 
 #[derive(Eq, PartialEq)]
@@ -98,6 +107,10 @@ pub enum SourceProp {
     ControlElementType,
     ControlElementId,
     TimerMillis,
+    MusicalTimerUnit,
+    MusicalTimerInterval,
+    MusicalTimerOffset,
+    MusicalTimerOnlyWhilePlaying,
     ParameterIndex,
     Keystroke,
     ButtonIndex,
@@ -106,6 +119,10 @@ pub enum SourceProp {
     ButtonForegroundType,
     ButtonForegroundImagePath,
     ButtonStaticText,
+    EvdevEventKind,
+    EvdevCode,
+    ChordMembers,
+    ChordWindowMillis,
 }
 
 impl GetProcessingRelevance for SourceProp {
@@ -224,6 +241,22 @@ impl Change<'_> for SourceModel {
                 self.timer_millis = v;
                 A::One(P::TimerMillis)
             }
+            C::SetMusicalTimerUnit(v) => {
+                self.musical_timer_unit = v;
+                A::One(P::MusicalTimerUnit)
+            }
+            C::SetMusicalTimerInterval(v) => {
+                self.musical_timer_interval = v;
+                A::One(P::MusicalTimerInterval)
+            }
+            C::SetMusicalTimerOffset(v) => {
+                self.musical_timer_offset = v;
+                A::One(P::MusicalTimerOffset)
+            }
+            C::SetMusicalTimerOnlyWhilePlaying(v) => {
+                self.musical_timer_only_while_playing = v;
+                A::One(P::MusicalTimerOnlyWhilePlaying)
+            }
             C::SetParameterIndex(v) => {
                 self.parameter_index = v;
                 A::One(P::ParameterIndex)
@@ -256,6 +289,22 @@ impl Change<'_> for SourceModel {
                 self.button_static_text = v;
                 A::One(P::ButtonStaticText)
             }
+            C::SetEvdevEventKind(v) => {
+                self.evdev_event_kind = v;
+                A::One(P::EvdevEventKind)
+            }
+            C::SetEvdevCode(v) => {
+                self.evdev_code = v;
+                A::One(P::EvdevCode)
+            }
+            C::SetChordMembers(v) => {
+                self.chord_members = v;
+                A::One(P::ChordMembers)
+            }
+            C::SetChordWindowMillis(v) => {
+                self.chord_window_millis = v;
+                A::One(P::ChordWindowMillis)
+            }
         };
         Some(affected)
     }
@@ -290,6 +339,10 @@ pub struct SourceModel {
     // REAPER
     reaper_source_type: ReaperSourceType,
     timer_millis: u64,
+    musical_timer_unit: MusicalTimerUnit,
+    musical_timer_interval: f64,
+    musical_timer_offset: f64,
+    musical_timer_only_while_playing: bool,
     parameter_index: CompartmentParamIndex,
     // Key
     keystroke: Option<Keystroke>,
@@ -300,9 +353,15 @@ pub struct SourceModel {
     button_foreground_type: StreamDeckButtonForegroundType,
     button_foreground_image_path: Utf8PathBuf,
     button_static_text: String,
+    // Evdev
+    evdev_event_kind: EvdevEventKind,
+    evdev_code: u16,
     // Virtual
     control_element_character: VirtualControlElementCharacter,
     control_element_id: VirtualControlElementId,
+    // Chord
+    chord_members: Vec<SourceModel>,
+    chord_window_millis: u64,
 }
 
 impl Default for SourceModel {
@@ -339,6 +398,10 @@ impl SourceModel {
             osc_feedback_args: vec![],
             reaper_source_type: Default::default(),
             timer_millis: Default::default(),
+            musical_timer_unit: Default::default(),
+            musical_timer_interval: DEFAULT_MUSICAL_TIMER_INTERVAL,
+            musical_timer_offset: 0.0,
+            musical_timer_only_while_playing: false,
             parameter_index: Default::default(),
             keystroke: None,
             button_index: 0,
@@ -347,6 +410,10 @@ impl SourceModel {
             button_foreground_type: Default::default(),
             button_foreground_image_path: Default::default(),
             button_static_text: Default::default(),
+            evdev_event_kind: Default::default(),
+            evdev_code: 0,
+            chord_members: vec![],
+            chord_window_millis: DEFAULT_CHORD_WINDOW.as_millis() as _,
         }
     }
 
@@ -462,6 +529,14 @@ impl SourceModel {
         self.button_foreground_type
     }
 
+    pub fn evdev_event_kind(&self) -> EvdevEventKind {
+        self.evdev_event_kind
+    }
+
+    pub fn evdev_code(&self) -> u16 {
+        self.evdev_code
+    }
+
     pub fn reaper_source_type(&self) -> ReaperSourceType {
         self.reaper_source_type
     }
@@ -474,6 +549,27 @@ impl SourceModel {
         self.timer_millis
     }
 
+    pub fn is_musical_timer(&self) -> bool {
+        self.category == SourceCategory::Reaper
+            && self.reaper_source_type == ReaperSourceType::MusicalTimer
+    }
+
+    pub fn musical_timer_unit(&self) -> MusicalTimerUnit {
+        self.musical_timer_unit
+    }
+
+    pub fn musical_timer_interval(&self) -> f64 {
+        self.musical_timer_interval
+    }
+
+    pub fn musical_timer_offset(&self) -> f64 {
+        self.musical_timer_offset
+    }
+
+    pub fn musical_timer_only_while_playing(&self) -> bool {
+        self.musical_timer_only_while_playing
+    }
+
     pub fn control_element_character(&self) -> VirtualControlElementCharacter {
         self.control_element_character
     }
@@ -482,13 +578,21 @@ impl SourceModel {
         self.control_element_id
     }
 
+    pub fn chord_members(&self) -> &[SourceModel] {
+        &self.chord_members
+    }
+
+    pub fn chord_window_millis(&self) -> u64 {
+        self.chord_window_millis
+    }
+
     pub fn supports_control(&self) -> bool {
         use SourceCategory as C;
         match self.category {
             C::Midi => self.midi_source_type.supports_control(),
             C::Osc => self.osc_arg_type_tag.supports_control(),
             C::Reaper => self.reaper_source_type.supports_control(),
-            C::Virtual | C::Keyboard | C::StreamDeck => true,
+            C::Virtual | C::Keyboard | C::StreamDeck | C::Evdev | C::Chord => true,
             // Main use case: Group interaction (follow-only).
             C::Never => true,
         }
@@ -501,7 +605,7 @@ impl SourceModel {
             C::Osc => self.osc_arg_type_tag.supports_feedback(),
             C::Reaper => self.reaper_source_type.supports_feedback(),
             C::StreamDeck | C::Virtual => true,
-            C::Keyboard | C::Never => false,
+            C::Keyboard | C::Evdev | C::Chord | C::Never => false,
         }
     }
 
@@ -591,6 +695,12 @@ impl SourceModel {
                     RealearnParameter(p) => {
                         self.parameter_index = p.parameter_index;
                     }
+                    MusicalTimer(t) => {
+                        self.musical_timer_unit = t.unit();
+                        self.musical_timer_interval = t.interval();
+                        self.musical_timer_offset = t.offset();
+                        self.musical_timer_only_while_playing = t.only_while_playing();
+                    }
                     MidiDeviceChanges
                     | RealearnInstanceStart
                     | RealearnCompartmentLoaded
@@ -602,6 +712,11 @@ impl SourceModel {
                 self.category = SourceCategory::StreamDeck;
                 self.button_index = s.button_index;
             }
+            S::Evdev(s) => {
+                self.category = SourceCategory::Evdev;
+                self.evdev_event_kind = s.event_kind;
+                self.evdev_code = s.code;
+            }
             S::Never => {
                 self.category = SourceCategory::Never;
             }
@@ -609,6 +724,19 @@ impl SourceModel {
                 self.category = SourceCategory::Keyboard;
                 self.keystroke = Some(s.stroke());
             }
+            S::Chord(s) => {
+                self.category = SourceCategory::Chord;
+                self.chord_members = s
+                    .members()
+                    .iter()
+                    .map(|m| {
+                        let mut member = SourceModel::new();
+                        let _ = member.apply_from_source(m);
+                        member
+                    })
+                    .collect();
+                self.chord_window_millis = s.window().as_millis() as _;
+            }
         };
         Some(Affected::Multiple)
     }
@@ -651,7 +779,14 @@ impl SourceModel {
                 DetailedSourceCharacter::RangeControl,
                 DetailedSourceCharacter::Relative,
             ],
-            CompoundMappingSource::Key(_) | CompoundMappingSource::StreamDeck(_) => {
+            CompoundMappingSource::Evdev(s) => match s.event_kind {
+                EvdevEventKind::Key => vec![DetailedSourceCharacter::MomentaryOnOffButton],
+                EvdevEventKind::AbsoluteAxis => vec![DetailedSourceCharacter::RangeControl],
+                EvdevEventKind::RelativeAxis => vec![DetailedSourceCharacter::Relative],
+            },
+            CompoundMappingSource::Key(_)
+            | CompoundMappingSource::StreamDeck(_)
+            | CompoundMappingSource::Chord(_) => {
                 vec![DetailedSourceCharacter::MomentaryOnOffButton]
             }
         }
@@ -735,6 +870,9 @@ impl SourceModel {
                                             .ok()?,
                                     )
                                 }
+                                MidiScriptKind::Wasm => FlexibleMidiSourceScript::Wasm(
+                                    WasmMidiSourceScript::compile(&self.midi_script).ok()?,
+                                ),
                             };
                             CloneAsDefault::new(Some(script))
                         },
@@ -767,6 +905,7 @@ impl SourceModel {
                     RealearnUnitStart => ReaperSource::RealearnInstanceStart,
                     RealearnCompartmentLoaded => ReaperSource::RealearnCompartmentLoaded,
                     Timer => ReaperSource::Timer(self.create_timer_source()),
+                    MusicalTimer => ReaperSource::MusicalTimer(self.create_musical_timer_source()),
                     RealearnParameter => {
                         ReaperSource::RealearnParameter(self.create_realearn_parameter_source())
                     }
@@ -776,6 +915,8 @@ impl SourceModel {
             }
             Keyboard => CompoundMappingSource::Key(self.create_key_source()?),
             StreamDeck => CompoundMappingSource::StreamDeck(self.create_stream_deck_source()),
+            Evdev => CompoundMappingSource::Evdev(self.create_evdev_source()),
+            Chord => CompoundMappingSource::Chord(self.create_chord_source()),
             Never => CompoundMappingSource::Never,
         };
         Some(source)
@@ -825,6 +966,20 @@ impl SourceModel {
         }
     }
 
+    fn create_evdev_source(&self) -> EvdevSource {
+        EvdevSource::new(self.evdev_event_kind, self.evdev_code)
+    }
+
+    fn create_chord_source(&self) -> ChordSource {
+        let members = self
+            .chord_members
+            .iter()
+            .filter(|m| m.category.is_allowed_as_chord_member())
+            .map(|m| m.create_source())
+            .collect();
+        ChordSource::new(members, Duration::from_millis(self.chord_window_millis))
+    }
+
     pub fn create_key_source(&self) -> Option<KeySource> {
         Some(KeySource::new(self.keystroke?))
     }
@@ -833,6 +988,15 @@ impl SourceModel {
         TimerSource::new(Duration::from_millis(self.timer_millis))
     }
 
+    fn create_musical_timer_source(&self) -> MusicalTimerSource {
+        MusicalTimerSource::new(
+            self.musical_timer_unit,
+            self.musical_timer_interval,
+            self.musical_timer_offset,
+            self.musical_timer_only_while_playing,
+        )
+    }
+
     fn create_realearn_parameter_source(&self) -> RealearnParameterSource {
         RealearnParameterSource {
             parameter_index: self.parameter_index,
@@ -1068,6 +1232,17 @@ impl Display for SourceModel {
                     ReaperSourceType::Timer => {
                         vec![type_label, format!("{} ms", self.timer_millis).into()]
                     }
+                    ReaperSourceType::MusicalTimer => {
+                        vec![
+                            type_label,
+                            format!(
+                                "Every {} {}",
+                                self.musical_timer_interval,
+                                self.musical_timer_unit.to_string().to_lowercase()
+                            )
+                            .into(),
+                        ]
+                    }
                     ReaperSourceType::RealearnParameter => {
                         vec![
                             type_label,
@@ -1091,6 +1266,15 @@ impl Display for SourceModel {
                 let text = self.create_stream_deck_source().to_string();
                 vec![Cow::Owned(text)]
             }
+            Evdev => vec![
+                "Evdev".into(),
+                self.create_evdev_source().to_string().into(),
+            ],
+            Chord => vec![
+                "Chord".into(),
+                format!("{} members", self.chord_members.len()).into(),
+                format!("Within {} ms", self.chord_window_millis).into(),
+            ],
         };
         let non_empty_lines: Vec<_> = lines.into_iter().filter(|l| !l.is_empty()).collect();
         write!(f, "{}", non_empty_lines.join("\n"))
@@ -1134,6 +1318,14 @@ pub enum SourceCategory {
     #[serde(rename = "stream-deck")]
     #[display(fmt = "Stream Deck")]
     StreamDeck,
+    #[serde(rename = "evdev")]
+    #[display(fmt = "Evdev (Linux)")]
+    Evdev,
+    // Must stay in front of "Virtual" because the source category combo box omits "Virtual" in
+    // the controller compartment and relies on the remaining indexes to stay the same.
+    #[serde(rename = "chord")]
+    #[display(fmt = "Chord")]
+    Chord,
     #[serde(rename = "virtual")]
     #[display(fmt = "Virtual")]
     Virtual,
@@ -1158,11 +1350,19 @@ impl SourceCategory {
                 Reaper => true,
                 Keyboard => true,
                 StreamDeck => true,
+                Evdev => true,
+                Chord => true,
                 Virtual => false,
             },
             CompartmentKind::Main => true,
         }
     }
+
+    /// Chords can only be made of sources which represent physical buttons.
+    pub fn is_allowed_as_chord_member(self) -> bool {
+        use SourceCategory::*;
+        matches!(self, Midi | Osc | Keyboard | StreamDeck | Evdev)
+    }
 }
 
 /// Type of a MIDI source
@@ -1339,6 +1539,9 @@ pub enum ReaperSourceType {
     #[serde(rename = "speech")]
     #[display(fmt = "Speech (feedback only, no Linux)")]
     Speech,
+    #[serde(rename = "musical-timer")]
+    #[display(fmt = "Musical timer")]
+    MusicalTimer,
 }
 
 impl ReaperSourceType {
@@ -1349,6 +1552,7 @@ impl ReaperSourceType {
             RealearnInstanceStart => Self::RealearnUnitStart,
             RealearnCompartmentLoaded => Self::RealearnCompartmentLoaded,
             Timer(_) => Self::Timer,
+            MusicalTimer(_) => Self::MusicalTimer,
             RealearnParameter(_) => Self::RealearnParameter,
             Speech(_) => Self::Speech,
         }
@@ -1361,6 +1565,7 @@ impl ReaperSourceType {
             | RealearnUnitStart
             | RealearnCompartmentLoaded
             | Timer
+            | MusicalTimer
             | RealearnParameter => true,
             Speech => false,
         }
@@ -1373,6 +1578,7 @@ impl ReaperSourceType {
             | RealearnUnitStart
             | RealearnCompartmentLoaded
             | Timer
+            | MusicalTimer
             | RealearnParameter => false,
             Speech => true,
         }
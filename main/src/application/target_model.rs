@@ -4,6 +4,7 @@ use helgoboss_learn::{
     AbsoluteValue, ControlType, Interval, OscArgDescriptor, OscTypeTag, Target,
     DEFAULT_OSC_ARG_VALUE_RANGE,
 };
+use helgoboss_midi::Channel;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use reaper_high::{
     Action, BookmarkType, Fx, FxChain, FxParameter, Guid, Project, Track, TrackRoute,
@@ -19,38 +20,46 @@ use crate::application::{
 use crate::domain::{
     find_bookmark, get_fx_name, get_fx_params, get_non_present_virtual_route_label,
     get_non_present_virtual_track_label, get_track_routes, ActionInvocationType, AnyOnParameter,
-    CompartmentKind, CompartmentParamIndex, CompoundMappingTarget, Exclusivity,
-    ExpressionEvaluator, ExtendedProcessorContext, FeedbackResolution, FxDescriptor, FxDisplayType,
-    FxParameterDescriptor, GroupId, MappingId, MappingKey, MappingRef, MappingSnapshotId,
-    MouseActionType, OscDeviceId, PotFilterItemsTargetSettings, ProcessorContext,
-    QualifiedMappingId, RealearnTarget, ReaperTarget, ReaperTargetType, SeekOptions,
-    SendMidiDestinationType, SoloBehavior, Tag, TagScope, TouchedRouteParameterType,
+    BookmarkAddress, CompartmentKind, CompartmentParamIndex, CompoundMappingTarget, EnvelopeRef,
+    Exclusivity, ExpressionEvaluator, ExtendedProcessorContext, FeedbackResolution, FxDescriptor,
+    FxDisplayType, FxParameterDescriptor, GroupId, MappingId, MappingKey, MappingRef,
+    MappingSnapshotId, MouseActionType, OscDeviceId, PotFilterItemsTargetSettings,
+    ProcessorContext, QualifiedMappingId, RealearnTarget, ReaperTarget, ReaperTargetType,
+    SeekOptions, SendMidiDestinationType, SoloBehavior, Tag, TagScope, TouchedRouteParameterType,
     TouchedTrackParameterType, TrackDescriptor, TrackExclusivity, TrackGangBehavior,
     TrackRouteDescriptor, TrackRouteSelector, TrackRouteType, TransportAction,
-    UnresolvedActionTarget, UnresolvedAllTrackFxEnableTarget, UnresolvedAnyOnTarget,
-    UnresolvedAutomationModeOverrideTarget, UnresolvedBrowseFxsTarget, UnresolvedBrowseGroupTarget,
+    UnresolvedActionTarget, UnresolvedAddBookmarkTarget, UnresolvedAllTrackFxEnableTarget,
+    UnresolvedAnyOnTarget, UnresolvedAutomationModeOverrideTarget, UnresolvedBookmarkAddress,
+    UnresolvedBrowseEnvelopePointsTarget, UnresolvedBrowseFxsTarget, UnresolvedBrowseGroupTarget,
     UnresolvedBrowsePotFilterItemsTarget, UnresolvedBrowsePotPresetsTarget,
-    UnresolvedBrowseTracksTarget, UnresolvedCompartmentParameterValueTarget,
-    UnresolvedCompoundMappingTarget, UnresolvedDummyTarget, UnresolvedEnableInstancesTarget,
-    UnresolvedEnableMappingsTarget, UnresolvedEnableUnitsTarget, UnresolvedFxEnableTarget,
+    UnresolvedBrowseTakesTarget, UnresolvedBrowseTracksTarget,
+    UnresolvedCompartmentParameterValueTarget, UnresolvedCompoundMappingTarget,
+    UnresolvedDeleteBookmarkTarget, UnresolvedDummyTarget, UnresolvedEnableInstancesTarget,
+    UnresolvedEnableMappingsTarget, UnresolvedEnableUnitsTarget, UnresolvedEnvelopeArmStateTarget,
+    UnresolvedEnvelopeValueTarget, UnresolvedFxEnableTarget, UnresolvedFxNameTarget,
     UnresolvedFxOnlineTarget, UnresolvedFxOpenTarget, UnresolvedFxParameterTarget,
     UnresolvedFxParameterTouchStateTarget, UnresolvedFxPresetTarget, UnresolvedFxToolTarget,
-    UnresolvedGoToBookmarkTarget, UnresolvedLastTouchedTarget, UnresolvedLoadFxSnapshotTarget,
-    UnresolvedLoadMappingSnapshotTarget, UnresolvedLoadPotPresetTarget, UnresolvedMidiSendTarget,
-    UnresolvedModifyMappingTarget, UnresolvedMouseTarget, UnresolvedOscSendTarget,
+    UnresolvedGoToBookmarkTarget, UnresolvedItemParameterValueTarget, UnresolvedLastTouchedTarget,
+    UnresolvedLoadFxSnapshotTarget, UnresolvedLoadMappingSnapshotTarget,
+    UnresolvedLoadPotPresetTarget, UnresolvedMidiSendTarget, UnresolvedModifyMappingTarget,
+    UnresolvedMorphMappingSnapshotsTarget, UnresolvedMouseTarget, UnresolvedOscSendTarget,
     UnresolvedPlayrateTarget, UnresolvedPreviewPotPresetTarget, UnresolvedReaperTarget,
-    UnresolvedRouteAutomationModeTarget, UnresolvedRouteMonoTarget, UnresolvedRouteMuteTarget,
-    UnresolvedRoutePanTarget, UnresolvedRoutePhaseTarget, UnresolvedRouteTouchStateTarget,
-    UnresolvedRouteVolumeTarget, UnresolvedSeekTarget, UnresolvedStreamDeckBrightnessTarget,
-    UnresolvedTakeMappingSnapshotTarget, UnresolvedTempoTarget, UnresolvedTrackArmTarget,
-    UnresolvedTrackAutomationModeTarget, UnresolvedTrackMonitoringModeTarget,
-    UnresolvedTrackMuteTarget, UnresolvedTrackPanTarget, UnresolvedTrackParentSendTarget,
-    UnresolvedTrackPeakTarget, UnresolvedTrackPhaseTarget, UnresolvedTrackSelectionTarget,
-    UnresolvedTrackShowTarget, UnresolvedTrackSoloTarget, UnresolvedTrackToolTarget,
-    UnresolvedTrackTouchStateTarget, UnresolvedTrackVolumeTarget, UnresolvedTrackWidthTarget,
-    UnresolvedTransportTarget, VirtualChainFx, VirtualControlElement, VirtualControlElementId,
-    VirtualFx, VirtualFxParameter, VirtualMappingSnapshotIdForLoad,
-    VirtualMappingSnapshotIdForTake, VirtualTarget, VirtualTrack, VirtualTrackRoute,
+    UnresolvedRenameBookmarkTarget, UnresolvedRouteAutomationModeTarget, UnresolvedRouteMonoTarget,
+    UnresolvedRouteMuteTarget, UnresolvedRoutePanTarget, UnresolvedRoutePhaseTarget,
+    UnresolvedRouteTouchStateTarget, UnresolvedRouteVolumeTarget, UnresolvedSeekTarget,
+    UnresolvedStreamDeckBrightnessTarget, UnresolvedTakeMappingSnapshotTarget,
+    UnresolvedTakeParameterValueTarget, UnresolvedTempoTarget, UnresolvedTrackArmTarget,
+    UnresolvedTrackAutomationModeTarget, UnresolvedTrackColorTarget,
+    UnresolvedTrackInputFxEnableTarget, UnresolvedTrackMonitoringModeTarget,
+    UnresolvedTrackMuteTarget, UnresolvedTrackNameTarget, UnresolvedTrackPanTarget,
+    UnresolvedTrackParentSendTarget, UnresolvedTrackPeakTarget, UnresolvedTrackPhaseTarget,
+    UnresolvedTrackRecordInputTarget, UnresolvedTrackRecordModeTarget,
+    UnresolvedTrackSelectionTarget, UnresolvedTrackShowTarget, UnresolvedTrackSoloTarget,
+    UnresolvedTrackToolTarget, UnresolvedTrackTouchStateTarget, UnresolvedTrackVolumeTarget,
+    UnresolvedTrackWidthTarget, UnresolvedTransportTarget, VirtualChainFx, VirtualControlElement,
+    VirtualControlElementId, VirtualEnvelope, VirtualFx, VirtualFxParameter, VirtualItem,
+    VirtualMappingSnapshotIdForLoad, VirtualMappingSnapshotIdForTake, VirtualTake, VirtualTarget,
+    VirtualTrack, VirtualTrackRoute, DEFAULT_BOOKMARK_TOLERANCE,
 };
 
 use crate::domain::{VirtualPlaytimeColumn, VirtualPlaytimeRow, VirtualPlaytimeSlot};
@@ -61,24 +70,27 @@ use std::error::Error;
 use crate::domain::ui_util::format_tags_as_csv;
 use base::hash_util::NonCryptoHashSet;
 use helgobox_api::persistence::{
-    ActionScope, Axis, BrowseTracksMode, ClipColumnTrackContext, FxChainDescriptor,
-    FxDescriptorCommons, FxToolAction, InputDeviceMidiDestination, InstanceTagKind,
-    LearnTargetMappingModification, LearnableTargetKind, MappingModification,
-    MappingSnapshotDescForLoad, MappingSnapshotDescForTake, MonitoringMode, MouseAction,
-    MouseButton, PlaytimeColumnAction, PlaytimeColumnDescriptor, PlaytimeMatrixAction,
-    PlaytimeRowAction, PlaytimeRowDescriptor, PlaytimeSlotDescriptor, PlaytimeSlotManagementAction,
-    PlaytimeSlotTransportAction, PotFilterKind, SeekBehavior, SendMidiDestination,
-    SetTargetToLastTouchedMappingModification, TargetTouchCause, TrackDescriptorCommons,
-    TrackFxChain, TrackScope, TrackToolAction, VirtualControlElementCharacter,
+    ActionScope, Axis, BrowseTracksMode, ClipColumnTrackContext, EnvelopePosition,
+    FxChainDescriptor, FxDescriptorCommons, FxNameKind, FxToolAction, InputDeviceMidiDestination,
+    InstanceTagKind, ItemDescriptor, ItemParameter, LearnTargetMappingModification,
+    LearnableTargetKind, MappingModification, MappingSnapshotDescForLoad,
+    MappingSnapshotDescForTake, MonitoringMode, MouseAction, MouseButton, PlaytimeColumnAction,
+    PlaytimeColumnDescriptor, PlaytimeMatrixAction, PlaytimeRowAction, PlaytimeRowDescriptor,
+    PlaytimeSlotDescriptor, PlaytimeSlotManagementAction, PlaytimeSlotTransportAction,
+    PotFilterKind, RecordInput, RecordMode, SeekBehavior, SendMidiDestination,
+    SetTargetToLastTouchedMappingModification, TakeDescriptor, TakeParameter, TargetTouchCause,
+    TrackDescriptorCommons, TrackFxChain, TrackScope, TrackToolAction,
+    VirtualControlElementCharacter,
 };
 use playtime_api::persistence::ColumnAddress;
 use reaper_medium::{
     AutomationMode, BookmarkId, GlobalAutomationModeOverride, InputMonitoringMode,
-    MidiInputDeviceId, SectionId, TrackArea, TrackLocation, TrackSendDirection,
+    MidiInputDeviceId, RecordingInput, SectionId, TrackArea, TrackLocation, TrackSendDirection,
 };
 use std::fmt;
 use std::fmt::{Display, Formatter};
 use std::rc::Rc;
+use std::time::Duration;
 use strum::{EnumIter, IntoEnumIterator};
 use wildmatch::WildMatch;
 
@@ -122,6 +134,7 @@ pub enum TargetCommand {
     SetGangBehavior(TrackGangBehavior),
     SetBrowseTracksMode(BrowseTracksMode),
     SetFxToolAction(FxToolAction),
+    SetFxNameKind(FxNameKind),
     SetTransportAction(TransportAction),
     SetAnyOnParameter(AnyOnParameter),
     SetFxSnapshot(Option<FxSnapshot>),
@@ -130,6 +143,8 @@ pub enum TargetCommand {
     SetBookmarkRef(u32),
     SetBookmarkType(BookmarkType),
     SetBookmarkAnchorType(BookmarkAnchorType),
+    SetUseNearestMarker(bool),
+    SetBookmarkTolerance(Duration),
     SetUseTimeSelection(bool),
     SetUseLoopPoints(bool),
     SetUseRegions(bool),
@@ -140,6 +155,8 @@ pub enum TargetCommand {
     SetTrackArea(RealearnTrackArea),
     SetAutomationMode(RealearnAutomationMode),
     SetMonitoringMode(MonitoringMode),
+    SetRecordInput(RecordInput),
+    SetRecordMode(RecordMode),
     SetAutomationModeOverrideType(AutomationModeOverrideType),
     SetFxDisplayType(FxDisplayType),
     SetScrollArrangeView(bool),
@@ -164,6 +181,12 @@ pub enum TargetCommand {
     SetPlaytimeColumnAction(PlaytimeColumnAction),
     SetPlaytimeRowAction(PlaytimeRowAction),
     SetStopColumnIfSlotEmpty(bool),
+    SetItem(ItemDescriptor),
+    SetTake(TakeDescriptor),
+    SetItemParameter(ItemParameter),
+    SetTakeParameter(TakeParameter),
+    SetEnvelopeName(String),
+    SetEnvelopePosition(EnvelopePosition),
     SetPollForFeedback(bool),
     SetInstanceTagKind(InstanceTagKind),
     SetTags(Vec<Tag>),
@@ -173,6 +196,7 @@ pub enum TargetCommand {
     SetMappingSnapshotTypeForLoad(MappingSnapshotTypeForLoad),
     SetMappingSnapshotTypeForTake(MappingSnapshotTypeForTake),
     SetMappingSnapshotId(Option<MappingSnapshotId>),
+    SetSecondMappingSnapshotId(Option<MappingSnapshotId>),
     SetMappingSnapshotDefaultValue(Option<AbsoluteValue>),
     SetPotFilterItemKind(PotFilterKind),
     SetMappingModificationKind(MappingModificationKind),
@@ -225,6 +249,7 @@ pub enum TargetProp {
     GangBehavior,
     BrowseTracksMode,
     FxToolAction,
+    FxNameKind,
     TransportAction,
     AnyOnParameter,
     FxSnapshot,
@@ -233,6 +258,8 @@ pub enum TargetProp {
     BookmarkRef,
     BookmarkType,
     BookmarkAnchorType,
+    UseNearestMarker,
+    BookmarkTolerance,
     UseTimeSelection,
     UseLoopPoints,
     UseRegions,
@@ -243,6 +270,8 @@ pub enum TargetProp {
     TrackArea,
     AutomationMode,
     MonitoringMode,
+    RecordInput,
+    RecordMode,
     AutomationModeOverrideType,
     FxDisplayType,
     ScrollArrangeView,
@@ -267,6 +296,12 @@ pub enum TargetProp {
     PlaytimeColumnAction,
     PlaytimeRowAction,
     StopColumnIfSlotEmpty,
+    Item,
+    Take,
+    ItemParameter,
+    TakeParameter,
+    EnvelopeName,
+    EnvelopePosition,
     PollForFeedback,
     InstanceTagKind,
     Tags,
@@ -276,6 +311,7 @@ pub enum TargetProp {
     MappingSnapshotTypeForLoad,
     MappingSnapshotTypeForTake,
     MappingSnapshotId,
+    SecondMappingSnapshotId,
     MappingSnapshotDefaultValue,
     PotFilterItemKind,
     MappingModificationKind,
@@ -452,6 +488,10 @@ impl Change<'_> for TargetModel {
                 self.fx_tool_action = v;
                 One(P::FxToolAction)
             }
+            C::SetFxNameKind(v) => {
+                self.fx_name_kind = v;
+                One(P::FxNameKind)
+            }
             C::SetTransportAction(v) => {
                 self.transport_action = v;
                 One(P::TransportAction)
@@ -484,6 +524,14 @@ impl Change<'_> for TargetModel {
                 self.bookmark_anchor_type = v;
                 One(P::BookmarkAnchorType)
             }
+            C::SetUseNearestMarker(v) => {
+                self.use_nearest_marker = v;
+                One(P::UseNearestMarker)
+            }
+            C::SetBookmarkTolerance(v) => {
+                self.bookmark_tolerance = v;
+                One(P::BookmarkTolerance)
+            }
             C::SetUseTimeSelection(v) => {
                 self.use_time_selection = v;
                 One(P::UseTimeSelection)
@@ -524,6 +572,14 @@ impl Change<'_> for TargetModel {
                 self.monitoring_mode = v;
                 One(P::MonitoringMode)
             }
+            C::SetRecordInput(v) => {
+                self.record_input = v;
+                One(P::RecordInput)
+            }
+            C::SetRecordMode(v) => {
+                self.record_mode = v;
+                One(P::RecordMode)
+            }
             C::SetAutomationModeOverrideType(v) => {
                 self.automation_mode_override_type = v;
                 One(P::AutomationModeOverrideType)
@@ -620,6 +676,10 @@ impl Change<'_> for TargetModel {
                 self.mapping_snapshot_id = v;
                 One(P::MappingSnapshotId)
             }
+            C::SetSecondMappingSnapshotId(v) => {
+                self.second_mapping_snapshot_id = v;
+                One(P::SecondMappingSnapshotId)
+            }
             C::SetMappingSnapshotDefaultValue(v) => {
                 self.mapping_snapshot_default_value = v;
                 One(P::MappingSnapshotDefaultValue)
@@ -660,6 +720,30 @@ impl Change<'_> for TargetModel {
                 self.stop_column_if_slot_empty = v;
                 One(P::StopColumnIfSlotEmpty)
             }
+            C::SetItem(v) => {
+                self.item = v;
+                One(P::Item)
+            }
+            C::SetTake(v) => {
+                self.take = v;
+                One(P::Take)
+            }
+            C::SetItemParameter(v) => {
+                self.item_parameter = v;
+                One(P::ItemParameter)
+            }
+            C::SetTakeParameter(v) => {
+                self.take_parameter = v;
+                One(P::TakeParameter)
+            }
+            C::SetEnvelopeName(v) => {
+                self.envelope_name = v;
+                One(P::EnvelopeName)
+            }
+            C::SetEnvelopePosition(v) => {
+                self.envelope_position = v;
+                One(P::EnvelopePosition)
+            }
             C::SetPotFilterItemKind(v) => {
                 self.pot_filter_item_kind = v;
                 One(P::PotFilterItemKind)
@@ -723,6 +807,7 @@ pub struct TargetModel {
     fx_expression: String,
     enable_only_if_fx_has_focus: bool,
     fx_tool_action: FxToolAction,
+    fx_name_kind: FxNameKind,
     // # For track FX or compartment parameter targets
     param_index: u32,
     // # For track FX parameter targets
@@ -757,6 +842,11 @@ pub struct TargetModel {
     bookmark_ref: u32,
     bookmark_type: BookmarkType,
     bookmark_anchor_type: BookmarkAnchorType,
+    // # For marker/region editing targets
+    /// If `true`, the marker nearest to the play position is edited instead of the one given by
+    /// the bookmark fields above.
+    use_nearest_marker: bool,
+    bookmark_tolerance: Duration,
     // # For "Go to marker/region" target and "Seek" target
     use_time_selection: bool,
     use_loop_points: bool,
@@ -772,6 +862,10 @@ pub struct TargetModel {
     automation_mode: RealearnAutomationMode,
     // # For track monitoring mode target
     monitoring_mode: MonitoringMode,
+    // # For track record input target
+    record_input: RecordInput,
+    // # For track record mode target
+    record_mode: RecordMode,
     // # For automation mode override target
     automation_mode_override_type: AutomationModeOverrideType,
     // # For FX Open and Browse FXs target
@@ -803,6 +897,15 @@ pub struct TargetModel {
     playtime_column_action: PlaytimeColumnAction,
     playtime_row_action: PlaytimeRowAction,
     stop_column_if_slot_empty: bool,
+    // # For media item targets
+    item: ItemDescriptor,
+    take: TakeDescriptor,
+    item_parameter: ItemParameter,
+    take_parameter: TakeParameter,
+    // # For envelope targets
+    /// Name of the track envelope. If empty, the envelope of the FX parameter is used.
+    envelope_name: String,
+    envelope_position: EnvelopePosition,
     // # For targets that might have to be polled to get automatic feedback in all cases.
     poll_for_feedback: bool,
     instance_tag_kind: InstanceTagKind,
@@ -810,6 +913,8 @@ pub struct TargetModel {
     mapping_snapshot_type_for_load: MappingSnapshotTypeForLoad,
     mapping_snapshot_type_for_take: MappingSnapshotTypeForTake,
     mapping_snapshot_id: Option<MappingSnapshotId>,
+    /// Only used for morphing between mapping snapshots (snapshot B).
+    second_mapping_snapshot_id: Option<MappingSnapshotId>,
     mapping_snapshot_default_value: Option<AbsoluteValue>,
     exclusivity: Exclusivity,
     group_id: GroupId,
@@ -917,6 +1022,8 @@ impl Default for TargetModel {
             bookmark_ref: 0,
             bookmark_type: BookmarkType::Marker,
             bookmark_anchor_type: Default::default(),
+            use_nearest_marker: false,
+            bookmark_tolerance: DEFAULT_BOOKMARK_TOLERANCE,
             use_time_selection: false,
             use_loop_points: false,
             use_regions: false,
@@ -927,6 +1034,8 @@ impl Default for TargetModel {
             track_area: Default::default(),
             automation_mode: Default::default(),
             monitoring_mode: Default::default(),
+            record_input: Default::default(),
+            record_mode: Default::default(),
             automation_mode_override_type: Default::default(),
             fx_display_type: Default::default(),
             scroll_arrange_view: false,
@@ -948,6 +1057,7 @@ impl Default for TargetModel {
             mapping_snapshot_type_for_load: MappingSnapshotTypeForLoad::Initial,
             mapping_snapshot_type_for_take: MappingSnapshotTypeForTake::LastLoaded,
             mapping_snapshot_id: None,
+            second_mapping_snapshot_id: None,
             mapping_snapshot_default_value: None,
             exclusivity: Default::default(),
             group_id: Default::default(),
@@ -960,10 +1070,17 @@ impl Default for TargetModel {
             playtime_column_action: Default::default(),
             playtime_matrix_action: Default::default(),
             stop_column_if_slot_empty: false,
+            item: Default::default(),
+            take: Default::default(),
+            item_parameter: Default::default(),
+            take_parameter: Default::default(),
+            envelope_name: Default::default(),
+            envelope_position: Default::default(),
             clip_column_track_context: Default::default(),
             playtime_row_action: Default::default(),
             track_tool_action: Default::default(),
             fx_tool_action: Default::default(),
+            fx_name_kind: Default::default(),
             gang_behavior: Default::default(),
             browse_tracks_mode: Default::default(),
             pot_filter_item_kind: Default::default(),
@@ -1128,6 +1245,10 @@ impl TargetModel {
         self.fx_tool_action
     }
 
+    pub fn fx_name_kind(&self) -> FxNameKind {
+        self.fx_name_kind
+    }
+
     pub fn transport_action(&self) -> TransportAction {
         self.transport_action
     }
@@ -1164,6 +1285,10 @@ impl TargetModel {
         self.mapping_snapshot_id.as_ref()
     }
 
+    pub fn second_mapping_snapshot_id(&self) -> Option<&MappingSnapshotId> {
+        self.second_mapping_snapshot_id.as_ref()
+    }
+
     pub fn touched_track_parameter_type(&self) -> TouchedTrackParameterType {
         self.touched_track_parameter_type
     }
@@ -1184,6 +1309,14 @@ impl TargetModel {
         self.bookmark_anchor_type
     }
 
+    pub fn use_nearest_marker(&self) -> bool {
+        self.use_nearest_marker
+    }
+
+    pub fn bookmark_tolerance(&self) -> Duration {
+        self.bookmark_tolerance
+    }
+
     pub fn use_time_selection(&self) -> bool {
         self.use_time_selection
     }
@@ -1224,6 +1357,23 @@ impl TargetModel {
         self.monitoring_mode
     }
 
+    pub fn record_input(&self) -> RecordInput {
+        self.record_input
+    }
+
+    pub fn record_input_kind(&self) -> RecordInputKind {
+        match self.record_input {
+            RecordInput::None => RecordInputKind::None,
+            RecordInput::Mono { .. } => RecordInputKind::Mono,
+            RecordInput::Stereo { .. } => RecordInputKind::Stereo,
+            RecordInput::Midi { .. } => RecordInputKind::Midi,
+        }
+    }
+
+    pub fn record_mode(&self) -> RecordMode {
+        self.record_mode
+    }
+
     pub fn automation_mode_override_type(&self) -> AutomationModeOverrideType {
         self.automation_mode_override_type
     }
@@ -1802,12 +1952,33 @@ impl TargetModel {
                 self.bookmark_ref = t.index;
                 self.bookmark_type = t.bookmark_type;
             }
+            AddBookmark(t) => {
+                self.bookmark_type = t.bookmark_type;
+                self.bookmark_tolerance = t.tolerance;
+            }
+            RenameBookmark(t) => {
+                self.bookmark_tolerance = t.tolerance;
+                self.apply_from_bookmark_address(t.address);
+            }
+            DeleteBookmark(t) => {
+                self.bookmark_tolerance = t.tolerance;
+                self.apply_from_bookmark_address(t.address);
+            }
             TrackAutomationMode(t) => {
                 self.automation_mode = RealearnAutomationMode::from_reaper(t.mode);
             }
             TrackMonitoringMode(t) => {
                 self.monitoring_mode = convert_monitoring_mode_to_realearn(t.mode);
             }
+            TrackRecordInput(t) => {
+                self.record_input = convert_record_input_to_realearn(t.input);
+            }
+            TrackRecordMode(t) => {
+                self.record_mode = t.mode;
+            }
+            FxName(t) => {
+                self.fx_name_kind = t.kind;
+            }
             RouteAutomationMode(t) => {
                 self.automation_mode = RealearnAutomationMode::from_reaper(t.mode);
             }
@@ -1829,6 +2000,22 @@ impl TargetModel {
             PlaytimeColumnAction(t) => {
                 self.playtime_column_action = t.action;
             }
+            ItemParameterValue(t) => {
+                self.item_parameter = t.parameter;
+            }
+            TakeParameterValue(t) => {
+                self.take_parameter = t.parameter;
+            }
+            EnvelopeValue(t) => {
+                self.envelope_position = t.position;
+                self.apply_from_envelope(&t.envelope);
+            }
+            BrowseEnvelopePoints(t) => {
+                self.apply_from_envelope(&t.envelope);
+            }
+            EnvelopeArmState(t) => {
+                self.apply_from_envelope(&t.envelope);
+            }
             PlaytimeRowAction(t) => {
                 self.playtime_row_action = t.basics.action;
             }
@@ -1840,6 +2027,36 @@ impl TargetModel {
         Some(Affected::Multiple)
     }
 
+    fn apply_from_bookmark_address(&mut self, address: BookmarkAddress) {
+        match address {
+            BookmarkAddress::Fixed {
+                bookmark_type,
+                index_within_type,
+            } => {
+                self.use_nearest_marker = false;
+                self.bookmark_type = bookmark_type;
+                self.bookmark_anchor_type = BookmarkAnchorType::Index;
+                self.bookmark_ref = index_within_type;
+            }
+            BookmarkAddress::NearestMarker => {
+                self.use_nearest_marker = true;
+            }
+        }
+    }
+
+    fn apply_from_envelope(&mut self, envelope: &EnvelopeRef) {
+        match envelope {
+            EnvelopeRef::Track { name, .. } => {
+                self.envelope_name = name.clone();
+            }
+            EnvelopeRef::FxParameter(param) => {
+                self.envelope_name.clear();
+                self.param_type = VirtualFxParameterType::ById;
+                self.param_index = param.index();
+            }
+        }
+    }
+
     pub fn virtual_default(
         control_element_character: VirtualControlElementCharacter,
         next_index: u32,
@@ -1969,6 +2186,15 @@ impl TargetModel {
     pub fn mapping_snapshot_desc_for_load(&self) -> MappingSnapshotDescForLoad {
         if self.target_type() == ReaperTargetType::TakeMappingSnapshot {
             Default::default()
+        } else if self.target_type() == ReaperTargetType::MorphMappingSnapshots {
+            // Snapshot A of the morph target is always addressed by ID
+            MappingSnapshotDescForLoad::ById {
+                id: self
+                    .mapping_snapshot_id
+                    .as_ref()
+                    .map(|id| id.to_string())
+                    .unwrap_or_default(),
+            }
         } else {
             match self.mapping_snapshot_type_for_load {
                 MappingSnapshotTypeForLoad::Initial => MappingSnapshotDescForLoad::Initial,
@@ -2277,6 +2503,30 @@ impl TargetModel {
         Ok(desc)
     }
 
+    fn unresolved_bookmark_address(&self) -> UnresolvedBookmarkAddress {
+        if self.use_nearest_marker {
+            UnresolvedBookmarkAddress::NearestMarker
+        } else {
+            UnresolvedBookmarkAddress::Fixed {
+                bookmark_type: self.bookmark_type,
+                anchor_type: self.bookmark_anchor_type,
+                bookmark_ref: self.bookmark_ref,
+            }
+        }
+    }
+
+    fn virtual_envelope(&self) -> Result<VirtualEnvelope, &'static str> {
+        let envelope = if self.envelope_name.is_empty() {
+            VirtualEnvelope::FxParameter(self.fx_parameter_descriptor()?)
+        } else {
+            VirtualEnvelope::Track {
+                track_descriptor: self.track_descriptor()?,
+                name: self.envelope_name.clone(),
+            }
+        };
+        Ok(envelope)
+    }
+
     pub fn create_target(
         &self,
         compartment: CompartmentKind,
@@ -2334,6 +2584,49 @@ impl TargetModel {
                     TrackPeak => UnresolvedReaperTarget::TrackPeak(UnresolvedTrackPeakTarget {
                         track_descriptor: self.track_descriptor()?,
                     }),
+                    TrackName => UnresolvedReaperTarget::TrackName(UnresolvedTrackNameTarget {
+                        track_descriptor: self.track_descriptor()?,
+                    }),
+                    TrackColor => UnresolvedReaperTarget::TrackColor(UnresolvedTrackColorTarget {
+                        track_descriptor: self.track_descriptor()?,
+                    }),
+                    ItemParameterValue => UnresolvedReaperTarget::ItemParameterValue(
+                        UnresolvedItemParameterValueTarget {
+                            track_descriptor: self.track_descriptor()?,
+                            item: VirtualItem::from_descriptor(&self.item)?,
+                            parameter: self.item_parameter,
+                        },
+                    ),
+                    TakeParameterValue => UnresolvedReaperTarget::TakeParameterValue(
+                        UnresolvedTakeParameterValueTarget {
+                            track_descriptor: self.track_descriptor()?,
+                            item: VirtualItem::from_descriptor(&self.item)?,
+                            take: VirtualTake::from_descriptor(&self.take)?,
+                            parameter: self.take_parameter,
+                        },
+                    ),
+                    BrowseTakes => {
+                        UnresolvedReaperTarget::BrowseTakes(UnresolvedBrowseTakesTarget {
+                            track_descriptor: self.track_descriptor()?,
+                            item: VirtualItem::from_descriptor(&self.item)?,
+                        })
+                    }
+                    EnvelopeValue => {
+                        UnresolvedReaperTarget::EnvelopeValue(UnresolvedEnvelopeValueTarget {
+                            envelope: self.virtual_envelope()?,
+                            position: self.envelope_position,
+                        })
+                    }
+                    BrowseEnvelopePoints => UnresolvedReaperTarget::BrowseEnvelopePoints(
+                        UnresolvedBrowseEnvelopePointsTarget {
+                            envelope: self.virtual_envelope()?,
+                        },
+                    ),
+                    EnvelopeArmState => {
+                        UnresolvedReaperTarget::EnvelopeArmState(UnresolvedEnvelopeArmStateTarget {
+                            envelope: self.virtual_envelope()?,
+                        })
+                    }
                     RouteVolume => {
                         UnresolvedReaperTarget::TrackSendVolume(UnresolvedRouteVolumeTarget {
                             descriptor: self.route_descriptor()?,
@@ -2400,6 +2693,26 @@ impl TargetModel {
                             gang_behavior: self.fixed_gang_behavior(),
                         },
                     ),
+                    TrackRecordInput => {
+                        UnresolvedReaperTarget::TrackRecordInput(UnresolvedTrackRecordInputTarget {
+                            track_descriptor: self.track_descriptor()?,
+                            exclusivity: self.track_exclusivity,
+                            input: convert_record_input_to_reaper(self.record_input),
+                        })
+                    }
+                    TrackRecordMode => {
+                        UnresolvedReaperTarget::TrackRecordMode(UnresolvedTrackRecordModeTarget {
+                            track_descriptor: self.track_descriptor()?,
+                            exclusivity: self.track_exclusivity,
+                            mode: self.record_mode,
+                        })
+                    }
+                    TrackInputFxEnable => UnresolvedReaperTarget::TrackInputFxEnable(
+                        UnresolvedTrackInputFxEnableTarget {
+                            track_descriptor: self.track_descriptor()?,
+                            exclusivity: self.track_exclusivity,
+                        },
+                    ),
                     TrackSolo => UnresolvedReaperTarget::TrackSolo(UnresolvedTrackSoloTarget {
                         track_descriptor: self.track_descriptor()?,
                         behavior: self.solo_behavior,
@@ -2469,6 +2782,10 @@ impl TargetModel {
                     FxPreset => UnresolvedReaperTarget::FxPreset(UnresolvedFxPresetTarget {
                         fx_descriptor: self.fx_descriptor()?,
                     }),
+                    FxName => UnresolvedReaperTarget::FxName(UnresolvedFxNameTarget {
+                        fx_descriptor: self.fx_descriptor()?,
+                        kind: self.fx_name_kind,
+                    }),
                     BrowseTracks => {
                         UnresolvedReaperTarget::SelectedTrack(UnresolvedBrowseTracksTarget {
                             scroll_arrange_view: self.scroll_arrange_view,
@@ -2531,6 +2848,24 @@ impl TargetModel {
                             seek_behavior: self.seek_behavior,
                         })
                     }
+                    AddBookmark => {
+                        UnresolvedReaperTarget::AddBookmark(UnresolvedAddBookmarkTarget {
+                            bookmark_type: self.bookmark_type,
+                            tolerance: self.bookmark_tolerance,
+                        })
+                    }
+                    RenameBookmark => {
+                        UnresolvedReaperTarget::RenameBookmark(UnresolvedRenameBookmarkTarget {
+                            bookmark: self.unresolved_bookmark_address(),
+                            tolerance: self.bookmark_tolerance,
+                        })
+                    }
+                    DeleteBookmark => {
+                        UnresolvedReaperTarget::DeleteBookmark(UnresolvedDeleteBookmarkTarget {
+                            bookmark: self.unresolved_bookmark_address(),
+                            tolerance: self.bookmark_tolerance,
+                        })
+                    }
                     Seek => UnresolvedReaperTarget::Seek(UnresolvedSeekTarget {
                         options: self.seek_options(),
                         behavior: self.seek_behavior,
@@ -2624,6 +2959,18 @@ impl TargetModel {
                             snapshot_id: self.virtual_mapping_snapshot_id_for_take()?,
                         },
                     ),
+                    MorphMappingSnapshots => UnresolvedReaperTarget::MorphMappingSnapshots(
+                        UnresolvedMorphMappingSnapshotsTarget {
+                            compartment,
+                            scope: self.tag_scope(),
+                            active_mappings_only: self.active_mappings_only,
+                            snapshot_a: self.mapping_snapshot_id.clone().ok_or("no snapshot A")?,
+                            snapshot_b: self
+                                .second_mapping_snapshot_id
+                                .clone()
+                                .ok_or("no snapshot B")?,
+                        },
+                    ),
                     EnableMappings => {
                         UnresolvedReaperTarget::EnableMappings(UnresolvedEnableMappingsTarget {
                             compartment,
@@ -2747,6 +3094,30 @@ impl TargetModel {
         self.playtime_column_action
     }
 
+    pub fn item(&self) -> &ItemDescriptor {
+        &self.item
+    }
+
+    pub fn take(&self) -> &TakeDescriptor {
+        &self.take
+    }
+
+    pub fn item_parameter(&self) -> ItemParameter {
+        self.item_parameter
+    }
+
+    pub fn take_parameter(&self) -> TakeParameter {
+        self.take_parameter
+    }
+
+    pub fn envelope_name(&self) -> &str {
+        &self.envelope_name
+    }
+
+    pub fn envelope_position(&self) -> EnvelopePosition {
+        self.envelope_position
+    }
+
     pub fn simple_target(&self) -> Option<playtime_api::runtime::SimpleMappingTarget> {
         use helgobox_api::persistence;
         use playtime_api::runtime::SimpleMappingTarget;
@@ -2992,6 +3363,32 @@ impl TargetModel {
         self.r#type.supports_fx()
     }
 
+    /// Returns whether this target addresses a particular marker or region (by ID or position).
+    pub fn addresses_particular_bookmark(&self) -> bool {
+        if !self.is_reaper() {
+            return false;
+        }
+        use ReaperTargetType::*;
+        match self.r#type {
+            GoToBookmark => true,
+            RenameBookmark | DeleteBookmark => !self.use_nearest_marker,
+            _ => false,
+        }
+    }
+
+    /// Returns whether this is an envelope target which addresses a track envelope by name
+    /// (instead of the envelope of an FX parameter).
+    pub fn uses_track_envelope(&self) -> bool {
+        if !self.is_reaper() {
+            return false;
+        }
+        use ReaperTargetType::*;
+        matches!(
+            self.r#type,
+            EnvelopeValue | BrowseEnvelopePoints | EnvelopeArmState
+        ) && !self.envelope_name.is_empty()
+    }
+
     pub fn supports_route(&self) -> bool {
         if !self.is_reaper() {
             return false;
@@ -3025,6 +3422,7 @@ impl TargetModel {
             TakeMappingSnapshot => {
                 self.mapping_snapshot_type_for_take == MappingSnapshotTypeForTake::ById
             }
+            MorphMappingSnapshots => true,
             _ => false,
         }
     }
@@ -3214,6 +3612,13 @@ impl<'a> TargetModelFormatMultiLine<'a> {
         }
     }
 
+    fn second_mapping_snapshot_id_label(&self) -> String {
+        match &self.target.second_mapping_snapshot_id {
+            None => "-".into(),
+            Some(id) => id.to_string(),
+        }
+    }
+
     fn route_label(&self) -> Cow<str> {
         let virtual_route = self.target.virtual_track_route().ok();
         let virtual_route = match virtual_route.as_ref() {
@@ -3359,6 +3764,41 @@ impl Display for TargetModelFormatMultiLine<'_> {
                         let action = &self.target.playtime_slot_management_action;
                         write!(f, "{tt}\n{slot}\n{action}")
                     }
+                    ItemParameterValue => {
+                        let item = &self.target.item;
+                        let parameter = &self.target.item_parameter;
+                        write!(f, "{tt}\n{item}\n{parameter}")
+                    }
+                    TakeParameterValue => {
+                        let item = &self.target.item;
+                        let take = &self.target.take;
+                        let parameter = &self.target.take_parameter;
+                        write!(f, "{tt}\n{item}\n{take}\n{parameter}")
+                    }
+                    BrowseTakes => {
+                        let item = &self.target.item;
+                        write!(f, "{tt}\n{item}")
+                    }
+                    EnvelopeValue | BrowseEnvelopePoints | EnvelopeArmState => {
+                        if self.target.envelope_name.is_empty() {
+                            write!(
+                                f,
+                                "{}\nTrack {}\nFX {}\nParam {}",
+                                tt,
+                                self.track_label(),
+                                self.fx_label(),
+                                self.fx_param_label()
+                            )
+                        } else {
+                            write!(
+                                f,
+                                "{}\nTrack {}\nEnvelope {}",
+                                tt,
+                                self.track_label(),
+                                self.target.envelope_name
+                            )
+                        }
+                    }
                     Action => write!(
                         f,
                         "{}\n{}\n{}",
@@ -3376,7 +3816,7 @@ impl Display for TargetModelFormatMultiLine<'_> {
                     ),
                     TrackTool | TrackVolume | TrackPeak | TrackPan | TrackWidth | TrackArm
                     | TrackSelection | TrackMute | TrackPhase | TrackSolo | TrackShow
-                    | BrowseFxs | AllTrackFxEnable | TrackParentSend => {
+                    | BrowseFxs | AllTrackFxEnable | TrackParentSend | TrackName | TrackColor => {
                         write!(f, "{}\nTrack {}", tt, self.track_label())
                     }
                     EnableInstances => {
@@ -3408,7 +3848,7 @@ impl Display for TargetModelFormatMultiLine<'_> {
                         self.target.route_type,
                         self.route_label()
                     ),
-                    FxOpen | FxEnable | FxPreset | FxTool => write!(
+                    FxOpen | FxEnable | FxPreset | FxTool | FxName => write!(
                         f,
                         "{}\nTrack {}\nFX {}",
                         tt,
@@ -3452,6 +3892,19 @@ impl Display for TargetModelFormatMultiLine<'_> {
                             format_tags_as_csv(self.target.tags())
                         )
                     }
+                    MorphMappingSnapshots => {
+                        write!(
+                            f,
+                            "{}\n\
+                            Snapshot A: {}\n\
+                            Snapshot B: {}\n\
+                            Tags: {}",
+                            tt,
+                            self.mapping_snapshot_id_label(),
+                            self.second_mapping_snapshot_id_label(),
+                            format_tags_as_csv(self.target.tags())
+                        )
+                    }
                     TrackTouchState => write!(
                         f,
                         "{}\nTrack {}\n{}",
@@ -3462,6 +3915,20 @@ impl Display for TargetModelFormatMultiLine<'_> {
                     GoToBookmark => {
                         write!(f, "{}\n{}", tt, self.bookmark_label())
                     }
+                    AddBookmark => {
+                        let type_label = match self.target.bookmark_type {
+                            BookmarkType::Marker => "Marker at play position",
+                            BookmarkType::Region => "Region from time selection",
+                        };
+                        write!(f, "{tt}\n{type_label}")
+                    }
+                    RenameBookmark | DeleteBookmark => {
+                        if self.target.use_nearest_marker {
+                            write!(f, "{tt}\nNearest marker")
+                        } else {
+                            write!(f, "{}\n{}", tt, self.bookmark_label())
+                        }
+                    }
                     Mouse => {
                         write!(f, "{}\n{}", tt, self.target.mouse_action_type)?;
                         if self.target.supports_axis() {
@@ -3966,6 +4433,35 @@ impl Default for BookmarkAnchorType {
     }
 }
 
+/// Kind of record input, used for choosing it in the UI.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter, TryFromPrimitive, IntoPrimitive, Display)]
+#[repr(usize)]
+pub enum RecordInputKind {
+    #[display(fmt = "None")]
+    None,
+    #[display(fmt = "Mono audio")]
+    Mono,
+    #[display(fmt = "Stereo audio")]
+    Stereo,
+    #[display(fmt = "MIDI")]
+    Midi,
+}
+
+impl RecordInputKind {
+    /// Returns the first input of this kind.
+    pub fn first_input(self) -> RecordInput {
+        match self {
+            Self::None => RecordInput::None,
+            Self::Mono => RecordInput::Mono { index: 0 },
+            Self::Stereo => RecordInput::Stereo { index: 0 },
+            Self::Midi => RecordInput::Midi {
+                device_id: None,
+                channel: None,
+            },
+        }
+    }
+}
+
 impl VirtualTrackType {
     pub fn from_virtual_track(virtual_track: &VirtualTrack) -> Self {
         use VirtualTrack::*;
@@ -4748,6 +5244,31 @@ fn convert_monitoring_mode_to_realearn(monitoring_mode: InputMonitoringMode) ->
     }
 }
 
+fn convert_record_input_to_reaper(input: RecordInput) -> Option<RecordingInput> {
+    match input {
+        RecordInput::None => None,
+        RecordInput::Mono { index } => Some(RecordingInput::Mono(index)),
+        RecordInput::Stereo { index } => Some(RecordingInput::Stereo(index)),
+        RecordInput::Midi { device_id, channel } => Some(RecordingInput::Midi {
+            device_id: device_id.map(MidiInputDeviceId::new),
+            channel: channel.and_then(|ch| Channel::try_from(ch).ok()),
+        }),
+    }
+}
+
+fn convert_record_input_to_realearn(input: Option<RecordingInput>) -> RecordInput {
+    match input {
+        Some(RecordingInput::Mono(index)) => RecordInput::Mono { index },
+        Some(RecordingInput::Stereo(index)) => RecordInput::Stereo { index },
+        Some(RecordingInput::Midi { device_id, channel }) => RecordInput::Midi {
+            device_id: device_id.map(|id| id.get()),
+            channel: channel.map(|ch| ch.get()),
+        },
+        // Multichannel and ReaRoute inputs can't be configured in ReaLearn (yet)
+        _ => RecordInput::None,
+    }
+}
+
 #[derive(
     Copy,
     Clone,
@@ -11,16 +11,16 @@ use crate::domain::{
     convert_plugin_param_index_range_to_iter, create_lua_feedback_script_runtime,
     create_lua_midi_script_source_runtime, lua_module_path_without_ext, Backbone, BasicSettings,
     CompartmentKind, CompartmentParamIndex, CompartmentSettings, CompoundMappingSource,
-    ControlContext, ControlInput, DomainEvent, DomainEventHandler, ExtendedProcessorContext,
-    FeedbackAudioHookTask, FeedbackOutput, FeedbackRealTimeTask, FinalSourceFeedbackValue, GroupId,
-    GroupKey, IncomingCompoundSourceValue, InputDescriptor, InstanceId, InternalInfoEvent,
-    LastTouchedTargetFilter, MainMapping, MappingId, MappingKey, MappingMatchedEvent,
-    MessageCaptureEvent, MidiControlInput, NormalMainTask, OscFeedbackTask, ParamSetting,
-    PluginParams, ProcessorContext, ProjectionFeedbackValue, QualifiedMappingId,
+    ControlContext, ControlInput, DomainEvent, DomainEventHandler, EvdevDeviceId,
+    ExtendedProcessorContext, FeedbackAudioHookTask, FeedbackOutput, FeedbackRealTimeTask,
+    FinalSourceFeedbackValue, GroupId, GroupKey, IncomingCompoundSourceValue, InputDescriptor,
+    InstanceId, InternalInfoEvent, LastTouchedTargetFilter, MainMapping, MappingId, MappingKey,
+    MappingMatchedEvent, MessageCaptureEvent, MidiControlInput, NormalMainTask, OscFeedbackTask,
+    ParamSetting, PluginParams, ProcessorContext, ProjectionFeedbackValue, QualifiedMappingId,
     RealearnControlSurfaceMainTask, RealearnTarget, ReaperTarget, ReaperTargetType, SharedInstance,
-    SharedUnit, SourceFeedbackEvent, StayActiveWhenProjectInBackground, StreamDeckDeviceId, Tag,
-    TargetControlEvent, TargetTouchEvent, TargetValueChangedEvent, Unit, UnitContainer, UnitId,
-    VirtualControlElementId, VirtualFx, VirtualSource, VirtualSourceValue,
+    SharedUnit, SourceControlEvent, SourceFeedbackEvent, StayActiveWhenProjectInBackground,
+    StreamDeckDeviceId, Tag, TargetControlEvent, TargetTouchEvent, TargetValueChangedEvent, Unit,
+    UnitContainer, UnitId, VirtualControlElementId, VirtualFx, VirtualSource, VirtualSourceValue,
     LUA_FEEDBACK_SCRIPT_RUNTIME_NAME, LUA_MIDI_SCRIPT_SOURCE_RUNTIME_NAME,
 };
 use base::{Global, NamedChannelSender, SenderToNormalThread, SenderToRealTimeThread};
@@ -62,6 +62,7 @@ pub trait UnitUi {
     fn mapping_matched(&self, event: MappingMatchedEvent);
     fn handle_target_control(&self, event: TargetControlEvent);
     fn handle_source_feedback(&self, event: SourceFeedbackEvent);
+    fn send_control_event(&self, unit: &UnitModel, event: StreamedControlEvent);
     fn handle_internal_info_event(&self, event: &InternalInfoEvent);
     fn handle_external_info_event(&self, event: InstanceInfoEvent);
     fn handle_everything_changed(&self, unit_model: &UnitModel);
@@ -74,6 +75,16 @@ pub trait UnitUi {
     );
 }
 
+/// An event that is streamed to clients which observe the control and feedback flow of a unit.
+#[derive(Copy, Clone, Debug)]
+pub enum StreamedControlEvent<'a> {
+    SourceControl(&'a SourceControlEvent),
+    MappingMatched(MappingMatchedEvent),
+    TargetControl(TargetControlEvent),
+    TargetValueChanged(&'a TargetValueChangedEvent<'a>),
+    SourceFeedback(SourceFeedbackEvent<'a>),
+}
+
 /// This represents the user unit with one ReaLearn instance.
 ///
 /// It's ReaLearn's main object which keeps everything together.
@@ -101,7 +112,10 @@ pub struct UnitModel {
     pub control_input: Prop<ControlInput>,
     wants_keyboard_input: bool,
     match_even_inactive_mappings: bool,
+    /// Not persistent. Enabled as long as at least one client subscribed to control events.
+    control_event_streaming_enabled: bool,
     stream_deck_device_id: Option<StreamDeckDeviceId>,
+    evdev_device_id: Option<EvdevDeviceId>,
     pub feedback_output: Prop<Option<FeedbackOutput>>,
     pub auto_load_mode: Prop<AutoLoadMode>,
     pub auto_load_fallback_compartment: Option<CompartmentModel>,
@@ -280,7 +294,9 @@ impl UnitModel {
             control_input: prop(initial_input),
             wants_keyboard_input: unit_defaults::WANTS_KEYBOARD_INPUT,
             match_even_inactive_mappings: unit_defaults::MATCH_EVEN_INACTIVE_MAPPINGS,
+            control_event_streaming_enabled: false,
             stream_deck_device_id: None,
+            evdev_device_id: None,
             feedback_output: prop(initial_output),
             auto_load_mode: prop(unit_defaults::MAIN_PRESET_AUTO_LOAD_MODE),
             auto_load_fallback_compartment: None,
@@ -485,6 +501,7 @@ impl UnitModel {
             InputDescriptor::StreamDeck { device_id } => {
                 self.stream_deck_device_id == Some(*device_id)
             }
+            InputDescriptor::Evdev { device_id } => self.evdev_device_id == Some(*device_id),
         }
     }
 
@@ -793,6 +810,10 @@ impl UnitModel {
         self.stream_deck_device_id
     }
 
+    pub fn evdev_device_id(&self) -> Option<EvdevDeviceId> {
+        self.evdev_device_id
+    }
+
     pub fn wants_keyboard_input(&self) -> bool {
         self.wants_keyboard_input
     }
@@ -801,6 +822,24 @@ impl UnitModel {
         self.match_even_inactive_mappings
     }
 
+    pub fn control_event_streaming_enabled(&self) -> bool {
+        self.control_event_streaming_enabled
+    }
+
+    pub fn set_control_event_streaming_enabled(&mut self, value: bool) {
+        if value == self.control_event_streaming_enabled {
+            return;
+        }
+        self.control_event_streaming_enabled = value;
+        self.sync_settings();
+    }
+
+    fn stream_control_event(&self, event: StreamedControlEvent) {
+        if self.control_event_streaming_enabled {
+            self.ui().send_control_event(self, event);
+        }
+    }
+
     fn active_virtual_controller_mappings<'a>(
         &'a self,
         instance_state: &'a Unit,
@@ -1198,6 +1237,10 @@ impl UnitModel {
                 self.stream_deck_device_id = value;
                 Some(A::One(P::StreamDeckDeviceId))
             }
+            C::SetEvdevDevice(value) => {
+                self.evdev_device_id = value;
+                Some(A::One(P::EvdevDeviceId))
+            }
             C::ChangeCompartment(compartment, cmd) => self
                 .change_compartment_internal(compartment, cmd)?
                 .map(|affected| A::One(P::InCompartment(compartment, affected))),
@@ -1316,6 +1359,7 @@ impl UnitModel {
                             UP::Enabled
                             | UP::WantsKeyboardInput
                             | UP::StreamDeckDeviceId
+                            | UP::EvdevDeviceId
                             | UP::MatchEvenInactiveMappings,
                         ) => {
                             model.sync_settings();
@@ -2660,12 +2704,14 @@ impl UnitModel {
             wants_keyboard_input: self.wants_keyboard_input,
             match_even_inactive_mappings: self.match_even_inactive_mappings,
             streamdeck_device_id: self.stream_deck_device_id,
+            evdev_device_id: self.evdev_device_id,
             feedback_output: self.feedback_output(),
             real_input_logging_enabled: self.real_input_logging_enabled.get(),
             real_output_logging_enabled: self.real_output_logging_enabled.get(),
             virtual_input_logging_enabled: self.virtual_input_logging_enabled.get(),
             virtual_output_logging_enabled: self.virtual_output_logging_enabled.get(),
             target_control_logging_enabled: self.target_control_logging_enabled.get(),
+            control_event_streaming_enabled: self.control_event_streaming_enabled,
             send_feedback_only_if_armed: self.send_feedback_only_if_armed.get(),
             reset_feedback_when_releasing_source: self.reset_feedback_when_releasing_source.get(),
             let_matched_events_through: self.let_matched_events_through.get(),
@@ -2873,7 +2919,9 @@ impl DomainEventHandler for WeakUnitModel {
                 // particular case of reentrancy (because of a quirk in REAPER related to master
                 // tempo notification, https://github.com/helgoboss/helgobox/issues/199). If the
                 // target value slider is not updated then ... so what.
-                unit.try_borrow()?.ui().target_value_changed(e);
+                let s = unit.try_borrow()?;
+                s.stream_control_event(StreamedControlEvent::TargetValueChanged(&e));
+                s.ui().target_value_changed(e);
             }
             E::UpdatedSingleParameterValue { index, value } => {
                 let mut unit = unit.try_borrow_mut()?;
@@ -2898,16 +2946,23 @@ impl DomainEventHandler for WeakUnitModel {
             }
             E::MappingMatched(event) => {
                 let s = unit.try_borrow()?;
+                s.stream_control_event(StreamedControlEvent::MappingMatched(event));
                 s.ui().mapping_matched(event);
             }
             E::HandleTargetControl(event) => {
                 let s = unit.try_borrow()?;
+                s.stream_control_event(StreamedControlEvent::TargetControl(event));
                 s.ui().handle_target_control(event);
             }
             E::HandleSourceFeedback(event) => {
                 let s = unit.try_borrow()?;
+                s.stream_control_event(StreamedControlEvent::SourceFeedback(event));
                 s.ui().handle_source_feedback(event);
             }
+            E::HandleSourceControl(event) => {
+                let s = unit.try_borrow()?;
+                s.stream_control_event(StreamedControlEvent::SourceControl(&event));
+            }
             E::MappingEnabledChangeRequested(event) => {
                 let mut s = unit.try_borrow_mut()?;
                 let id = QualifiedMappingId::new(event.compartment, event.mapping_id);
@@ -3087,6 +3142,7 @@ pub enum UnitCommand {
     SetWantsKeyboardInput(bool),
     SetMatchEvenInactiveMappings(bool),
     SetStreamDeckDevice(Option<StreamDeckDeviceId>),
+    SetEvdevDevice(Option<EvdevDeviceId>),
     ChangeCompartment(CompartmentKind, CompartmentCommand),
     AdjustMappingModeIfNecessary(QualifiedMappingId),
 }
@@ -3101,6 +3157,7 @@ pub enum UnitProp {
     WantsKeyboardInput,
     MatchEvenInactiveMappings,
     StreamDeckDeviceId,
+    EvdevDeviceId,
     InCompartment(CompartmentKind, Affected<CompartmentProp>),
 }
 
@@ -380,6 +380,9 @@ impl RealearnAudioHook {
             block_props.frame_rate,
             DurationInSeconds::ZERO,
         );
+        // Real-time processors might already push MIDI events (e.g. held back chord members).
+        self.midi_transformation_container
+            .prepare(block_props.frame_rate);
         for (_, p) in self.real_time_processors.iter() {
             // Since 1.12.0, we "drive" each plug-in instance's real-time processor
             // primarily by the global audio hook. See https://github.com/helgoboss/helgobox/issues/84 why this is
@@ -388,7 +391,11 @@ impl RealearnAudioHook {
             // stop doing so synchronously if the plug-in is
             // gone.
             let mut guard = p.lock_recover();
-            guard.run_from_audio_hook_all(might_be_rebirth, start_of_block_timestamp);
+            guard.run_from_audio_hook_all(
+                might_be_rebirth,
+                start_of_block_timestamp,
+                &mut self.midi_transformation_container,
+            );
             if guard.control_is_globally_enabled() {
                 if let MidiControlInput::Device(dev_id) = guard.midi_control_input() {
                     midi_dev_id_is_used[dev_id.get() as usize] = true;
@@ -413,8 +420,6 @@ impl RealearnAudioHook {
         midi_dev_id_is_used: &[bool; MidiInputDeviceId::MAX_DEVICE_COUNT as usize],
         sample_count: u64,
     ) {
-        self.midi_transformation_container
-            .prepare(block_props.frame_rate);
         for dev_id in 0..MidiInputDeviceId::MAX_DEVICE_COUNT {
             if !midi_dev_id_is_used[dev_id as usize] {
                 continue;
@@ -3,11 +3,12 @@ use base::{
 };
 
 use crate::domain::{
-    AdditionalFeedbackEvent, ControlInput, DeviceControlInput, DeviceFeedbackOutput,
-    FeedbackOutput, InstanceId, QualifiedStreamDeckMessage, RealearnSourceState,
-    RealearnTargetState, ReaperTarget, ReaperTargetType, SafeLua, SharedInstance,
-    StreamDeckDeviceId, StreamDeckDeviceManager, StreamDeckMessage,
-    StreamDeckSourceFeedbackPayload, StreamDeckSourceFeedbackValue, UnitId, WeakInstance,
+    AdditionalFeedbackEvent, ConnectedEvdevDevice, ControlInput, DeviceControlInput,
+    DeviceFeedbackOutput, EvdevDeviceId, EvdevDeviceManager, FeedbackOutput, InstanceId,
+    QualifiedEvdevMessage, QualifiedStreamDeckMessage, RealearnSourceState, RealearnTargetState,
+    ReaperTarget, ReaperTargetType, SafeLua, SharedInstance, StreamDeckDeviceId,
+    StreamDeckDeviceManager, StreamDeckMessage, StreamDeckSourceFeedbackPayload,
+    StreamDeckSourceFeedbackValue, UnitId, WeakInstance,
 };
 #[allow(unused)]
 use anyhow::{anyhow, Context};
@@ -68,6 +69,11 @@ pub struct Backbone {
     stream_deck_device_manager: RefCell<StreamDeckDeviceManager>,
     stream_decks: RefCell<NonCryptoHashMap<StreamDeckDeviceId, StreamDeck>>,
     stream_deck_button_states: RefCell<NonCryptoHashMap<StreamDeckDeviceId, Vec<u8>>>,
+    evdev_device_manager: RefCell<EvdevDeviceManager>,
+    evdev_devices: RefCell<NonCryptoHashMap<EvdevDeviceId, ConnectedEvdevDevice>>,
+    /// Devices in use which we couldn't connect to. Used for warning only once and not on each
+    /// reconnection attempt.
+    unreachable_evdev_devices: RefCell<NonCryptoHashSet<EvdevDeviceId>>,
 }
 
 #[derive(Debug, Default)]
@@ -185,6 +191,9 @@ impl Backbone {
             stream_deck_device_manager: Default::default(),
             stream_decks: Default::default(),
             stream_deck_button_states: Default::default(),
+            evdev_device_manager: Default::default(),
+            evdev_devices: Default::default(),
+            unreachable_evdev_devices: Default::default(),
         }
     }
 
@@ -267,6 +276,79 @@ impl Backbone {
         messages
     }
 
+    /// Reconnects evdev devices which are in use but got disconnected, e.g. unplugged gamepads.
+    pub fn detect_evdev_device_changes(&self) {
+        let devices_in_use = self.evdev_device_manager.borrow().devices_in_use();
+        let actually_connected_devices: NonCryptoHashSet<_> =
+            self.evdev_devices.borrow().keys().copied().collect();
+        if devices_in_use == actually_connected_devices {
+            return;
+        }
+        self.connect_or_disconnect_evdev_devices(&devices_in_use);
+    }
+
+    pub fn register_evdev_usage(&self, unit_id: UnitId, device: Option<EvdevDeviceId>) {
+        // Change device usage
+        let mut manager = self.evdev_device_manager.borrow_mut();
+        manager.register_device_usage(unit_id, device);
+        let devices_in_use = manager.devices_in_use();
+        // Update connections
+        self.connect_or_disconnect_evdev_devices(&devices_in_use);
+    }
+
+    fn connect_or_disconnect_evdev_devices(
+        &self,
+        devices_in_use: &NonCryptoHashSet<EvdevDeviceId>,
+    ) {
+        let mut devices = self.evdev_devices.borrow_mut();
+        let mut unreachable_devices = self.unreachable_evdev_devices.borrow_mut();
+        // Disconnect from devices that are not in use anymore
+        devices.retain(|id, _| devices_in_use.contains(id));
+        unreachable_devices.retain(|id| devices_in_use.contains(id));
+        // Connect to devices
+        for dev_id in devices_in_use {
+            if devices.contains_key(dev_id) {
+                continue;
+            }
+            match dev_id.connect() {
+                Ok(dev) => {
+                    devices.insert(*dev_id, dev);
+                    if unreachable_devices.remove(dev_id) {
+                        tracing::info!(msg = "Reconnected to evdev device", %dev_id);
+                    }
+                }
+                Err(e) => {
+                    if unreachable_devices.insert(*dev_id) {
+                        tracing::warn!(msg = "Couldn't connect to evdev device", %dev_id, %e);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn poll_evdev_messages(&self) -> Vec<QualifiedEvdevMessage> {
+        let mut devices = self.evdev_devices.borrow_mut();
+        let mut messages = vec![];
+        let mut device_messages = vec![];
+        devices.retain(|id, dev| {
+            device_messages.clear();
+            let result = dev.poll(&mut device_messages);
+            messages.extend(
+                device_messages
+                    .drain(..)
+                    .map(|msg| QualifiedEvdevMessage { dev_id: *id, msg }),
+            );
+            match result {
+                Ok(_) => true,
+                Err(e) => {
+                    tracing::warn!(msg = "Error polling for evdev events", %e);
+                    false
+                }
+            }
+        });
+        messages
+    }
+
     pub fn send_stream_deck_feedback(
         &self,
         dev_id: StreamDeckDeviceId,
@@ -0,0 +1,327 @@
+use crate::application::BookmarkAnchorType;
+use crate::domain::{find_bookmark, low_reaper};
+use reaper_high::{BookmarkType, Project};
+use reaper_medium::PositionInSeconds;
+use std::ffi::CString;
+use std::ptr::null_mut;
+use std::time::Duration;
+
+/// Markers and regions which are this close to the play position (or the time selection) count
+/// as being there.
+pub const DEFAULT_BOOKMARK_TOLERANCE: Duration = Duration::from_millis(500);
+
+/// Marker or region which a bookmark-editing target works on.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum BookmarkAddress {
+    /// The marker or region at the given position within its type (e.g. the third region).
+    Fixed {
+        bookmark_type: BookmarkType,
+        index_within_type: u32,
+    },
+    /// The marker nearest to the play position, provided it's within the tolerance.
+    NearestMarker,
+}
+
+/// Describes the marker or region to be edited before the target is resolved.
+#[derive(Debug)]
+pub enum UnresolvedBookmarkAddress {
+    /// Bookmark looked up by ID or position, just like with the "Go to" target.
+    Fixed {
+        bookmark_type: BookmarkType,
+        anchor_type: BookmarkAnchorType,
+        bookmark_ref: u32,
+    },
+    NearestMarker,
+}
+
+impl UnresolvedBookmarkAddress {
+    pub fn resolve(&self, project: Project) -> Result<BookmarkAddress, &'static str> {
+        let address = match *self {
+            UnresolvedBookmarkAddress::Fixed {
+                bookmark_type,
+                anchor_type,
+                bookmark_ref,
+            } => {
+                let res = find_bookmark(project, bookmark_type, anchor_type, bookmark_ref)?;
+                BookmarkAddress::Fixed {
+                    bookmark_type,
+                    index_within_type: res.index_within_type,
+                }
+            }
+            UnresolvedBookmarkAddress::NearestMarker => BookmarkAddress::NearestMarker,
+        };
+        Ok(address)
+    }
+}
+
+impl BookmarkAddress {
+    /// Returns the index (counting both markers and regions) of the addressed bookmark.
+    pub fn find_index(&self, project: Project, tolerance: Duration) -> Option<u32> {
+        match *self {
+            BookmarkAddress::Fixed {
+                bookmark_type,
+                index_within_type,
+            } => project
+                .find_bookmark_by_type_and_index(bookmark_type, index_within_type)
+                .map(|res| res.index),
+            BookmarkAddress::NearestMarker => {
+                let pos = project.play_or_edit_cursor_position().unwrap_or_default();
+                find_nearest_marker(project, pos, tolerance)
+            }
+        }
+    }
+}
+
+/// Returns whether the addressed bookmark exists at the play position.
+pub fn bookmark_is_at_play_position(
+    project: Project,
+    address: BookmarkAddress,
+    tolerance: Duration,
+) -> bool {
+    let Some(index) = address.find_index(project, tolerance) else {
+        return false;
+    };
+    let pos = project.play_or_edit_cursor_position().unwrap_or_default();
+    bookmark_is_at(project, index, pos, tolerance)
+}
+
+/// Returns the index of the marker nearest to the given position if it's within the tolerance.
+pub fn find_nearest_marker(
+    project: Project,
+    pos: PositionInSeconds,
+    tolerance: Duration,
+) -> Option<u32> {
+    nearest_marker_index(bookmark_spans(&project), pos.get(), tolerance)
+}
+
+/// Returns the index of the region which spans the given range, give or take the tolerance.
+pub fn find_region(
+    project: Project,
+    start: PositionInSeconds,
+    end: PositionInSeconds,
+    tolerance: Duration,
+) -> Option<u32> {
+    region_index(bookmark_spans(&project), start.get(), end.get(), tolerance)
+}
+
+/// Returns whether the given bookmark is at the given position.
+///
+/// Markers count as being at the position if they are within the tolerance, regions if they
+/// contain the position.
+pub fn bookmark_is_at(
+    project: Project,
+    index: u32,
+    pos: PositionInSeconds,
+    tolerance: Duration,
+) -> bool {
+    let Some(bookmark) = project.find_bookmark_by_index(index) else {
+        return false;
+    };
+    let info = bookmark.basic_info();
+    BookmarkSpan::new(info.position, info.region_end_position).is_at(pos.get(), tolerance)
+}
+
+/// Position of a marker or region in seconds.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct BookmarkSpan {
+    start: f64,
+    /// `None` for markers.
+    end: Option<f64>,
+}
+
+impl BookmarkSpan {
+    fn new(start: PositionInSeconds, end: Option<PositionInSeconds>) -> Self {
+        Self {
+            start: start.get(),
+            end: end.map(|end| end.get()),
+        }
+    }
+
+    fn is_at(&self, pos: f64, tolerance: Duration) -> bool {
+        let tolerance = tolerance.as_secs_f64();
+        match self.end {
+            None => (self.start - pos).abs() <= tolerance,
+            Some(end) => pos >= self.start - tolerance && pos <= end,
+        }
+    }
+}
+
+fn bookmark_spans(project: &Project) -> impl Iterator<Item = BookmarkSpan> + '_ {
+    project.bookmarks().map(|b| {
+        let info = b.basic_info();
+        BookmarkSpan::new(info.position, info.region_end_position)
+    })
+}
+
+fn nearest_marker_index(
+    spans: impl Iterator<Item = BookmarkSpan>,
+    pos: f64,
+    tolerance: Duration,
+) -> Option<u32> {
+    spans
+        .enumerate()
+        .filter_map(|(i, span)| {
+            if span.end.is_some() {
+                return None;
+            }
+            let distance = (span.start - pos).abs();
+            (distance <= tolerance.as_secs_f64()).then_some((i as u32, distance))
+        })
+        .min_by(|(_, d1), (_, d2)| d1.total_cmp(d2))
+        .map(|(i, _)| i)
+}
+
+fn region_index(
+    mut spans: impl Iterator<Item = BookmarkSpan>,
+    start: f64,
+    end: f64,
+    tolerance: Duration,
+) -> Option<u32> {
+    let tolerance = tolerance.as_secs_f64();
+    spans.enumerate().find_map(|(i, span)| {
+        let region_end = span.end?;
+        let matches =
+            (span.start - start).abs() <= tolerance && (region_end - end).abs() <= tolerance;
+        matches.then_some(i as u32)
+    })
+}
+
+/// Adds a marker at the given position.
+pub fn add_marker(project: Project, pos: PositionInSeconds) {
+    unsafe {
+        low_reaper().AddProjectMarker2(
+            project.raw().as_ptr(),
+            false,
+            pos.get(),
+            0.0,
+            c"".as_ptr(),
+            -1,
+            0,
+        );
+    }
+}
+
+/// Adds a region spanning the given range.
+pub fn add_region(project: Project, start: PositionInSeconds, end: PositionInSeconds) {
+    unsafe {
+        low_reaper().AddProjectMarker2(
+            project.raw().as_ptr(),
+            true,
+            start.get(),
+            end.get(),
+            c"".as_ptr(),
+            -1,
+            0,
+        );
+    }
+}
+
+/// Renames the bookmark with the given index (counting both markers and regions).
+///
+/// An empty name clears the name.
+pub fn rename_bookmark(project: Project, index: u32, name: &str) -> Result<(), &'static str> {
+    let c_name = CString::new(name).map_err(|_| "name contains nul character")?;
+    let raw_project = project.raw().as_ptr();
+    let mut is_region = false;
+    let mut pos = 0.0;
+    let mut region_end = 0.0;
+    let mut id = 0;
+    let mut color = 0;
+    let successful = unsafe {
+        low_reaper().EnumProjectMarkers3(
+            raw_project,
+            index as _,
+            &mut is_region,
+            &mut pos,
+            &mut region_end,
+            null_mut(),
+            &mut id,
+            &mut color,
+        ) > 0
+    };
+    if !successful {
+        return Err("bookmark not found");
+    }
+    // Flag 1 clears the name. Otherwise, an empty name would leave the name untouched.
+    let flags = if name.is_empty() { 1 } else { 0 };
+    unsafe {
+        low_reaper().SetProjectMarkerByIndex2(
+            raw_project,
+            index as _,
+            is_region,
+            pos,
+            region_end,
+            id,
+            c_name.as_ptr(),
+            color,
+            flags,
+        );
+    }
+    Ok(())
+}
+
+/// Deletes the bookmark with the given index (counting both markers and regions).
+pub fn delete_bookmark(project: Project, index: u32) -> Result<(), &'static str> {
+    let successful =
+        unsafe { low_reaper().DeleteProjectMarkerByIndex(project.raw().as_ptr(), index as _) };
+    if !successful {
+        return Err("bookmark not found");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOLERANCE: Duration = Duration::from_millis(500);
+
+    fn marker(start: f64) -> BookmarkSpan {
+        BookmarkSpan { start, end: None }
+    }
+
+    fn region(start: f64, end: f64) -> BookmarkSpan {
+        BookmarkSpan {
+            start,
+            end: Some(end),
+        }
+    }
+
+    #[test]
+    fn find_nearest_marker_within_tolerance() {
+        // Given
+        let spans = [marker(10.0), region(10.1, 20.0), marker(10.3), marker(30.0)];
+        // When
+        let nearest = nearest_marker_index(spans.into_iter(), 10.2, TOLERANCE);
+        let too_far = nearest_marker_index(spans.into_iter(), 25.0, TOLERANCE);
+        // Then
+        assert_eq!(nearest, Some(2));
+        assert_eq!(too_far, None);
+    }
+
+    #[test]
+    fn find_region_spanning_range() {
+        // Given
+        let spans = [marker(10.0), region(10.0, 20.0), region(30.0, 40.0)];
+        // When
+        let roughly_matching = region_index(spans.into_iter(), 30.2, 39.9, TOLERANCE);
+        let end_too_far = region_index(spans.into_iter(), 10.0, 21.0, TOLERANCE);
+        // Then
+        assert_eq!(roughly_matching, Some(2));
+        assert_eq!(end_too_far, None);
+    }
+
+    #[test]
+    fn bookmark_at_position() {
+        // Given
+        let m = marker(10.0);
+        let r = region(10.0, 20.0);
+        // When
+        // Then
+        assert!(m.is_at(10.4, TOLERANCE));
+        assert!(!m.is_at(15.0, TOLERANCE));
+        assert!(r.is_at(9.6, TOLERANCE));
+        assert!(r.is_at(15.0, TOLERANCE));
+        assert!(!r.is_at(20.1, TOLERANCE));
+    }
+}
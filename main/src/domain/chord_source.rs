@@ -0,0 +1,474 @@
+use crate::domain::{
+    CompoundMappingSource, ControlEvent, ControlEventTimestamp, IncomingCompoundSourceValue,
+    QualifiedMappingId,
+};
+use base::hash_util::NonCryptoHashMap;
+use helgoboss_learn::{ControlResult, MidiSource, MidiSourceValue};
+use helgoboss_midi::RawShortMessage;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+pub const DEFAULT_CHORD_WINDOW: Duration = Duration::from_millis(50);
+
+/// A source which fires when all of its member sources are pressed together within a short
+/// time window.
+///
+/// The chord itself is detected by the [`ChordDetector`] in the main processor, not by the source.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ChordSource {
+    members: Vec<CompoundMappingSource>,
+    window: Duration,
+}
+
+impl ChordSource {
+    pub fn new(members: Vec<CompoundMappingSource>, window: Duration) -> Self {
+        Self { members, window }
+    }
+
+    pub fn members(&self) -> &[CompoundMappingSource] {
+        &self.members
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Returns the index of the member which reacts to the given value and whether the value
+    /// represents a press (as opposed to a release).
+    pub fn find_member(&self, value: IncomingCompoundSourceValue) -> Option<(usize, bool)> {
+        self.members.iter().enumerate().find_map(|(i, m)| {
+            let is_press = match m.reacts_to_source_value_with(value)? {
+                ControlResult::Processed(v) => v.to_unit_value().is_ok_and(|v| !v.is_zero()),
+                ControlResult::Consumed => return None,
+            };
+            Some((i, is_press))
+        })
+    }
+
+    pub fn midi_members(&self) -> impl Iterator<Item = &MidiSource> {
+        self.members.iter().filter_map(|m| match m {
+            CompoundMappingSource::Midi(s) => Some(s),
+            _ => None,
+        })
+    }
+}
+
+/// MIDI member sources of all chord mappings whose control is on.
+///
+/// The real-time processor rebuilds it whenever mappings change, so deciding whether to forward
+/// an incoming MIDI message to the chord detector doesn't need to look at all mappings.
+#[derive(Debug, Default)]
+pub struct ChordMidiMemberLookup {
+    members: Vec<MidiSource>,
+}
+
+impl ChordMidiMemberLookup {
+    pub fn rebuild<'a>(&mut self, chords: impl Iterator<Item = &'a ChordSource>) {
+        self.members.clear();
+        for chord in chords {
+            for member in chord.midi_members() {
+                if !self.members.contains(member) {
+                    self.members.push(member.clone());
+                }
+            }
+        }
+    }
+
+    /// Checks if the given MIDI message is a member of at least one chord. Doesn't allocate.
+    pub fn contains(&self, value: &MidiSourceValue<RawShortMessage>) -> bool {
+        self.members.iter().any(|s| s.control(value).is_some())
+    }
+}
+
+/// Switches a chord mapping on or off. Emitted when the [`ChordDetector`] detects a chord or its
+/// release.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct ChordMessage {
+    pub chord_id: QualifiedMappingId,
+    pub is_on: bool,
+}
+
+/// Describes that an incoming event matches a particular member of a chord mapping.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct ChordMemberMatch {
+    pub chord_id: QualifiedMappingId,
+    pub member_index: usize,
+    pub member_count: usize,
+    pub window: Duration,
+}
+
+/// What the main processor should do after feeding an event into the [`ChordDetector`].
+#[derive(Debug)]
+pub struct ChordDetectorOutcome<E> {
+    /// Contains the event if it's not relevant for chord detection. It should be processed
+    /// normally.
+    pub pass_through: Option<E>,
+    /// Events which turned out to be part of a chord, in the order in which they arrived. They
+    /// must not be processed as individual presses or releases.
+    pub consumed: Vec<E>,
+    /// Chord mappings which need to be switched on (`true`) or off (`false`).
+    pub fired_chords: Vec<(QualifiedMappingId, bool)>,
+}
+
+/// Detects chords by holding back presses of member sources until either the chord is complete
+/// or the chord window has elapsed.
+///
+/// Held events whose window elapsed without completing a chord are handed back by
+/// [`Self::poll`] in their original order, so that they can be processed as if they had just
+/// arrived. Held events which complete a chord are dropped, which suppresses the individual
+/// presses.
+#[derive(Debug)]
+pub struct ChordDetector<E> {
+    states: NonCryptoHashMap<QualifiedMappingId, ChordState>,
+    held_events: VecDeque<HeldEvent<E>>,
+}
+
+#[derive(Debug, Default)]
+struct ChordState {
+    /// For each member, the time of the last press which hasn't been released yet.
+    pressed: Vec<Option<ControlEventTimestamp>>,
+    /// For each member, whether its next release belongs to a fired chord and must be swallowed.
+    swallow_release: Vec<bool>,
+    /// Whether the chord has fired and is not yet switched off again.
+    engaged: bool,
+}
+
+#[derive(Debug)]
+struct HeldEvent<E> {
+    payload: E,
+    timestamp: ControlEventTimestamp,
+    window: Duration,
+    is_press: bool,
+    matches: Vec<ChordMemberMatch>,
+}
+
+impl<E> Default for ChordDetector<E> {
+    fn default() -> Self {
+        Self {
+            states: Default::default(),
+            held_events: Default::default(),
+        }
+    }
+}
+
+impl<E> ChordDetector<E> {
+    /// Feeds an incoming event along with the chord members that it matches.
+    pub fn process(
+        &mut self,
+        event: ControlEvent<E>,
+        is_press: bool,
+        matches: Vec<ChordMemberMatch>,
+    ) -> ChordDetectorOutcome<E> {
+        if matches.is_empty() {
+            return ChordDetectorOutcome {
+                pass_through: Some(event.into_payload()),
+                consumed: vec![],
+                fired_chords: vec![],
+            };
+        }
+        if is_press {
+            self.process_press(event, matches)
+        } else {
+            self.process_release(event, matches)
+        }
+    }
+
+    /// Returns held events whose chord window has elapsed, in the order in which they arrived.
+    pub fn poll(&mut self, now: ControlEventTimestamp) -> Vec<ControlEvent<E>> {
+        let mut expired_events = vec![];
+        while let Some(e) = self.held_events.front() {
+            if now - e.timestamp < e.window {
+                break;
+            }
+            let e = self.held_events.pop_front().unwrap();
+            expired_events.push(ControlEvent::new(e.payload, e.timestamp));
+        }
+        expired_events
+    }
+
+    fn process_press(
+        &mut self,
+        event: ControlEvent<E>,
+        matches: Vec<ChordMemberMatch>,
+    ) -> ChordDetectorOutcome<E> {
+        let timestamp = event.timestamp();
+        let mut fired_chords = vec![];
+        let mut swallow = false;
+        for m in &matches {
+            let state = self.state_mut(m);
+            if state.engaged {
+                // Repeated press while chord is engaged
+                swallow = true;
+                continue;
+            }
+            state.pressed[m.member_index] = Some(timestamp);
+            let is_complete = state
+                .pressed
+                .iter()
+                .all(|p| p.is_some_and(|p| timestamp - p <= m.window));
+            if is_complete {
+                state.pressed.fill(None);
+                state.swallow_release.fill(true);
+                state.engaged = true;
+                fired_chords.push((m.chord_id, true));
+            }
+        }
+        if !fired_chords.is_empty() {
+            // Suppress the individual presses which made up the fired chords
+            let mut consumed = self.drop_held_events_of(&fired_chords);
+            consumed.push(event.into_payload());
+            return ChordDetectorOutcome {
+                pass_through: None,
+                consumed,
+                fired_chords,
+            };
+        }
+        if swallow {
+            return ChordDetectorOutcome {
+                pass_through: None,
+                consumed: vec![event.into_payload()],
+                fired_chords,
+            };
+        }
+        let window = matches.iter().map(|m| m.window).max().unwrap_or_default();
+        self.held_events.push_back(HeldEvent {
+            payload: event.into_payload(),
+            timestamp,
+            window,
+            is_press: true,
+            matches,
+        });
+        ChordDetectorOutcome {
+            pass_through: None,
+            consumed: vec![],
+            fired_chords,
+        }
+    }
+
+    fn process_release(
+        &mut self,
+        event: ControlEvent<E>,
+        matches: Vec<ChordMemberMatch>,
+    ) -> ChordDetectorOutcome<E> {
+        let mut fired_chords = vec![];
+        let mut swallow = false;
+        for m in &matches {
+            let state = self.state_mut(m);
+            if state.swallow_release[m.member_index] {
+                state.swallow_release[m.member_index] = false;
+                swallow = true;
+                // The first release of a member switches the chord off
+                if state.engaged {
+                    state.engaged = false;
+                    fired_chords.push((m.chord_id, false));
+                }
+            } else {
+                state.pressed[m.member_index] = None;
+            }
+        }
+        if swallow {
+            return ChordDetectorOutcome {
+                pass_through: None,
+                consumed: vec![event.into_payload()],
+                fired_chords,
+            };
+        }
+        let corresponding_press_is_held = self.held_events.iter().any(|e| {
+            e.is_press
+                && e.matches.iter().any(|held_match| {
+                    matches.iter().any(|m| {
+                        m.chord_id == held_match.chord_id
+                            && m.member_index == held_match.member_index
+                    })
+                })
+        });
+        if !corresponding_press_is_held {
+            return ChordDetectorOutcome {
+                pass_through: Some(event.into_payload()),
+                consumed: vec![],
+                fired_chords,
+            };
+        }
+        // Hold the release as well, otherwise it would overtake its press
+        let timestamp = event.timestamp();
+        self.held_events.push_back(HeldEvent {
+            payload: event.into_payload(),
+            timestamp,
+            window: Duration::ZERO,
+            is_press: false,
+            matches,
+        });
+        ChordDetectorOutcome {
+            pass_through: None,
+            consumed: vec![],
+            fired_chords,
+        }
+    }
+
+    /// Returns the payloads of the dropped events.
+    fn drop_held_events_of(&mut self, fired_chords: &[(QualifiedMappingId, bool)]) -> Vec<E> {
+        let (dropped, kept): (Vec<_>, Vec<_>) = self.held_events.drain(..).partition(|e| {
+            e.matches
+                .iter()
+                .any(|m| fired_chords.iter().any(|(id, _)| *id == m.chord_id))
+        });
+        self.held_events = kept.into();
+        // Dropped presses can't contribute to other chords anymore
+        dropped
+            .into_iter()
+            .map(|e| {
+                for m in e.matches {
+                    if let Some(state) = self.states.get_mut(&m.chord_id) {
+                        if let Some(p) = state.pressed.get_mut(m.member_index) {
+                            *p = None;
+                        }
+                    }
+                }
+                e.payload
+            })
+            .collect()
+    }
+
+    fn state_mut(&mut self, m: &ChordMemberMatch) -> &mut ChordState {
+        let state = self.states.entry(m.chord_id).or_default();
+        if state.pressed.len() != m.member_count {
+            *state = ChordState {
+                pressed: vec![None; m.member_count],
+                swallow_release: vec![false; m.member_count],
+                engaged: false,
+            };
+        }
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{CompartmentKind, MappingId};
+    use helgoboss_midi::{Channel, KeyNumber, ShortMessageFactory, U7};
+    use reaper_common_types::{DurationInSeconds, Hz};
+
+    #[test]
+    fn fire_chord_and_suppress_member_presses() {
+        // Given
+        let chord_id = QualifiedMappingId::new(CompartmentKind::Main, MappingId::random());
+        let mut detector = ChordDetector::default();
+        // When
+        let outcome_1 = detector.process(event("pad 1", 0), true, member(chord_id, 0));
+        let outcome_2 = detector.process(event("pad 2", 20), true, member(chord_id, 1));
+        let replayed = detector.poll(timestamp(200));
+        // Then
+        assert!(outcome_1.pass_through.is_none());
+        assert!(outcome_1.fired_chords.is_empty());
+        assert!(outcome_2.pass_through.is_none());
+        assert_eq!(outcome_2.consumed, vec!["pad 1", "pad 2"]);
+        assert_eq!(outcome_2.fired_chords, vec![(chord_id, true)]);
+        assert!(replayed.is_empty());
+    }
+
+    #[test]
+    fn replay_member_presses_if_chord_incomplete() {
+        // Given
+        let chord_id = QualifiedMappingId::new(CompartmentKind::Main, MappingId::random());
+        let mut detector = ChordDetector::default();
+        // When
+        detector.process(event("pad 1 on", 0), true, member(chord_id, 0));
+        detector.process(event("pad 1 off", 10), false, member(chord_id, 0));
+        let replayed_early = detector.poll(timestamp(30));
+        let outcome = detector.process(event("pad 2 on", 60), true, member(chord_id, 1));
+        let replayed_late: Vec<_> = detector
+            .poll(timestamp(60))
+            .into_iter()
+            .map(|e| e.into_payload())
+            .collect();
+        // Then
+        assert!(replayed_early.is_empty());
+        assert!(outcome.fired_chords.is_empty());
+        assert_eq!(replayed_late, vec!["pad 1 on", "pad 1 off"]);
+    }
+
+    #[test]
+    fn switch_chord_off_on_first_release() {
+        // Given
+        let chord_id = QualifiedMappingId::new(CompartmentKind::Main, MappingId::random());
+        let mut detector = ChordDetector::default();
+        detector.process(event("pad 1 on", 0), true, member(chord_id, 0));
+        detector.process(event("pad 2 on", 10), true, member(chord_id, 1));
+        // When
+        let outcome_1 = detector.process(event("pad 2 off", 500), false, member(chord_id, 1));
+        let outcome_2 = detector.process(event("pad 1 off", 510), false, member(chord_id, 0));
+        // Then
+        assert!(outcome_1.pass_through.is_none());
+        assert_eq!(outcome_1.consumed, vec!["pad 2 off"]);
+        assert_eq!(outcome_1.fired_chords, vec![(chord_id, false)]);
+        assert!(outcome_2.pass_through.is_none());
+        assert_eq!(outcome_2.consumed, vec!["pad 1 off"]);
+        assert!(outcome_2.fired_chords.is_empty());
+    }
+
+    #[test]
+    fn look_up_chord_midi_members() {
+        // Given
+        let chord = ChordSource::new(
+            vec![
+                note_source(36),
+                note_source(37),
+                CompoundMappingSource::Never,
+            ],
+            DEFAULT_CHORD_WINDOW,
+        );
+        let mut lookup = ChordMidiMemberLookup::default();
+        // When
+        lookup.rebuild([&chord, &chord].into_iter());
+        // Then
+        assert!(lookup.contains(&note_on(36)));
+        assert!(lookup.contains(&note_on(37)));
+        assert!(!lookup.contains(&note_on(38)));
+        assert_eq!(lookup.members.len(), 2);
+    }
+
+    #[test]
+    fn clear_chord_midi_member_lookup_on_rebuild() {
+        // Given
+        let chord = ChordSource::new(vec![note_source(36)], DEFAULT_CHORD_WINDOW);
+        let mut lookup = ChordMidiMemberLookup::default();
+        lookup.rebuild([&chord].into_iter());
+        // When
+        lookup.rebuild(std::iter::empty());
+        // Then
+        assert!(!lookup.contains(&note_on(36)));
+    }
+
+    fn note_source(key_number: u8) -> CompoundMappingSource {
+        CompoundMappingSource::Midi(MidiSource::NoteVelocity {
+            channel: Some(Channel::new(0)),
+            key_number: Some(KeyNumber::new(key_number)),
+        })
+    }
+
+    fn note_on(key_number: u8) -> MidiSourceValue<RawShortMessage> {
+        MidiSourceValue::Plain(RawShortMessage::note_on(
+            Channel::new(0),
+            KeyNumber::new(key_number),
+            U7::new(100),
+        ))
+    }
+
+    fn member(chord_id: QualifiedMappingId, member_index: usize) -> Vec<ChordMemberMatch> {
+        vec![ChordMemberMatch {
+            chord_id,
+            member_index,
+            member_count: 2,
+            window: Duration::from_millis(50),
+        }]
+    }
+
+    fn event(payload: &'static str, millis: u64) -> ControlEvent<&'static str> {
+        ControlEvent::new(payload, timestamp(millis))
+    }
+
+    fn timestamp(millis: u64) -> ControlEventTimestamp {
+        ControlEventTimestamp::from_rt(millis, Hz::new_panic(1000.0), DurationInSeconds::ZERO)
+    }
+}
@@ -5,8 +5,12 @@ use crate::domain::{
 };
 use base::hash_util::NonCryptoHashSet;
 use base::regex;
+use derive_more::Display;
 use helgoboss_learn::AbsoluteValue;
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
 use std::error::Error;
+use strum::EnumIter;
 
 #[derive(Debug)]
 pub enum ActivationCondition {
@@ -23,23 +27,46 @@ pub enum ActivationCondition {
         lead_mapping: Option<MappingId>,
         condition: Box<ExpressionEvaluator>,
     },
+    Composite(Box<CompositeCondition>),
 }
 
 impl ActivationCondition {
     /// Returns if this activation condition can be affected by parameter changes in general.
     pub fn can_be_affected_by_parameters(&self) -> bool {
-        !matches!(self, ActivationCondition::Always)
+        match self {
+            ActivationCondition::Always => false,
+            ActivationCondition::Composite(c) => c
+                .operands
+                .iter()
+                .any(|o| o.condition.can_be_affected_by_parameters()),
+            _ => true,
+        }
+    }
+
+    /// Returns the referenced lead mappings of this activation condition (the ones of target-value
+    /// based conditions, also nested ones).
+    pub fn target_value_lead_mappings(&self) -> Vec<MappingId> {
+        let mut mapping_ids = vec![];
+        self.collect_target_value_lead_mappings(&mut mapping_ids);
+        mapping_ids
     }
 
-    /// Returns the referenced lead mapping of this activation condition if it's a target-value
-    /// based one.
-    pub fn target_value_lead_mapping(&self) -> Option<MappingId> {
+    fn collect_target_value_lead_mappings(&self, mapping_ids: &mut Vec<MappingId>) {
         match self {
             ActivationCondition::TargetValue {
                 lead_mapping: Some(m),
                 ..
-            } => Some(*m),
-            _ => None,
+            } => {
+                if !mapping_ids.contains(m) {
+                    mapping_ids.push(*m);
+                }
+            }
+            ActivationCondition::Composite(c) => {
+                for o in &c.operands {
+                    o.condition.collect_target_value_lead_mappings(mapping_ids);
+                }
+            }
+            _ => {}
         }
     }
 
@@ -63,6 +90,7 @@ impl ActivationCondition {
             }
             Expression(condition) => condition.is_fulfilled(params),
             TargetValue { .. } => return None,
+            Composite(condition) => condition.is_fulfilled(params),
         };
         Some(res)
     }
@@ -92,6 +120,9 @@ impl ActivationCondition {
                 });
                 result.ok().map(|v| v > 0.0)
             }
+            ActivationCondition::Composite(c) => c.process_operand_updates(|condition| {
+                condition.process_target_value_update(lead_mapping_id, target_value)
+            }),
             _ => None,
         }
     }
@@ -152,11 +183,109 @@ impl ActivationCondition {
             // This conditional activation doesn't depend on parameter values, it's evaluated
             // in other ways.
             TargetValue { .. } => return None,
+            Composite(condition) => {
+                return condition.process_operand_updates(|operand| {
+                    operand.process_param_update(params, index, previous_value)
+                })
+            }
         };
         Some(is_fulfilled)
     }
 }
 
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Serialize, Deserialize, EnumIter, Display)]
+pub enum BooleanOperator {
+    /// Fulfilled if all operands are fulfilled.
+    #[default]
+    #[serde(rename = "and")]
+    #[display(fmt = "AND")]
+    And,
+    /// Fulfilled if at least one operand is fulfilled.
+    #[serde(rename = "or")]
+    #[display(fmt = "OR")]
+    Or,
+    /// Fulfilled if none of the operands is fulfilled (so with one operand, it's a negation).
+    #[serde(rename = "not")]
+    #[display(fmt = "NOT")]
+    Not,
+}
+
+impl BooleanOperator {
+    fn apply(&self, mut operand_values: impl Iterator<Item = bool>) -> bool {
+        match self {
+            BooleanOperator::And => operand_values.all(|v| v),
+            BooleanOperator::Or => operand_values.any(|v| v),
+            BooleanOperator::Not => !operand_values.any(|v| v),
+        }
+    }
+}
+
+/// Combines other activation conditions (which can be composite as well) using a boolean
+/// operator.
+#[derive(Debug)]
+pub struct CompositeCondition {
+    operator: BooleanOperator,
+    operands: Vec<Operand>,
+}
+
+#[derive(Debug)]
+struct Operand {
+    condition: ActivationCondition,
+    /// Last known state of this operand.
+    ///
+    /// Necessary because not all conditions can be evaluated from parameters (target-value based
+    /// ones) and because single updates should only need to re-evaluate the affected operands.
+    is_fulfilled: Cell<bool>,
+}
+
+impl CompositeCondition {
+    pub fn new(operator: BooleanOperator, conditions: Vec<ActivationCondition>) -> Self {
+        Self {
+            operator,
+            operands: conditions
+                .into_iter()
+                .map(|condition| Operand {
+                    condition,
+                    is_fulfilled: Cell::new(false),
+                })
+                .collect(),
+        }
+    }
+
+    fn is_fulfilled(&self, params: &CompartmentParams) -> bool {
+        for o in &self.operands {
+            if let Some(is_fulfilled) = o.condition.is_fulfilled(params) {
+                o.is_fulfilled.set(is_fulfilled);
+            }
+        }
+        self.evaluate()
+    }
+
+    /// Lets the given function process the update for each operand and returns the new state
+    /// if at least one operand was affected.
+    fn process_operand_updates(
+        &self,
+        mut process: impl FnMut(&ActivationCondition) -> Option<bool>,
+    ) -> Option<bool> {
+        let mut is_affected = false;
+        for o in &self.operands {
+            if let Some(is_fulfilled) = process(&o.condition) {
+                o.is_fulfilled.set(is_fulfilled);
+                is_affected = true;
+            }
+        }
+        if !is_affected {
+            return None;
+        }
+        Some(self.evaluate())
+    }
+
+    fn evaluate(&self) -> bool {
+        self.operator
+            .apply(self.operands.iter().map(|o| o.is_fulfilled.get()))
+    }
+}
+
 fn modifier_conditions_are_fulfilled(
     conditions: &[ModifierCondition],
     params: &CompartmentParams,
@@ -318,3 +447,70 @@ fn extract_used_param_indexes(eel_script: &str) -> NonCryptoHashSet<u32> {
         .map(|i: u32| i - 1)
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helgoboss_learn::UnitValue;
+
+    #[test]
+    fn composite_condition_tracks_operand_changes() {
+        // Given
+        let shift = CompartmentParamIndex::try_from(0).unwrap();
+        let unrelated = CompartmentParamIndex::try_from(5).unwrap();
+        let lead_mapping = MappingId::random();
+        let mut params = CompartmentParams::default();
+        params.at_mut(shift).set_raw_value(1.0);
+        let condition = ActivationCondition::Composite(Box::new(CompositeCondition::new(
+            BooleanOperator::And,
+            vec![
+                ActivationCondition::Modifiers(vec![ModifierCondition::new(shift, true)]),
+                ActivationCondition::TargetValue {
+                    lead_mapping: Some(lead_mapping),
+                    condition: Box::new(ExpressionEvaluator::compile("y > 0").unwrap()),
+                },
+            ],
+        )));
+        // When
+        // Then
+        assert_eq!(condition.target_value_lead_mappings(), vec![lead_mapping]);
+        assert!(condition.can_be_affected_by_parameters());
+        // Target value not known yet
+        assert_eq!(condition.is_fulfilled(&params), Some(false));
+        let on = Some(AbsoluteValue::Continuous(UnitValue::MAX));
+        assert_eq!(
+            condition.process_target_value_update(MappingId::random(), on),
+            None
+        );
+        assert_eq!(
+            condition.process_target_value_update(lead_mapping, on),
+            Some(true)
+        );
+        assert_eq!(
+            condition.process_param_update(&params, unrelated, 0.0),
+            None
+        );
+        params.at_mut(shift).set_raw_value(0.0);
+        assert_eq!(
+            condition.process_param_update(&params, shift, 1.0),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn not_operator() {
+        // Given
+        let mute = CompartmentParamIndex::try_from(0).unwrap();
+        let params = CompartmentParams::default();
+        let condition = ActivationCondition::Composite(Box::new(CompositeCondition::new(
+            BooleanOperator::Not,
+            vec![ActivationCondition::Modifiers(vec![
+                ModifierCondition::new(mute, true),
+            ])],
+        )));
+        // When
+        let is_fulfilled = condition.is_fulfilled(&params);
+        // Then
+        assert_eq!(is_fulfilled, Some(true));
+    }
+}
@@ -5,13 +5,14 @@ use crate::domain::{
     MonitoringFxChainChangeDetector, OscDeviceId, OscInputDevice, OscScanResult,
     QualifiedInstanceEvent, ReaperConfigChangeDetector, ReaperMessage, ReaperTarget,
     SharedInstance, SharedMainProcessors, StreamDeckDevicePayload, TargetTouchEvent,
-    TouchedTrackParameterType, UnitEvent, UnitId, WeakInstance,
+    TouchedTrackParameterType, TrackRecordingChangeDetector, UnitEvent, UnitId, WeakInstance,
 };
 use base::{metrics_util, Global, NamedChannelSender, SenderToNormalThread};
 use crossbeam_channel::Receiver;
+use helgobox_api::persistence::RecordMode;
 use reaper_high::{
     ChangeDetectionMiddleware, ChangeEvent, ControlSurfaceEvent, ControlSurfaceMiddleware,
-    FutureMiddleware, Fx, FxParameter, MainTaskMiddleware, Project, Reaper,
+    FutureMiddleware, Fx, FxParameter, MainTaskMiddleware, Project, Reaper, Track,
 };
 use reaper_rx::ControlSurfaceRxMiddleware;
 use rosc::{OscMessage, OscPacket};
@@ -45,6 +46,7 @@ pub struct RealearnControlSurfaceMiddleware<EH: DomainEventHandler> {
     change_detection_middleware: ChangeDetectionMiddleware,
     change_event_queue: RefCell<Vec<ChangeEvent>>,
     monitoring_fx_chain_change_detector: MonitoringFxChainChangeDetector,
+    track_recording_change_detector: TrackRecordingChangeDetector,
     rx_middleware: ControlSurfaceRxMiddleware,
     instances: NonCryptoIndexMap<InstanceId, WeakInstance>,
     main_processors: SharedMainProcessors<EH>,
@@ -138,6 +140,10 @@ pub enum AdditionalFeedbackEvent {
         unit_event: UnitEvent,
     },
     LastTouchedTargetChanged,
+    /// Detected by polling because REAPER doesn't notify about it.
+    TrackRecordModeChanged(TrackRecordModeChangedEvent),
+    /// Detected by polling because REAPER only notifies about single FX being enabled/disabled.
+    TrackInputFxEnabledChanged(TrackInputFxEnabledChangedEvent),
 }
 
 #[derive(Debug)]
@@ -193,6 +199,18 @@ pub struct RealearnMonitoringFxParameterValueChangedEvent {
     pub new_value: ReaperNormalizedFxParamValue,
 }
 
+#[derive(Debug)]
+pub struct TrackRecordModeChangedEvent {
+    pub track: Track,
+    pub new_value: RecordMode,
+}
+
+#[derive(Debug)]
+pub struct TrackInputFxEnabledChangedEvent {
+    pub track: Track,
+    pub new_value: bool,
+}
+
 #[derive(Debug)]
 pub struct ParameterAutomationTouchStateChangedEvent {
     pub track: MediaTrack,
@@ -223,6 +241,7 @@ impl<EH: DomainEventHandler> RealearnControlSurfaceMiddleware<EH> {
             change_detection_middleware: ChangeDetectionMiddleware::new(),
             change_event_queue: RefCell::new(Vec::with_capacity(100)),
             monitoring_fx_chain_change_detector: Default::default(),
+            track_recording_change_detector: Default::default(),
             rx_middleware: ControlSurfaceRxMiddleware::new(Global::control_surface_rx().clone()),
             instances: Default::default(),
             main_processors,
@@ -276,7 +295,8 @@ impl<EH: DomainEventHandler> RealearnControlSurfaceMiddleware<EH> {
         // whether the changes were caused by ReaLearn targets or direct interaction with REAPER
         // (e.g. via mouse). However, since change events detected by polling are currently not
         // used for target learning / last touched target detection anyway, we don't need to know
-        // about their cause.
+        // about their cause. The only exception are track recording changes, which can be used
+        // for last touched target detection. They are always treated as not caused by ReaLearn.
         self.poll_for_more_change_events();
         // Process REAPER events that occurred since the last call of run(). All events
         // accumulated up to this point are most likely *not* caused by ReaLearn, but by direct
@@ -299,6 +319,7 @@ impl<EH: DomainEventHandler> RealearnControlSurfaceMiddleware<EH> {
         self.emit_focus_switch_between_main_and_fx_as_feedback_event();
         self.emit_instance_events();
         self.emit_stream_deck_events(timestamp);
+        self.emit_evdev_events(timestamp);
         self.emit_beats_as_feedback_events();
         self.detect_device_changes(timestamp);
         self.process_incoming_osc_messages(timestamp);
@@ -721,6 +742,19 @@ impl<EH: DomainEventHandler> RealearnControlSurfaceMiddleware<EH> {
         }
     }
 
+    fn emit_evdev_events(&mut self, timestamp: ControlEventTimestamp) {
+        let backbone = Backbone::get();
+        for msg in backbone.poll_evdev_messages() {
+            for p in &mut *self.main_processors.borrow_mut() {
+                if !p.wants_evdev_input_from(msg.dev_id) {
+                    continue;
+                }
+                let event = ControlEvent::new(msg.msg, timestamp);
+                p.process_incoming_evdev_msg(event);
+            }
+        }
+    }
+
     fn emit_beats_as_feedback_events(&mut self) {
         for project in Reaper::get().projects() {
             let reference_pos = if project.is_playing() {
@@ -747,6 +781,8 @@ impl<EH: DomainEventHandler> RealearnControlSurfaceMiddleware<EH> {
         }
         // Stream deck
         let added_stream_deck_device_ids = Backbone::get().detect_stream_deck_device_changes();
+        // Evdev
+        Backbone::get().detect_evdev_device_changes();
         // MIDI
         let midi_in_diff = self
             .device_change_detector
@@ -855,15 +891,45 @@ impl<EH: DomainEventHandler> RealearnControlSurfaceMiddleware<EH> {
     }
 
     fn poll_for_more_change_events(&mut self) {
-        let mut change_event_queue = self.change_event_queue.borrow_mut();
-        measure_time(
-            "helgobox.control_surface.poll_for_more_change_events",
-            || {
-                self.change_detection_middleware.run(&mut |change_event| {
-                    change_event_queue.push(change_event);
-                });
-            },
+        {
+            let mut change_event_queue = self.change_event_queue.borrow_mut();
+            measure_time(
+                "helgobox.control_surface.poll_for_more_change_events",
+                || {
+                    self.change_detection_middleware.run(&mut |change_event| {
+                        change_event_queue.push(change_event);
+                    });
+                },
+            );
+        }
+        self.poll_for_track_recording_changes();
+    }
+
+    fn poll_for_track_recording_changes(&mut self) {
+        // Polling all tracks is not free, so we only do it if someone is interested in the result
+        let is_learning_targets = !self.target_capture_senders.is_empty();
+        let mut main_processors = self.main_processors.borrow_mut();
+        if !is_learning_targets
+            && !main_processors
+                .iter()
+                .any(|p| p.wants_track_recording_changes())
+        {
+            self.track_recording_change_detector.reset();
+            return;
+        }
+        let events = measure_time(
+            "helgobox.control_surface.detect_track_recording_changes",
+            || self.track_recording_change_detector.poll_for_changes(),
         );
+        for event in events {
+            for p in main_processors.iter_mut() {
+                p.process_additional_feedback_event(&event)
+            }
+            if let Some(target) = ReaperTarget::touched_from_additional_event(&event) {
+                // Like all polled changes, we can't tell whether ReaLearn caused them
+                process_touched_target(target, false, &self.target_capture_senders);
+            }
+        }
     }
 }
 
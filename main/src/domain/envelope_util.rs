@@ -0,0 +1,348 @@
+use crate::domain::low_reaper;
+use reaper_high::{Fx, FxParameter, Project, Track};
+use reaper_low::raw;
+use std::ffi::{c_char, CStr, CString};
+use std::ptr::{null_mut, NonNull};
+
+/// Describes where to find an automation envelope.
+///
+/// Envelopes can appear and disappear at any time (e.g. when the user shows or removes an
+/// envelope lane) without REAPER notifying us, so targets keep this description and ask for
+/// the envelope only when they need it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EnvelopeRef {
+    /// Track envelope with the given name, e.g. "Volume".
+    Track { track: Track, name: String },
+    /// Automation envelope of the given FX parameter.
+    FxParameter(FxParameter),
+}
+
+impl EnvelopeRef {
+    pub fn envelope(&self) -> Option<AutomationEnvelope> {
+        match self {
+            EnvelopeRef::Track { track, name } => {
+                AutomationEnvelope::track_envelope_by_name(track, name)
+            }
+            EnvelopeRef::FxParameter(param) => AutomationEnvelope::fx_parameter_envelope(param),
+        }
+    }
+
+    pub fn project(&self) -> Option<Project> {
+        match self {
+            EnvelopeRef::Track { track, .. } => Some(track.project()),
+            EnvelopeRef::FxParameter(param) => param.fx().project(),
+        }
+    }
+
+    pub fn track(&self) -> Option<&Track> {
+        match self {
+            EnvelopeRef::Track { track, .. } => Some(track),
+            EnvelopeRef::FxParameter(param) => param.fx().track(),
+        }
+    }
+
+    pub fn fx(&self) -> Option<&Fx> {
+        match self {
+            EnvelopeRef::Track { .. } => None,
+            EnvelopeRef::FxParameter(param) => Some(param.fx()),
+        }
+    }
+}
+
+/// Describes how envelope values are to be interpreted.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum EnvelopeValueRange {
+    /// Volume envelope. Values are amplitudes (1.0 = 0 dB).
+    Volume,
+    /// Values go linearly from the given minimum to the given maximum, e.g. from -1.0 to 1.0 for
+    /// pan envelopes.
+    Linear { min: f64, max: f64 },
+}
+
+/// A track or FX parameter automation envelope, together with the range of its values.
+///
+/// Don't keep instances of this around, use [`EnvelopeRef`] instead.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AutomationEnvelope {
+    raw: NonNull<raw::TrackEnvelope>,
+    range: EnvelopeValueRange,
+}
+
+impl AutomationEnvelope {
+    /// Returns the track envelope with the given name if it exists.
+    pub fn track_envelope_by_name(track: &Track, name: &str) -> Option<Self> {
+        let raw_track = track.raw().ok()?;
+        let c_name = CString::new(name).ok()?;
+        let raw =
+            unsafe { low_reaper().GetTrackEnvelopeByName(raw_track.as_ptr(), c_name.as_ptr()) };
+        let raw = NonNull::new(raw)?;
+        let range = built_in_track_envelope_range(raw_track, raw);
+        Some(Self { raw, range })
+    }
+
+    /// Returns the automation envelope of the given FX parameter if it exists.
+    pub fn fx_parameter_envelope(param: &FxParameter) -> Option<Self> {
+        let fx = param.fx();
+        let raw_track = fx.track()?.raw().ok()?;
+        let fx_index = fx.query_index().to_raw();
+        let param_index = param.index() as i32;
+        let raw =
+            unsafe { low_reaper().GetFXEnvelope(raw_track.as_ptr(), fx_index, param_index, false) };
+        let mut min = 0.0;
+        let mut max = 0.0;
+        unsafe {
+            low_reaper().TrackFX_GetParam(
+                raw_track.as_ptr(),
+                fx_index,
+                param_index,
+                &mut min,
+                &mut max,
+            );
+        }
+        let range = if max > min {
+            EnvelopeValueRange::Linear { min, max }
+        } else {
+            EnvelopeValueRange::Linear { min: 0.0, max: 1.0 }
+        };
+        Self::from_raw(raw, range)
+    }
+
+    fn from_raw(raw: *mut raw::TrackEnvelope, range: EnvelopeValueRange) -> Option<Self> {
+        let envelope = Self {
+            raw: NonNull::new(raw)?,
+            range,
+        };
+        Some(envelope)
+    }
+
+    fn scaling_mode(&self) -> i32 {
+        envelope_scaling_mode(self.raw)
+    }
+
+    pub fn range(&self) -> EnvelopeValueRange {
+        self.range
+    }
+
+    /// Returns the effective envelope value at the given position in seconds.
+    pub fn value_at(&self, time: f64) -> f64 {
+        let mut value = 0.0;
+        unsafe {
+            low_reaper().Envelope_Evaluate(
+                self.raw.as_ptr(),
+                time,
+                0.0,
+                0,
+                &mut value,
+                null_mut(),
+                null_mut(),
+                null_mut(),
+            );
+        }
+        self.scale_from_envelope_mode(value)
+    }
+
+    /// Sets the envelope value at the given position in seconds.
+    ///
+    /// Adjusts the point at exactly this position if there is one, otherwise inserts a new point.
+    pub fn set_value_at(&self, time: f64, value: f64) {
+        let mut value = self.scale_to_envelope_mode(value);
+        let existing_index = self.point_index_at(time).filter(|i| {
+            self.point_time(*i)
+                .is_some_and(|t| (t - time).abs() < 0.0001)
+        });
+        match existing_index {
+            Some(i) => unsafe {
+                low_reaper().SetEnvelopePoint(
+                    self.raw.as_ptr(),
+                    i as _,
+                    null_mut(),
+                    &mut value,
+                    null_mut(),
+                    null_mut(),
+                    null_mut(),
+                    null_mut(),
+                );
+            },
+            None => unsafe {
+                let mut no_sort = true;
+                low_reaper().InsertEnvelopePoint(
+                    self.raw.as_ptr(),
+                    time,
+                    value,
+                    0,
+                    0.0,
+                    false,
+                    &mut no_sort,
+                );
+                low_reaper().Envelope_SortPoints(self.raw.as_ptr());
+            },
+        }
+        low_reaper().UpdateArrange();
+    }
+
+    pub fn point_count(&self) -> u32 {
+        let count = unsafe { low_reaper().CountEnvelopePoints(self.raw.as_ptr()) };
+        count.max(0) as u32
+    }
+
+    /// Returns the position of the given point in seconds.
+    pub fn point_time(&self, index: u32) -> Option<f64> {
+        let mut time = 0.0;
+        let exists = unsafe {
+            low_reaper().GetEnvelopePoint(
+                self.raw.as_ptr(),
+                index as _,
+                &mut time,
+                null_mut(),
+                null_mut(),
+                null_mut(),
+                null_mut(),
+            )
+        };
+        exists.then_some(time)
+    }
+
+    /// Returns the index of the last point at or before the given position in seconds.
+    pub fn point_index_at(&self, time: f64) -> Option<u32> {
+        let index = unsafe { low_reaper().GetEnvelopePointByTime(self.raw.as_ptr(), time) };
+        u32::try_from(index).ok()
+    }
+
+    pub fn is_armed(&self) -> bool {
+        let mut buffer = [0 as c_char; 64];
+        let successful = unsafe {
+            low_reaper().GetSetEnvelopeInfo_String(
+                self.raw.as_ptr(),
+                c"ARM".as_ptr(),
+                buffer.as_mut_ptr(),
+                false,
+            )
+        };
+        if !successful {
+            return false;
+        }
+        let value = unsafe { CStr::from_ptr(buffer.as_ptr()) };
+        value.to_bytes() == b"1"
+    }
+
+    pub fn set_armed(&self, armed: bool) {
+        let mut value = if armed { *b"1\0" } else { *b"0\0" };
+        unsafe {
+            low_reaper().GetSetEnvelopeInfo_String(
+                self.raw.as_ptr(),
+                c"ARM".as_ptr(),
+                value.as_mut_ptr() as *mut c_char,
+                true,
+            );
+        }
+        low_reaper().UpdateArrange();
+    }
+
+    /// Volume envelopes might store their values fader-scaled. We always work with the unscaled
+    /// values.
+    fn scale_from_envelope_mode(&self, value: f64) -> f64 {
+        let mode = self.scaling_mode();
+        if mode == 0 {
+            return value;
+        }
+        low_reaper().ScaleFromEnvelopeMode(mode, value)
+    }
+
+    fn scale_to_envelope_mode(&self, value: f64) -> f64 {
+        let mode = self.scaling_mode();
+        if mode == 0 {
+            return value;
+        }
+        low_reaper().ScaleToEnvelopeMode(mode, value)
+    }
+}
+
+/// Chunk names of the built-in track envelopes whose value range is known.
+///
+/// In contrast to envelope names, they are not localized.
+const BUILT_IN_TRACK_ENVELOPE_CHUNK_NAMES: [&CStr; 8] = [
+    c"<VOLENV2",
+    c"<VOLENV",
+    c"<VOLENV3",
+    c"<PANENV2",
+    c"<PANENV",
+    c"<WIDTHENV2",
+    c"<WIDTHENV",
+    c"<MUTEENV",
+];
+
+/// Determines the value range of the given track envelope by finding out which built-in track
+/// envelope it is.
+fn built_in_track_envelope_range(
+    raw_track: NonNull<raw::MediaTrack>,
+    raw_envelope: NonNull<raw::TrackEnvelope>,
+) -> EnvelopeValueRange {
+    // Only volume envelopes are fader-scaled
+    if envelope_scaling_mode(raw_envelope) != 0 {
+        return EnvelopeValueRange::Volume;
+    }
+    let chunk_name = BUILT_IN_TRACK_ENVELOPE_CHUNK_NAMES
+        .into_iter()
+        .find(|chunk_name| {
+            let candidate = unsafe {
+                low_reaper().GetTrackEnvelopeByChunkName(raw_track.as_ptr(), chunk_name.as_ptr())
+            };
+            candidate == raw_envelope.as_ptr()
+        });
+    track_envelope_range_by_chunk_name(chunk_name)
+}
+
+fn track_envelope_range_by_chunk_name(chunk_name: Option<&CStr>) -> EnvelopeValueRange {
+    let Some(chunk_name) = chunk_name else {
+        return EnvelopeValueRange::Linear { min: 0.0, max: 1.0 };
+    };
+    let chunk_name = chunk_name.to_bytes();
+    if chunk_name.starts_with(b"<VOLENV") {
+        EnvelopeValueRange::Volume
+    } else if chunk_name.starts_with(b"<PANENV") || chunk_name.starts_with(b"<WIDTHENV") {
+        EnvelopeValueRange::Linear {
+            min: -1.0,
+            max: 1.0,
+        }
+    } else {
+        EnvelopeValueRange::Linear { min: 0.0, max: 1.0 }
+    }
+}
+
+fn envelope_scaling_mode(raw_envelope: NonNull<raw::TrackEnvelope>) -> i32 {
+    unsafe { low_reaper().GetEnvelopeScalingMode(raw_envelope.as_ptr()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derive_range_from_built_in_track_envelope() {
+        // Given
+        let bipolar = EnvelopeValueRange::Linear {
+            min: -1.0,
+            max: 1.0,
+        };
+        let unipolar = EnvelopeValueRange::Linear { min: 0.0, max: 1.0 };
+        // When
+        // Then
+        assert_eq!(
+            track_envelope_range_by_chunk_name(Some(c"<VOLENV3")),
+            EnvelopeValueRange::Volume
+        );
+        assert_eq!(
+            track_envelope_range_by_chunk_name(Some(c"<PANENV2")),
+            bipolar
+        );
+        assert_eq!(
+            track_envelope_range_by_chunk_name(Some(c"<WIDTHENV")),
+            bipolar
+        );
+        assert_eq!(
+            track_envelope_range_by_chunk_name(Some(c"<MUTEENV")),
+            unipolar
+        );
+        assert_eq!(track_envelope_range_by_chunk_name(None), unipolar);
+    }
+}
@@ -0,0 +1,207 @@
+use crate::domain::{EvdevMessage, UnitId};
+use base::hash_util::{NonCryptoHashMap, NonCryptoHashSet};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Debug)]
+pub struct EvdevDevice {
+    pub id: EvdevDeviceId,
+    pub name: String,
+}
+
+/// Returns all evdev devices which the current user is allowed to read.
+///
+/// Devices are usually only readable by members of the `input` group.
+#[cfg(target_os = "linux")]
+pub fn probe_evdev_devices() -> Vec<EvdevDevice> {
+    let mut devices: Vec<_> = evdev::enumerate()
+        .map(|(_, dev)| EvdevDevice {
+            id: EvdevDeviceId::from_input_id(dev.input_id()),
+            name: dev.name().unwrap_or("Unknown device").to_string(),
+        })
+        .collect();
+    devices.sort_by_key(|d| d.id);
+    // Devices with the same vendor and product ID can't be told apart
+    devices.dedup_by_key(|d| d.id);
+    devices
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn probe_evdev_devices() -> Vec<EvdevDevice> {
+    vec![]
+}
+
+/// Identifies an evdev device by its vendor and product ID.
+///
+/// Unlike the number N in the device file path `/dev/input/eventN`, this doesn't change when the
+/// device is plugged into another port or when devices are enumerated in a different order after
+/// a reboot. If multiple devices of the same type are connected, the first one is used.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub struct EvdevDeviceId {
+    /// Vendor ID.
+    pub vid: u16,
+    /// Product ID.
+    pub pid: u16,
+}
+
+impl EvdevDeviceId {
+    pub fn new(vid: u16, pid: u16) -> Self {
+        Self { vid, pid }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn from_input_id(input_id: evdev::InputId) -> Self {
+        Self::new(input_id.vendor(), input_id.product())
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn connect(&self) -> anyhow::Result<ConnectedEvdevDevice> {
+        ConnectedEvdevDevice::open(*self)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn connect(&self) -> anyhow::Result<ConnectedEvdevDevice> {
+        anyhow::bail!("evdev devices are only available on Linux")
+    }
+}
+
+impl Display for EvdevDeviceId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04x}:{:04x}", self.vid, self.pid)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct EvdevDeviceManager {
+    device_usage: NonCryptoHashMap<UnitId, EvdevDeviceId>,
+}
+
+impl EvdevDeviceManager {
+    pub fn register_device_usage(&mut self, unit_id: UnitId, device: Option<EvdevDeviceId>) {
+        if let Some(d) = device {
+            self.device_usage.insert(unit_id, d);
+        } else {
+            self.device_usage.remove(&unit_id);
+        }
+    }
+
+    pub fn devices_in_use(&self) -> NonCryptoHashSet<EvdevDeviceId> {
+        self.device_usage.values().copied().collect()
+    }
+}
+
+/// An evdev device opened in non-blocking mode, so it can be polled from the main thread.
+pub struct ConnectedEvdevDevice {
+    #[cfg(target_os = "linux")]
+    device: evdev::Device,
+    /// Minimum and maximum value of each absolute axis, for normalizing axis values.
+    #[cfg(target_os = "linux")]
+    abs_ranges: NonCryptoHashMap<u16, (i32, i32)>,
+}
+
+#[cfg(target_os = "linux")]
+impl ConnectedEvdevDevice {
+    fn open(id: EvdevDeviceId) -> anyhow::Result<Self> {
+        use anyhow::Context;
+        use std::os::fd::AsRawFd;
+        let (_, device) = evdev::enumerate()
+            .find(|(_, dev)| EvdevDeviceId::from_input_id(dev.input_id()) == id)
+            .context("evdev device not connected or not readable")?;
+        // Make fetch_events() return WouldBlock instead of blocking the main thread
+        let fd = device.as_raw_fd();
+        let result = unsafe {
+            let flags = libc::fcntl(fd, libc::F_GETFL);
+            libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK)
+        };
+        if result < 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+        let abs_state = device.get_abs_state()?;
+        let abs_ranges = device
+            .supported_absolute_axes()
+            .map(|axes| {
+                axes.iter()
+                    .map(|axis| {
+                        let info = abs_state[axis.0 as usize];
+                        (axis.0, (info.minimum, info.maximum))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let dev = Self { device, abs_ranges };
+        Ok(dev)
+    }
+
+    /// Appends all events that arrived since the last poll.
+    pub fn poll(&mut self, messages: &mut Vec<EvdevMessage>) -> std::io::Result<()> {
+        use crate::domain::EvdevEventPayload;
+        use evdev::EventType;
+        let events = match self.device.fetch_events() {
+            Ok(events) => events,
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        for evt in events {
+            let payload = match evt.event_type() {
+                EventType::KEY => match evt.value() {
+                    0 => EvdevEventPayload::Key { press: false },
+                    1 => EvdevEventPayload::Key { press: true },
+                    // Autorepeat
+                    _ => continue,
+                },
+                EventType::ABSOLUTE => {
+                    let Some((min, max)) = self.abs_ranges.get(&evt.code()) else {
+                        continue;
+                    };
+                    EvdevEventPayload::AbsoluteAxis(normalize_abs_value(evt.value(), *min, *max))
+                }
+                EventType::RELATIVE => EvdevEventPayload::RelativeAxis(evt.value()),
+                _ => continue,
+            };
+            messages.push(EvdevMessage::new(evt.code(), payload));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+impl ConnectedEvdevDevice {
+    pub fn poll(&mut self, _messages: &mut Vec<EvdevMessage>) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn normalize_abs_value(value: i32, min: i32, max: i32) -> helgoboss_learn::UnitValue {
+    if max <= min {
+        return helgoboss_learn::UnitValue::MIN;
+    }
+    let span = max as f64 - min as f64;
+    helgoboss_learn::UnitValue::new_clamped((value as f64 - min as f64) / span)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_device_id() {
+        // Given
+        let id = EvdevDeviceId::new(0x045e, 0x028e);
+        // When
+        let text = id.to_string();
+        // Then
+        assert_eq!(text, "045e:028e");
+    }
+
+    #[test]
+    fn persist_device_id_independently_of_device_file() {
+        // Given
+        let id = EvdevDeviceId::new(0x045e, 0x028e);
+        // When
+        let json = serde_json::to_value(id).unwrap();
+        // Then
+        assert_eq!(json, serde_json::json!({"vid": 0x045e, "pid": 0x028e}));
+        assert_eq!(serde_json::from_value::<EvdevDeviceId>(json).unwrap(), id);
+    }
+}
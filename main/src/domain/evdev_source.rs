@@ -0,0 +1,157 @@
+use crate::domain::EvdevDeviceId;
+use helgoboss_learn::{ControlValue, DiscreteIncrement, SourceCharacter, UnitValue};
+use helgobox_api::persistence::EvdevEventKind;
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct EvdevSource {
+    pub event_kind: EvdevEventKind,
+    pub code: u16,
+}
+
+impl EvdevSource {
+    pub fn new(event_kind: EvdevEventKind, code: u16) -> Self {
+        Self { event_kind, code }
+    }
+
+    pub fn control(&self, msg: EvdevMessage) -> Option<ControlValue> {
+        if msg.code != self.code || msg.payload.event_kind() != self.event_kind {
+            return None;
+        }
+        let value = match msg.payload {
+            EvdevEventPayload::Key { press } => {
+                let val = if press {
+                    UnitValue::MAX
+                } else {
+                    UnitValue::MIN
+                };
+                ControlValue::AbsoluteContinuous(val)
+            }
+            EvdevEventPayload::AbsoluteAxis(val) => ControlValue::AbsoluteContinuous(val),
+            EvdevEventPayload::RelativeAxis(0) => return None,
+            EvdevEventPayload::RelativeAxis(delta) => {
+                ControlValue::RelativeDiscrete(DiscreteIncrement::new(delta))
+            }
+        };
+        Some(value)
+    }
+
+    pub fn character(&self) -> SourceCharacter {
+        match self.event_kind {
+            EvdevEventKind::Key => SourceCharacter::MomentaryButton,
+            EvdevEventKind::AbsoluteAxis => SourceCharacter::RangeElement,
+            EvdevEventKind::RelativeAxis => SourceCharacter::Encoder1,
+        }
+    }
+}
+
+impl Display for EvdevSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.event_kind, self.code)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct EvdevMessage {
+    pub code: u16,
+    pub payload: EvdevEventPayload,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum EvdevEventPayload {
+    Key {
+        press: bool,
+    },
+    /// Already normalized according to the minimum and maximum value reported by the device.
+    AbsoluteAxis(UnitValue),
+    RelativeAxis(i32),
+}
+
+impl EvdevEventPayload {
+    pub fn event_kind(&self) -> EvdevEventKind {
+        match self {
+            EvdevEventPayload::Key { .. } => EvdevEventKind::Key,
+            EvdevEventPayload::AbsoluteAxis(_) => EvdevEventKind::AbsoluteAxis,
+            EvdevEventPayload::RelativeAxis(_) => EvdevEventKind::RelativeAxis,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct QualifiedEvdevMessage {
+    pub dev_id: EvdevDeviceId,
+    pub msg: EvdevMessage,
+}
+
+impl EvdevMessage {
+    pub fn new(code: u16, payload: EvdevEventPayload) -> Self {
+        Self { code, payload }
+    }
+}
+
+impl Display for EvdevMessage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} ", self.payload.event_kind(), self.code)?;
+        match self.payload {
+            EvdevEventPayload::Key { press } => write!(f, "{press}"),
+            EvdevEventPayload::AbsoluteAxis(v) => write!(f, "{v}"),
+            EvdevEventPayload::RelativeAxis(delta) => write!(f, "{delta:+}"),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct EvdevScanResult {
+    pub message: EvdevMessage,
+    pub dev_id: Option<EvdevDeviceId>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn control_key() {
+        // Given
+        let source = EvdevSource::new(EvdevEventKind::Key, 304);
+        // When
+        let press = source.control(EvdevMessage::new(
+            304,
+            EvdevEventPayload::Key { press: true },
+        ));
+        let other_key = source.control(EvdevMessage::new(
+            305,
+            EvdevEventPayload::Key { press: true },
+        ));
+        // Then
+        assert_eq!(
+            press,
+            Some(ControlValue::AbsoluteContinuous(UnitValue::MAX))
+        );
+        assert_eq!(other_key, None);
+    }
+
+    #[test]
+    fn control_axes() {
+        // Given
+        let abs_source = EvdevSource::new(EvdevEventKind::AbsoluteAxis, 0);
+        let rel_source = EvdevSource::new(EvdevEventKind::RelativeAxis, 0);
+        let abs_msg = EvdevMessage::new(0, EvdevEventPayload::AbsoluteAxis(UnitValue::new(0.5)));
+        let rel_msg = EvdevMessage::new(0, EvdevEventPayload::RelativeAxis(-2));
+        // When
+        // Then
+        assert_eq!(
+            abs_source.control(abs_msg),
+            Some(ControlValue::AbsoluteContinuous(UnitValue::new(0.5)))
+        );
+        assert_eq!(abs_source.control(rel_msg), None);
+        assert_eq!(
+            rel_source.control(rel_msg),
+            Some(ControlValue::RelativeDiscrete(DiscreteIncrement::new(-2)))
+        );
+        assert_eq!(
+            rel_source.control(EvdevMessage::new(0, EvdevEventPayload::RelativeAxis(0))),
+            None
+        );
+    }
+}
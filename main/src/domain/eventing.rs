@@ -1,7 +1,7 @@
 use crate::domain::{
     CompartmentKind, CompoundMappingTarget, ControlLogContext, ControlLogEntry, FeedbackLogEntry,
-    InternalInfoEvent, MappingId, MessageCaptureResult, PluginParamIndex, PluginParams,
-    ProjectionFeedbackValue, QualifiedMappingId, RawParamValue,
+    InternalInfoEvent, MappingId, MatchOutcome, MessageCaptureResult, PluginParamIndex,
+    PluginParams, ProjectionFeedbackValue, QualifiedMappingId, RawParamValue,
 };
 use base::hash_util::NonCryptoHashSet;
 use helgoboss_learn::{AbsoluteValue, ControlValue};
@@ -27,6 +27,7 @@ pub enum DomainEvent<'a> {
     MappingMatched(MappingMatchedEvent),
     HandleTargetControl(TargetControlEvent),
     HandleSourceFeedback(SourceFeedbackEvent<'a>),
+    HandleSourceControl(SourceControlEvent),
     FullResyncRequested,
     MidiDevicesChanged,
     MappingEnabledChangeRequested(MappingEnabledChangeRequestedEvent),
@@ -97,6 +98,16 @@ pub struct SourceFeedbackEvent<'a> {
     pub log_entry: FeedbackLogEntry<'a>,
 }
 
+/// A real control input message as it arrived at the unit, reported only if control event
+/// streaming is enabled.
+#[derive(Clone, Debug)]
+pub struct SourceControlEvent {
+    /// Human-readable representation of the message, same as in the log.
+    pub message: String,
+    /// `None` if the message was reported before knowing whether it matched.
+    pub match_outcome: Option<MatchOutcome>,
+}
+
 impl TargetControlEvent {
     pub fn new(
         id: QualifiedMappingId,
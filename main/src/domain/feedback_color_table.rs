@@ -0,0 +1,138 @@
+use helgoboss_learn::{RgbColor, UnitValue};
+
+/// Maps normalized feedback values to colors, e.g. for a volume LED going green → yellow → red.
+#[derive(Clone, PartialEq, Debug)]
+pub struct FeedbackColorTable {
+    kind: FeedbackColorTableKind,
+    /// Sorted by value.
+    stops: Vec<FeedbackColorStop>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum FeedbackColorTableKind {
+    /// Blends between the colors of the two stops surrounding the feedback value.
+    Gradient,
+    /// Uses the color of the highest stop reached by the feedback value.
+    Threshold,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct FeedbackColorStop {
+    pub value: UnitValue,
+    pub color: RgbColor,
+}
+
+impl FeedbackColorTable {
+    pub fn new(kind: FeedbackColorTableKind, mut stops: Vec<FeedbackColorStop>) -> Self {
+        stops.sort_by(|a, b| a.value.get().total_cmp(&b.value.get()));
+        Self { kind, stops }
+    }
+
+    /// Returns `None` if the table doesn't contain any stops.
+    ///
+    /// Values below the first stop get the color of the first stop, values above the last stop
+    /// the color of the last stop.
+    pub fn color_at(&self, value: UnitValue) -> Option<RgbColor> {
+        let first = self.stops.first()?;
+        let next_index = self.stops.iter().position(|s| s.value.get() > value.get());
+        let color = match next_index {
+            // Below first stop
+            Some(0) => first.color,
+            // Between two stops
+            Some(i) => {
+                let prev = &self.stops[i - 1];
+                match self.kind {
+                    FeedbackColorTableKind::Threshold => prev.color,
+                    FeedbackColorTableKind::Gradient => {
+                        let next = &self.stops[i];
+                        let span = next.value.get() - prev.value.get();
+                        let ratio = (value.get() - prev.value.get()) / span;
+                        blend(prev.color, next.color, ratio)
+                    }
+                }
+            }
+            // At or above last stop
+            None => self.stops.last()?.color,
+        };
+        Some(color)
+    }
+}
+
+fn blend(a: RgbColor, b: RgbColor, ratio: f64) -> RgbColor {
+    let channel = |x: u8, y: u8| (x as f64 + (y as f64 - x as f64) * ratio).round() as u8;
+    RgbColor::new(
+        channel(a.r(), b.r()),
+        channel(a.g(), b.g()),
+        channel(a.b(), b.b()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn green() -> RgbColor {
+        RgbColor::new(0, 255, 0)
+    }
+
+    fn yellow() -> RgbColor {
+        RgbColor::new(255, 255, 0)
+    }
+
+    fn red() -> RgbColor {
+        RgbColor::new(255, 0, 0)
+    }
+
+    fn stop(value: f64, color: RgbColor) -> FeedbackColorStop {
+        FeedbackColorStop {
+            value: UnitValue::new(value),
+            color,
+        }
+    }
+
+    #[test]
+    fn gradient() {
+        // Given
+        let table = FeedbackColorTable::new(
+            FeedbackColorTableKind::Gradient,
+            vec![stop(1.0, red()), stop(0.0, green()), stop(0.5, yellow())],
+        );
+        // When
+        // Then
+        assert_eq!(table.color_at(UnitValue::MIN), Some(green()));
+        assert_eq!(
+            table.color_at(UnitValue::new(0.25)),
+            Some(RgbColor::new(128, 255, 0))
+        );
+        assert_eq!(table.color_at(UnitValue::new(0.5)), Some(yellow()));
+        assert_eq!(
+            table.color_at(UnitValue::new(0.75)),
+            Some(RgbColor::new(255, 128, 0))
+        );
+        assert_eq!(table.color_at(UnitValue::MAX), Some(red()));
+    }
+
+    #[test]
+    fn threshold() {
+        // Given
+        let table = FeedbackColorTable::new(
+            FeedbackColorTableKind::Threshold,
+            vec![stop(0.7, yellow()), stop(0.2, green()), stop(0.9, red())],
+        );
+        // When
+        // Then
+        assert_eq!(table.color_at(UnitValue::MIN), Some(green()));
+        assert_eq!(table.color_at(UnitValue::new(0.69)), Some(green()));
+        assert_eq!(table.color_at(UnitValue::new(0.7)), Some(yellow()));
+        assert_eq!(table.color_at(UnitValue::new(0.95)), Some(red()));
+    }
+
+    #[test]
+    fn empty() {
+        // Given
+        let table = FeedbackColorTable::new(FeedbackColorTableKind::Gradient, vec![]);
+        // When
+        // Then
+        assert_eq!(table.color_at(UnitValue::new(0.5)), None);
+    }
+}
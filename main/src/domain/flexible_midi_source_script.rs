@@ -1,4 +1,7 @@
-use crate::domain::{AdditionalLuaMidiSourceScriptInput, EelMidiSourceScript, LuaMidiSourceScript};
+use crate::domain::{
+    AdditionalLuaMidiSourceScriptInput, EelMidiSourceScript, LuaMidiSourceScript,
+    WasmMidiSourceScript,
+};
 use helgoboss_learn::{FeedbackValue, MidiSourceScript, MidiSourceScriptOutcome};
 use std::borrow::Cow;
 
@@ -6,6 +9,7 @@ use std::borrow::Cow;
 pub enum FlexibleMidiSourceScript<'lua> {
     Eel(EelMidiSourceScript),
     Lua(LuaMidiSourceScript<'lua>),
+    Wasm(WasmMidiSourceScript),
 }
 
 impl<'a, 'lua: 'a> MidiSourceScript<'a> for FlexibleMidiSourceScript<'lua> {
@@ -19,6 +23,7 @@ impl<'a, 'lua: 'a> MidiSourceScript<'a> for FlexibleMidiSourceScript<'lua> {
         match self {
             FlexibleMidiSourceScript::Eel(s) => s.execute(input_value, ()),
             FlexibleMidiSourceScript::Lua(s) => s.execute(input_value, additional_input),
+            FlexibleMidiSourceScript::Wasm(s) => s.execute(input_value, ()),
         }
     }
 }
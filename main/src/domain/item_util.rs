@@ -0,0 +1,271 @@
+use crate::domain::low_reaper;
+use reaper_high::{Project, Track};
+use reaper_low::raw;
+use std::ffi::CStr;
+use std::ptr::NonNull;
+
+/// Describes how to find a media item, not the item itself.
+///
+/// REAPER doesn't notify us when items are selected, moved, added or removed, so targets can't
+/// be re-resolved when the referenced item changes. Targets hold this reference and ask it for
+/// the currently addressed item whenever they need it, so they never work with a deleted item.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ItemRef {
+    /// First selected item in the project.
+    Selected(Project),
+    /// Item on the track at the edit cursor position.
+    UnderEditCursor(Track),
+    /// Item at the given position on the track.
+    ByIndex(Track, u32),
+}
+
+impl ItemRef {
+    pub fn item(&self) -> Option<Item> {
+        match self {
+            ItemRef::Selected(project) => Item::first_selected(*project),
+            ItemRef::UnderEditCursor(track) => Item::on_track_under_edit_cursor(track),
+            ItemRef::ByIndex(track, index) => Item::on_track_by_index(track, *index),
+        }
+    }
+
+    pub fn project(&self) -> Project {
+        match self {
+            ItemRef::Selected(project) => *project,
+            ItemRef::UnderEditCursor(track) | ItemRef::ByIndex(track, _) => track.project(),
+        }
+    }
+
+    pub fn track(&self) -> Option<&Track> {
+        match self {
+            ItemRef::Selected(_) => None,
+            ItemRef::UnderEditCursor(track) | ItemRef::ByIndex(track, _) => Some(track),
+        }
+    }
+}
+
+/// Describes how to find a take of an item addressed by [`ItemRef`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TakeRef {
+    pub item: ItemRef,
+    /// `None` means the active take.
+    pub index: Option<u32>,
+}
+
+impl TakeRef {
+    pub fn take(&self) -> Option<Take> {
+        let item = self.item.item()?;
+        match self.index {
+            None => item.active_take(),
+            Some(i) => item.take_by_index(i),
+        }
+    }
+}
+
+/// A media item in the arrange view.
+///
+/// Item properties are read and written via `GetMediaItemInfo_Value` and
+/// `SetMediaItemInfo_Value`. Don't keep instances of this around, the item could be deleted at
+/// any time. Use [`ItemRef`] instead.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Item {
+    raw: NonNull<raw::MediaItem>,
+}
+
+/// A take of a media item.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Take {
+    raw: NonNull<raw::MediaItem_Take>,
+}
+
+impl Item {
+    /// Returns the first selected item in the given project.
+    pub fn first_selected(project: Project) -> Option<Self> {
+        let raw = unsafe { low_reaper().GetSelectedMediaItem(project.raw().as_ptr(), 0) };
+        Self::from_raw(raw)
+    }
+
+    /// Returns the item at the given position on the given track (items are ordered by position).
+    pub fn on_track_by_index(track: &Track, index: u32) -> Option<Self> {
+        let raw_track = track.raw().ok()?;
+        let raw = unsafe { low_reaper().GetTrackMediaItem(raw_track.as_ptr(), index as _) };
+        Self::from_raw(raw)
+    }
+
+    /// Returns the item on the given track which is located at the edit cursor position.
+    ///
+    /// If multiple items overlap at that position, the one which starts last wins.
+    pub fn on_track_under_edit_cursor(track: &Track) -> Option<Self> {
+        let project = track.project();
+        let cursor_pos = unsafe { low_reaper().GetCursorPositionEx(project.raw().as_ptr()) };
+        (0..Self::count_on_track(track))
+            .filter_map(|i| Self::on_track_by_index(track, i))
+            .filter(|item| {
+                let pos = item.position();
+                pos <= cursor_pos && cursor_pos < pos + item.length()
+            })
+            .last()
+    }
+
+    pub fn count_on_track(track: &Track) -> u32 {
+        let Ok(raw_track) = track.raw() else {
+            return 0;
+        };
+        let count = unsafe { low_reaper().CountTrackMediaItems(raw_track.as_ptr()) };
+        count.max(0) as u32
+    }
+
+    fn from_raw(raw: *mut raw::MediaItem) -> Option<Self> {
+        let item = Self {
+            raw: NonNull::new(raw)?,
+        };
+        Some(item)
+    }
+
+    /// Position in seconds.
+    pub fn position(&self) -> f64 {
+        self.get(c"D_POSITION")
+    }
+
+    pub fn set_position(&self, seconds: f64) {
+        self.set(c"D_POSITION", seconds.max(0.0));
+    }
+
+    /// Length in seconds.
+    pub fn length(&self) -> f64 {
+        self.get(c"D_LENGTH")
+    }
+
+    /// Item volume as REAPER amplitude value (1.0 = 0 dB).
+    pub fn volume(&self) -> f64 {
+        self.get(c"D_VOL")
+    }
+
+    pub fn set_volume(&self, volume: f64) {
+        self.set(c"D_VOL", volume);
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.get(c"B_MUTE") != 0.0
+    }
+
+    pub fn set_muted(&self, muted: bool) {
+        self.set(c"B_MUTE", if muted { 1.0 } else { 0.0 });
+    }
+
+    /// Fade-in length in seconds.
+    pub fn fade_in_length(&self) -> f64 {
+        self.get(c"D_FADEINLEN")
+    }
+
+    pub fn set_fade_in_length(&self, seconds: f64) {
+        self.set(c"D_FADEINLEN", seconds.max(0.0));
+    }
+
+    /// Fade-out length in seconds.
+    pub fn fade_out_length(&self) -> f64 {
+        self.get(c"D_FADEOUTLEN")
+    }
+
+    pub fn set_fade_out_length(&self, seconds: f64) {
+        self.set(c"D_FADEOUTLEN", seconds.max(0.0));
+    }
+
+    pub fn take_count(&self) -> u32 {
+        let count = unsafe { low_reaper().CountTakes(self.raw.as_ptr()) };
+        count.max(0) as u32
+    }
+
+    pub fn take_by_index(&self, index: u32) -> Option<Take> {
+        let raw = unsafe { low_reaper().GetTake(self.raw.as_ptr(), index as _) };
+        Take::from_raw(raw)
+    }
+
+    pub fn active_take(&self) -> Option<Take> {
+        let raw = unsafe { low_reaper().GetActiveTake(self.raw.as_ptr()) };
+        Take::from_raw(raw)
+    }
+
+    fn get(&self, key: &CStr) -> f64 {
+        unsafe { low_reaper().GetMediaItemInfo_Value(self.raw.as_ptr(), key.as_ptr()) }
+    }
+
+    fn set(&self, key: &CStr, value: f64) {
+        unsafe {
+            low_reaper().SetMediaItemInfo_Value(self.raw.as_ptr(), key.as_ptr(), value);
+        }
+        low_reaper().UpdateArrange();
+    }
+}
+
+impl Take {
+    fn from_raw(raw: *mut raw::MediaItem_Take) -> Option<Self> {
+        let take = Self {
+            raw: NonNull::new(raw)?,
+        };
+        Some(take)
+    }
+
+    /// Zero-based index of this take within its item.
+    pub fn index(&self) -> u32 {
+        self.get(c"IP_TAKENUMBER").max(0.0) as u32
+    }
+
+    pub fn name(&self) -> String {
+        let ptr = unsafe { low_reaper().GetTakeName(self.raw.as_ptr()) };
+        if ptr.is_null() {
+            return String::new();
+        }
+        unsafe { CStr::from_ptr(ptr) }
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    pub fn make_active(&self) {
+        unsafe {
+            low_reaper().SetActiveTake(self.raw.as_ptr());
+        }
+        low_reaper().UpdateArrange();
+    }
+
+    /// Take volume as REAPER amplitude value (1.0 = 0 dB).
+    ///
+    /// REAPER encodes an inverted take polarity as negative volume. This is stripped here.
+    pub fn volume(&self) -> f64 {
+        self.get(c"D_VOL").abs()
+    }
+
+    /// Sets the take volume, preserving the take polarity.
+    pub fn set_volume(&self, volume: f64) {
+        let sign = self.get(c"D_VOL").signum();
+        self.set(c"D_VOL", sign * volume.abs());
+    }
+
+    /// Pitch adjustment in semitones.
+    pub fn pitch(&self) -> f64 {
+        self.get(c"D_PITCH")
+    }
+
+    pub fn set_pitch(&self, semitones: f64) {
+        self.set(c"D_PITCH", semitones);
+    }
+
+    /// Playback speed factor (1.0 = normal speed).
+    pub fn playrate(&self) -> f64 {
+        self.get(c"D_PLAYRATE")
+    }
+
+    pub fn set_playrate(&self, factor: f64) {
+        self.set(c"D_PLAYRATE", factor);
+    }
+
+    fn get(&self, key: &CStr) -> f64 {
+        unsafe { low_reaper().GetMediaItemTakeInfo_Value(self.raw.as_ptr(), key.as_ptr()) }
+    }
+
+    fn set(&self, key: &CStr, value: f64) {
+        unsafe {
+            low_reaper().SetMediaItemTakeInfo_Value(self.raw.as_ptr(), key.as_ptr(), value);
+        }
+        low_reaper().UpdateArrange();
+    }
+}
@@ -0,0 +1,92 @@
+use crate::domain::ui_util::{format_osc_message, log_lifecycle_output};
+use crate::domain::{
+    CompartmentKind, CompartmentParamIndex, ControlContext, FeedbackOutput, OscDeviceId,
+    OscFeedbackTask,
+};
+use base::NamedChannelSender;
+use helgoboss_learn::UnitValue;
+use reaper_high::Action;
+use reaper_medium::ReaperNormalizedFxParamValue;
+use rosc::OscMessage;
+
+/// An action that is executed in the main thread when a mapping gets activated or deactivated.
+///
+/// In contrast to lifecycle MIDI messages, these actions are not splintered into the real-time
+/// mapping but stay with the main mapping.
+#[derive(Debug)]
+pub enum LifecycleAction {
+    SendOsc {
+        /// If `None`, the message is sent to the feedback output (if it's an OSC device).
+        device_id: Option<OscDeviceId>,
+        message: OscMessage,
+    },
+    SetCompartmentParameter {
+        index: CompartmentParamIndex,
+        value: UnitValue,
+    },
+    InvokeAction(Action),
+}
+
+#[derive(Debug, Default)]
+pub struct LifecycleActionData {
+    pub activation_actions: Vec<LifecycleAction>,
+    pub deactivation_actions: Vec<LifecycleAction>,
+}
+
+impl LifecycleAction {
+    pub fn execute(
+        &self,
+        compartment: CompartmentKind,
+        context: ControlContext,
+    ) -> Result<(), &'static str> {
+        match self {
+            LifecycleAction::SendOsc { device_id, message } => {
+                let effective_dev_id = device_id
+                    .or_else(|| {
+                        if let FeedbackOutput::Osc(dev_id) = context.feedback_output? {
+                            Some(dev_id)
+                        } else {
+                            None
+                        }
+                    })
+                    .ok_or("no destination device for sending OSC")?;
+                if context.output_logging_enabled {
+                    let text = format!(
+                        "Device {} | {}",
+                        effective_dev_id.fmt_short(),
+                        format_osc_message(message)
+                    );
+                    log_lifecycle_output(context.unit_id, text);
+                }
+                context
+                    .osc_feedback_task_sender
+                    .send_complaining(OscFeedbackTask::new(effective_dev_id, message.clone()));
+            }
+            LifecycleAction::SetCompartmentParameter { index, value } => {
+                let plugin_param_index = compartment.to_plugin_param_index(*index);
+                if context.unit.borrow().is_main_unit() {
+                    // Same as in the compartment parameter value target: The compartment
+                    // parameters of the main unit are connected to the VST plug-in parameters.
+                    context
+                        .processor_context
+                        .containing_fx()
+                        .parameter_by_index(plugin_param_index.get())
+                        .set_reaper_normalized_value(ReaperNormalizedFxParamValue::new(
+                            value.get(),
+                        ))?;
+                } else {
+                    context
+                        .unit
+                        .borrow()
+                        .parameter_manager()
+                        .set_single_parameter(plugin_param_index, value.get() as _);
+                }
+            }
+            LifecycleAction::InvokeAction(action) => {
+                let project = context.processor_context.project_or_current_project();
+                action.invoke_as_trigger(Some(project), None)?;
+            }
+        }
+        Ok(())
+    }
+}
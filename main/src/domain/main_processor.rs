@@ -1,15 +1,17 @@
 use crate::domain::{
     aggregate_target_values, format_as_pretty_hex, get_project_options, say,
     AdditionalFeedbackEvent, AdditionalLuaFeedbackScriptInput, AdditionalLuaMidiSourceScriptInput,
-    Backbone, CompartmentKind, CompoundChangeEvent, CompoundFeedbackValue, CompoundMappingSource,
+    Backbone, ChordDetector, ChordMemberMatch, ChordMemberMidiEvent, ChordMessage, CompartmentKind,
+    CompoundChangeEvent, CompoundFeedbackValue, CompoundMappingSource,
     CompoundMappingSourceAddress, CompoundMappingTarget, ControlContext, ControlEvent,
     ControlEventTimestamp, ControlInput, ControlLogContext, ControlLogEntry, ControlLogEntryKind,
     ControlMode, ControlOutcome, DeviceFeedbackOutput, DomainEvent, DomainEventHandler,
-    ExtendedProcessorContext, FeedbackAudioHookTask, FeedbackCause, FeedbackCollector,
-    FeedbackDestinations, FeedbackLogEntry, FeedbackOutput, FeedbackRealTimeTask,
-    FeedbackResolution, FeedbackSendBehavior, FinalRealFeedbackValue, FinalSourceFeedbackValue,
-    GlobalControlAndFeedbackState, GroupId, HitInstructionContext, HitInstructionResponse,
-    InstanceId, InternalInfoEvent, IoUpdatedEvent, KeyMessage, MainMapping, MainSourceMessage,
+    EvdevDeviceId, EvdevMessage, ExtendedProcessorContext, FeedbackAudioHookTask, FeedbackCause,
+    FeedbackCollector, FeedbackDestinations, FeedbackLogEntry, FeedbackOutput,
+    FeedbackRealTimeTask, FeedbackResolution, FeedbackSendBehavior, FinalRealFeedbackValue,
+    FinalSourceFeedbackValue, GlobalControlAndFeedbackState, GroupId, HitInstructionContext,
+    HitInstructionResponse, IncomingCompoundSourceValue, IncomingControlData, InstanceId,
+    InternalInfoEvent, IoUpdatedEvent, KeyMessage, LifecyclePhase, MainMapping, MainSourceMessage,
     MappingActivationEffect, MappingControlResult, MappingId, MappingInfo, MessageCaptureEvent,
     MessageCaptureResult, MidiControlInput, MidiDestination, MidiScanResult, NoopLogger,
     NormalRealTimeTask, OrderedMappingIdSet, OrderedMappingMap, OscDeviceId, OscFeedbackTask,
@@ -17,11 +19,11 @@ use crate::domain::{
     QualifiedInstanceEvent, QualifiedMappingId, RawParamValue, RealTimeMappingUpdate,
     RealTimeTargetUpdate, RealearnModeContext, RealearnMonitoringFxParameterValueChangedEvent,
     RealearnParameterChangePayload, RealearnSourceContext, ReaperConfigChange, ReaperMessage,
-    ReaperSourceFeedbackValue, ReaperTarget, SharedInstance, SharedUnit, SourceFeedbackEvent,
-    SourceFeedbackLogger, SourceReleasedEvent, SpecificCompoundFeedbackValue, StreamDeckDeviceId,
-    StreamDeckMessage, StreamDeckSourceFeedbackValue, TargetControlEvent, TargetValueChangedEvent,
-    UnitContainer, UnitEvent, UnitOrchestrationEvent, UpdatedSingleMappingOnStateEvent,
-    VirtualControlElement, VirtualSourceValue,
+    ReaperSourceFeedbackValue, ReaperTarget, SharedInstance, SharedUnit, SourceControlEvent,
+    SourceFeedbackEvent, SourceFeedbackLogger, SourceReleasedEvent, SpecificCompoundFeedbackValue,
+    StreamDeckDeviceId, StreamDeckMessage, StreamDeckSourceFeedbackValue, TargetControlEvent,
+    TargetValueChangedEvent, UnitContainer, UnitEvent, UnitOrchestrationEvent,
+    UpdatedSingleMappingOnStateEvent, VirtualControlElement, VirtualSourceValue,
 };
 use derive_more::Display;
 use enum_map::EnumMap;
@@ -53,7 +55,7 @@ use std::hash::{Hash, Hasher};
 use std::rc::Rc;
 use std::sync::atomic::{AtomicU32, Ordering};
 use strum::EnumIter;
-use tracing::{debug, trace};
+use tracing::{debug, trace, warn};
 
 /// This can be come pretty big when multiple track volumes are adjusted at once.
 const FEEDBACK_TASK_QUEUE_SIZE: usize = 20_000;
@@ -81,6 +83,8 @@ pub struct MainProcessor<EH: DomainEventHandler> {
     collections: Collections,
     /// Contains IDs of those mappings who need to be polled as frequently as possible.
     poll_control_mappings: EnumMap<CompartmentKind, OrderedMappingIdSet>,
+    /// Holds back incoming messages which could be part of a chord.
+    chord_detector: ChordDetector<ChordMemberEvent>,
 }
 
 #[derive(Debug)]
@@ -345,6 +349,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                 previous_target_values: Default::default(),
             },
             poll_control_mappings: Default::default(),
+            chord_detector: Default::default(),
         }
     }
 
@@ -432,6 +437,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
             }
         }
         self.poll_control(timestamp);
+        self.replay_expired_chord_member_events(timestamp);
     }
 
     fn process_control_task(&mut self, task: ControlMainTask) {
@@ -445,6 +451,9 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
             } => {
                 let _ = self.control_from_real_time(compartment, mapping_id, event, options);
             }
+            ControlChordMemberFromRealTime { event } => {
+                self.process_incoming_chord_member_midi(event);
+            }
             LogVirtualControlInput {
                 event: value,
                 match_outcome: match_result,
@@ -459,15 +468,9 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                 match_outcome: match_result,
             } => {
                 let timestamp = event.timestamp();
-                log_real_control_input(
-                    self.unit_id(),
-                    format_control_input_with_match_result(
-                        ControlEvent::new(
-                            format_midi_source_value(&event.into_payload()),
-                            timestamp,
-                        ),
-                        match_result,
-                    ),
+                self.report_real_control_input(
+                    ControlEvent::new(format_midi_source_value(&event.into_payload()), timestamp),
+                    Some(match_result),
                 );
             }
             LogRealLearnInput { event } => {
@@ -532,13 +535,15 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                         (false, mode_poll_result)
                     } else if m.source().wants_to_be_polled() && m.control_is_effectively_on() {
                         // Mode was either not polled at all or without result, poll source.
-                        let res = if let Some(source_control_value) = m.poll_source() {
+                        let project = self.basics.context.project_or_current_project();
+                        let res = if let Some(source_control_value) = m.poll_source(project) {
                             let control_event = ControlEvent::new(source_control_value, timestamp);
                             control_mapping_stage_one(
                                 &self.basics,
                                 &self.collections.parameters,
                                 m,
                                 control_event,
+                                &IncomingControlData::default(),
                                 ControlOptions::default(),
                             )
                         } else {
@@ -623,6 +628,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                 &self.collections.parameters,
                 m,
                 control_event,
+                &IncomingControlData::default(),
                 options,
                 ManualFeedbackProcessing::On {
                     mappings_with_virtual_targets: &self.collections.mappings_with_virtual_targets,
@@ -1074,8 +1080,8 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
             value,
         };
         let control_msg = ReaperMessage::RealearnParameterChange(control_payload);
-        if self.basics.settings.real_input_logging_enabled {
-            self.log_incoming_message(&control_msg);
+        if self.basics.settings.real_input_reporting_enabled() {
+            self.report_incoming_message(ControlEvent::new(&control_msg, timestamp));
         }
         let control_event = ControlEvent::new(MainSourceMessage::Reaper(&control_msg), timestamp);
         self.process_incoming_message_internal(control_event);
@@ -1370,6 +1376,13 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
             project_options,
         );
         if let Some(new_feedback_is_enabled) = new_feedback_is_enabled {
+            // Handle lifecycle actions
+            for compartment in CompartmentKind::enum_iter() {
+                self.execute_lifecycle_actions_for_all_mappings_in(
+                    compartment,
+                    new_feedback_is_enabled.into(),
+                );
+            }
             if new_feedback_is_enabled {
                 for compartment in CompartmentKind::enum_iter() {
                     self.handle_feedback_after_having_updated_all_mappings(
@@ -1471,6 +1484,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
         self.potentially_enable_or_disable_control_or_feedback(any_main_mapping_is_effectively_on);
         Backbone::get()
             .register_stream_deck_usage(self.basics.unit_id, settings.streamdeck_device_id);
+        Backbone::get().register_evdev_usage(self.basics.unit_id, settings.evdev_device_id);
     }
 
     fn update_all_mappings(
@@ -1479,6 +1493,13 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
         mut mappings: Vec<MainMapping>,
     ) {
         tracing::debug!("Updating {} mappings in {}...", mappings.len(), compartment,);
+        // Handle deactivation actions (of the mappings which are about to be replaced)
+        if self.basics.feedback_is_globally_enabled {
+            self.execute_lifecycle_actions_for_all_mappings_in(
+                compartment,
+                LifecyclePhase::Deactivation,
+            );
+        }
         self.basics.clear_last_feedback();
         let mut mappings_by_group: NonCryptoHashMap<GroupId, Vec<MappingId>> = HashMap::default();
         let mut mapping_infos: NonCryptoHashMap<QualifiedMappingId, MappingInfo> =
@@ -1561,6 +1582,13 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                 compartment,
                 real_time_mappings,
             ));
+        // Handle activation actions
+        if self.basics.feedback_is_globally_enabled {
+            self.execute_lifecycle_actions_for_all_mappings_in(
+                compartment,
+                LifecyclePhase::Activation,
+            );
+        }
         // Important to send IO event first ...
         self.notify_feedback_dev_usage_might_have_changed(compartment);
         // ... and then mapping update. Otherwise, if this is an upper-floor instance
@@ -1854,6 +1882,12 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
         }
     }
 
+    /// Returns whether this unit has mappings which need the track recording state to be polled.
+    pub fn wants_track_recording_changes(&self) -> bool {
+        self.all_mappings_without_virtual_targets()
+            .any(|m| m.has_track_recording_target())
+    }
+
     pub fn wants_keyboard_input(&self) -> bool {
         self.wants_messages_in_general() && self.basics.settings.wants_keyboard_input
     }
@@ -1908,8 +1942,8 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
         if self.basics.control_mode != ControlMode::Controlling {
             return;
         }
-        if self.basics.settings.real_input_logging_enabled {
-            log_real_control_input(self.basics.unit_id, evt);
+        if self.basics.settings.real_input_reporting_enabled() {
+            self.report_real_control_input(evt, None);
         }
         if !self.basics.unit_control_is_effectively_enabled() {
             return;
@@ -1935,25 +1969,55 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
         self.process_mappings_with_real_targets(evt);
     }
 
-    fn log_incoming_message<T: Display>(&self, msg: T) {
+    fn report_incoming_message<T: Display>(&self, evt: ControlEvent<T>) {
         match self.basics.control_mode {
             ControlMode::Controlling => {
-                log_real_control_input(self.basics.unit_id, msg);
+                self.report_real_control_input(evt, None);
             }
             ControlMode::LearningSource { .. } => {
-                log_real_learn_input(self.basics.unit_id, msg);
+                if self.basics.settings.real_input_logging_enabled {
+                    log_real_learn_input(self.basics.unit_id, evt);
+                }
             }
             ControlMode::Disabled => {}
         }
     }
 
+    /// Logs the given real control input and/or streams it to interested clients, depending on
+    /// the settings.
+    fn report_real_control_input<T: Display>(
+        &self,
+        evt: ControlEvent<T>,
+        match_outcome: Option<MatchOutcome>,
+    ) {
+        let settings = &self.basics.settings;
+        if settings.real_input_logging_enabled {
+            match match_outcome {
+                None => log_real_control_input(self.basics.unit_id, &evt),
+                Some(o) => log_real_control_input(
+                    self.basics.unit_id,
+                    format_control_input_with_match_result(&evt, o),
+                ),
+            }
+        }
+        if settings.control_event_streaming_enabled {
+            let event = SourceControlEvent {
+                message: evt.into_payload().to_string(),
+                match_outcome,
+            };
+            self.basics
+                .event_handler
+                .handle_event_ignoring_error(DomainEvent::HandleSourceControl(event));
+        }
+    }
+
     /// This doesn't check if control enabled! You need to check before.
     pub fn process_incoming_key_msg(
         &mut self,
         evt: ControlEvent<KeyMessage>,
     ) -> KeyProcessingResult {
-        if self.basics.settings.real_input_logging_enabled {
-            self.log_incoming_message(evt);
+        if self.basics.settings.real_input_reporting_enabled() {
+            self.report_incoming_message(evt);
         }
         let match_outcome =
             self.process_incoming_message_internal(evt.map_payload(MainSourceMessage::Key));
@@ -1970,8 +2034,8 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
 
     /// This doesn't check if control enabled! You need to check before.
     pub fn process_incoming_stream_deck_msg(&mut self, evt: ControlEvent<StreamDeckMessage>) {
-        if self.basics.settings.real_input_logging_enabled {
-            self.log_incoming_message(evt);
+        if self.basics.settings.real_input_reporting_enabled() {
+            self.report_incoming_message(evt);
         }
         self.process_incoming_message_internal(evt.map_payload(MainSourceMessage::StreamDeck));
     }
@@ -1985,9 +2049,174 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                 .is_some_and(|d| d == dev)
     }
 
+    /// This doesn't check if control enabled! You need to check before.
+    pub fn process_incoming_evdev_msg(&mut self, evt: ControlEvent<EvdevMessage>) {
+        if self.basics.settings.real_input_reporting_enabled() {
+            self.report_incoming_message(evt);
+        }
+        self.process_incoming_message_internal(evt.map_payload(MainSourceMessage::Evdev));
+    }
+
+    pub fn wants_evdev_input_from(&self, dev: EvdevDeviceId) -> bool {
+        self.wants_messages_in_general()
+            && self
+                .basics
+                .settings
+                .evdev_device_id
+                .is_some_and(|d| d == dev)
+    }
+
     fn process_incoming_msg_for_controlling(
         &mut self,
         evt: ControlEvent<MainSourceMessage>,
+    ) -> MatchOutcome {
+        let value = match evt.payload() {
+            MainSourceMessage::Osc(msg) => IncomingCompoundSourceValue::Osc(msg),
+            MainSourceMessage::Key(msg) => IncomingCompoundSourceValue::Key(msg),
+            MainSourceMessage::StreamDeck(msg) => IncomingCompoundSourceValue::StreamDeck(msg),
+            MainSourceMessage::Evdev(msg) => IncomingCompoundSourceValue::Evdev(msg),
+            MainSourceMessage::Reaper(_) | MainSourceMessage::Chord(_) => {
+                return self.process_incoming_msg_for_controlling_ignoring_chords(evt);
+            }
+        };
+        let held_back_by_chord_detector =
+            self.feed_chord_detector(evt.with_payload(value), || match evt.payload() {
+                MainSourceMessage::Osc(msg) => ChordMemberEvent::Osc(msg.clone()),
+                MainSourceMessage::Key(msg) => ChordMemberEvent::Key(msg),
+                MainSourceMessage::StreamDeck(msg) => ChordMemberEvent::StreamDeck(msg),
+                MainSourceMessage::Evdev(msg) => ChordMemberEvent::Evdev(msg),
+                MainSourceMessage::Reaper(_) | MainSourceMessage::Chord(_) => unreachable!(),
+            });
+        if held_back_by_chord_detector {
+            // The message is either part of a chord or will be replayed later
+            return MatchOutcome::Matched;
+        }
+        self.process_incoming_msg_for_controlling_ignoring_chords(evt)
+    }
+
+    /// Processes a MIDI message that the real-time processor identified as potential chord member.
+    fn process_incoming_chord_member_midi(&mut self, evt: ControlEvent<ChordMemberMidiEvent>) {
+        let msg = evt.payload();
+        let source_value = MidiSourceValue::Plain(msg.event.payload());
+        let held_back_by_chord_detector = self.feed_chord_detector(
+            evt.with_payload(IncomingCompoundSourceValue::Midi(&source_value)),
+            || ChordMemberEvent::Midi(msg),
+        );
+        if !held_back_by_chord_detector {
+            self.replay_chord_member_event(evt.with_payload(ChordMemberEvent::Midi(msg)));
+        }
+    }
+
+    /// Returns `true` if the chord detector took care of the given message, in which case it
+    /// must not be processed any further.
+    fn feed_chord_detector(
+        &mut self,
+        evt: ControlEvent<IncomingCompoundSourceValue>,
+        create_member_event: impl FnOnce() -> ChordMemberEvent,
+    ) -> bool {
+        let mut is_press = false;
+        let mut matches = vec![];
+        for m in self
+            .collections
+            .mappings
+            .values()
+            .flat_map(|mappings| mappings.values())
+            .chain(self.collections.mappings_with_virtual_targets.values())
+            .filter(|m| m.control_is_effectively_on())
+        {
+            let CompoundMappingSource::Chord(s) = m.source() else {
+                continue;
+            };
+            if let Some((member_index, member_is_pressed)) = s.find_member(evt.payload()) {
+                is_press = member_is_pressed;
+                matches.push(ChordMemberMatch {
+                    chord_id: m.qualified_id(),
+                    member_index,
+                    member_count: s.members().len(),
+                    window: s.window(),
+                });
+            }
+        }
+        if matches.is_empty() {
+            return false;
+        }
+        let outcome =
+            self.chord_detector
+                .process(evt.with_payload(create_member_event()), is_press, matches);
+        for (chord_id, is_on) in outcome.fired_chords {
+            let msg = MainSourceMessage::Chord(ChordMessage { chord_id, is_on });
+            self.process_incoming_msg_for_controlling_ignoring_chords(evt.with_payload(msg));
+        }
+        for consumed_evt in outcome.consumed {
+            // Held back MIDI messages must be returned to the real-time processor, which decides
+            // whether to let them through. Other messages are simply dropped.
+            if let ChordMemberEvent::Midi(msg) = consumed_evt {
+                self.send_chord_member_midi_to_real_time_processor(evt.with_payload(msg), true);
+            }
+        }
+        outcome.pass_through.is_none()
+    }
+
+    fn replay_expired_chord_member_events(&mut self, timestamp: ControlEventTimestamp) {
+        for evt in self.chord_detector.poll(timestamp) {
+            self.replay_chord_member_event(evt);
+        }
+    }
+
+    fn replay_chord_member_event(&mut self, evt: ControlEvent<ChordMemberEvent>) {
+        let timestamp = evt.timestamp();
+        match evt.into_payload() {
+            ChordMemberEvent::Midi(msg) => {
+                // MIDI is matched in the real-time processor
+                self.send_chord_member_midi_to_real_time_processor(
+                    ControlEvent::new(msg, timestamp),
+                    false,
+                );
+            }
+            ChordMemberEvent::Osc(msg) => {
+                let msg = MainSourceMessage::Osc(&msg);
+                self.process_incoming_msg_for_controlling_ignoring_chords(ControlEvent::new(
+                    msg, timestamp,
+                ));
+            }
+            ChordMemberEvent::Key(msg) => {
+                let msg = MainSourceMessage::Key(msg);
+                self.process_incoming_msg_for_controlling_ignoring_chords(ControlEvent::new(
+                    msg, timestamp,
+                ));
+            }
+            ChordMemberEvent::StreamDeck(msg) => {
+                let msg = MainSourceMessage::StreamDeck(msg);
+                self.process_incoming_msg_for_controlling_ignoring_chords(ControlEvent::new(
+                    msg, timestamp,
+                ));
+            }
+            ChordMemberEvent::Evdev(msg) => {
+                let msg = MainSourceMessage::Evdev(msg);
+                self.process_incoming_msg_for_controlling_ignoring_chords(ControlEvent::new(
+                    msg, timestamp,
+                ));
+            }
+        }
+    }
+
+    fn send_chord_member_midi_to_real_time_processor(
+        &self,
+        event: ControlEvent<ChordMemberMidiEvent>,
+        is_part_of_chord: bool,
+    ) {
+        self.basics
+            .channels
+            .normal_real_time_task_sender
+            .send_complaining(NormalRealTimeTask::ProcessChordMemberMidi {
+                event,
+                is_part_of_chord,
+            });
+    }
+
+    fn process_incoming_msg_for_controlling_ignoring_chords(
+        &mut self,
+        evt: ControlEvent<MainSourceMessage>,
     ) -> MatchOutcome {
         let virtual_result = self
             .basics
@@ -2012,9 +2241,9 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
 
     /// This doesn't check if control enabled! You need to check before.
     pub fn process_incoming_osc_packet(&mut self, evt: ControlEvent<&OscPacket>) {
-        if self.basics.settings.real_input_logging_enabled {
+        if self.basics.settings.real_input_reporting_enabled() {
             let timestamp = evt.timestamp();
-            self.log_incoming_message(ControlEvent::new(
+            self.report_incoming_message(ControlEvent::new(
                 format_osc_packet(evt.into_payload()),
                 timestamp,
             ));
@@ -2111,6 +2340,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                     _ => continue,
                 };
                 let control_event = evt.with_payload(control_value);
+                let incoming_data = m.incoming_control_data(evt.payload());
                 let options = ControlOptions {
                     enforce_target_refresh,
                     ..Default::default()
@@ -2120,6 +2350,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                     &self.collections.parameters,
                     m,
                     control_event,
+                    &incoming_data,
                     options,
                     ManualFeedbackProcessing::On {
                         mappings_with_virtual_targets: &self
@@ -2172,6 +2403,20 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
         if mapping_updates.is_empty() && target_updates.is_empty() {
             return;
         }
+        // Handle lifecycle actions
+        if self.basics.feedback_is_globally_enabled {
+            for update in mapping_updates.iter() {
+                if let Some(activation_change) = update.activation_change {
+                    if let Some(m) =
+                        self.get_normal_or_virtual_target_mapping(compartment, update.id)
+                    {
+                        if m.feedback_is_effectively_on_ignoring_mapping_activation() {
+                            self.execute_lifecycle_actions(m, activation_change.is_active.into());
+                        }
+                    }
+                }
+            }
+        }
         // Propagate updates to real-time processor
         if !mapping_updates.is_empty() {
             self.basics
@@ -2221,6 +2466,20 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                 target_value,
             );
         }
+        // Handle lifecycle actions
+        if self.basics.feedback_is_globally_enabled {
+            for update in target_updates.iter() {
+                if let Some(activation_change) = update.activation_change {
+                    if let Some(m) =
+                        self.get_normal_or_virtual_target_mapping(compartment, update.id)
+                    {
+                        if m.feedback_is_effectively_on_ignoring_target_activation() {
+                            self.execute_lifecycle_actions(m, activation_change.is_active.into());
+                        }
+                    }
+                }
+            }
+        }
         // In some cases like closing projects, it's possible that this will
         // fail because the real-time processor is
         // already gone. But it doesn't matter.
@@ -2259,6 +2518,51 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
             .handle_event_ignoring_error(DomainEvent::UpdatedOnMappings(on_mappings));
     }
 
+    fn execute_lifecycle_actions_diff(
+        &self,
+        m: &MainMapping,
+        was_on_before: bool,
+        is_on_now: bool,
+    ) {
+        if is_on_now {
+            self.execute_lifecycle_actions(m, LifecyclePhase::Activation);
+        } else if was_on_before {
+            self.execute_lifecycle_actions(m, LifecyclePhase::Deactivation);
+        }
+    }
+
+    fn execute_lifecycle_actions_for_all_mappings_in(
+        &self,
+        compartment: CompartmentKind,
+        phase: LifecyclePhase,
+    ) {
+        for m in self.all_mappings_in_compartment(compartment) {
+            if m.feedback_is_effectively_on() {
+                self.execute_lifecycle_actions(m, phase);
+            }
+        }
+    }
+
+    /// Executes the main-thread lifecycle actions of the given mapping in the defined order.
+    ///
+    /// The triggering conditions mirror the ones for sending lifecycle MIDI in the real-time
+    /// processor.
+    fn execute_lifecycle_actions(&self, m: &MainMapping, phase: LifecyclePhase) {
+        let actions = m.lifecycle_actions(phase);
+        if actions.is_empty() {
+            return;
+        }
+        let control_context = self.basics.control_context(m.compartment());
+        for action in actions {
+            if let Err(e) = action.execute(m.compartment(), control_context) {
+                warn!(
+                    "Couldn't execute {phase:?} lifecycle action of mapping {:?}: {e}",
+                    m.id()
+                );
+            }
+        }
+    }
+
     fn send_feedback(
         &self,
         reason: FeedbackReason,
@@ -2625,8 +2929,21 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
             &mapping,
         );
         let target_is_active = mapping.target_is_active();
+        let was_on_before = self
+            .get_normal_or_virtual_target_mapping(compartment, id.id)
+            .is_some_and(|m| m.feedback_is_effectively_on());
         self.update_map_entries(compartment, *mapping);
         self.send_diff_feedback(diff_feedback);
+        // Handle lifecycle actions
+        if self.basics.feedback_is_globally_enabled {
+            if let Some(m) = self.get_normal_or_virtual_target_mapping(compartment, id.id) {
+                self.execute_lifecycle_actions_diff(
+                    m,
+                    was_on_before,
+                    m.feedback_is_effectively_on(),
+                );
+            }
+        }
         self.update_single_mapping_on_state(id);
         // This could be a lead mapping in terms of target-based conditional activation. If so,
         // the target value probably changed and all follow mappings can be affected.
@@ -2706,6 +3023,12 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                 self.send_feedback(fb.0, fb.1);
             }
         }
+        // Handle lifecycle actions
+        if self.basics.feedback_is_globally_enabled {
+            if let Some(m) = self.get_normal_or_virtual_target_mapping(id.compartment, id.id) {
+                self.execute_lifecycle_actions_diff(m, was_on_before, is_on_now);
+            }
+        }
         self.update_single_mapping_on_state(id);
     }
 
@@ -2936,12 +3259,15 @@ pub struct BasicSettings {
     pub control_input: ControlInput,
     pub wants_keyboard_input: bool,
     pub streamdeck_device_id: Option<StreamDeckDeviceId>,
+    pub evdev_device_id: Option<EvdevDeviceId>,
     pub feedback_output: Option<FeedbackOutput>,
     pub real_input_logging_enabled: bool,
     pub real_output_logging_enabled: bool,
     pub virtual_input_logging_enabled: bool,
     pub virtual_output_logging_enabled: bool,
     pub target_control_logging_enabled: bool,
+    /// Whether at least one client wants to receive a live stream of control events.
+    pub control_event_streaming_enabled: bool,
     pub send_feedback_only_if_armed: bool,
     pub match_even_inactive_mappings: bool,
     pub let_matched_events_through: bool,
@@ -2985,6 +3311,12 @@ impl Default for StayActiveWhenProjectInBackground {
 }
 
 impl BasicSettings {
+    /// Returns whether incoming real control messages need to be reported, either for logging
+    /// them or for streaming them.
+    pub fn real_input_reporting_enabled(&self) -> bool {
+        self.real_input_logging_enabled || self.control_event_streaming_enabled
+    }
+
     pub fn target_control_logger<'a>(
         &'a self,
         instance_state: &'a SharedUnit,
@@ -3081,6 +3413,10 @@ pub enum ControlMainTask {
         event: ControlEvent<ControlValue>,
         options: ControlOptions,
     },
+    /// MIDI message coming in from real-time processor which could be part of a chord.
+    ControlChordMemberFromRealTime {
+        event: ControlEvent<ChordMemberMidiEvent>,
+    },
     LogVirtualControlInput {
         event: ControlEvent<VirtualSourceValue>,
         match_outcome: MatchOutcome,
@@ -3101,6 +3437,16 @@ pub enum ControlMainTask {
     },
 }
 
+/// An incoming message which has been held back by the chord detector.
+#[derive(Debug)]
+enum ChordMemberEvent {
+    Midi(ChordMemberMidiEvent),
+    Osc(OscMessage),
+    Key(KeyMessage),
+    StreamDeck(StreamDeckMessage),
+    Evdev(EvdevMessage),
+}
+
 pub enum OwnedIncomingMidiMessage {
     Short(RawShortMessage),
     SysEx(Vec<u8>),
@@ -3480,6 +3826,7 @@ impl<EH: DomainEventHandler> Basics<EH> {
                                 parameters,
                                 other_mapping,
                                 control_event,
+                                &IncomingControlData::default(),
                                 options,
                                 ManualFeedbackProcessing::Off,
                             )
@@ -3728,11 +4075,11 @@ impl<EH: DomainEventHandler> Basics<EH> {
                         return vec![];
                     }
                 };
-                self.event_handler
-                    .notify_mapping_matched(CompartmentKind::Controller, m.id());
+                let incoming_data = m.incoming_control_data(evt.payload());
                 let virtual_result = self.process_main_mappings_with_virtual_sources(
                     main_mappings,
                     evt.with_payload(virtual_source_value),
+                    &incoming_data,
                     ControlOptions {
                         // We inherit "Send feedback after control" if it's
                         // enabled for the virtual mapping. That's the easy way to do it.
@@ -3754,6 +4101,15 @@ impl<EH: DomainEventHandler> Basics<EH> {
                     },
                     params,
                 );
+                // A message which just transports text or a color only matches if some main mapping
+                // makes use of it. Otherwise it would cause spurious matches.
+                if !incoming_data.is_empty()
+                    && virtual_result.match_outcome != MatchOutcome::Matched
+                {
+                    return vec![];
+                }
+                self.event_handler
+                    .notify_mapping_matched(CompartmentKind::Controller, m.id());
                 match_outcome.upgrade_from(virtual_result.match_outcome);
                 if self.settings.virtual_input_logging_enabled {
                     log_virtual_control_input(
@@ -4065,6 +4421,7 @@ impl<EH: DomainEventHandler> Basics<EH> {
         &self,
         main_mappings: &mut OrderedMappingMap<MainMapping>,
         evt: ControlEvent<VirtualSourceValue>,
+        incoming_data: &IncomingControlData,
         options: ControlOptions,
         params: &PluginParams,
     ) -> ProcessVirtualResult {
@@ -4086,6 +4443,11 @@ impl<EH: DomainEventHandler> Basics<EH> {
                     return None;
                 };
                 let control_value = s.control(&evt.payload())?;
+                // A control value which just transports text or a color is only relevant for
+                // targets which make use of it.
+                if !incoming_data.is_empty() && !m.consumes_incoming_control_data() {
+                    return None;
+                }
                 // We found an associated main mapping, so it's not just consumed, it's matched.
                 match_outcome = MatchOutcome::Matched;
                 if !mapping_is_active {
@@ -4101,6 +4463,7 @@ impl<EH: DomainEventHandler> Basics<EH> {
                     params,
                     m,
                     control_event,
+                    incoming_data,
                     options,
                     ManualFeedbackProcessing::Off,
                 );
@@ -4240,10 +4603,12 @@ fn control_mapping_stage_one_and_two<EH: DomainEventHandler>(
     params: &PluginParams,
     m: &mut MainMapping,
     control_event: ControlEvent<ControlValue>,
+    incoming_data: &IncomingControlData,
     options: ControlOptions,
     feedback_handling: ManualFeedbackProcessing,
 ) -> MappingControlResult {
-    let mut control_result = control_mapping_stage_one(basics, params, m, control_event, options);
+    let mut control_result =
+        control_mapping_stage_one(basics, params, m, control_event, incoming_data, options);
     control_mapping_stage_two(basics, &mut control_result, m, feedback_handling);
     control_result
 }
@@ -4260,6 +4625,7 @@ fn control_mapping_stage_one<EH: DomainEventHandler>(
     params: &PluginParams,
     m: &mut MainMapping,
     control_event: ControlEvent<ControlValue>,
+    incoming_data: &IncomingControlData,
     options: ControlOptions,
 ) -> MappingControlResult {
     basics
@@ -4267,6 +4633,7 @@ fn control_mapping_stage_one<EH: DomainEventHandler>(
         .notify_mapping_matched(m.compartment(), m.id());
     let result = m.control_from_mode(
         control_event,
+        incoming_data,
         options,
         basics.control_context(m.compartment()),
         ExtendedProcessorContext::new(
@@ -1,8 +1,10 @@
 use crate::domain::{
     prop_feedback_resolution, prop_is_affected_by, ActivationChange, ActivationCondition,
-    BoxedHitInstruction, CompartmentParamIndex, CompoundChangeEvent, ControlContext, ControlEvent,
-    ControlEventTimestamp, ControlOptions, ExtendedProcessorContext, FeedbackResolution, GroupId,
-    HitResponse, KeyMessage, KeySource, MappingActivationEffect, MappingControlContext,
+    BoxedHitInstruction, ChordMessage, ChordSource, CompartmentParamIndex, CompoundChangeEvent,
+    ControlContext, ControlEvent, ControlEventTimestamp, ControlOptions, EvdevDeviceId,
+    EvdevMessage, EvdevScanResult, EvdevSource, ExtendedProcessorContext, FeedbackColorTable,
+    FeedbackResolution, GroupId, HitResponse, IncomingControlData, KeyMessage, KeySource,
+    LifecycleAction, LifecycleActionData, MappingActivationEffect, MappingControlContext,
     MappingData, MappingInfo, MappingPropProvider, MessageCaptureEvent, MidiScanResult, MidiSource,
     Mode, OscDeviceId, OscScanResult, PersistentMappingProcessingState, PluginParamIndex,
     PluginParams, RealTimeMappingUpdate, RealTimeReaperTarget, RealTimeTargetUpdate,
@@ -20,7 +22,7 @@ use helgoboss_learn::{
     ControlType, ControlValue, FeedbackValue, GroupInteraction, MidiSourceAddress, MidiSourceValue,
     ModeControlOptions, ModeControlResult, ModeFeedbackOptions, NumericFeedbackValue, NumericValue,
     OscSource, OscSourceAddress, PreliminaryMidiSourceFeedbackValue, PropValue, RawMidiEvent,
-    SourceCharacter, Target, UnitValue, ValueFormatter, ValueParser,
+    RgbColor, SourceCharacter, Target, UnitValue, ValueFormatter, ValueParser,
 };
 use helgoboss_midi::{Channel, RawShortMessage, ShortMessage};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
@@ -32,7 +34,7 @@ use base::hash_util::{NonCryptoHashSet, NonCryptoIndexMap, NonCryptoIndexSet};
 use playtime_api::persistence::{ColumnAddress, RowAddress, SlotAddress};
 use reaper_high::{Fx, Project, Track, TrackRoute};
 use reaper_medium::MidiInputDeviceId;
-use rosc::OscMessage;
+use rosc::{OscMessage, OscType};
 use serde::{Deserialize, Serialize};
 use std::convert::TryInto;
 use std::fmt;
@@ -167,12 +169,18 @@ pub struct LifecycleMidiData {
 pub struct MappingExtension {
     /// If it's None, it means it's splintered already.
     lifecycle_midi_data: Option<LifecycleMidiData>,
+    /// Stays in the main mapping (not splintered).
+    lifecycle_action_data: LifecycleActionData,
 }
 
 impl MappingExtension {
-    pub fn new(lifecycle_midi_data: LifecycleMidiData) -> Self {
+    pub fn new(
+        lifecycle_midi_data: LifecycleMidiData,
+        lifecycle_action_data: LifecycleActionData,
+    ) -> Self {
         Self {
             lifecycle_midi_data: Some(lifecycle_midi_data),
+            lifecycle_action_data,
         }
     }
 }
@@ -197,6 +205,8 @@ pub struct MainMapping {
     activation_condition_2: ActivationCondition,
     activation_state: ActivationState,
     extension: MappingExtension,
+    /// Overrides the feedback color depending on the feedback value.
+    feedback_color_table: Option<FeedbackColorTable>,
     initial_target_value: Option<AbsoluteValue>,
     /// Called "y_last" in the control transformation formula.
     last_non_performance_target_value: Cell<Option<AbsoluteValue>>,
@@ -231,6 +241,7 @@ impl MainMapping {
         activation_condition_2: ActivationCondition,
         options: ProcessorMappingOptions,
         extension: MappingExtension,
+        feedback_color_table: Option<FeedbackColorTable>,
     ) -> MainMapping {
         MainMapping {
             core: MappingCore {
@@ -256,6 +267,7 @@ impl MainMapping {
             activation_condition_2,
             activation_state: Default::default(),
             extension,
+            feedback_color_table,
             initial_target_value: None,
             last_non_performance_target_value: Cell::new(None),
         }
@@ -406,6 +418,15 @@ impl MainMapping {
         }
     }
 
+    pub fn lifecycle_actions(&self, phase: LifecyclePhase) -> &[LifecycleAction] {
+        use LifecyclePhase::*;
+        let data = &self.extension.lifecycle_action_data;
+        match phase {
+            Activation => &data.activation_actions,
+            Deactivation => &data.deactivation_actions,
+        }
+    }
+
     pub fn splinter_first_real_time_target(&self) -> Option<RealTimeCompoundMappingTarget> {
         self.targets
             .first()
@@ -496,9 +517,9 @@ impl MainMapping {
     /// target values that influence the activation state).
     pub fn activation_can_be_affected_by_target_values(&self) -> impl Iterator<Item = MappingId> {
         self.activation_condition_1
-            .target_value_lead_mapping()
+            .target_value_lead_mappings()
             .into_iter()
-            .chain(self.activation_condition_2.target_value_lead_mapping())
+            .chain(self.activation_condition_2.target_value_lead_mappings())
     }
 
     pub fn update_activation_from_effect(
@@ -586,6 +607,20 @@ impl MainMapping {
         )
     }
 
+    /// Returns whether this mapping has a target whose value can only be detected by polling
+    /// the track recording state (see `TrackRecordingChangeDetector`).
+    pub fn has_track_recording_target(&self) -> bool {
+        self.targets.iter().any(|t| {
+            matches!(
+                t,
+                CompoundMappingTarget::Reaper(t) if matches!(
+                    **t,
+                    ReaperTarget::TrackRecordMode(_) | ReaperTarget::TrackInputFxEnable(_)
+                )
+            )
+        })
+    }
+
     /// `None` means that no polling is necessary for feedback because we are notified via events.
     pub fn feedback_resolution(&self) -> Option<FeedbackResolution> {
         let t = self.unresolved_target.as_ref()?;
@@ -739,6 +774,14 @@ impl MainMapping {
         )
     }
 
+    pub fn feedback_is_effectively_on_ignoring_mapping_activation(&self) -> bool {
+        self.target_is_effectively_active() && self.core.options.feedback_is_effectively_enabled()
+    }
+
+    pub fn feedback_is_effectively_on_ignoring_target_activation(&self) -> bool {
+        self.activation_state.is_active() && self.core.options.feedback_is_effectively_enabled()
+    }
+
     pub fn source(&self) -> &CompoundMappingSource {
         &self.core.source
     }
@@ -758,6 +801,7 @@ impl MainMapping {
     ) -> MappingControlResult {
         self.control_internal(
             ControlOptions::default(),
+            &IncomingControlData::default(),
             context,
             processor_context,
             true,
@@ -779,6 +823,7 @@ impl MainMapping {
     pub fn control_from_mode(
         &mut self,
         source_control_event: ControlEvent<ControlValue>,
+        incoming_data: &IncomingControlData,
         options: ControlOptions,
         context: ControlContext,
         processor_context: ExtendedProcessorContext,
@@ -787,6 +832,7 @@ impl MainMapping {
     ) -> MappingControlResult {
         let result = self.control_internal(
             options,
+            incoming_data,
             context,
             processor_context,
             false,
@@ -830,6 +876,7 @@ impl MainMapping {
     ) -> MappingControlResult {
         self.control_internal(
             options,
+            &IncomingControlData::default(),
             context,
             processor_context,
             false,
@@ -880,6 +927,7 @@ impl MainMapping {
     ) -> MappingControlResult {
         self.control_internal(
             ControlOptions::default(),
+            &IncomingControlData::default(),
             context,
             processor_context,
             false,
@@ -893,6 +941,7 @@ impl MainMapping {
     fn control_internal(
         &mut self,
         options: ControlOptions,
+        incoming_data: &IncomingControlData,
         context: ControlContext,
         processor_context: ExtendedProcessorContext,
         is_polling: bool,
@@ -934,6 +983,8 @@ impl MainMapping {
             control_context: context,
             mapping_data: self.data(),
             coming_from_real_time: options.coming_from_real_time,
+            incoming_text: incoming_data.text.as_deref(),
+            incoming_color: incoming_data.color,
         };
         let actual_targets = if enforce_target_refresh {
             &mut fresh_targets
@@ -1234,12 +1285,35 @@ impl MainMapping {
             options,
             Default::default(),
         )?;
+        let mode_value = self.apply_feedback_color_table(mode_value);
         logger.log(FeedbackLogEntry {
             feedback_value: mode_value.as_ref(),
         });
         self.feedback_given_mode_value(mode_value, destinations, source_context)
     }
 
+    /// Overrides the color of numeric feedback values if a feedback color table is defined.
+    fn apply_feedback_color_table<'a>(
+        &self,
+        mode_value: Cow<'a, FeedbackValue>,
+    ) -> Cow<'a, FeedbackValue> {
+        let Some(table) = &self.feedback_color_table else {
+            return mode_value;
+        };
+        let color = match mode_value.as_ref() {
+            FeedbackValue::Numeric(v) => table.color_at(v.value.to_unit_value()),
+            _ => None,
+        };
+        let Some(color) = color else {
+            return mode_value;
+        };
+        let mut mode_value = mode_value.into_owned();
+        if let FeedbackValue::Numeric(v) = &mut mode_value {
+            v.style.color = Some(color);
+        }
+        Cow::Owned(mode_value)
+    }
+
     fn feedback_given_mode_value(
         &self,
         mode_value: Cow<FeedbackValue>,
@@ -1326,11 +1400,21 @@ impl MainMapping {
         msg: MainSourceMessage,
     ) -> Option<ControlOutcome<ControlValue>> {
         let compartment = self.compartment();
+        let qualified_id = self.qualified_id();
+        let consumes_incoming_data = self.consumes_incoming_control_data();
         match (msg, &mut self.core.source) {
             (MainSourceMessage::Osc(m), CompoundMappingSource::Osc(s)) => {
                 // With OSC sources, we don't distinguish between matched or consumed because
                 // there's no such thing such as "letting messages through".
-                s.control(m).map(ControlOutcome::Matched)
+                let value = s.control(m).or_else(|| {
+                    // String and color arguments don't carry a control value. We treat them as
+                    // trigger, but only if the target can do something with the text or color.
+                    let carries_data =
+                        matches!(osc_arg(s, m), Some(OscType::String(_) | OscType::Color(_)));
+                    (carries_data && consumes_incoming_data)
+                        .then_some(ControlValue::AbsoluteContinuous(UnitValue::MAX))
+                });
+                value.map(ControlOutcome::Matched)
             }
             (MainSourceMessage::Reaper(m), CompoundMappingSource::Reaper(s)) => {
                 // With REAPER sources, we don't distinguish between matched or consumed because
@@ -1341,18 +1425,68 @@ impl MainMapping {
             (MainSourceMessage::StreamDeck(m), CompoundMappingSource::StreamDeck(s)) => {
                 s.control(m).map(ControlOutcome::Matched)
             }
+            (MainSourceMessage::Evdev(m), CompoundMappingSource::Evdev(s)) => {
+                s.control(m).map(ControlOutcome::Matched)
+            }
+            (MainSourceMessage::Chord(m), CompoundMappingSource::Chord(_)) => {
+                if m.chord_id != qualified_id {
+                    return None;
+                }
+                let value = if m.is_on {
+                    UnitValue::MAX
+                } else {
+                    UnitValue::MIN
+                };
+                Some(ControlOutcome::Matched(ControlValue::AbsoluteContinuous(
+                    value,
+                )))
+            }
             _ => None,
         }
     }
 
     /// Polls the source.
-    pub fn poll_source(&mut self) -> Option<ControlValue> {
+    ///
+    /// The project is used by sources which follow the transport, e.g. musical timers.
+    pub fn poll_source(&mut self, project: Project) -> Option<ControlValue> {
         match &mut self.core.source {
-            CompoundMappingSource::Reaper(s) => s.poll(),
+            CompoundMappingSource::Reaper(s) => s.poll(project),
             _ => None,
         }
     }
 
+    /// Returns the text or color which the given message carries in addition to the control
+    /// value.
+    pub fn incoming_control_data(&self, msg: MainSourceMessage) -> IncomingControlData {
+        let (MainSourceMessage::Osc(m), CompoundMappingSource::Osc(s)) = (msg, &self.core.source)
+        else {
+            return IncomingControlData::default();
+        };
+        match osc_arg(s, m) {
+            Some(OscType::String(text)) => IncomingControlData {
+                text: Some(text.clone()),
+                color: None,
+            },
+            Some(OscType::Color(c)) => IncomingControlData {
+                text: None,
+                color: Some(RgbColor::new(c.red, c.green, c.blue)),
+            },
+            _ => IncomingControlData::default(),
+        }
+    }
+
+    /// Returns whether the targets make use of text or colors sent along with the control value.
+    ///
+    /// Always `true` for mappings with virtual targets. Whether the data is actually used is
+    /// decided by the main mappings with the corresponding virtual source. If none of them uses
+    /// it, the message doesn't count as matched (see the main processor).
+    pub fn consumes_incoming_control_data(&self) -> bool {
+        self.targets.iter().any(|t| match t {
+            CompoundMappingTarget::Reaper(t) => t.consumes_incoming_control_data(),
+            CompoundMappingTarget::Virtual(_) => true,
+        })
+    }
+
     pub fn control_virtualizing(
         &mut self,
         evt: ControlEvent<MainSourceMessage>,
@@ -1383,6 +1517,9 @@ pub enum MainSourceMessage<'a> {
     Reaper(&'a ReaperMessage),
     Key(KeyMessage),
     StreamDeck(StreamDeckMessage),
+    Evdev(EvdevMessage),
+    /// Emitted by the chord detector, never by a real device.
+    Chord(ChordMessage),
 }
 
 impl MainSourceMessage<'_> {
@@ -1400,6 +1537,11 @@ impl MainSourceMessage<'_> {
                 message: msg,
                 dev_id: None,
             }),
+            Evdev(msg) => MessageCaptureResult::Evdev(EvdevScanResult {
+                message: msg,
+                dev_id: None,
+            }),
+            Chord(_) => return None,
             Reaper(msg) => {
                 use ReaperMessage::*;
                 match msg {
@@ -1628,6 +1770,8 @@ pub enum CompoundMappingSource {
     Reaper(ReaperSource),
     Key(KeySource),
     StreamDeck(StreamDeckSource),
+    Evdev(EvdevSource),
+    Chord(ChordSource),
 }
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
@@ -1771,6 +1915,14 @@ impl CompoundMappingSource {
             (StreamDeck(s), IncomingCompoundSourceValue::StreamDeck(m)) => {
                 s.control(m).map(ControlResult::Processed)
             }
+            (Evdev(s), IncomingCompoundSourceValue::Evdev(m)) => {
+                s.control(m).map(ControlResult::Processed)
+            }
+            // For learning and filtering purposes, a chord reacts to each of its members.
+            (Chord(s), v) => s
+                .members()
+                .iter()
+                .find_map(|m| m.reacts_to_source_value_with(v)),
             _ => None,
         }
     }
@@ -1797,6 +1949,10 @@ impl CompoundMappingSource {
                     StreamDeckSource::new(scan_result.message.button_index, Default::default());
                 Self::StreamDeck(source)
             }
+            Evdev(scan_result) => {
+                let msg = scan_result.message;
+                Self::Evdev(EvdevSource::new(msg.payload.event_kind(), msg.code))
+            }
             RealearnParameter(payload) => {
                 let reaper_source = ReaperSource::RealearnParameter(RealearnParameterSource {
                     parameter_index: payload.parameter_index,
@@ -1814,7 +1970,7 @@ impl CompoundMappingSource {
             Virtual(s) => s.format_control_value(value),
             Osc(s) => s.format_control_value(value),
             Reaper(s) => s.format_control_value(value),
-            Never | Key(_) | StreamDeck(_) => {
+            Never | Key(_) | StreamDeck(_) | Evdev(_) | Chord(_) => {
                 Ok(format_percentage_without_unit(value.to_unit_value()?.get()))
             }
         }
@@ -1827,7 +1983,9 @@ impl CompoundMappingSource {
             Virtual(s) => s.parse_control_value(text),
             Osc(s) => s.parse_control_value(text),
             Reaper(s) => s.parse_control_value(text),
-            Never | Key(_) | StreamDeck(_) => parse_percentage_without_unit(text)?.try_into(),
+            Never | Key(_) | StreamDeck(_) | Evdev(_) | Chord(_) => {
+                parse_percentage_without_unit(text)?.try_into()
+            }
         }
     }
 
@@ -1838,8 +1996,9 @@ impl CompoundMappingSource {
             Virtual(s) => s.character(),
             Osc(s) => ExtendedSourceCharacter::Normal(s.character()),
             Reaper(s) => ExtendedSourceCharacter::Normal(s.character()),
+            Evdev(s) => ExtendedSourceCharacter::Normal(s.character()),
             Never => ExtendedSourceCharacter::VirtualContinuous,
-            Key(_) | StreamDeck(_) => {
+            Key(_) | StreamDeck(_) | Chord(_) => {
                 ExtendedSourceCharacter::Normal(SourceCharacter::MomentaryButton)
             }
         }
@@ -1867,7 +2026,7 @@ impl CompoundMappingSource {
             // This is handled in a special way by consumers.
             Virtual(_) => None,
             // No feedback for other sources.
-            Key(_) | Never => None,
+            Key(_) | Evdev(_) | Chord(_) | Never => None,
         }
     }
 
@@ -1875,7 +2034,8 @@ impl CompoundMappingSource {
         use CompoundMappingSource::*;
         match self {
             Midi(s) => s.consumes(msg),
-            Reaper(_) | Virtual(_) | Osc(_) | Never | Key(_) | StreamDeck(_) => false,
+            Reaper(_) | Virtual(_) | Osc(_) | Never | Key(_) | StreamDeck(_) | Evdev(_)
+            | Chord(_) => false,
         }
     }
 
@@ -1889,7 +2049,8 @@ impl CompoundMappingSource {
             Midi(s) => s.max_discrete_value(),
             // TODO-medium OSC will also support discrete values as soon as we allow integers and
             //  configuring max values
-            Reaper(_) | Virtual(_) | Osc(_) | Never | Key(_) | StreamDeck(_) => None,
+            Reaper(_) | Virtual(_) | Osc(_) | Never | Key(_) | StreamDeck(_) | Evdev(_)
+            | Chord(_) => None,
         }
     }
 }
@@ -2678,6 +2839,15 @@ pub struct MappingControlResult {
     pub celebrate_success: bool,
 }
 
+/// Returns the argument which the given OSC source refers to, if the message matches.
+fn osc_arg<'a>(source: &OscSource, msg: &'a OscMessage) -> Option<&'a OscType> {
+    if msg.addr != source.address_pattern() {
+        return None;
+    }
+    let arg_index = source.arg_descriptor()?.index();
+    msg.args.get(arg_index as usize)
+}
+
 /// Not usable for mappings with virtual targets.
 fn should_send_manual_feedback_due_to_target(
     target: &ReaperTarget,
@@ -2742,6 +2912,7 @@ pub enum MessageCaptureResult {
     Osc(OscScanResult),
     Keyboard(KeyMessage),
     StreamDeck(StreamDeckScanResult),
+    Evdev(EvdevScanResult),
     RealearnParameter(RealearnParameterChangePayload),
 }
 
@@ -2756,6 +2927,7 @@ impl MessageCaptureResult {
             Osc(res) => IncomingCompoundSourceValue::Osc(&res.message),
             Keyboard(res) => IncomingCompoundSourceValue::Key(*res),
             StreamDeck(res) => IncomingCompoundSourceValue::StreamDeck(res.message),
+            Evdev(res) => IncomingCompoundSourceValue::Evdev(res.message),
             RealearnParameter(payload) => IncomingCompoundSourceValue::RealearnParameter(*payload),
         }
     }
@@ -2779,6 +2951,9 @@ impl MessageCaptureResult {
             StreamDeck(r) => InputDescriptor::StreamDeck {
                 device_id: r.dev_id?,
             },
+            Evdev(r) => InputDescriptor::Evdev {
+                device_id: r.dev_id?,
+            },
             RealearnParameter(_) => return None,
         };
         Some(res)
@@ -2792,6 +2967,7 @@ pub enum IncomingCompoundSourceValue<'a> {
     Virtual(&'a VirtualSourceValue),
     Key(KeyMessage),
     StreamDeck(StreamDeckMessage),
+    Evdev(EvdevMessage),
     RealearnParameter(RealearnParameterChangePayload),
 }
 
@@ -2807,6 +2983,9 @@ pub enum InputDescriptor {
     StreamDeck {
         device_id: StreamDeckDeviceId,
     },
+    Evdev {
+        device_id: EvdevDeviceId,
+    },
 }
 
 #[derive(Copy, Clone)]
@@ -1,7 +1,7 @@
 use crate::domain::{MappingId, Tag, TagScope, VirtualMappingSnapshotIdForLoad};
 use base::hash_util::{NonCryptoHashMap, NonCryptoHashSet};
 use base::{convert_to_identifier, SmallAsciiString};
-use helgoboss_learn::AbsoluteValue;
+use helgoboss_learn::{AbsoluteValue, UnitValue};
 use std::str::FromStr;
 
 #[derive(Debug, Default)]
@@ -121,6 +121,30 @@ impl MappingSnapshot {
     }
 }
 
+/// Computes the target value at the given position between two snapshot target values.
+///
+/// Continuous values are interpolated linearly. Discrete values can't be interpolated in a
+/// meaningful way, so they switch from `a` to `b` at the midpoint.
+pub fn morph_target_values(
+    a: AbsoluteValue,
+    b: AbsoluteValue,
+    position: UnitValue,
+) -> AbsoluteValue {
+    match (a, b) {
+        (AbsoluteValue::Continuous(a), AbsoluteValue::Continuous(b)) => {
+            let interpolated = a.get() + (b.get() - a.get()) * position.get();
+            AbsoluteValue::Continuous(UnitValue::new_clamped(interpolated))
+        }
+        _ => {
+            if position.get() < 0.5 {
+                a
+            } else {
+                b
+            }
+        }
+    }
+}
+
 #[derive(
     Clone,
     Eq,
@@ -143,3 +167,37 @@ impl FromStr for MappingSnapshotId {
         Ok(Self(small_ascii_string))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helgoboss_learn::Fraction;
+
+    #[test]
+    fn morph_continuous_values() {
+        // Given
+        let a = AbsoluteValue::Continuous(UnitValue::new(0.2));
+        let b = AbsoluteValue::Continuous(UnitValue::new(0.6));
+        // When
+        let start = morph_target_values(a, b, UnitValue::MIN);
+        let middle = morph_target_values(a, b, UnitValue::new(0.5));
+        let end = morph_target_values(a, b, UnitValue::MAX);
+        // Then
+        assert_eq!(start, a);
+        assert!((middle.to_unit_value().get() - 0.4).abs() < 0.00001);
+        assert_eq!(end, b);
+    }
+
+    #[test]
+    fn morph_discrete_values() {
+        // Given
+        let a = AbsoluteValue::Discrete(Fraction::new(1, 4));
+        let b = AbsoluteValue::Discrete(Fraction::new(3, 4));
+        // When
+        let before_midpoint = morph_target_values(a, b, UnitValue::new(0.49));
+        let at_midpoint = morph_target_values(a, b, UnitValue::new(0.5));
+        // Then
+        assert_eq!(before_midpoint, a);
+        assert_eq!(at_midpoint, b);
+    }
+}
@@ -7,6 +7,9 @@ pub use main_processor::*;
 mod mapping;
 pub use mapping::*;
 
+mod lifecycle_action;
+pub use lifecycle_action::*;
+
 mod control_surface;
 pub use control_surface::*;
 
@@ -19,6 +22,9 @@ pub use audio_hook::*;
 mod mode;
 pub use mode::*;
 
+mod feedback_color_table;
+pub use feedback_color_table::*;
+
 mod source;
 pub use source::*;
 
@@ -40,6 +46,8 @@ pub use lua_midi_source_script::*;
 mod lua_feedback_script;
 pub use lua_feedback_script::*;
 
+mod wasm_midi_source_script;
+pub use wasm_midi_source_script::*;
 mod flexible_midi_source_script;
 pub use flexible_midi_source_script::*;
 
@@ -61,6 +69,24 @@ pub use r#virtual::*;
 mod midi_util;
 pub use midi_util::*;
 
+mod reaper_low_util;
+pub use reaper_low_util::*;
+
+mod item_util;
+pub use item_util::*;
+
+mod envelope_util;
+pub use envelope_util::*;
+
+mod bookmark_util;
+pub use bookmark_util::*;
+
+mod track_recording_util;
+pub use track_recording_util::*;
+
+mod naming_util;
+pub use naming_util::*;
+
 mod midi_source_scanner;
 pub use midi_source_scanner::*;
 
@@ -93,6 +119,9 @@ pub use unit::*;
 mod osc;
 pub use osc::*;
 
+mod osc_stream;
+pub use osc_stream::*;
+
 mod exclusivity;
 pub use exclusivity::*;
 
@@ -120,6 +149,15 @@ pub use stream_deck_device::*;
 mod stream_deck_source;
 pub use stream_deck_source::*;
 
+mod evdev_device;
+pub use evdev_device::*;
+
+mod evdev_source;
+pub use evdev_source::*;
+
+mod chord_source;
+pub use chord_source::*;
+
 mod device_change_detector;
 pub use device_change_detector::*;
 
@@ -129,6 +167,9 @@ pub use reaper_config_change_detector::*;
 mod monitoring_fx_chain_change_detector;
 pub use monitoring_fx_chain_change_detector::*;
 
+mod track_recording_change_detector;
+pub use track_recording_change_detector::*;
+
 mod tag;
 pub use tag::*;
 
@@ -153,11 +194,7 @@ pub use parameter_manager::*;
 mod control_event;
 pub use control_event::*;
 
-mod lua_support;
-pub use lua_support::*;
-
-mod lua_module_container;
-pub use lua_module_container::*;
+pub use helgobox_api::lua::*;
 
 mod internal_info_event;
 pub use internal_info_event::*;
@@ -0,0 +1,284 @@
+use crate::domain::{convert_reaper_color_to_helgoboss_learn, low_reaper};
+use helgoboss_learn::RgbColor;
+use reaper_high::{Fx, Reaper, Track};
+use reaper_low::Swell;
+use std::ffi::{CStr, CString};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Number of custom colors which can be defined in REAPER's color picker dialog.
+const REAPER_CUSTOM_COLOR_COUNT: usize = 16;
+
+/// Colors which make up the track color palette if no custom colors are defined in REAPER.
+const FALLBACK_TRACK_COLORS: [(u8, u8, u8); 7] = [
+    (255, 0, 0),
+    (255, 255, 0),
+    (0, 255, 0),
+    (0, 255, 255),
+    (0, 0, 255),
+    (255, 0, 255),
+    (255, 255, 255),
+];
+
+/// The track color palette is needed in every feedback polling cycle, but the custom colors are
+/// only available in "reaper.ini". So we read them at most once within this interval.
+const TRACK_COLOR_PALETTE_MAX_AGE: Duration = Duration::from_secs(1);
+
+static TRACK_COLOR_PALETTE_CACHE: Mutex<Option<(Instant, Vec<Option<RgbColor>>)>> =
+    Mutex::new(None);
+
+/// Returns the colors selectable via palette index in the "Track: Set color" target.
+///
+/// The first entry resets the track to REAPER's default color. The other entries are the custom
+/// colors defined in REAPER's color picker dialog or, if there are none, [`FALLBACK_TRACK_COLORS`].
+pub fn track_color_palette() -> Vec<Option<RgbColor>> {
+    let mut cache = TRACK_COLOR_PALETTE_CACHE
+        .lock()
+        .expect("track color palette cache poisoned");
+    match cache.as_ref() {
+        Some((time, palette)) if time.elapsed() < TRACK_COLOR_PALETTE_MAX_AGE => palette.clone(),
+        _ => {
+            let palette = build_track_color_palette(read_reaper_custom_colors());
+            *cache = Some((Instant::now(), palette.clone()));
+            palette
+        }
+    }
+}
+
+fn build_track_color_palette(custom_colors: Option<Vec<RgbColor>>) -> Vec<Option<RgbColor>> {
+    let colors = custom_colors.unwrap_or_else(|| {
+        FALLBACK_TRACK_COLORS
+            .iter()
+            .map(|(r, g, b)| RgbColor::new(*r, *g, *b))
+            .collect()
+    });
+    std::iter::once(None)
+        .chain(colors.into_iter().map(Some))
+        .collect()
+}
+
+/// Reads the custom colors defined in REAPER's color picker dialog.
+fn read_reaper_custom_colors() -> Option<Vec<RgbColor>> {
+    let ini_file = Reaper::get()
+        .medium_reaper()
+        .get_ini_file(|p| CString::new(p.as_str()).ok())?;
+    let mut buffer = vec![0u8; 256];
+    unsafe {
+        Swell::get().GetPrivateProfileString(
+            c"REAPER".as_ptr(),
+            c"custcolors".as_ptr(),
+            c"".as_ptr(),
+            buffer.as_mut_ptr() as _,
+            buffer.len() as _,
+            ini_file.as_ptr(),
+        );
+    }
+    let text = CStr::from_bytes_until_nul(&buffer).ok()?.to_str().ok()?;
+    parse_reaper_custom_colors(text)
+}
+
+/// Parses the "custcolors" entry of "reaper.ini".
+///
+/// It contains one `RRGGBB00` hex chunk per custom color. Returns `None` if no custom color is
+/// defined (all black).
+fn parse_reaper_custom_colors(text: &str) -> Option<Vec<RgbColor>> {
+    let text = text.trim();
+    if text.len() != REAPER_CUSTOM_COLOR_COUNT * 8 {
+        return None;
+    }
+    let colors = (0..REAPER_CUSTOM_COLOR_COUNT)
+        .map(|i| parse_color(text.get(i * 8..i * 8 + 6)?))
+        .collect::<Option<Vec<_>>>()?;
+    if colors.iter().all(|c| *c == RgbColor::new(0, 0, 0)) {
+        return None;
+    }
+    Some(colors)
+}
+
+/// Returns the custom color of the given track or `None` if it uses the default color.
+pub fn track_color(track: &Track) -> Option<RgbColor> {
+    track
+        .custom_color()
+        .map(convert_reaper_color_to_helgoboss_learn)
+}
+
+/// Sets the custom color of the given track. `None` resets it to the default color.
+pub fn set_track_color(track: &Track, color: Option<RgbColor>) {
+    let Ok(raw_track) = track.raw() else {
+        return;
+    };
+    let value = match color {
+        None => 0,
+        Some(c) => {
+            let native = unsafe { low_reaper().ColorToNative(c.r() as _, c.g() as _, c.b() as _) };
+            // This flag tells REAPER that the custom color is in use
+            native | 0x1000000
+        }
+    };
+    unsafe {
+        low_reaper().SetMediaTrackInfo_Value(
+            raw_track.as_ptr(),
+            c"I_CUSTOMCOLOR".as_ptr(),
+            value as f64,
+        );
+    }
+}
+
+/// Returns the index of the given track color within the given palette.
+pub fn track_color_palette_index(
+    palette: &[Option<RgbColor>],
+    color: Option<RgbColor>,
+) -> Option<u32> {
+    let index = palette.iter().position(|c| *c == color)?;
+    Some(index as u32)
+}
+
+/// Returns the color at the given index of the given palette.
+pub fn track_color_from_palette(palette: &[Option<RgbColor>], index: u32) -> Option<RgbColor> {
+    palette.get(index as usize).copied().flatten()
+}
+
+/// Parses colors in the form of `#RRGGBB` or `RRGGBB`.
+pub fn parse_color(text: &str) -> Option<RgbColor> {
+    let hex = text.trim().trim_start_matches('#');
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    Some(RgbColor::new(channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// Formats the given color in the form of `#RRGGBB`.
+pub fn format_color(color: RgbColor) -> String {
+    format!("#{:02X}{:02X}{:02X}", color.r(), color.g(), color.b())
+}
+
+/// Returns the custom name given to the FX instance or `None` if it doesn't have one.
+pub fn fx_alias(fx: &Fx) -> Option<String> {
+    let raw_track = fx.track()?.raw().ok()?;
+    let mut buffer = vec![0u8; 512];
+    let successful = unsafe {
+        low_reaper().TrackFX_GetNamedConfigParm(
+            raw_track.as_ptr(),
+            fx.query_index().to_raw(),
+            c"renamed_name".as_ptr(),
+            buffer.as_mut_ptr() as _,
+            buffer.len() as _,
+        )
+    };
+    if !successful {
+        return None;
+    }
+    let alias = CStr::from_bytes_until_nul(&buffer).ok()?.to_string_lossy();
+    if alias.is_empty() {
+        return None;
+    }
+    Some(alias.into_owned())
+}
+
+/// Gives the FX instance a custom name. An empty name removes it.
+pub fn set_fx_alias(fx: &Fx, alias: &str) -> Result<(), &'static str> {
+    let raw_track = fx
+        .track()
+        .ok_or("FX not on track")?
+        .raw()
+        .map_err(|_| "track not available")?;
+    let value = CString::new(alias).map_err(|_| "name contains nul character")?;
+    let successful = unsafe {
+        low_reaper().TrackFX_SetNamedConfigParm(
+            raw_track.as_ptr(),
+            fx.query_index().to_raw(),
+            c"renamed_name".as_ptr(),
+            value.as_ptr(),
+        )
+    };
+    if !successful {
+        return Err("couldn't rename FX");
+    }
+    Ok(())
+}
+
+/// Activates the preset with the given name, as shown in REAPER's preset dropdown.
+pub fn activate_fx_preset_by_name(fx: &Fx, name: &str) -> Result<(), &'static str> {
+    let raw_track = fx
+        .track()
+        .ok_or("FX not on track")?
+        .raw()
+        .map_err(|_| "track not available")?;
+    let value = CString::new(name).map_err(|_| "name contains nul character")?;
+    let successful = unsafe {
+        low_reaper().TrackFX_SetPreset(
+            raw_track.as_ptr(),
+            fx.query_index().to_raw(),
+            value.as_ptr(),
+        )
+    };
+    if !successful {
+        return Err("preset not found");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_format_color() {
+        // Given
+        let color = parse_color("#ff8000").unwrap();
+        // When
+        let text = format_color(color);
+        // Then
+        assert_eq!(color, RgbColor::new(255, 128, 0));
+        assert_eq!(text, "#FF8000");
+        assert_eq!(parse_color("00FF00"), Some(RgbColor::new(0, 255, 0)));
+        assert_eq!(parse_color("#ff80"), None);
+        assert_eq!(parse_color("#gg0000"), None);
+    }
+
+    #[test]
+    fn palette() {
+        // Given
+        let palette = build_track_color_palette(None);
+        // When
+        let red = track_color_from_palette(&palette, 1).unwrap();
+        // Then
+        assert_eq!(palette.len(), 8);
+        assert_eq!(track_color_palette_index(&palette, None), Some(0));
+        assert_eq!(track_color_from_palette(&palette, 0), None);
+        assert_eq!(red, RgbColor::new(255, 0, 0));
+        assert_eq!(track_color_palette_index(&palette, Some(red)), Some(1));
+        assert_eq!(
+            track_color_palette_index(&palette, Some(RgbColor::new(1, 2, 3))),
+            None
+        );
+        assert_eq!(track_color_from_palette(&palette, 8), None);
+    }
+
+    #[test]
+    fn palette_from_reaper_custom_colors() {
+        // Given
+        let text = format!("FF800000{}", "00000000".repeat(14)) + "0080FF00";
+        // When
+        let palette = build_track_color_palette(parse_reaper_custom_colors(&text));
+        // Then
+        assert_eq!(palette.len(), 17);
+        assert_eq!(palette[0], None);
+        assert_eq!(palette[1], Some(RgbColor::new(255, 128, 0)));
+        assert_eq!(palette[2], Some(RgbColor::new(0, 0, 0)));
+        assert_eq!(palette[16], Some(RgbColor::new(0, 128, 255)));
+    }
+
+    #[test]
+    fn palette_without_reaper_custom_colors() {
+        // Given
+        let texts = ["", "FF800000", &"00000000".repeat(16)];
+        for text in texts {
+            // When
+            let custom_colors = parse_reaper_custom_colors(text);
+            // Then
+            assert_eq!(custom_colors, None);
+        }
+    }
+}
@@ -1,24 +1,37 @@
-use crossbeam_channel::Receiver;
+use crate::domain::{OscStreamDecoder, OscStreamFraming};
+use crossbeam_channel::{Receiver, TryRecvError};
 use derive_more::Display;
 use rosc::{OscBundle, OscMessage, OscPacket};
 use serde::{Deserialize, Serialize};
 
+use std::collections::VecDeque;
 use std::error::Error;
 use std::io;
-use std::net::{SocketAddrV4, UdpSocket};
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, UdpSocket};
 
 use anyhow::Context;
+use base::metrics_util::metrics_are_enabled;
 use core::mem;
+use indexmap::IndexMap;
+use std::num::NonZeroU32;
 use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread::JoinHandle;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tracing::{trace, warn};
 use uuid::Uuid;
 
 const MAX_INCOMING_PACKET_SIZE: usize = 10_000;
 const OSC_OUTGOING_BULK_SIZE: usize = 16;
+/// If the rate limit doesn't keep up, we drop the oldest messages beyond this count.
+const MAX_PENDING_OSC_MESSAGES_PER_DEVICE: usize = 10_000;
+const OSC_FEEDBACK_COALESCED_METRIC: &str = "helgobox.osc.feedback.coalesced_messages";
+const OSC_FEEDBACK_DROPPED_METRIC: &str = "helgobox.osc.feedback.dropped_messages";
+const TCP_CONNECT_TIMEOUT: Duration = Duration::from_millis(500);
+const TCP_WRITE_TIMEOUT: Duration = Duration::from_millis(500);
+const TCP_RECONNECT_INTERVAL: Duration = Duration::from_secs(3);
 
 pub struct OscFeedbackTask {
     dev_id: OscDeviceId,
@@ -116,18 +129,18 @@ struct OscFeedbackHandler {
 
 impl OscFeedbackHandler {
     pub fn cycle(&mut self) {
-        use itertools::Itertools;
-        let grouped_by_device = self
-            .task_receiver
-            .try_iter()
-            .take(OSC_OUTGOING_BULK_SIZE)
-            .sorted_by_key(|task| task.dev_id)
-            .group_by(|task| task.dev_id);
-        for (dev_id, group) in grouped_by_device.into_iter() {
-            if let Some(dev) = self.osc_output_devices.iter().find(|d| d.id() == dev_id) {
-                let _ = dev.send(group.map(|task| task.msg));
+        for task in self.task_receiver.try_iter().take(OSC_OUTGOING_BULK_SIZE) {
+            if let Some(dev) = self
+                .osc_output_devices
+                .iter_mut()
+                .find(|d| d.id() == task.dev_id)
+            {
+                dev.enqueue(task.msg);
             }
         }
+        for dev in &mut self.osc_output_devices {
+            let _ = dev.flush();
+        }
         std::thread::sleep(Duration::from_millis(1));
     }
 
@@ -139,15 +152,59 @@ impl OscFeedbackHandler {
 #[derive(Debug)]
 pub struct OscInputDevice {
     id: OscDeviceId,
-    socket: UdpSocket,
+    socket: OscInputSocket,
     osc_buffer: [u8; MAX_INCOMING_PACKET_SIZE],
 }
 
+#[derive(Debug)]
+enum OscInputSocket {
+    Udp(UdpSocket),
+    Tcp(OscTcpInput),
+}
+
+/// Accepts incoming TCP connections and decodes the OSC packets sent over them.
+#[derive(Debug)]
+struct OscTcpInput {
+    listener: TcpListener,
+    framing: OscStreamFraming,
+    connections: Vec<OscTcpInputConnection>,
+    decoded_packets: VecDeque<OscPacket>,
+}
+
+#[derive(Debug)]
+struct OscTcpInputConnection {
+    stream: TcpStream,
+    decoder: OscStreamDecoder,
+}
+
 impl OscInputDevice {
     pub fn bind(id: OscDeviceId, socket: UdpSocket) -> Result<OscInputDevice, Box<dyn Error>> {
         let dev = OscInputDevice {
             id,
-            socket,
+            socket: OscInputSocket::Udp(socket),
+            osc_buffer: [0; MAX_INCOMING_PACKET_SIZE],
+        };
+        Ok(dev)
+    }
+
+    /// Creates an input device which accepts any number of TCP connections on the given
+    /// listener.
+    ///
+    /// The listener must be non-blocking.
+    pub fn listen(
+        id: OscDeviceId,
+        listener: TcpListener,
+        framing: OscStreamFraming,
+    ) -> Result<OscInputDevice, Box<dyn Error>> {
+        let tcp_input = OscTcpInput {
+            listener,
+            framing,
+            connections: vec![],
+            decoded_packets: Default::default(),
+        };
+        let dev = OscInputDevice {
+            id,
+            socket: OscInputSocket::Tcp(tcp_input),
             osc_buffer: [0; MAX_INCOMING_PACKET_SIZE],
         };
         Ok(dev)
@@ -158,45 +215,165 @@ impl OscInputDevice {
     }
 
     pub fn poll(&mut self) -> Result<Option<OscPacket>, &'static str> {
-        match self.socket.recv(&mut self.osc_buffer) {
-            Ok(num_bytes) => match rosc::decoder::decode_udp(&self.osc_buffer[..num_bytes]) {
+        match &mut self.socket {
+            OscInputSocket::Udp(socket) => poll_udp(socket, &mut self.osc_buffer),
+            OscInputSocket::Tcp(tcp_input) => tcp_input.poll(&mut self.osc_buffer),
+        }
+    }
+
+    pub fn poll_multiple(&mut self, n: usize) -> impl Iterator<Item = OscPacket> + '_ {
+        (0..n).flat_map(move |_| self.poll().ok().flatten())
+    }
+}
+
+fn poll_udp(socket: &UdpSocket, buffer: &mut [u8]) -> Result<Option<OscPacket>, &'static str> {
+    match socket.recv(buffer) {
+        Ok(num_bytes) => match rosc::decoder::decode_udp(&buffer[..num_bytes]) {
+            Ok((_, packet)) => {
+                trace!("Received packet with {} bytes: {:#?}", num_bytes, &packet);
+                Ok(Some(packet))
+            }
+            Err(err) => {
+                warn!("Error trying to decode OSC packet: {:?}", err);
+                Err("error trying to decode OSC messages")
+            }
+        },
+        Err(ref err) if err.kind() != io::ErrorKind::WouldBlock => {
+            warn!("Error trying to receive OSC packet: {}", err);
+            Err("error trying to receive OSC message")
+        }
+        // We don't need to handle "would block" because we are running in a loop anyway.
+        _ => Ok(None),
+    }
+}
+
+impl OscTcpInput {
+    fn poll(&mut self, buffer: &mut [u8]) -> Result<Option<OscPacket>, &'static str> {
+        if let Some(packet) = self.decoded_packets.pop_front() {
+            return Ok(Some(packet));
+        }
+        self.accept_new_connections();
+        self.connections
+            .retain_mut(|con| read_from_tcp_connection(con, buffer, &mut self.decoded_packets));
+        Ok(self.decoded_packets.pop_front())
+    }
+
+    fn accept_new_connections(&mut self) {
+        loop {
+            match self.listener.accept() {
+                Ok((stream, peer_address)) => {
+                    if let Err(e) = stream.set_nonblocking(true) {
+                        warn!("Couldn't make OSC TCP connection non-blocking: {e}");
+                        continue;
+                    }
+                    trace!("Accepted OSC TCP connection from {peer_address}");
+                    let con = OscTcpInputConnection {
+                        stream,
+                        decoder: OscStreamDecoder::new(self.framing),
+                    };
+                    self.connections.push(con);
+                }
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) => {
+                    warn!("Error trying to accept OSC TCP connection: {}", err);
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Reads available bytes from the given connection and decodes all complete packets.
+///
+/// Returns `false` if the connection has been closed and should be removed.
+fn read_from_tcp_connection(
+    con: &mut OscTcpInputConnection,
+    buffer: &mut [u8],
+    decoded_packets: &mut VecDeque<OscPacket>,
+) -> bool {
+    match con.stream.read(buffer) {
+        // Peer has closed the connection
+        Ok(0) => return false,
+        Ok(num_bytes) => con.decoder.feed(&buffer[..num_bytes]),
+        Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => return true,
+        Err(err) => {
+            warn!("Error trying to receive OSC packet via TCP: {}", err);
+            return false;
+        }
+    }
+    loop {
+        match con.decoder.next_packet() {
+            Ok(Some(bytes)) => match rosc::decoder::decode_udp(&bytes) {
                 Ok((_, packet)) => {
-                    trace!("Received packet with {} bytes: {:#?}", num_bytes, &packet);
-                    Ok(Some(packet))
+                    trace!("Received packet with {} bytes: {:#?}", bytes.len(), &packet);
+                    decoded_packets.push_back(packet);
                 }
                 Err(err) => {
                     warn!("Error trying to decode OSC packet: {:?}", err);
-                    Err("error trying to decode OSC messages")
                 }
             },
-            Err(ref err) if err.kind() != io::ErrorKind::WouldBlock => {
-                warn!("Error trying to receive OSC packet: {}", err);
-                Err("error trying to receive OSC message")
+            Ok(None) => return true,
+            Err(err) => {
+                warn!("Error trying to decode OSC TCP stream: {}", err);
+                return true;
             }
-            // We don't need to handle "would block" because we are running in a loop anyway.
-            _ => Ok(None),
         }
     }
-
-    pub fn poll_multiple(&mut self, n: usize) -> impl Iterator<Item = OscPacket> + '_ {
-        (0..n).flat_map(move |_| self.poll().ok().flatten())
-    }
 }
 
 #[derive(Debug)]
 pub struct OscOutputDevice {
     id: OscDeviceId,
-    socket: UdpSocket,
-    dest_address: SocketAddrV4,
+    socket: OscOutputSocket,
     can_deal_with_bundles: bool,
+    pending_messages: PendingOscMessages,
+    rate_limiter: Option<OscRateLimiter>,
+}
+
+/// Determines how feedback messages are sent to a particular OSC device.
+///
+/// The default policy sends all messages as soon as possible, without any modification.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OscOutputPolicy {
+    /// Maximum number of messages sent per second. `None` means unlimited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_messages_per_second: Option<NonZeroU32>,
+    /// If enabled, pending messages with the same address are merged, so only the latest one
+    /// goes out.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub coalesce_by_address: bool,
+}
+
+#[derive(Debug)]
+enum OscOutputSocket {
+    Udp {
+        socket: UdpSocket,
+        dest_address: SocketAddr,
+    },
+    Tcp(OscTcpOutput),
+}
+
+/// Lazily connects to the device via TCP and reconnects if the connection got lost.
+///
+/// Connecting happens in a separate thread, so that an unreachable device doesn't stall feedback
+/// for all the other devices.
+#[derive(Debug)]
+struct OscTcpOutput {
+    dest_address: SocketAddr,
+    framing: OscStreamFraming,
+    stream: Option<TcpStream>,
+    pending_connection: Option<Receiver<io::Result<TcpStream>>>,
+    last_connection_attempt: Option<Instant>,
 }
 
 impl OscOutputDevice {
     pub fn new(
         id: OscDeviceId,
         socket: UdpSocket,
-        dest_address: SocketAddrV4,
+        dest_address: SocketAddr,
         can_deal_with_bundles: bool,
+        policy: OscOutputPolicy,
     ) -> Self {
         // Attention: It's important that we don't use `UdpSocket::connect` here as this breaks
         // control. No idea why exactly, but it must have something to do with the fact that we
@@ -205,9 +382,41 @@ impl OscOutputDevice {
         // https://github.com/helgoboss/helgobox/issues/551.
         OscOutputDevice {
             id,
-            socket,
+            socket: OscOutputSocket::Udp {
+                socket,
+                dest_address,
+            },
+            can_deal_with_bundles,
+            pending_messages: PendingOscMessages::new(policy.coalesce_by_address),
+            rate_limiter: policy.max_messages_per_second.map(OscRateLimiter::new),
+        }
+    }
+
+    /// Creates an output device which sends packets via TCP.
+    ///
+    /// Doesn't connect immediately. The connection is established (and reestablished) in the
+    /// background when there's something to send, so that a temporarily unavailable device
+    /// doesn't block anything.
+    pub fn new_tcp(
+        id: OscDeviceId,
+        dest_address: SocketAddr,
+        framing: OscStreamFraming,
+        can_deal_with_bundles: bool,
+        policy: OscOutputPolicy,
+    ) -> Self {
+        let tcp_output = OscTcpOutput {
             dest_address,
+            framing,
+            stream: None,
+            pending_connection: None,
+            last_connection_attempt: None,
+        };
+        OscOutputDevice {
+            id,
+            socket: OscOutputSocket::Tcp(tcp_output),
             can_deal_with_bundles,
+            pending_messages: PendingOscMessages::new(policy.coalesce_by_address),
+            rate_limiter: policy.max_messages_per_second.map(OscRateLimiter::new),
         }
     }
 
@@ -215,7 +424,47 @@ impl OscOutputDevice {
         self.id
     }
 
-    pub fn send(&self, messages: impl Iterator<Item = OscMessage>) -> Result<(), &'static str> {
+    /// Queues the given message for being sent with the next flush.
+    pub fn enqueue(&mut self, msg: OscMessage) {
+        match self.pending_messages.push(msg) {
+            PushOutcome::Added => {}
+            PushOutcome::Coalesced => {
+                record_osc_feedback_occurrence(OSC_FEEDBACK_COALESCED_METRIC, self.id);
+            }
+            PushOutcome::DroppedOldest => {
+                record_osc_feedback_occurrence(OSC_FEEDBACK_DROPPED_METRIC, self.id);
+            }
+        }
+    }
+
+    /// Sends as many pending messages as the rate limit allows (all if there's no rate limit).
+    ///
+    /// Messages which couldn't be sent stay pending and are sent with the next flush.
+    pub fn flush(&mut self) -> Result<(), &'static str> {
+        let wanted_count = self.pending_messages.len();
+        if wanted_count == 0 {
+            return Ok(());
+        }
+        // As long as the TCP connection is not established, we don't even take the messages
+        // (and don't use up the rate limit).
+        self.socket
+            .ensure_ready()
+            .map_err(|_| "OSC device not connected")?;
+        let count = match &mut self.rate_limiter {
+            None => wanted_count,
+            Some(l) => l.acquire(wanted_count, Instant::now()),
+        };
+        if count == 0 {
+            return Ok(());
+        }
+        let messages = self.pending_messages.take(count);
+        self.send(messages).map_err(|e| {
+            self.pending_messages.put_back(e.unsent_messages);
+            e.msg
+        })
+    }
+
+    fn send(&mut self, messages: Vec<OscMessage>) -> Result<(), OscSendError> {
         if self.can_deal_with_bundles {
             // Haven't realized a performance difference between sending a bundle or single
             // messages. However, REAPER sends a bundle (maybe in order to use time tags).
@@ -226,50 +475,309 @@ impl OscOutputDevice {
         }
     }
 
-    fn send_as_bundle(
-        &self,
-        messages: impl Iterator<Item = OscMessage>,
-    ) -> Result<(), &'static str> {
+    fn send_as_bundle(&mut self, messages: Vec<OscMessage>) -> Result<(), OscSendError> {
         let bundle = OscBundle {
             // That should be "immediately" according to the OSC Time Tag spec.
             timetag: (0, 1).into(),
-            content: messages.map(OscPacket::Message).collect(),
+            content: messages.into_iter().map(OscPacket::Message).collect(),
         };
         let packet = OscPacket::Bundle(bundle);
-        let bytes = rosc::encoder::encode(&packet)
-            .map_err(|_| "error trying to encode OSC bundle packet")?;
+        let bytes = rosc::encoder::encode(&packet).map_err(|_| OscSendError {
+            msg: "error trying to encode OSC bundle packet",
+            unsent_messages: vec![],
+        })?;
         trace!(
             "Sending bundle packet with {} bytes: {:#?}",
             bytes.len(),
             &packet
         );
-        self.socket
-            .send_to(&bytes, self.dest_address)
-            .map_err(|_| "error trying to send OSC bundle packet")?;
+        if self.socket.send_packet(&bytes).is_err() {
+            return Err(OscSendError {
+                msg: "error trying to send OSC bundle packet",
+                unsent_messages: packet_into_messages(packet),
+            });
+        }
         Ok(())
     }
 
-    fn send_as_messages(
-        &self,
-        messages: impl Iterator<Item = OscMessage>,
-    ) -> Result<(), &'static str> {
-        for m in messages {
+    fn send_as_messages(&mut self, messages: Vec<OscMessage>) -> Result<(), OscSendError> {
+        let mut messages = messages.into_iter();
+        while let Some(m) = messages.next() {
             let packet = OscPacket::Message(m);
-            let bytes = rosc::encoder::encode(&packet)
-                .map_err(|_| "error trying to encode OSC message packet")?;
+            let Ok(bytes) = rosc::encoder::encode(&packet) else {
+                // Retrying this message doesn't make sense, but the others should go out later
+                return Err(OscSendError {
+                    msg: "error trying to encode OSC message packet",
+                    unsent_messages: messages.collect(),
+                });
+            };
             trace!(
                 "Sending message packet with {} bytes: {:#?}",
                 bytes.len(),
                 &packet
             );
-            self.socket
-                .send_to(&bytes, self.dest_address)
-                .map_err(|_| "error trying to send OSC message packet")?;
+            if self.socket.send_packet(&bytes).is_err() {
+                return Err(OscSendError {
+                    msg: "error trying to send OSC message packet",
+                    unsent_messages: packet_into_messages(packet)
+                        .into_iter()
+                        .chain(messages)
+                        .collect(),
+                });
+            }
         }
         Ok(())
     }
 }
 
+struct OscSendError {
+    msg: &'static str,
+    /// Messages which should be sent again later.
+    unsent_messages: Vec<OscMessage>,
+}
+
+fn packet_into_messages(packet: OscPacket) -> Vec<OscMessage> {
+    match packet {
+        OscPacket::Message(m) => vec![m],
+        OscPacket::Bundle(b) => b
+            .content
+            .into_iter()
+            .flat_map(packet_into_messages)
+            .collect(),
+    }
+}
+
+impl OscOutputSocket {
+    /// Returns an error if the socket can't send packets at the moment.
+    fn ensure_ready(&mut self) -> io::Result<()> {
+        match self {
+            OscOutputSocket::Udp { .. } => Ok(()),
+            OscOutputSocket::Tcp(tcp_output) => {
+                tcp_output.ensure_connected()?;
+                Ok(())
+            }
+        }
+    }
+
+    fn send_packet(&mut self, bytes: &[u8]) -> io::Result<()> {
+        match self {
+            OscOutputSocket::Udp {
+                socket,
+                dest_address,
+            } => {
+                socket.send_to(bytes, *dest_address)?;
+                Ok(())
+            }
+            OscOutputSocket::Tcp(tcp_output) => tcp_output.send_packet(bytes),
+        }
+    }
+}
+
+impl OscTcpOutput {
+    fn send_packet(&mut self, bytes: &[u8]) -> io::Result<()> {
+        let stream = self.ensure_connected()?;
+        let frame = self.framing.encode(bytes);
+        let result = stream.write_all(&frame);
+        if result.is_err() {
+            // Connection is probably broken. Reconnect next time.
+            self.stream = None;
+        }
+        result
+    }
+
+    fn ensure_connected(&mut self) -> io::Result<&mut TcpStream> {
+        if self.stream.is_none() {
+            let stream = self.poll_connection()?;
+            stream.set_nodelay(true)?;
+            stream.set_write_timeout(Some(TCP_WRITE_TIMEOUT))?;
+            self.stream = Some(stream);
+        }
+        Ok(self.stream.as_mut().unwrap())
+    }
+
+    /// Returns the stream as soon as the background connection attempt succeeded.
+    fn poll_connection(&mut self) -> io::Result<TcpStream> {
+        if let Some(receiver) = &self.pending_connection {
+            return match receiver.try_recv() {
+                Ok(result) => {
+                    self.pending_connection = None;
+                    result
+                }
+                Err(TryRecvError::Empty) => Err(io::ErrorKind::NotConnected.into()),
+                Err(TryRecvError::Disconnected) => {
+                    self.pending_connection = None;
+                    Err(io::ErrorKind::NotConnected.into())
+                }
+            };
+        }
+        if let Some(t) = self.last_connection_attempt {
+            if t.elapsed() < TCP_RECONNECT_INTERVAL {
+                return Err(io::ErrorKind::NotConnected.into());
+            }
+        }
+        self.last_connection_attempt = Some(Instant::now());
+        let (sender, receiver) = crossbeam_channel::bounded(1);
+        let dest_address = self.dest_address;
+        std::thread::Builder::new()
+            .name("ReaLearn OSC TCP connector".to_owned())
+            .spawn(move || {
+                let result = TcpStream::connect_timeout(&dest_address, TCP_CONNECT_TIMEOUT);
+                let _ = sender.send(result);
+            })?;
+        self.pending_connection = Some(receiver);
+        Err(io::ErrorKind::NotConnected.into())
+    }
+}
+
+/// Messages waiting to be sent to one OSC device.
+#[derive(Debug)]
+enum PendingOscMessages {
+    Queue(VecDeque<OscMessage>),
+    /// Keyed by OSC address. Replacing a message keeps the original position, so that addresses
+    /// which change all the time don't starve the others.
+    Coalescing(IndexMap<String, OscMessage>),
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum PushOutcome {
+    Added,
+    Coalesced,
+    DroppedOldest,
+}
+
+impl PendingOscMessages {
+    fn new(coalesce_by_address: bool) -> Self {
+        if coalesce_by_address {
+            Self::Coalescing(Default::default())
+        } else {
+            Self::Queue(Default::default())
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            PendingOscMessages::Queue(q) => q.len(),
+            PendingOscMessages::Coalescing(m) => m.len(),
+        }
+    }
+
+    fn push(&mut self, msg: OscMessage) -> PushOutcome {
+        let dropped_oldest = if self.len() >= MAX_PENDING_OSC_MESSAGES_PER_DEVICE {
+            match self {
+                PendingOscMessages::Queue(q) => {
+                    q.pop_front();
+                }
+                PendingOscMessages::Coalescing(m) => {
+                    // Only necessary if the message has a new address
+                    if !m.contains_key(&msg.addr) {
+                        m.shift_remove_index(0);
+                    }
+                }
+            }
+            true
+        } else {
+            false
+        };
+        let coalesced = match self {
+            PendingOscMessages::Queue(q) => {
+                q.push_back(msg);
+                false
+            }
+            PendingOscMessages::Coalescing(m) => {
+                if let Some(existing) = m.get_mut(&msg.addr) {
+                    *existing = msg;
+                    true
+                } else {
+                    m.insert(msg.addr.clone(), msg);
+                    false
+                }
+            }
+        };
+        if coalesced {
+            PushOutcome::Coalesced
+        } else if dropped_oldest {
+            PushOutcome::DroppedOldest
+        } else {
+            PushOutcome::Added
+        }
+    }
+
+    fn take(&mut self, count: usize) -> Vec<OscMessage> {
+        match self {
+            PendingOscMessages::Queue(q) => q.drain(..count.min(q.len())).collect(),
+            PendingOscMessages::Coalescing(m) => {
+                m.drain(..count.min(m.len())).map(|(_, msg)| msg).collect()
+            }
+        }
+    }
+
+    /// Puts taken messages back to the front, in their original order.
+    ///
+    /// When coalescing, a message which has been pushed for the same address in the meantime
+    /// wins (but takes the position of the put-back message).
+    fn put_back(&mut self, messages: Vec<OscMessage>) {
+        match self {
+            PendingOscMessages::Queue(q) => {
+                for msg in messages.into_iter().rev() {
+                    q.push_front(msg);
+                }
+            }
+            PendingOscMessages::Coalescing(m) => {
+                let mut restored: IndexMap<String, OscMessage> = messages
+                    .into_iter()
+                    .map(|msg| (msg.addr.clone(), msg))
+                    .collect();
+                restored.extend(mem::take(m));
+                *m = restored;
+            }
+        }
+    }
+}
+
+/// A token bucket which allows short bursts of up to a tenth of the maximum rate.
+#[derive(Debug)]
+struct OscRateLimiter {
+    max_messages_per_second: NonZeroU32,
+    available: f64,
+    last_refill: Option<Instant>,
+}
+
+impl OscRateLimiter {
+    fn new(max_messages_per_second: NonZeroU32) -> Self {
+        let mut limiter = Self {
+            max_messages_per_second,
+            available: 0.0,
+            last_refill: None,
+        };
+        limiter.available = limiter.capacity();
+        limiter
+    }
+
+    fn capacity(&self) -> f64 {
+        (self.max_messages_per_second.get() as f64 / 10.0).max(1.0)
+    }
+
+    /// Returns how many of the wanted messages may be sent now.
+    fn acquire(&mut self, wanted_count: usize, now: Instant) -> usize {
+        if let Some(last_refill) = self.last_refill {
+            let elapsed = now.saturating_duration_since(last_refill).as_secs_f64();
+            let refill = elapsed * self.max_messages_per_second.get() as f64;
+            self.available = (self.available + refill).min(self.capacity());
+        }
+        self.last_refill = Some(now);
+        let granted = (self.available.floor() as usize).min(wanted_count);
+        self.available -= granted as f64;
+        granted
+    }
+}
+
+fn record_osc_feedback_occurrence(metric_id: &'static str, dev_id: OscDeviceId) {
+    if !metrics_are_enabled() {
+        return;
+    }
+    metrics::counter!(metric_id, "device" => dev_id.to_string()).increment(1);
+}
+
 /// An OSC device ID.
 ///
 /// This uniquely identifies an OSC device according to ReaLearn's device configuration.
@@ -302,3 +810,88 @@ pub struct OscScanResult {
     pub message: OscMessage,
     pub dev_id: Option<OscDeviceId>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(addr: &str, value: f32) -> OscMessage {
+        OscMessage {
+            addr: addr.to_string(),
+            args: vec![rosc::OscType::Float(value)],
+        }
+    }
+
+    #[test]
+    fn coalesce_by_address() {
+        // Given
+        let mut pending = PendingOscMessages::new(true);
+        // When
+        let outcomes = [
+            pending.push(msg("/a", 1.0)),
+            pending.push(msg("/b", 1.0)),
+            pending.push(msg("/a", 2.0)),
+        ];
+        // Then
+        assert_eq!(
+            outcomes,
+            [
+                PushOutcome::Added,
+                PushOutcome::Added,
+                PushOutcome::Coalesced
+            ]
+        );
+        assert_eq!(pending.take(10), vec![msg("/a", 2.0), msg("/b", 1.0)]);
+        assert_eq!(pending.len(), 0);
+    }
+
+    #[test]
+    fn put_back_unsent_messages() {
+        // Given
+        let mut queue = PendingOscMessages::new(false);
+        let mut coalescing = PendingOscMessages::new(true);
+        for pending in [&mut queue, &mut coalescing] {
+            pending.push(msg("/a", 1.0));
+            pending.push(msg("/b", 1.0));
+            pending.push(msg("/c", 1.0));
+        }
+        // When
+        let taken_from_queue = queue.take(2);
+        queue.push(msg("/a", 2.0));
+        queue.put_back(taken_from_queue);
+        let taken_from_coalescing = coalescing.take(2);
+        coalescing.push(msg("/a", 2.0));
+        coalescing.put_back(taken_from_coalescing);
+        // Then
+        assert_eq!(
+            queue.take(10),
+            vec![
+                msg("/a", 1.0),
+                msg("/b", 1.0),
+                msg("/c", 1.0),
+                msg("/a", 2.0)
+            ]
+        );
+        assert_eq!(
+            coalescing.take(10),
+            vec![msg("/a", 2.0), msg("/b", 1.0), msg("/c", 1.0)]
+        );
+    }
+
+    #[test]
+    fn rate_limit() {
+        // Given
+        let mut limiter = OscRateLimiter::new(NonZeroU32::new(100).unwrap());
+        let start = Instant::now();
+        // When
+        let initial_burst = limiter.acquire(50, start);
+        let immediately_after = limiter.acquire(50, start);
+        let after_50_ms = limiter.acquire(50, start + Duration::from_millis(50));
+        let after_1_s = limiter.acquire(50, start + Duration::from_secs(1));
+        // Then
+        assert_eq!(initial_burst, 10);
+        assert_eq!(immediately_after, 0);
+        assert_eq!(after_50_ms, 5);
+        assert_eq!(after_1_s, 10);
+    }
+}
@@ -0,0 +1,258 @@
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+
+const SLIP_END: u8 = 0xC0;
+const SLIP_ESC: u8 = 0xDB;
+const SLIP_ESC_END: u8 = 0xDC;
+const SLIP_ESC_ESC: u8 = 0xDD;
+const LENGTH_PREFIX_SIZE: usize = 4;
+/// Protects us from allocating insane amounts of memory if a peer sends garbage.
+const MAX_STREAM_PACKET_SIZE: usize = 1_000_000;
+
+/// The transport protocol used for exchanging OSC packets with a device.
+#[derive(
+    Copy, Clone, Eq, PartialEq, Hash, Debug, Default, Display, EnumIter, Serialize, Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum OscTransport {
+    #[default]
+    #[display(fmt = "UDP")]
+    Udp,
+    /// OSC 1.1 style: Packets are delimited by double-ended SLIP (RFC 1055) framing.
+    #[display(fmt = "TCP with SLIP framing (OSC 1.1)")]
+    TcpSlip,
+    /// OSC 1.0 style: Each packet is preceded by its size as big-endian 32-bit integer.
+    #[display(fmt = "TCP with length-prefix framing (OSC 1.0)")]
+    TcpLengthPrefix,
+}
+
+impl OscTransport {
+    /// Returns the framing to be used if this is a stream-based transport.
+    pub fn stream_framing(self) -> Option<OscStreamFraming> {
+        match self {
+            OscTransport::Udp => None,
+            OscTransport::TcpSlip => Some(OscStreamFraming::Slip),
+            OscTransport::TcpLengthPrefix => Some(OscStreamFraming::LengthPrefix),
+        }
+    }
+}
+
+/// Describes how OSC packets are delimited within a byte stream.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum OscStreamFraming {
+    Slip,
+    LengthPrefix,
+}
+
+impl OscStreamFraming {
+    /// Wraps the given encoded OSC packet into a frame.
+    pub fn encode(self, packet: &[u8]) -> Vec<u8> {
+        match self {
+            OscStreamFraming::Slip => {
+                let mut frame = Vec::with_capacity(packet.len() + 2);
+                frame.push(SLIP_END);
+                for &b in packet {
+                    match b {
+                        SLIP_END => frame.extend_from_slice(&[SLIP_ESC, SLIP_ESC_END]),
+                        SLIP_ESC => frame.extend_from_slice(&[SLIP_ESC, SLIP_ESC_ESC]),
+                        _ => frame.push(b),
+                    }
+                }
+                frame.push(SLIP_END);
+                frame
+            }
+            OscStreamFraming::LengthPrefix => {
+                let mut frame = Vec::with_capacity(packet.len() + LENGTH_PREFIX_SIZE);
+                frame.extend_from_slice(&(packet.len() as u32).to_be_bytes());
+                frame.extend_from_slice(packet);
+                frame
+            }
+        }
+    }
+}
+
+/// Incrementally extracts OSC packets from a byte stream.
+///
+/// Bytes can be fed in arbitrary chunks, frames don't need to be aligned with reads.
+#[derive(Debug)]
+pub struct OscStreamDecoder {
+    framing: OscStreamFraming,
+    buffer: Vec<u8>,
+}
+
+#[derive(Debug, Display)]
+pub enum OscStreamDecodeError {
+    #[display(fmt = "invalid SLIP escape sequence")]
+    InvalidEscapeSequence,
+    #[display(fmt = "OSC packet exceeds maximum size")]
+    PacketTooLarge,
+}
+
+impl std::error::Error for OscStreamDecodeError {}
+
+impl OscStreamDecoder {
+    pub fn new(framing: OscStreamFraming) -> Self {
+        Self {
+            framing,
+            buffer: Vec::new(),
+        }
+    }
+
+    pub fn feed(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Returns the next complete packet (without framing) if one is available.
+    ///
+    /// After an error, the buffered data is discarded so that decoding can resynchronize.
+    pub fn next_packet(&mut self) -> Result<Option<Vec<u8>>, OscStreamDecodeError> {
+        let result = match self.framing {
+            OscStreamFraming::Slip => self.next_slip_packet(),
+            OscStreamFraming::LengthPrefix => self.next_length_prefixed_packet(),
+        };
+        if result.is_err() {
+            self.buffer.clear();
+        }
+        result
+    }
+
+    fn next_slip_packet(&mut self) -> Result<Option<Vec<u8>>, OscStreamDecodeError> {
+        loop {
+            let Some(end_index) = self.buffer.iter().position(|b| *b == SLIP_END) else {
+                if self.buffer.len() > MAX_STREAM_PACKET_SIZE {
+                    return Err(OscStreamDecodeError::PacketTooLarge);
+                }
+                return Ok(None);
+            };
+            let frame: Vec<u8> = self.buffer.drain(..=end_index).collect();
+            let frame = &frame[..frame.len() - 1];
+            // Double-ended SLIP produces empty frames between two END bytes. Skip them.
+            if frame.is_empty() {
+                continue;
+            }
+            return unescape_slip(frame).map(Some);
+        }
+    }
+
+    fn next_length_prefixed_packet(&mut self) -> Result<Option<Vec<u8>>, OscStreamDecodeError> {
+        if self.buffer.len() < LENGTH_PREFIX_SIZE {
+            return Ok(None);
+        }
+        let mut size_bytes = [0; LENGTH_PREFIX_SIZE];
+        size_bytes.copy_from_slice(&self.buffer[..LENGTH_PREFIX_SIZE]);
+        let size = u32::from_be_bytes(size_bytes) as usize;
+        if size > MAX_STREAM_PACKET_SIZE {
+            return Err(OscStreamDecodeError::PacketTooLarge);
+        }
+        if self.buffer.len() < LENGTH_PREFIX_SIZE + size {
+            return Ok(None);
+        }
+        let packet = self.buffer[LENGTH_PREFIX_SIZE..LENGTH_PREFIX_SIZE + size].to_vec();
+        self.buffer.drain(..LENGTH_PREFIX_SIZE + size);
+        Ok(Some(packet))
+    }
+}
+
+fn unescape_slip(frame: &[u8]) -> Result<Vec<u8>, OscStreamDecodeError> {
+    let mut packet = Vec::with_capacity(frame.len());
+    let mut bytes = frame.iter();
+    while let Some(&b) = bytes.next() {
+        if b == SLIP_ESC {
+            match bytes.next() {
+                Some(&SLIP_ESC_END) => packet.push(SLIP_END),
+                Some(&SLIP_ESC_ESC) => packet.push(SLIP_ESC),
+                _ => return Err(OscStreamDecodeError::InvalidEscapeSequence),
+            }
+        } else {
+            packet.push(b);
+        }
+    }
+    Ok(packet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slip_roundtrip() {
+        // Given
+        let packet = [1, SLIP_END, 2, SLIP_ESC, 3];
+        let mut decoder = OscStreamDecoder::new(OscStreamFraming::Slip);
+        // When
+        let frame = OscStreamFraming::Slip.encode(&packet);
+        decoder.feed(&frame[..3]);
+        let first_attempt = decoder.next_packet().unwrap();
+        decoder.feed(&frame[3..]);
+        // Then
+        assert_eq!(
+            frame,
+            vec![
+                SLIP_END,
+                1,
+                SLIP_ESC,
+                SLIP_ESC_END,
+                2,
+                SLIP_ESC,
+                SLIP_ESC_ESC,
+                3,
+                SLIP_END
+            ]
+        );
+        assert_eq!(first_attempt, None);
+        assert_eq!(decoder.next_packet().unwrap(), Some(packet.to_vec()));
+        assert_eq!(decoder.next_packet().unwrap(), None);
+    }
+
+    #[test]
+    fn slip_multiple_packets_in_one_chunk() {
+        // Given
+        let mut decoder = OscStreamDecoder::new(OscStreamFraming::Slip);
+        // When
+        decoder.feed(&OscStreamFraming::Slip.encode(&[1, 2]));
+        decoder.feed(&OscStreamFraming::Slip.encode(&[3]));
+        // Then
+        assert_eq!(decoder.next_packet().unwrap(), Some(vec![1, 2]));
+        assert_eq!(decoder.next_packet().unwrap(), Some(vec![3]));
+        assert_eq!(decoder.next_packet().unwrap(), None);
+    }
+
+    #[test]
+    fn slip_invalid_escape() {
+        // Given
+        let mut decoder = OscStreamDecoder::new(OscStreamFraming::Slip);
+        // When
+        decoder.feed(&[1, SLIP_ESC, 5, SLIP_END]);
+        // Then
+        assert!(decoder.next_packet().is_err());
+        assert_eq!(decoder.next_packet().unwrap(), None);
+    }
+
+    #[test]
+    fn length_prefix_roundtrip() {
+        // Given
+        let packet = [5, 6, 7, 8];
+        let mut decoder = OscStreamDecoder::new(OscStreamFraming::LengthPrefix);
+        // When
+        let frame = OscStreamFraming::LengthPrefix.encode(&packet);
+        decoder.feed(&frame[..5]);
+        let first_attempt = decoder.next_packet().unwrap();
+        decoder.feed(&frame[5..]);
+        // Then
+        assert_eq!(frame, vec![0, 0, 0, 4, 5, 6, 7, 8]);
+        assert_eq!(first_attempt, None);
+        assert_eq!(decoder.next_packet().unwrap(), Some(packet.to_vec()));
+        assert_eq!(decoder.next_packet().unwrap(), None);
+    }
+
+    #[test]
+    fn length_prefix_too_large() {
+        // Given
+        let mut decoder = OscStreamDecoder::new(OscStreamFraming::LengthPrefix);
+        // When
+        decoder.feed(&[0xFF, 0xFF, 0xFF, 0xFF]);
+        // Then
+        assert!(decoder.next_packet().is_err());
+    }
+}
@@ -1,13 +1,13 @@
 use crate::domain::{
-    classify_midi_message, match_partially, BasicSettings, CompartmentKind, CompoundMappingSource,
-    ControlEvent, ControlEventTimestamp, ControlLogEntry, ControlLogEntryKind, ControlMainTask,
-    ControlMode, ControlOptions, FeedbackSendBehavior, LifecycleMidiMessage, LifecyclePhase,
-    MappingCore, MappingId, MatchOutcome, MidiClockCalculator, MidiEvent,
-    MidiMessageClassification, MidiScanResult, MidiScanner, MidiTransformationContainer,
-    NormalRealTimeToMainThreadTask, OrderedMappingMap, OwnedIncomingMidiMessage,
-    PersistentMappingProcessingState, QualifiedMappingId, RealTimeCompoundMappingTarget,
-    RealTimeControlContext, RealTimeMapping, RealTimeReaperTarget, SampleOffset, UnitId,
-    VirtualSourceValue, WeakRealTimeInstance,
+    classify_midi_message, match_partially, BasicSettings, ChordMidiMemberLookup, CompartmentKind,
+    CompoundMappingSource, ControlEvent, ControlEventTimestamp, ControlLogEntry,
+    ControlLogEntryKind, ControlMainTask, ControlMode, ControlOptions, FeedbackSendBehavior,
+    LifecycleMidiMessage, LifecyclePhase, MappingCore, MappingId, MatchOutcome,
+    MidiClockCalculator, MidiEvent, MidiMessageClassification, MidiScanResult, MidiScanner,
+    MidiTransformationContainer, NormalRealTimeToMainThreadTask, OrderedMappingMap,
+    OwnedIncomingMidiMessage, PersistentMappingProcessingState, QualifiedMappingId,
+    RealTimeCompoundMappingTarget, RealTimeControlContext, RealTimeMapping, RealTimeReaperTarget,
+    SampleOffset, UnitId, VirtualSourceValue, WeakRealTimeInstance,
 };
 use helgoboss_learn::{ControlValue, MidiSourceValue, ModeControlResult, RawMidiEvent};
 use helgoboss_midi::{
@@ -26,13 +26,14 @@ use helgobox_allocator::permit_alloc;
 use std::convert::TryInto;
 use std::ptr::null_mut;
 use std::time::Duration;
-use tracing::{debug, trace};
+use tracing::{debug, trace, warn};
 use vst::api::{EventType, Events, SysExEvent};
 use vst::host::Host;
 use vst::plugin::HostCallback;
 
 const NORMAL_BULK_SIZE: usize = 100;
 const FEEDBACK_BULK_SIZE: usize = 100;
+const DECIDED_CHORD_MEMBER_CAPACITY: usize = 100;
 
 #[derive(Debug)]
 pub struct RealTimeProcessor {
@@ -59,6 +60,10 @@ pub struct RealTimeProcessor {
     midi_clock_calculator: MidiClockCalculator,
     sample_rate: Hz,
     instance: WeakRealTimeInstance,
+    // For chord detection (which happens in the main processor)
+    chord_midi_member_lookup: ChordMidiMemberLookup,
+    decided_fx_input_chord_members: Vec<DecidedChordMember>,
+    decided_device_chord_members: Vec<DecidedChordMember>,
 }
 
 impl RealTimeProcessor {
@@ -94,6 +99,9 @@ impl RealTimeProcessor {
             control_is_globally_enabled: false,
             feedback_is_globally_enabled: false,
             sample_rate: Hz::new_panic(1.0),
+            chord_midi_member_lookup: Default::default(),
+            decided_fx_input_chord_members: Vec::with_capacity(DECIDED_CHORD_MEMBER_CAPACITY),
+            decided_device_chord_members: Vec::with_capacity(DECIDED_CHORD_MEMBER_CAPACITY),
         }
     }
 
@@ -118,6 +126,9 @@ impl RealTimeProcessor {
                 self.process_unmatched(event.payload(), Caller::Vst(host));
                 return;
             }
+            if self.hold_back_chord_member(event, MidiInputOrigin::FxInput) {
+                return;
+            }
             self.process_incoming_midi(event, Caller::Vst(host), None);
         } else {
             // #33, #290 If MIDI input device is not set to <FX input>, we want to pass through all
@@ -127,6 +138,7 @@ impl RealTimeProcessor {
     }
 
     pub fn run_from_vst(&mut self, host: &HostCallback) {
+        self.process_decided_chord_members(Caller::Vst(host), None);
         self.process_feedback_tasks(Caller::Vst(host));
     }
 
@@ -135,8 +147,10 @@ impl RealTimeProcessor {
         &mut self,
         might_be_rebirth: bool,
         timestamp: ControlEventTimestamp,
+        transformation_container: &mut MidiTransformationContainer,
     ) {
         self.run_from_audio_hook_essential(might_be_rebirth);
+        self.process_decided_chord_members(Caller::AudioHook, Some(transformation_container));
         self.run_from_audio_hook_control_and_learn(timestamp);
     }
 
@@ -157,6 +171,13 @@ impl RealTimeProcessor {
         event: ControlEvent<MidiEvent<IncomingMidiMessage>>,
         transformation_container: &mut MidiTransformationContainer,
     ) -> bool {
+        if let MidiControlInput::Device(dev) = self.settings.midi_control_input() {
+            if self.hold_back_chord_member(event, MidiInputOrigin::Device(dev)) {
+                // Filter it out for now. Once the chord detector made a decision, we
+                // either let it through or not, just as if it had been processed right away.
+                return true;
+            }
+        }
         let match_outcome =
             self.process_incoming_midi(event, Caller::AudioHook, Some(transformation_container));
         let let_through = (match_outcome.matched_or_consumed()
@@ -199,12 +220,22 @@ impl RealTimeProcessor {
         }
         // Process occasional tasks sent from other thread (probably main thread)
         let normal_task_count = self.normal_task_receiver.len();
+        let mut mappings_changed = false;
         for task in self.normal_task_receiver.try_iter().take(NORMAL_BULK_SIZE) {
             use NormalRealTimeTask::*;
             match task {
                 UpdateControlIsGloballyEnabled(is_enabled) => {
                     self.control_is_globally_enabled = is_enabled;
                 }
+                ProcessChordMemberMidi {
+                    event,
+                    is_part_of_chord,
+                } => {
+                    self.enqueue_decided_chord_member(DecidedChordMember {
+                        event,
+                        is_part_of_chord,
+                    });
+                }
                 UpdateFeedbackIsGloballyEnabled(is_enabled) => {
                     // Handle lifecycle MIDI
                     if self.settings.midi_destination().is_some()
@@ -226,6 +257,7 @@ impl RealTimeProcessor {
                             LifecyclePhase::Deactivation,
                         );
                     }
+                    mappings_changed = true;
                     // Clear existing mappings
                     self.mappings[compartment].clear();
                     // Set new mappings
@@ -252,6 +284,7 @@ impl RealTimeProcessor {
                     }
                     // Update
                     self.mappings[compartment].insert(m.id(), *m);
+                    mappings_changed = true;
                 }
                 UpdatePersistentMappingProcessingState { id, state } => {
                     permit_alloc(|| {
@@ -260,6 +293,7 @@ impl RealTimeProcessor {
                             id.id, id.compartment
                         );
                     });
+                    mappings_changed = true;
                     // Update
                     let (was_on_before, is_on_now) =
                         if let Some(m) = self.mappings[id.compartment].get_mut(&id.id) {
@@ -277,6 +311,7 @@ impl RealTimeProcessor {
                     }
                 }
                 UpdateTargetsPartially(compartment, mut target_updates) => {
+                    mappings_changed = true;
                     // Apply updates
                     for update in target_updates.iter_mut() {
                         if let Some(m) = self.mappings[compartment].get_mut(&update.id) {
@@ -358,6 +393,7 @@ impl RealTimeProcessor {
                     permit_alloc(|| {
                         debug!("Updating mapping activations...");
                     });
+                    mappings_changed = true;
                     // Apply updates
                     for update in mapping_updates.iter() {
                         if let Some(m) = self.mappings[compartment].get_mut(&update.id) {
@@ -382,6 +418,181 @@ impl RealTimeProcessor {
                 }
             }
         }
+        if mappings_changed {
+            self.rebuild_chord_midi_member_lookup();
+        }
+    }
+
+    fn rebuild_chord_midi_member_lookup(&mut self) {
+        let mappings = &self.mappings;
+        let lookup = &mut self.chord_midi_member_lookup;
+        // Happens only when mappings change, not on each incoming message
+        permit_alloc(|| {
+            let chords = CompartmentKind::enum_iter()
+                .flat_map(|compartment| mappings[compartment].values())
+                .filter(|m| m.control_is_effectively_on())
+                .filter_map(|m| match m.source() {
+                    CompoundMappingSource::Chord(s) => Some(s),
+                    _ => None,
+                });
+            lookup.rebuild(chords);
+        });
+    }
+
+    /// Hands the given message over to the chord detector in the main processor if it could be
+    /// part of a chord.
+    ///
+    /// Returns `true` if the message was held back. In that case, it must not be processed and
+    /// neither be let through until the chord detector has made a decision (see
+    /// [`Self::process_decided_chord_members`]).
+    fn hold_back_chord_member(
+        &self,
+        event: ControlEvent<MidiEvent<IncomingMidiMessage>>,
+        origin: MidiInputOrigin,
+    ) -> bool {
+        if self.control_mode != ControlMode::Controlling || !self.control_is_globally_enabled {
+            return false;
+        }
+        let midi_event = event.payload();
+        let IncomingMidiMessage::Short(short_msg) = midi_event.payload() else {
+            return false;
+        };
+        let source_value = MidiSourceValue::Plain(short_msg);
+        if !self.chord_midi_member_lookup.contains(&source_value)
+            || self.is_consumed_by_at_least_one_source(midi_event.payload())
+        {
+            return false;
+        }
+        let chord_member_event = ChordMemberMidiEvent {
+            event: MidiEvent::new(midi_event.offset(), short_msg),
+            origin,
+        };
+        self.control_main_task_sender.send_complaining(
+            ControlMainTask::ControlChordMemberFromRealTime {
+                event: event.with_payload(chord_member_event),
+            },
+        );
+        if self.settings.real_input_reporting_enabled() {
+            self.log_real_control_input(event.with_payload(source_value), false, true);
+        }
+        true
+    }
+
+    fn enqueue_decided_chord_member(&mut self, member: DecidedChordMember) {
+        let queue = match member.event.payload().origin {
+            MidiInputOrigin::FxInput => &mut self.decided_fx_input_chord_members,
+            MidiInputOrigin::Device(_) => &mut self.decided_device_chord_members,
+        };
+        if queue.len() == queue.capacity() {
+            permit_alloc(|| {
+                warn!(
+                    msg =
+                        "Too many chord member messages waiting to be processed. Dropping message.",
+                    ?member
+                );
+            });
+            return;
+        }
+        queue.push(member);
+    }
+
+    /// Processes MIDI messages which were held back because they could have been part of a chord,
+    /// now that the chord detector has decided about them.
+    ///
+    /// Messages are processed with the same caller and sample offset as they came in. Messages
+    /// from FX input can only be processed from the VST plug-in, messages from a MIDI input device
+    /// only from the audio hook.
+    fn process_decided_chord_members(
+        &mut self,
+        caller: Caller,
+        mut transformation_container: Option<&mut MidiTransformationContainer>,
+    ) {
+        let queue = if caller.is_vst() {
+            &mut self.decided_fx_input_chord_members
+        } else {
+            &mut self.decided_device_chord_members
+        };
+        if queue.is_empty() {
+            return;
+        }
+        // Take the queue temporarily (without allocating) in order to be able to call methods on
+        // self while processing.
+        let mut members = std::mem::take(queue);
+        for member in members.drain(..) {
+            self.process_decided_chord_member(member, caller, &mut transformation_container);
+        }
+        if caller.is_vst() {
+            self.decided_fx_input_chord_members = members;
+        } else {
+            self.decided_device_chord_members = members;
+        }
+    }
+
+    fn process_decided_chord_member(
+        &mut self,
+        member: DecidedChordMember,
+        caller: Caller,
+        transformation_container: &mut Option<&mut MidiTransformationContainer>,
+    ) {
+        let ChordMemberMidiEvent {
+            event: midi_event,
+            origin,
+        } = member.event.payload();
+        let match_outcome = if member.is_part_of_chord {
+            // The chord itself has been processed already by the main processor
+            MatchOutcome::Matched
+        } else if self.control_is_globally_enabled {
+            let source_value = MidiSourceValue::Plain(midi_event.payload());
+            let match_outcome = self.control_midi(
+                member
+                    .event
+                    .with_payload(MidiEvent::new(midi_event.offset(), &source_value)),
+                caller,
+                transformation_container,
+            );
+            if self.settings.real_input_reporting_enabled() {
+                self.log_real_control_input_internal(
+                    member.event.with_payload(source_value),
+                    match_outcome,
+                );
+            }
+            match_outcome
+        } else {
+            MatchOutcome::Unmatched
+        };
+        let let_through = if match_outcome.matched_or_consumed() {
+            self.settings.let_matched_events_through
+        } else {
+            self.settings.let_unmatched_events_through
+        };
+        if !let_through {
+            return;
+        }
+        match origin {
+            MidiInputOrigin::FxInput => {
+                if self.settings.midi_control_input() == MidiControlInput::FxInput {
+                    self.send_short_midi_to_fx_output(midi_event, caller);
+                }
+            }
+            MidiInputOrigin::Device(dev) => {
+                if self.settings.midi_control_input() != MidiControlInput::Device(dev) {
+                    return;
+                }
+                let Some(container) = transformation_container else {
+                    return;
+                };
+                let (status_byte, data_byte_1, data_byte_2) = midi_event.payload().to_bytes();
+                let frame_offset = midi_event
+                    .offset()
+                    .to_midi_input_frame_offset(container.current_device_sample_rate());
+                if let Ok(raw_event) = RawMidiEvent::try_from_slice(
+                    frame_offset,
+                    &[status_byte, data_byte_1.get(), data_byte_2.get()],
+                ) {
+                    container.push(Some(dev), raw_event);
+                }
+            }
+        }
     }
 
     fn send_lifecycle_midi_diff(&self, m: &RealTimeMapping, was_on_before: bool, is_on_now: bool) {
@@ -700,7 +911,7 @@ impl RealTimeProcessor {
             caller,
             transformation_container,
         );
-        if self.settings.real_input_logging_enabled {
+        if self.settings.real_input_reporting_enabled() {
             self.log_real_control_input_internal(event.with_payload(source_value), match_outcome);
         }
         if self.settings.midi_control_input() == MidiControlInput::FxInput
@@ -802,7 +1013,7 @@ impl RealTimeProcessor {
             caller,
             transformation_container,
         );
-        if self.settings.real_input_logging_enabled {
+        if self.settings.real_input_reporting_enabled() {
             self.log_real_control_input_internal(event.with_payload(source_value), match_outcome);
         }
         if self.settings.midi_control_input() == MidiControlInput::FxInput
@@ -831,7 +1042,7 @@ impl RealTimeProcessor {
         let midi_event = event.payload();
         let source_value = midi_event.payload().to_source_value();
         if self.is_consumed_by_at_least_one_source(midi_event.payload()) {
-            if self.settings.real_input_logging_enabled {
+            if self.settings.real_input_reporting_enabled() {
                 self.log_real_control_input(event.with_payload(source_value), true, false);
             }
             // Some short MIDI messages are just parts of bigger composite MIDI messages,
@@ -845,7 +1056,7 @@ impl RealTimeProcessor {
             caller,
             transformation_container,
         );
-        if self.settings.real_input_logging_enabled {
+        if self.settings.real_input_reporting_enabled() {
             self.log_real_control_input_internal(event.with_payload(source_value), match_outcome);
         }
         // At this point, we shouldn't have "consumed" anymore because for MIDI sources, no
@@ -1154,6 +1365,28 @@ fn build_short_midi_vst_event(event: MidiEvent<RawShortMessage>) -> vst::api::Mi
     }
 }
 
+/// A short MIDI message which could be part of a chord, together with the place where it came in.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct ChordMemberMidiEvent {
+    pub event: MidiEvent<RawShortMessage>,
+    pub origin: MidiInputOrigin,
+}
+
+/// Where an incoming MIDI message came from.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum MidiInputOrigin {
+    /// ReaLearn FX input.
+    FxInput,
+    /// A MIDI input device (processed by the audio hook).
+    Device(MidiInputDeviceId),
+}
+
+#[derive(Copy, Clone, Debug)]
+struct DecidedChordMember {
+    event: ControlEvent<ChordMemberMidiEvent>,
+    is_part_of_chord: bool,
+}
+
 #[derive(Copy, Clone)]
 pub enum Caller<'a> {
     Vst(&'a HostCallback),
@@ -1194,6 +1427,14 @@ pub enum NormalRealTimeTask {
     ReturnToControlMode,
     UpdateControlIsGloballyEnabled(bool),
     UpdateFeedbackIsGloballyEnabled(bool),
+    /// Returns a MIDI message which was held back because it could have been part of a chord,
+    /// after the chord detector in the main processor has decided about it.
+    ProcessChordMemberMidi {
+        event: ControlEvent<ChordMemberMidiEvent>,
+        /// If `true`, the message is part of a detected chord and must not control mappings on
+        /// its own anymore.
+        is_part_of_chord: bool,
+    },
 }
 
 #[derive(Copy, Clone, Debug)]
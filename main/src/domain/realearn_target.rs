@@ -11,22 +11,27 @@ use crate::domain::{
     ProcessorContext, QualifiedMappingId, RealTimeReaperTarget, RealearnModeContext,
     RealearnSourceContext, ReaperTarget, SharedInstance, SharedUnit, StreamDeckDeviceId, Tag,
     TagScope, TargetCharacter, TrackExclusivity, UnitEvent, UnitId, WeakRealTimeInstance,
-    ACTION_TARGET, ALL_TRACK_FX_ENABLE_TARGET, ANY_ON_TARGET, AUTOMATION_MODE_OVERRIDE_TARGET,
-    BROWSE_FXS_TARGET, BROWSE_GROUP_MAPPINGS_TARGET, BROWSE_POT_FILTER_ITEMS_TARGET,
-    BROWSE_POT_PRESETS_TARGET, COMPARTMENT_PARAMETER_VALUE_TARGET, DUMMY_TARGET,
-    ENABLE_INSTANCES_TARGET, ENABLE_MAPPINGS_TARGET, ENABLE_UNITS_TARGET, FX_ENABLE_TARGET,
+    ACTION_TARGET, ADD_BOOKMARK_TARGET, ALL_TRACK_FX_ENABLE_TARGET, ANY_ON_TARGET,
+    AUTOMATION_MODE_OVERRIDE_TARGET, BROWSE_ENVELOPE_POINTS_TARGET, BROWSE_FXS_TARGET,
+    BROWSE_GROUP_MAPPINGS_TARGET, BROWSE_POT_FILTER_ITEMS_TARGET, BROWSE_POT_PRESETS_TARGET,
+    BROWSE_TAKES_TARGET, COMPARTMENT_PARAMETER_VALUE_TARGET, DELETE_BOOKMARK_TARGET, DUMMY_TARGET,
+    ENABLE_INSTANCES_TARGET, ENABLE_MAPPINGS_TARGET, ENABLE_UNITS_TARGET,
+    ENVELOPE_ARM_STATE_TARGET, ENVELOPE_VALUE_TARGET, FX_ENABLE_TARGET, FX_NAME_TARGET,
     FX_ONLINE_TARGET, FX_OPEN_TARGET, FX_PARAMETER_TARGET, FX_PARAMETER_TOUCH_STATE_TARGET,
-    FX_PRESET_TARGET, FX_TOOL_TARGET, GO_TO_BOOKMARK_TARGET, LAST_TOUCHED_TARGET,
-    LEARN_MAPPING_TARGET, LOAD_FX_SNAPSHOT_TARGET, LOAD_MAPPING_SNAPSHOT_TARGET,
-    LOAD_POT_PRESET_TARGET, MIDI_SEND_TARGET, MOUSE_TARGET, OSC_SEND_TARGET, PLAYRATE_TARGET,
-    PREVIEW_POT_PRESET_TARGET, ROUTE_AUTOMATION_MODE_TARGET, ROUTE_MONO_TARGET, ROUTE_MUTE_TARGET,
-    ROUTE_PAN_TARGET, ROUTE_PHASE_TARGET, ROUTE_TOUCH_STATE_TARGET, ROUTE_VOLUME_TARGET,
-    SAVE_MAPPING_SNAPSHOT_TARGET, SEEK_TARGET, SELECTED_TRACK_TARGET,
-    STREAM_DECK_BRIGHTNESS_TARGET, TEMPO_TARGET, TRACK_ARM_TARGET, TRACK_AUTOMATION_MODE_TARGET,
-    TRACK_MONITORING_MODE_TARGET, TRACK_MUTE_TARGET, TRACK_PAN_TARGET, TRACK_PARENT_SEND_TARGET,
-    TRACK_PEAK_TARGET, TRACK_PHASE_TARGET, TRACK_SELECTION_TARGET, TRACK_SHOW_TARGET,
-    TRACK_SOLO_TARGET, TRACK_TOOL_TARGET, TRACK_TOUCH_STATE_TARGET, TRACK_VOLUME_TARGET,
-    TRACK_WIDTH_TARGET, TRANSPORT_TARGET,
+    FX_PRESET_TARGET, FX_TOOL_TARGET, GO_TO_BOOKMARK_TARGET, ITEM_PARAMETER_VALUE_TARGET,
+    LAST_TOUCHED_TARGET, LEARN_MAPPING_TARGET, LOAD_FX_SNAPSHOT_TARGET,
+    LOAD_MAPPING_SNAPSHOT_TARGET, LOAD_POT_PRESET_TARGET, MIDI_SEND_TARGET,
+    MORPH_MAPPING_SNAPSHOTS_TARGET, MOUSE_TARGET, OSC_SEND_TARGET, PLAYRATE_TARGET,
+    PREVIEW_POT_PRESET_TARGET, RENAME_BOOKMARK_TARGET, ROUTE_AUTOMATION_MODE_TARGET,
+    ROUTE_MONO_TARGET, ROUTE_MUTE_TARGET, ROUTE_PAN_TARGET, ROUTE_PHASE_TARGET,
+    ROUTE_TOUCH_STATE_TARGET, ROUTE_VOLUME_TARGET, SAVE_MAPPING_SNAPSHOT_TARGET, SEEK_TARGET,
+    SELECTED_TRACK_TARGET, STREAM_DECK_BRIGHTNESS_TARGET, TAKE_PARAMETER_VALUE_TARGET,
+    TEMPO_TARGET, TRACK_ARM_TARGET, TRACK_AUTOMATION_MODE_TARGET, TRACK_COLOR_TARGET,
+    TRACK_INPUT_FX_ENABLE_TARGET, TRACK_MONITORING_MODE_TARGET, TRACK_MUTE_TARGET,
+    TRACK_NAME_TARGET, TRACK_PAN_TARGET, TRACK_PARENT_SEND_TARGET, TRACK_PEAK_TARGET,
+    TRACK_PHASE_TARGET, TRACK_RECORD_INPUT_TARGET, TRACK_RECORD_MODE_TARGET,
+    TRACK_SELECTION_TARGET, TRACK_SHOW_TARGET, TRACK_SOLO_TARGET, TRACK_TOOL_TARGET,
+    TRACK_TOUCH_STATE_TARGET, TRACK_VOLUME_TARGET, TRACK_WIDTH_TARGET, TRANSPORT_TARGET,
 };
 use base::hash_util::NonCryptoHashSet;
 use base::{SenderToNormalThread, SenderToRealTimeThread};
@@ -285,6 +290,15 @@ pub trait RealearnTarget {
         true
     }
 
+    /// Whether the target makes use of text or colors sent along with the control value (see
+    /// [`IncomingControlData`]).
+    ///
+    /// Only then, control messages which carry nothing but text or a color (e.g. OSC messages
+    /// with a string argument) trigger the target.
+    fn consumes_incoming_control_data(&self) -> bool {
+        false
+    }
+
     /// Might return the new value if changed but is not required to! If it doesn't and the consumer
     /// wants to know the new value, it should just query the current value of the target.
     ///
@@ -530,6 +544,32 @@ pub struct MappingControlContext<'a> {
     /// This information is used by some particular targets whose work is partially done in real-time and partially
     /// in the main thread.
     pub coming_from_real_time: bool,
+    /// Text sent along with the control value, e.g. a string argument of an OSC message.
+    ///
+    /// Used by targets which set names.
+    pub incoming_text: Option<&'a str>,
+    /// Color sent along with the control value, e.g. a color argument of an OSC message.
+    ///
+    /// Used by targets which set colors.
+    pub incoming_color: Option<RgbColor>,
+}
+
+/// Data which is sent along with a control value but can't be expressed as one.
+///
+/// Currently only OSC sources provide it. It's passed on to main mappings with virtual sources,
+/// so it also works with controller presets.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct IncomingControlData {
+    /// String argument of an OSC message.
+    pub text: Option<String>,
+    /// Color argument of an OSC message.
+    pub color: Option<RgbColor>,
+}
+
+impl IncomingControlData {
+    pub fn is_empty(&self) -> bool {
+        self.text.is_none() && self.color.is_none()
+    }
 }
 
 impl TransformationInputProvider<AdditionalTransformationInput> for MappingControlContext<'_> {
@@ -670,6 +710,9 @@ pub enum ReaperTargetType {
 
     // Marker/region targets
     GoToBookmark = 22,
+    AddBookmark = 75,
+    RenameBookmark = 76,
+    DeleteBookmark = 77,
 
     // Track targets
     TrackTool = 44,
@@ -683,12 +726,22 @@ pub enum ReaperTargetType {
     TrackAutomationMode = 25,
     TrackTouchState = 21,
     TrackMonitoringMode = 49,
+    TrackRecordInput = 78,
+    TrackRecordMode = 79,
+    TrackInputFxEnable = 80,
+    TrackName = 81,
+    TrackColor = 82,
     TrackPan = 4,
     TrackWidth = 17,
     TrackVolume = 2,
     TrackShow = 24,
     TrackSolo = 8,
 
+    // Media item targets
+    ItemParameterValue = 69,
+    TakeParameterValue = 70,
+    BrowseTakes = 71,
+
     // FX chain targets
     BrowseFxs = 28,
 
@@ -699,12 +752,18 @@ pub enum ReaperTargetType {
     FxOnline = 42,
     LoadFxSnapshot = 19,
     FxOpen = 27,
+    FxName = 83,
 
     // FX parameter targets
     FxParameterTouchState = 47,
     #[default]
     FxParameterValue = 1,
 
+    // Envelope targets
+    EnvelopeValue = 72,
+    BrowseEnvelopePoints = 73,
+    EnvelopeArmState = 74,
+
     // Pot targets
     BrowsePotFilterItems = 61,
     BrowsePotPresets = 58,
@@ -751,6 +810,7 @@ pub enum ReaperTargetType {
     CompartmentParameterValue = 63,
     LoadMappingSnapshot = 35,
     TakeMappingSnapshot = 55,
+    MorphMappingSnapshots = 68,
     BrowseGroup = 37,
 }
 
@@ -790,6 +850,9 @@ impl ReaperTargetType {
             A::AutomationModeOverride => B::AutomationModeOverride,
             A::ReaperAction => B::Action,
             A::TransportAction => B::Transport,
+            A::TrackRecordInput => B::TrackRecordInput,
+            A::TrackRecordMode => B::TrackRecordMode,
+            A::TrackInputFxOnOffState => B::TrackInputFxEnable,
         }
     }
 
@@ -828,6 +891,9 @@ impl ReaperTargetType {
             PlayRate => &PLAYRATE_TARGET,
             Tempo => &TEMPO_TARGET,
             GoToBookmark => &GO_TO_BOOKMARK_TARGET,
+            AddBookmark => &ADD_BOOKMARK_TARGET,
+            RenameBookmark => &RENAME_BOOKMARK_TARGET,
+            DeleteBookmark => &DELETE_BOOKMARK_TARGET,
             TrackArm => &TRACK_ARM_TARGET,
             TrackParentSend => &TRACK_PARENT_SEND_TARGET,
             AllTrackFxEnable => &ALL_TRACK_FX_ENABLE_TARGET,
@@ -838,6 +904,11 @@ impl ReaperTargetType {
             TrackSelection => &TRACK_SELECTION_TARGET,
             TrackAutomationMode => &TRACK_AUTOMATION_MODE_TARGET,
             TrackMonitoringMode => &TRACK_MONITORING_MODE_TARGET,
+            TrackRecordInput => &TRACK_RECORD_INPUT_TARGET,
+            TrackRecordMode => &TRACK_RECORD_MODE_TARGET,
+            TrackInputFxEnable => &TRACK_INPUT_FX_ENABLE_TARGET,
+            TrackName => &TRACK_NAME_TARGET,
+            TrackColor => &TRACK_COLOR_TARGET,
             TrackTouchState => &TRACK_TOUCH_STATE_TARGET,
             TrackPan => &TRACK_PAN_TARGET,
             TrackWidth => &TRACK_WIDTH_TARGET,
@@ -851,6 +922,7 @@ impl ReaperTargetType {
             LoadFxSnapshot => &LOAD_FX_SNAPSHOT_TARGET,
             FxPreset => &FX_PRESET_TARGET,
             FxOpen => &FX_OPEN_TARGET,
+            FxName => &FX_NAME_TARGET,
             FxParameterValue => &FX_PARAMETER_TARGET,
             FxParameterTouchState => &FX_PARAMETER_TOUCH_STATE_TARGET,
             RouteAutomationMode => &ROUTE_AUTOMATION_MODE_TARGET,
@@ -878,6 +950,7 @@ impl ReaperTargetType {
             ModifyMapping => &LEARN_MAPPING_TARGET,
             LoadMappingSnapshot => &LOAD_MAPPING_SNAPSHOT_TARGET,
             TakeMappingSnapshot => &SAVE_MAPPING_SNAPSHOT_TARGET,
+            MorphMappingSnapshots => &MORPH_MAPPING_SNAPSHOTS_TARGET,
             BrowseGroup => &BROWSE_GROUP_MAPPINGS_TARGET,
             BrowsePotFilterItems => &BROWSE_POT_FILTER_ITEMS_TARGET,
             BrowsePotPresets => &BROWSE_POT_PRESETS_TARGET,
@@ -885,6 +958,12 @@ impl ReaperTargetType {
             LoadPotPreset => &LOAD_POT_PRESET_TARGET,
             CompartmentParameterValue => &COMPARTMENT_PARAMETER_VALUE_TARGET,
             StreamDeckBrightness => &STREAM_DECK_BRIGHTNESS_TARGET,
+            ItemParameterValue => &ITEM_PARAMETER_VALUE_TARGET,
+            TakeParameterValue => &TAKE_PARAMETER_VALUE_TARGET,
+            BrowseTakes => &BROWSE_TAKES_TARGET,
+            EnvelopeValue => &ENVELOPE_VALUE_TARGET,
+            BrowseEnvelopePoints => &BROWSE_ENVELOPE_POINTS_TARGET,
+            EnvelopeArmState => &ENVELOPE_ARM_STATE_TARGET,
         }
     }
 
@@ -969,12 +1048,15 @@ pub enum TargetSection {
     #[strum(serialize = "Marker/region")]
     Bookmark,
     Track,
+    #[strum(serialize = "Media item")]
+    Item,
     #[strum(serialize = "FX chain")]
     FxChain,
     #[strum(serialize = "FX")]
     Fx,
     #[strum(serialize = "FX parameter")]
     FxParameter,
+    Envelope,
     Pot,
     Send,
     Playtime,
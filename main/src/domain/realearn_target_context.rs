@@ -35,6 +35,14 @@ pub struct RealearnTargetState {
     ///
     /// Not persistent.
     touched_things: NonCryptoHashSet<TouchedThing>,
+    /// Memorizes for each track which input FX were enabled before the input FX chain was
+    /// disabled as a whole.
+    ///
+    /// For "Track: Enable/disable input FX" target, so that enabling the input FX chain again
+    /// doesn't also enable FX which were bypassed on purpose.
+    ///
+    /// Not persistent.
+    enabled_input_fx_by_track: NonCryptoHashMap<MediaTrack, Vec<Fx>>,
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
@@ -61,6 +69,7 @@ impl RealearnTargetState {
             fx_snapshot_chunk_hash_by_fx: Default::default(),
             touched_things: Default::default(),
             current_pot_preset_by_fx: Default::default(),
+            enabled_input_fx_by_track: Default::default(),
         }
     }
 
@@ -101,6 +110,55 @@ impl RealearnTargetState {
         Ok(())
     }
 
+    /// Disables all input FX of the given track and memorizes which of them were enabled.
+    pub fn disable_track_input_fx(&mut self, track: &Track) {
+        let Ok(raw_track) = track.raw() else {
+            return;
+        };
+        let enabled_fxs: Vec<_> = track
+            .input_fx_chain()
+            .fxs()
+            .filter(|fx| fx.is_enabled())
+            .filter_map(|fx| fx.guid_based())
+            .collect();
+        if enabled_fxs.is_empty() {
+            // Already disabled. Keep what we memorized when it got disabled.
+            return;
+        }
+        for fx in &enabled_fxs {
+            let _ = fx.disable();
+        }
+        self.enabled_input_fx_by_track
+            .insert(raw_track, enabled_fxs);
+    }
+
+    /// Enables the input FX of the given track which were enabled before
+    /// [`Self::disable_track_input_fx`] was called.
+    ///
+    /// Enables all input FX if we don't know which ones were enabled before (e.g. because the
+    /// input FX chain was disabled in REAPER itself or the memorized FX don't exist anymore).
+    pub fn enable_track_input_fx(&mut self, track: &Track) {
+        let Ok(raw_track) = track.raw() else {
+            return;
+        };
+        let memorized_fxs: Vec<_> = self
+            .enabled_input_fx_by_track
+            .remove(&raw_track)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|fx| fx.is_available())
+            .collect();
+        if memorized_fxs.is_empty() {
+            for fx in track.input_fx_chain().fxs() {
+                let _ = fx.enable();
+            }
+        } else {
+            for fx in memorized_fxs {
+                let _ = fx.enable();
+            }
+        }
+    }
+
     pub fn touch_automation_parameter(
         &mut self,
         track: &Track,
@@ -0,0 +1,8 @@
+use reaper_high::Reaper;
+
+/// Gives access to the low-level REAPER API.
+///
+/// Used for the REAPER functions which neither reaper-high nor reaper-medium expose yet.
+pub fn low_reaper() -> &'static reaper_low::Reaper {
+    Reaper::get().medium_reaper().low()
+}
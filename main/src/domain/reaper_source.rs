@@ -8,18 +8,22 @@ use helgoboss_learn::{
     format_percentage_without_unit, parse_percentage_without_unit, ControlValue,
     DetailedSourceCharacter, FeedbackValue, SourceCharacter, UnitValue,
 };
-use reaper_medium::{MidiInputDeviceId, MidiOutputDeviceId};
+use helgobox_api::persistence::MusicalTimerUnit;
+use reaper_high::Project;
+use reaper_medium::{MidiInputDeviceId, MidiOutputDeviceId, PositionInSeconds};
 use std::convert::TryInto;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 use std::time::{Duration, Instant};
 
-#[derive(Clone, Eq, PartialEq, Debug)]
+// No Eq because the musical timer source contains floats.
+#[derive(Clone, PartialEq, Debug)]
 pub enum ReaperSource {
     MidiDeviceChanges,
     RealearnInstanceStart,
     RealearnCompartmentLoaded,
     Timer(TimerSource),
+    MusicalTimer(MusicalTimerSource),
     RealearnParameter(RealearnParameterSource),
     Speech(SpeechSource),
 }
@@ -77,8 +81,85 @@ pub fn say(feedback_value: SpeechSourceFeedbackValue) -> Result<(), Box<dyn Erro
     }
     #[cfg(target_os = "linux")]
     {
-        let _ = feedback_value;
-        Err("speech source not yet supported on Linux".into())
+        linux_speech::ENGINE.lock()?.speak(&feedback_value.text)
+    }
+}
+
+/// Sets the command which is used for text-to-speech on Linux.
+///
+/// The command is split at whitespace and receives the text to be spoken on stdin. If empty,
+/// `espeak-ng --stdin` is used.
+#[cfg(target_os = "linux")]
+pub fn set_linux_speech_command(command: &str) {
+    if let Ok(mut engine) = linux_speech::ENGINE.lock() {
+        engine.command = command.to_string();
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux_speech {
+    use std::error::Error;
+    use std::io::Write;
+    use std::process::{Child, Command, Stdio};
+    use std::sync::Mutex;
+
+    pub const DEFAULT_COMMAND: &str = "espeak-ng --stdin";
+
+    pub static ENGINE: Mutex<LinuxSpeechEngine> = Mutex::new(LinuxSpeechEngine {
+        command: String::new(),
+        current_utterance: None,
+    });
+
+    /// Speaks text by spawning one process of the configured command per utterance.
+    pub struct LinuxSpeechEngine {
+        pub command: String,
+        /// Process which might still be speaking.
+        current_utterance: Option<Child>,
+    }
+
+    impl LinuxSpeechEngine {
+        /// Cancels ongoing utterances and starts speaking the given text.
+        ///
+        /// This corresponds to `speak(text, true)` of the Windows and macOS engines.
+        pub fn speak(&mut self, text: &str) -> Result<(), Box<dyn Error>> {
+            self.stop();
+            let command = if self.command.trim().is_empty() {
+                DEFAULT_COMMAND
+            } else {
+                self.command.as_str()
+            };
+            let mut parts = command.split_whitespace();
+            let program = parts.next().unwrap_or_default();
+            let mut child = Command::new(program)
+                .args(parts)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .map_err(|e| format!("couldn't start speech command \"{program}\": {e}"))?;
+            if let Some(mut stdin) = child.stdin.take() {
+                // Writing might block if the command doesn't consume its input fast enough, so
+                // we don't do it on the main thread. Dropping stdin closes it, which lets the
+                // command know that the text is complete.
+                let text = text.to_string();
+                std::thread::Builder::new()
+                    .name("ReaLearn speech input".to_string())
+                    .spawn(move || {
+                        let _ = stdin.write_all(text.as_bytes());
+                    })?;
+            }
+            self.current_utterance = Some(child);
+            Ok(())
+        }
+
+        fn stop(&mut self) {
+            if let Some(mut child) = self.current_utterance.take() {
+                if matches!(child.try_wait(), Ok(None)) {
+                    let _ = child.kill();
+                }
+                let _ = child.wait();
+            }
+        }
     }
 }
 
@@ -125,6 +206,135 @@ impl TimerSource {
     }
 }
 
+/// Fire once per unit (e.g. once per beat) by default.
+pub const DEFAULT_MUSICAL_TIMER_INTERVAL: f64 = 1.0;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct MusicalTimerSource {
+    unit: MusicalTimerUnit,
+    interval: f64,
+    offset: f64,
+    only_while_playing: bool,
+    /// Index of the interval in which the timer was polled most recently.
+    last_slot: Option<i64>,
+    /// Used to keep ticking at the current tempo while the transport is stopped.
+    stop_anchor: Option<StopAnchor>,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct StopAnchor {
+    instant: Instant,
+    position: f64,
+}
+
+impl MusicalTimerSource {
+    pub fn new(
+        unit: MusicalTimerUnit,
+        interval: f64,
+        offset: f64,
+        only_while_playing: bool,
+    ) -> Self {
+        Self {
+            unit,
+            interval,
+            offset,
+            only_while_playing,
+            last_slot: None,
+            stop_anchor: None,
+        }
+    }
+
+    pub fn unit(&self) -> MusicalTimerUnit {
+        self.unit
+    }
+
+    pub fn interval(&self) -> f64 {
+        self.interval
+    }
+
+    pub fn offset(&self) -> f64 {
+        self.offset
+    }
+
+    pub fn only_while_playing(&self) -> bool {
+        self.only_while_playing
+    }
+
+    pub fn on_deactivate(&mut self) {
+        self.last_slot = None;
+        self.stop_anchor = None;
+    }
+
+    pub fn poll(&mut self, project: Project) -> Option<ControlValue> {
+        let position = if project.is_playing() {
+            self.stop_anchor = None;
+            let pos = project.play_position_latency_compensated();
+            Some(self.position_in_units(project, pos))
+        } else if self.only_while_playing {
+            self.stop_anchor = None;
+            None
+        } else {
+            // When stopped, we keep ticking at the current tempo, starting at the edit cursor.
+            let now = Instant::now();
+            let anchor = match self.stop_anchor {
+                Some(a) => a,
+                None => {
+                    let pos = project.edit_cursor_position().unwrap_or_default();
+                    let a = StopAnchor {
+                        instant: now,
+                        position: self.position_in_units(project, pos),
+                    };
+                    self.stop_anchor = Some(a);
+                    a
+                }
+            };
+            let beats_per_second = project.tempo().bpm().get() / 60.0;
+            let elapsed_beats = (now - anchor.instant).as_secs_f64() * beats_per_second;
+            let elapsed_units = match self.unit {
+                MusicalTimerUnit::Beat => elapsed_beats,
+                MusicalTimerUnit::Bar => {
+                    let beat_info =
+                        project.beat_info_at(project.edit_cursor_position().unwrap_or_default());
+                    elapsed_beats / beat_info.time_signature.numerator.get() as f64
+                }
+            };
+            Some(anchor.position + elapsed_units)
+        };
+        self.poll_at(position)
+    }
+
+    /// Fires whenever the given position (in units) enters a new interval.
+    ///
+    /// `None` means that the timer is paused.
+    fn poll_at(&mut self, position: Option<f64>) -> Option<ControlValue> {
+        let Some(position) = position else {
+            self.last_slot = None;
+            return None;
+        };
+        if self.interval <= 0.0 {
+            return None;
+        }
+        let slot = ((position - self.offset) / self.interval).floor() as i64;
+        let last_slot = self.last_slot.replace(slot)?;
+        if slot == last_slot {
+            return None;
+        }
+        Some(ControlValue::AbsoluteContinuous(UnitValue::MAX))
+    }
+
+    fn position_in_units(&self, project: Project, pos: PositionInSeconds) -> f64 {
+        let beat_info = project.beat_info_at(pos);
+        match self.unit {
+            MusicalTimerUnit::Beat => beat_info.full_beats.get(),
+            MusicalTimerUnit::Bar => {
+                let beats_per_measure = beat_info.time_signature.numerator.get() as f64;
+                beat_info.measure_index as f64
+                    + beat_info.beats_since_measure.get() / beats_per_measure
+            }
+        }
+    }
+}
+
 impl ReaperSource {
     pub fn extract_feedback_address(&self) -> Option<ReaperSourceAddress> {
         use ReaperSource::*;
@@ -137,13 +347,14 @@ impl ReaperSource {
     pub fn on_deactivate(&mut self) {
         match self {
             ReaperSource::Timer(s) => s.on_deactivate(),
+            ReaperSource::MusicalTimer(s) => s.on_deactivate(),
             _ => {}
         }
     }
 
     /// If this returns `true`, the `poll` method should be called, on a regular basis.
     pub fn wants_to_be_polled(&self) -> bool {
-        matches!(self, ReaperSource::Timer(_))
+        matches!(self, ReaperSource::Timer(_) | ReaperSource::MusicalTimer(_))
     }
 
     pub fn possible_detailed_characters(&self) -> Vec<DetailedSourceCharacter> {
@@ -152,7 +363,7 @@ impl ReaperSource {
             MidiDeviceChanges => vec![DetailedSourceCharacter::MomentaryOnOffButton],
             RealearnInstanceStart => vec![DetailedSourceCharacter::MomentaryOnOffButton],
             RealearnCompartmentLoaded => vec![DetailedSourceCharacter::Trigger],
-            Timer(_) => vec![DetailedSourceCharacter::Trigger],
+            Timer(_) | MusicalTimer(_) => vec![DetailedSourceCharacter::Trigger],
             RealearnParameter(_) => vec![
                 DetailedSourceCharacter::RangeControl,
                 DetailedSourceCharacter::MomentaryVelocitySensitiveButton,
@@ -175,19 +386,21 @@ impl ReaperSource {
     pub fn character(&self) -> SourceCharacter {
         use ReaperSource::*;
         match self {
-            MidiDeviceChanges | RealearnInstanceStart | RealearnCompartmentLoaded | Timer(_) => {
-                SourceCharacter::MomentaryButton
-            }
+            MidiDeviceChanges
+            | RealearnInstanceStart
+            | RealearnCompartmentLoaded
+            | Timer(_)
+            | MusicalTimer(_) => SourceCharacter::MomentaryButton,
             RealearnParameter(_) => SourceCharacter::RangeElement,
             Speech(_) => SourceCharacter::RangeElement,
         }
     }
 
-    pub fn poll(&mut self) -> Option<ControlValue> {
-        if let ReaperSource::Timer(t) = self {
-            t.poll()
-        } else {
-            None
+    pub fn poll(&mut self, project: Project) -> Option<ControlValue> {
+        match self {
+            ReaperSource::Timer(t) => t.poll(),
+            ReaperSource::MusicalTimer(t) => t.poll(project),
+            _ => None,
         }
     }
 
@@ -241,6 +454,7 @@ impl ReaperSource {
             | RealearnInstanceStart
             | RealearnCompartmentLoaded
             | Timer(_)
+            | MusicalTimer(_)
             | RealearnParameter(_) => None,
             Speech(s) => Some(ReaperSourceFeedbackValue::Speech(
                 s.feedback(feedback_value),
@@ -322,3 +536,54 @@ impl Display for MidiDeviceChangePayload {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn musical_timer_fires_when_entering_new_interval() {
+        // Given
+        let mut timer = MusicalTimerSource::new(MusicalTimerUnit::Beat, 0.25, 0.0, true);
+        // When
+        let first = timer.poll_at(Some(1.1));
+        let same_interval = timer.poll_at(Some(1.2));
+        let next_interval = timer.poll_at(Some(1.26));
+        // Then
+        assert_eq!(first, None);
+        assert_eq!(same_interval, None);
+        assert_eq!(
+            next_interval,
+            Some(ControlValue::AbsoluteContinuous(UnitValue::MAX))
+        );
+    }
+
+    #[test]
+    fn musical_timer_respects_offset() {
+        // Given
+        let mut timer = MusicalTimerSource::new(MusicalTimerUnit::Bar, 1.0, 0.5, true);
+        // When
+        timer.poll_at(Some(0.2));
+        let at_bar_start = timer.poll_at(Some(1.0));
+        let at_offset = timer.poll_at(Some(1.5));
+        // Then
+        assert_eq!(at_bar_start, None);
+        assert_eq!(
+            at_offset,
+            Some(ControlValue::AbsoluteContinuous(UnitValue::MAX))
+        );
+    }
+
+    #[test]
+    fn musical_timer_doesnt_fire_when_resuming_after_pause() {
+        // Given
+        let mut timer = MusicalTimerSource::new(MusicalTimerUnit::Beat, 1.0, 0.0, true);
+        timer.poll_at(Some(3.5));
+        // When
+        let paused = timer.poll_at(None);
+        let resumed = timer.poll_at(Some(7.5));
+        // Then
+        assert_eq!(paused, None);
+        assert_eq!(resumed, None);
+    }
+}
@@ -26,28 +26,32 @@ use helgobox_api::persistence::{ActionScope, SeekBehavior, TrackScope};
 
 use crate::domain::ui_util::convert_bool_to_unit_value;
 use crate::domain::{
-    get_reaper_track_area_of_scope, handle_exclusivity, ActionTarget, AdditionalFeedbackEvent,
-    AllTrackFxEnableTarget, AutomationModeOverrideTarget, BrowseFxsTarget,
-    BrowsePotFilterItemsTarget, BrowsePotPresetsTarget, BrowseTracksTarget, Caller,
-    CompartmentParameterValueTarget, ControlContext, DummyTarget, EnableUnitsTarget,
-    EnigoMouseTarget, FxEnableTarget, FxOnlineTarget, FxOpenTarget, FxParameterTarget,
+    get_reaper_track_area_of_scope, handle_exclusivity, ActionTarget, AddBookmarkTarget,
+    AdditionalFeedbackEvent, AllTrackFxEnableTarget, AutomationModeOverrideTarget,
+    BrowseEnvelopePointsTarget, BrowseFxsTarget, BrowsePotFilterItemsTarget,
+    BrowsePotPresetsTarget, BrowseTakesTarget, BrowseTracksTarget, Caller,
+    CompartmentParameterValueTarget, ControlContext, DeleteBookmarkTarget, DummyTarget,
+    EnableUnitsTarget, EnigoMouseTarget, EnvelopeArmStateTarget, EnvelopeValueTarget,
+    FxEnableTarget, FxNameTarget, FxOnlineTarget, FxOpenTarget, FxParameterTarget,
     FxParameterTouchStateTarget, FxPresetTarget, FxToolTarget, GoToBookmarkTarget, HierarchyEntry,
-    HierarchyEntryProvider, LoadFxSnapshotTarget, LoadPotPresetTarget, MappingControlContext,
-    MidiSendTarget, ModifyMappingTarget, OscSendTarget, PlayrateTarget, PlaytimeColumnActionTarget,
-    PlaytimeMatrixActionTarget, PlaytimeRowActionTarget, PlaytimeSlotTransportTarget,
-    PreviewPotPresetTarget, RealTimeControlContext, RealTimeFxParameterTarget, RouteMuteTarget,
-    RoutePanTarget, RouteTouchStateTarget, RouteVolumeTarget, SeekTarget,
-    StreamDeckBrightnessTarget, TakeMappingSnapshotTarget, TargetTypeDef, TempoTarget,
-    TrackArmTarget, TrackAutomationModeTarget, TrackMonitoringModeTarget, TrackMuteTarget,
-    TrackPanTarget, TrackParentSendTarget, TrackPeakTarget, TrackSelectionTarget, TrackShowTarget,
-    TrackSoloTarget, TrackTouchStateTarget, TrackVolumeTarget, TrackWidthTarget, TransportTarget,
-    UnitEvent,
+    HierarchyEntryProvider, ItemParameterValueTarget, LoadFxSnapshotTarget, LoadPotPresetTarget,
+    MappingControlContext, MidiSendTarget, ModifyMappingTarget, OscSendTarget, PlayrateTarget,
+    PlaytimeColumnActionTarget, PlaytimeMatrixActionTarget, PlaytimeRowActionTarget,
+    PlaytimeSlotTransportTarget, PreviewPotPresetTarget, RealTimeControlContext,
+    RealTimeFxParameterTarget, RenameBookmarkTarget, RouteMuteTarget, RoutePanTarget,
+    RouteTouchStateTarget, RouteVolumeTarget, SeekTarget, StreamDeckBrightnessTarget,
+    TakeMappingSnapshotTarget, TakeParameterValueTarget, TargetTypeDef, TempoTarget,
+    TrackArmTarget, TrackAutomationModeTarget, TrackColorTarget, TrackInputFxEnableTarget,
+    TrackMonitoringModeTarget, TrackMuteTarget, TrackNameTarget, TrackPanTarget,
+    TrackParentSendTarget, TrackPeakTarget, TrackRecordInputTarget, TrackRecordModeTarget,
+    TrackSelectionTarget, TrackShowTarget, TrackSoloTarget, TrackTouchStateTarget,
+    TrackVolumeTarget, TrackWidthTarget, TransportTarget, UnitEvent,
 };
 use crate::domain::{
     AnyOnTarget, BrowseGroupMappingsTarget, CompoundChangeEvent, EnableInstancesTarget,
-    EnableMappingsTarget, HitResponse, LoadMappingSnapshotTarget, RealearnTarget, ReaperTargetType,
-    RouteAutomationModeTarget, RouteMonoTarget, RoutePhaseTarget, TrackPhaseTarget,
-    TrackToolTarget,
+    EnableMappingsTarget, HitResponse, LoadMappingSnapshotTarget, MorphMappingSnapshotsTarget,
+    RealearnTarget, ReaperTargetType, RouteAutomationModeTarget, RouteMonoTarget, RoutePhaseTarget,
+    TrackPhaseTarget, TrackToolTarget,
 };
 use base::default_util::is_default;
 use base::Global;
@@ -117,6 +121,11 @@ pub enum ReaperTarget {
     TrackSolo(TrackSoloTarget),
     TrackAutomationMode(TrackAutomationModeTarget),
     TrackMonitoringMode(TrackMonitoringModeTarget),
+    TrackRecordInput(TrackRecordInputTarget),
+    TrackRecordMode(TrackRecordModeTarget),
+    TrackInputFxEnable(TrackInputFxEnableTarget),
+    TrackName(TrackNameTarget),
+    TrackColor(TrackColorTarget),
     RoutePan(RoutePanTarget),
     RouteMute(RouteMuteTarget),
     RoutePhase(RoutePhaseTarget),
@@ -129,6 +138,7 @@ pub enum ReaperTarget {
     FxEnable(FxEnableTarget),
     FxOnline(FxOnlineTarget),
     FxOpen(FxOpenTarget),
+    FxName(FxNameTarget),
     FxPreset(FxPresetTarget),
     BrowseTracks(BrowseTracksTarget),
     BrowseFxs(BrowseFxsTarget),
@@ -138,6 +148,9 @@ pub enum ReaperTarget {
     LoadFxSnapshot(LoadFxSnapshotTarget),
     TrackAutomationTouchState(TrackTouchStateTarget),
     GoToBookmark(GoToBookmarkTarget),
+    AddBookmark(AddBookmarkTarget),
+    RenameBookmark(RenameBookmarkTarget),
+    DeleteBookmark(DeleteBookmarkTarget),
     Seek(SeekTarget),
     SendMidi(MidiSendTarget),
     SendOsc(OscSendTarget),
@@ -153,6 +166,7 @@ pub enum ReaperTarget {
     PlaytimeSlotManagementAction(crate::domain::PlaytimeSlotManagementActionTarget),
     LoadMappingSnapshot(LoadMappingSnapshotTarget),
     TakeMappingSnapshot(TakeMappingSnapshotTarget),
+    MorphMappingSnapshots(MorphMappingSnapshotsTarget),
     EnableMappings(EnableMappingsTarget),
     ModifyMapping(ModifyMappingTarget),
     EnableInstances(EnableInstancesTarget),
@@ -164,6 +178,12 @@ pub enum ReaperTarget {
     LoadPotPreset(LoadPotPresetTarget),
     CompartmentParameterValue(CompartmentParameterValueTarget),
     StreamDeckBrightness(StreamDeckBrightnessTarget),
+    ItemParameterValue(ItemParameterValueTarget),
+    TakeParameterValue(TakeParameterValueTarget),
+    BrowseTakes(BrowseTakesTarget),
+    EnvelopeValue(EnvelopeValueTarget),
+    BrowseEnvelopePoints(BrowseEnvelopePointsTarget),
+    EnvelopeArmState(EnvelopeArmStateTarget),
 }
 
 #[derive(
@@ -350,6 +370,19 @@ impl ReaperTarget {
                     .action_by_command_id(e.command_id);
                 determine_target_for_action(action)
             }
+            AdditionalFeedbackEvent::TrackRecordModeChanged(e) => {
+                ReaperTarget::TrackRecordMode(TrackRecordModeTarget {
+                    track: e.track.clone(),
+                    exclusivity: Default::default(),
+                    mode: e.new_value,
+                })
+            }
+            AdditionalFeedbackEvent::TrackInputFxEnabledChanged(e) => {
+                ReaperTarget::TrackInputFxEnable(TrackInputFxEnableTarget {
+                    track: e.track.clone(),
+                    exclusivity: Default::default(),
+                })
+            }
             _ => return None,
         };
         Some(target)
@@ -453,6 +486,14 @@ impl ReaperTarget {
                 mode: e.new_value,
                 gang_behavior: Default::default(),
             }),
+            TrackInputChanged(e) => {
+                let input = e.track.recording_input();
+                TrackRecordInput(TrackRecordInputTarget {
+                    track: e.track,
+                    exclusivity: Default::default(),
+                    input,
+                })
+            }
             GlobalAutomationOverrideChanged(e) => {
                 AutomationModeOverride(AutomationModeOverrideTarget {
                     mode_override: e.new_value,
@@ -651,6 +692,11 @@ impl<'a> Target<'a> for ReaperTarget {
             TrackSolo(t) => t.current_value(context),
             TrackAutomationMode(t) => t.current_value(context),
             TrackMonitoringMode(t) => t.current_value(context),
+            TrackRecordInput(t) => t.current_value(context),
+            TrackRecordMode(t) => t.current_value(context),
+            TrackInputFxEnable(t) => t.current_value(context),
+            TrackName(t) => t.current_value(context),
+            TrackColor(t) => t.current_value(context),
             RoutePan(t) => t.current_value(context),
             RouteMute(t) => t.current_value(context),
             RoutePhase(t) => t.current_value(context),
@@ -664,6 +710,7 @@ impl<'a> Target<'a> for ReaperTarget {
             FxEnable(t) => t.current_value(context),
             FxOnline(t) => t.current_value(context),
             FxOpen(t) => t.current_value(context),
+            FxName(t) => t.current_value(context),
             // Discrete
             FxPreset(t) => t.current_value(context),
             LoadFxSnapshot(t) => t.current_value(context),
@@ -676,6 +723,9 @@ impl<'a> Target<'a> for ReaperTarget {
             AnyOn(t) => t.current_value(context),
             TrackAutomationTouchState(t) => t.current_value(context),
             GoToBookmark(t) => t.current_value(context),
+            AddBookmark(t) => t.current_value(context),
+            RenameBookmark(t) => t.current_value(context),
+            DeleteBookmark(t) => t.current_value(context),
             Seek(t) => t.current_value(context),
             PlaytimeSlotTransportAction(t) => t.current_value(context),
             PlaytimeColumnAction(t) => t.current_value(context),
@@ -688,6 +738,7 @@ impl<'a> Target<'a> for ReaperTarget {
             PlaytimeBrowseCells(t) => t.current_value(context),
             LoadMappingSnapshot(t) => t.current_value(context),
             TakeMappingSnapshot(t) => t.current_value(context),
+            MorphMappingSnapshots(t) => t.current_value(context),
             EnableMappings(t) => t.current_value(context),
             ModifyMapping(t) => t.current_value(context),
             EnableInstances(t) => t.current_value(context),
@@ -699,6 +750,12 @@ impl<'a> Target<'a> for ReaperTarget {
             LoadPotPreset(t) => t.current_value(context),
             CompartmentParameterValue(t) => t.current_value(context),
             StreamDeckBrightness(t) => t.current_value(context),
+            ItemParameterValue(t) => t.current_value(context),
+            TakeParameterValue(t) => t.current_value(context),
+            BrowseTakes(t) => t.current_value(context),
+            EnvelopeValue(t) => t.current_value(context),
+            BrowseEnvelopePoints(t) => t.current_value(context),
+            EnvelopeArmState(t) => t.current_value(context),
         }
     }
 
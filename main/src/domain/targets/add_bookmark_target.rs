@@ -0,0 +1,143 @@
+use crate::domain::ui_util::convert_bool_to_unit_value;
+use crate::domain::{
+    add_marker, add_region, find_nearest_marker, find_region, format_value_as_on_off,
+    CompartmentKind, ControlContext, ExtendedProcessorContext, FeedbackResolution, HitResponse,
+    MappingControlContext, RealearnTarget, ReaperTarget, ReaperTargetType, TargetCharacter,
+    TargetSection, TargetTypeDef, UnresolvedReaperTargetDef, AUTOMATIC_FEEDBACK_VIA_POLLING_ONLY,
+    DEFAULT_TARGET,
+};
+use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, Target, UnitValue};
+use reaper_high::{BookmarkType, Project};
+use std::borrow::Cow;
+use std::time::Duration;
+
+#[derive(Debug)]
+pub struct UnresolvedAddBookmarkTarget {
+    pub bookmark_type: BookmarkType,
+    pub tolerance: Duration,
+}
+
+impl UnresolvedReaperTargetDef for UnresolvedAddBookmarkTarget {
+    fn resolve(
+        &self,
+        context: ExtendedProcessorContext,
+        _: CompartmentKind,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        Ok(vec![ReaperTarget::AddBookmark(AddBookmarkTarget {
+            project: context.context().project_or_current_project(),
+            bookmark_type: self.bookmark_type,
+            tolerance: self.tolerance,
+        })])
+    }
+
+    fn feedback_resolution(&self) -> Option<FeedbackResolution> {
+        Some(FeedbackResolution::High)
+    }
+}
+
+/// Adds a marker at the play position or a region spanning the time selection.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AddBookmarkTarget {
+    pub project: Project,
+    pub bookmark_type: BookmarkType,
+    /// Markers and regions which are this close to the desired position are considered as
+    /// already existing.
+    pub tolerance: Duration,
+}
+
+impl AddBookmarkTarget {
+    /// Returns the index of the bookmark which would be added, if it already exists.
+    fn find_existing_bookmark(&self) -> Option<u32> {
+        match self.bookmark_type {
+            BookmarkType::Marker => {
+                let pos = self
+                    .project
+                    .play_or_edit_cursor_position()
+                    .unwrap_or_default();
+                find_nearest_marker(self.project, pos, self.tolerance)
+            }
+            BookmarkType::Region => {
+                let range = self.project.time_selection()?;
+                find_region(self.project, range.start, range.end, self.tolerance)
+            }
+        }
+    }
+}
+
+impl RealearnTarget for AddBookmarkTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        (
+            ControlType::AbsoluteContinuousRetriggerable,
+            TargetCharacter::Trigger,
+        )
+    }
+
+    fn format_value(&self, value: UnitValue, _: ControlContext) -> String {
+        format_value_as_on_off(value).to_string()
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        _: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        if value.to_unit_value()?.is_zero() {
+            return Ok(HitResponse::ignored());
+        }
+        // Don't pile up duplicates when pressing the button twice
+        if self.find_existing_bookmark().is_some() {
+            return Ok(HitResponse::ignored());
+        }
+        match self.bookmark_type {
+            BookmarkType::Marker => {
+                let pos = self
+                    .project
+                    .play_or_edit_cursor_position()
+                    .unwrap_or_default();
+                add_marker(self.project, pos);
+            }
+            BookmarkType::Region => {
+                let range = self.project.time_selection().ok_or("no time selection")?;
+                add_region(self.project, range.start, range.end);
+            }
+        }
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        self.project.is_available()
+    }
+
+    fn project(&self) -> Option<Project> {
+        Some(self.project)
+    }
+
+    fn text_value(&self, context: ControlContext) -> Option<Cow<'static, str>> {
+        Some(format_value_as_on_off(self.current_value(context)?.to_unit_value()).into())
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::AddBookmark)
+    }
+}
+
+impl<'a> Target<'a> for AddBookmarkTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        let val = convert_bool_to_unit_value(self.find_existing_bookmark().is_some());
+        Some(AbsoluteValue::Continuous(val))
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+pub const ADD_BOOKMARK_TARGET: TargetTypeDef = TargetTypeDef {
+    section: TargetSection::Bookmark,
+    name: "Add",
+    short_name: "Add bookmark",
+    hint: AUTOMATIC_FEEDBACK_VIA_POLLING_ONLY,
+    ..DEFAULT_TARGET
+};
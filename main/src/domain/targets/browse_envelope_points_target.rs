@@ -0,0 +1,226 @@
+use crate::domain::{
+    convert_count_to_step_size, convert_discrete_to_unit_value, convert_unit_to_discrete_value,
+    CompartmentKind, ControlContext, EnvelopeRef, ExtendedProcessorContext, FeedbackResolution,
+    FxParameterDescriptor, HitResponse, MappingControlContext, RealearnTarget, ReaperTarget,
+    ReaperTargetType, TargetCharacter, TargetSection, TargetTypeDef, TrackDescriptor,
+    UnresolvedReaperTargetDef, VirtualEnvelope, AUTOMATIC_FEEDBACK_VIA_POLLING_ONLY,
+    DEFAULT_TARGET,
+};
+use helgoboss_learn::{
+    AbsoluteValue, ControlType, ControlValue, Fraction, NumericValue, Target, UnitValue,
+};
+use reaper_high::{Fx, Project, Track};
+use reaper_medium::{PositionInSeconds, SetEditCurPosOptions};
+
+#[derive(Debug)]
+pub struct UnresolvedBrowseEnvelopePointsTarget {
+    pub envelope: VirtualEnvelope,
+}
+
+impl UnresolvedReaperTargetDef for UnresolvedBrowseEnvelopePointsTarget {
+    fn resolve(
+        &self,
+        context: ExtendedProcessorContext,
+        compartment: CompartmentKind,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        let targets = self
+            .envelope
+            .resolve(context, compartment)?
+            .into_iter()
+            .map(|envelope| {
+                ReaperTarget::BrowseEnvelopePoints(BrowseEnvelopePointsTarget { envelope })
+            })
+            .collect();
+        Ok(targets)
+    }
+
+    fn feedback_resolution(&self) -> Option<FeedbackResolution> {
+        Some(FeedbackResolution::High)
+    }
+
+    fn track_descriptor(&self) -> Option<&TrackDescriptor> {
+        self.envelope.track_descriptor()
+    }
+
+    fn fx_parameter_descriptor(&self) -> Option<&FxParameterDescriptor> {
+        self.envelope.fx_parameter_descriptor()
+    }
+}
+
+/// Moves the edit cursor from envelope point to envelope point.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BrowseEnvelopePointsTarget {
+    pub envelope: EnvelopeRef,
+}
+
+impl BrowseEnvelopePointsTarget {
+    fn point_count(&self) -> u32 {
+        self.envelope
+            .envelope()
+            .map(|e| e.point_count())
+            .unwrap_or(0)
+    }
+
+    /// Returns the index of the last point at or before the edit cursor.
+    fn current_point_index(&self) -> Option<u32> {
+        let envelope = self.envelope.envelope()?;
+        let pos = self
+            .envelope
+            .project()?
+            .edit_cursor_position()
+            .unwrap_or_default();
+        point_index_at_or_first(envelope.point_index_at(pos.get()), envelope.point_count())
+    }
+}
+
+/// If the edit cursor is before the first point, we consider the first point as the current one.
+fn point_index_at_or_first(point_index_at: Option<u32>, point_count: u32) -> Option<u32> {
+    if point_count == 0 {
+        return None;
+    }
+    Some(point_index_at.unwrap_or(0))
+}
+
+impl RealearnTarget for BrowseEnvelopePointsTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        (
+            ControlType::AbsoluteDiscrete {
+                atomic_step_size: convert_count_to_step_size(self.point_count()),
+                is_retriggerable: false,
+            },
+            TargetCharacter::Discrete,
+        )
+    }
+
+    fn parse_as_value(
+        &self,
+        text: &str,
+        context: ControlContext,
+    ) -> Result<UnitValue, &'static str> {
+        self.parse_value_from_discrete_value(text, context)
+    }
+
+    fn parse_as_step_size(
+        &self,
+        text: &str,
+        context: ControlContext,
+    ) -> Result<UnitValue, &'static str> {
+        self.parse_value_from_discrete_value(text, context)
+    }
+
+    fn convert_unit_value_to_discrete_value(
+        &self,
+        input: UnitValue,
+        _: ControlContext,
+    ) -> Result<u32, &'static str> {
+        Ok(convert_unit_to_discrete_value(input, self.point_count()))
+    }
+
+    fn format_value(&self, value: UnitValue, _: ControlContext) -> String {
+        let index = convert_unit_to_discrete_value(value, self.point_count());
+        (index + 1).to_string()
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        _: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        let envelope = self.envelope.envelope().ok_or("envelope not found")?;
+        let project = self.envelope.project().ok_or("project not found")?;
+        let index = match value.to_absolute_value()? {
+            AbsoluteValue::Continuous(v) => {
+                convert_unit_to_discrete_value(v, envelope.point_count())
+            }
+            AbsoluteValue::Discrete(f) => f.actual(),
+        };
+        let time = envelope
+            .point_time(index)
+            .ok_or("envelope point not found")?;
+        project.set_edit_cursor_position(
+            PositionInSeconds::new_panic(time),
+            SetEditCurPosOptions {
+                move_view: true,
+                seek_play: true,
+            },
+        );
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        self.envelope.envelope().is_some()
+    }
+
+    fn project(&self) -> Option<Project> {
+        self.envelope.project()
+    }
+
+    fn track(&self) -> Option<&Track> {
+        self.envelope.track()
+    }
+
+    fn fx(&self) -> Option<&Fx> {
+        self.envelope.fx()
+    }
+
+    fn convert_discrete_value_to_unit_value(
+        &self,
+        value: u32,
+        _: ControlContext,
+    ) -> Result<UnitValue, &'static str> {
+        Ok(convert_discrete_to_unit_value(value, self.point_count()))
+    }
+
+    fn numeric_value(&self, _: ControlContext) -> Option<NumericValue> {
+        let index = self.current_point_index()?;
+        Some(NumericValue::Discrete(index as i32 + 1))
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::BrowseEnvelopePoints)
+    }
+}
+
+impl<'a> Target<'a> for BrowseEnvelopePointsTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        let index = self.current_point_index()?;
+        let max_value = self.point_count().saturating_sub(1);
+        Some(AbsoluteValue::Discrete(Fraction::new(index, max_value)))
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+pub const BROWSE_ENVELOPE_POINTS_TARGET: TargetTypeDef = TargetTypeDef {
+    section: TargetSection::Envelope,
+    name: "Browse points",
+    short_name: "Browse envelope points",
+    hint: AUTOMATIC_FEEDBACK_VIA_POLLING_ONLY,
+    supports_track: true,
+    supports_fx: true,
+    supports_fx_parameter: true,
+    ..DEFAULT_TARGET
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn consider_first_point_current_if_edit_cursor_before_it() {
+        // Given
+        let point_count = 3;
+        // When
+        let before_first_point = point_index_at_or_first(None, point_count);
+        let after_second_point = point_index_at_or_first(Some(1), point_count);
+        let without_points = point_index_at_or_first(None, 0);
+        // Then
+        assert_eq!(before_first_point, Some(0));
+        assert_eq!(after_second_point, Some(1));
+        assert_eq!(without_points, None);
+    }
+}
@@ -0,0 +1,173 @@
+use crate::domain::{
+    convert_count_to_step_size, convert_discrete_to_unit_value, convert_unit_to_discrete_value,
+    CompartmentKind, ControlContext, ExtendedProcessorContext, FeedbackResolution, HitResponse,
+    ItemRef, MappingControlContext, RealearnTarget, ReaperTarget, ReaperTargetType,
+    TargetCharacter, TargetSection, TargetTypeDef, TrackDescriptor, UnresolvedReaperTargetDef,
+    VirtualItem, AUTOMATIC_FEEDBACK_VIA_POLLING_ONLY, DEFAULT_TARGET,
+};
+use helgoboss_learn::{
+    AbsoluteValue, ControlType, ControlValue, Fraction, NumericValue, Target, UnitValue,
+};
+use reaper_high::{Project, Track};
+use std::borrow::Cow;
+
+#[derive(Debug)]
+pub struct UnresolvedBrowseTakesTarget {
+    pub track_descriptor: TrackDescriptor,
+    pub item: VirtualItem,
+}
+
+impl UnresolvedReaperTargetDef for UnresolvedBrowseTakesTarget {
+    fn resolve(
+        &self,
+        context: ExtendedProcessorContext,
+        compartment: CompartmentKind,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        let targets = self
+            .item
+            .resolve(&self.track_descriptor, context, compartment)?
+            .into_iter()
+            .map(|item| ReaperTarget::BrowseTakes(BrowseTakesTarget { item }))
+            .collect();
+        Ok(targets)
+    }
+
+    fn feedback_resolution(&self) -> Option<FeedbackResolution> {
+        Some(FeedbackResolution::High)
+    }
+
+    fn track_descriptor(&self) -> Option<&TrackDescriptor> {
+        Some(&self.track_descriptor)
+    }
+
+    fn virtual_item(&self) -> Option<&VirtualItem> {
+        Some(&self.item)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BrowseTakesTarget {
+    pub item: ItemRef,
+}
+
+impl BrowseTakesTarget {
+    fn take_count(&self) -> u32 {
+        self.item.item().map(|item| item.take_count()).unwrap_or(0)
+    }
+
+    fn active_take_index(&self) -> Option<u32> {
+        Some(self.item.item()?.active_take()?.index())
+    }
+}
+
+impl RealearnTarget for BrowseTakesTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        (
+            ControlType::AbsoluteDiscrete {
+                atomic_step_size: convert_count_to_step_size(self.take_count()),
+                is_retriggerable: false,
+            },
+            TargetCharacter::Discrete,
+        )
+    }
+
+    fn parse_as_value(
+        &self,
+        text: &str,
+        context: ControlContext,
+    ) -> Result<UnitValue, &'static str> {
+        self.parse_value_from_discrete_value(text, context)
+    }
+
+    fn parse_as_step_size(
+        &self,
+        text: &str,
+        context: ControlContext,
+    ) -> Result<UnitValue, &'static str> {
+        self.parse_value_from_discrete_value(text, context)
+    }
+
+    fn convert_unit_value_to_discrete_value(
+        &self,
+        input: UnitValue,
+        _: ControlContext,
+    ) -> Result<u32, &'static str> {
+        Ok(convert_unit_to_discrete_value(input, self.take_count()))
+    }
+
+    fn format_value(&self, value: UnitValue, _: ControlContext) -> String {
+        let index = convert_unit_to_discrete_value(value, self.take_count());
+        (index + 1).to_string()
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        _: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        let item = self.item.item().ok_or("item not found")?;
+        let index = match value.to_absolute_value()? {
+            AbsoluteValue::Continuous(v) => convert_unit_to_discrete_value(v, item.take_count()),
+            AbsoluteValue::Discrete(f) => f.actual(),
+        };
+        let take = item.take_by_index(index).ok_or("take not found")?;
+        take.make_active();
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        self.item.item().is_some()
+    }
+
+    fn project(&self) -> Option<Project> {
+        Some(self.item.project())
+    }
+
+    fn track(&self) -> Option<&Track> {
+        self.item.track()
+    }
+
+    fn convert_discrete_value_to_unit_value(
+        &self,
+        value: u32,
+        _: ControlContext,
+    ) -> Result<UnitValue, &'static str> {
+        Ok(convert_discrete_to_unit_value(value, self.take_count()))
+    }
+
+    fn text_value(&self, _: ControlContext) -> Option<Cow<'static, str>> {
+        Some(self.item.item()?.active_take()?.name().into())
+    }
+
+    fn numeric_value(&self, _: ControlContext) -> Option<NumericValue> {
+        let index = self.active_take_index()?;
+        Some(NumericValue::Discrete(index as i32 + 1))
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::BrowseTakes)
+    }
+}
+
+impl<'a> Target<'a> for BrowseTakesTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        let index = self.active_take_index()?;
+        let max_value = self.take_count().saturating_sub(1);
+        Some(AbsoluteValue::Discrete(Fraction::new(index, max_value)))
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+pub const BROWSE_TAKES_TARGET: TargetTypeDef = TargetTypeDef {
+    section: TargetSection::Item,
+    name: "Browse takes",
+    short_name: "Browse takes",
+    hint: AUTOMATIC_FEEDBACK_VIA_POLLING_ONLY,
+    supports_track: true,
+    ..DEFAULT_TARGET
+};
@@ -0,0 +1,115 @@
+use crate::domain::ui_util::convert_bool_to_unit_value;
+use crate::domain::{
+    bookmark_is_at_play_position, delete_bookmark, format_value_as_on_off, BookmarkAddress,
+    CompartmentKind, ControlContext, ExtendedProcessorContext, FeedbackResolution, HitResponse,
+    MappingControlContext, RealearnTarget, ReaperTarget, ReaperTargetType, TargetCharacter,
+    TargetSection, TargetTypeDef, UnresolvedBookmarkAddress, UnresolvedReaperTargetDef,
+    AUTOMATIC_FEEDBACK_VIA_POLLING_ONLY, DEFAULT_TARGET,
+};
+use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, Target, UnitValue};
+use reaper_high::Project;
+use std::borrow::Cow;
+use std::time::Duration;
+
+#[derive(Debug)]
+pub struct UnresolvedDeleteBookmarkTarget {
+    pub bookmark: UnresolvedBookmarkAddress,
+    pub tolerance: Duration,
+}
+
+impl UnresolvedReaperTargetDef for UnresolvedDeleteBookmarkTarget {
+    fn resolve(
+        &self,
+        context: ExtendedProcessorContext,
+        _: CompartmentKind,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        let project = context.context().project_or_current_project();
+        Ok(vec![ReaperTarget::DeleteBookmark(DeleteBookmarkTarget {
+            project,
+            address: self.bookmark.resolve(project)?,
+            tolerance: self.tolerance,
+        })])
+    }
+
+    fn feedback_resolution(&self) -> Option<FeedbackResolution> {
+        Some(FeedbackResolution::High)
+    }
+}
+
+/// Deletes a marker or region.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeleteBookmarkTarget {
+    pub project: Project,
+    pub address: BookmarkAddress,
+    pub tolerance: Duration,
+}
+
+impl RealearnTarget for DeleteBookmarkTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        (
+            ControlType::AbsoluteContinuousRetriggerable,
+            TargetCharacter::Trigger,
+        )
+    }
+
+    fn format_value(&self, value: UnitValue, _: ControlContext) -> String {
+        format_value_as_on_off(value).to_string()
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        _: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        if value.to_unit_value()?.is_zero() {
+            return Ok(HitResponse::ignored());
+        }
+        let index = self
+            .address
+            .find_index(self.project, self.tolerance)
+            .ok_or("bookmark not found")?;
+        delete_bookmark(self.project, index)?;
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        self.project.is_available()
+    }
+
+    fn project(&self) -> Option<Project> {
+        Some(self.project)
+    }
+
+    fn text_value(&self, context: ControlContext) -> Option<Cow<'static, str>> {
+        Some(format_value_as_on_off(self.current_value(context)?.to_unit_value()).into())
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::DeleteBookmark)
+    }
+}
+
+impl<'a> Target<'a> for DeleteBookmarkTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        let val = convert_bool_to_unit_value(bookmark_is_at_play_position(
+            self.project,
+            self.address,
+            self.tolerance,
+        ));
+        Some(AbsoluteValue::Continuous(val))
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+pub const DELETE_BOOKMARK_TARGET: TargetTypeDef = TargetTypeDef {
+    section: TargetSection::Bookmark,
+    name: "Delete",
+    short_name: "Delete bookmark",
+    hint: AUTOMATIC_FEEDBACK_VIA_POLLING_ONLY,
+    ..DEFAULT_TARGET
+};
@@ -0,0 +1,118 @@
+use crate::domain::ui_util::convert_bool_to_unit_value;
+use crate::domain::{
+    format_value_as_on_off, CompartmentKind, ControlContext, EnvelopeRef, ExtendedProcessorContext,
+    FeedbackResolution, FxParameterDescriptor, HitResponse, MappingControlContext, RealearnTarget,
+    ReaperTarget, ReaperTargetType, TargetCharacter, TargetSection, TargetTypeDef, TrackDescriptor,
+    UnresolvedReaperTargetDef, VirtualEnvelope, AUTOMATIC_FEEDBACK_VIA_POLLING_ONLY,
+    DEFAULT_TARGET,
+};
+use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, Target, UnitValue};
+use reaper_high::{Fx, Project, Track};
+use std::borrow::Cow;
+
+#[derive(Debug)]
+pub struct UnresolvedEnvelopeArmStateTarget {
+    pub envelope: VirtualEnvelope,
+}
+
+impl UnresolvedReaperTargetDef for UnresolvedEnvelopeArmStateTarget {
+    fn resolve(
+        &self,
+        context: ExtendedProcessorContext,
+        compartment: CompartmentKind,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        let targets = self
+            .envelope
+            .resolve(context, compartment)?
+            .into_iter()
+            .map(|envelope| ReaperTarget::EnvelopeArmState(EnvelopeArmStateTarget { envelope }))
+            .collect();
+        Ok(targets)
+    }
+
+    fn feedback_resolution(&self) -> Option<FeedbackResolution> {
+        Some(FeedbackResolution::High)
+    }
+
+    fn track_descriptor(&self) -> Option<&TrackDescriptor> {
+        self.envelope.track_descriptor()
+    }
+
+    fn fx_parameter_descriptor(&self) -> Option<&FxParameterDescriptor> {
+        self.envelope.fx_parameter_descriptor()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EnvelopeArmStateTarget {
+    pub envelope: EnvelopeRef,
+}
+
+impl RealearnTarget for EnvelopeArmStateTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        (ControlType::AbsoluteContinuous, TargetCharacter::Switch)
+    }
+
+    fn format_value(&self, value: UnitValue, _: ControlContext) -> String {
+        format_value_as_on_off(value).to_string()
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        _: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        let envelope = self.envelope.envelope().ok_or("envelope not found")?;
+        envelope.set_armed(!value.to_unit_value()?.is_zero());
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        self.envelope.envelope().is_some()
+    }
+
+    fn project(&self) -> Option<Project> {
+        self.envelope.project()
+    }
+
+    fn track(&self) -> Option<&Track> {
+        self.envelope.track()
+    }
+
+    fn fx(&self) -> Option<&Fx> {
+        self.envelope.fx()
+    }
+
+    fn text_value(&self, context: ControlContext) -> Option<Cow<'static, str>> {
+        Some(format_value_as_on_off(self.current_value(context)?.to_unit_value()).into())
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::EnvelopeArmState)
+    }
+}
+
+impl<'a> Target<'a> for EnvelopeArmStateTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        let envelope = self.envelope.envelope()?;
+        let val = convert_bool_to_unit_value(envelope.is_armed());
+        Some(AbsoluteValue::Continuous(val))
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+pub const ENVELOPE_ARM_STATE_TARGET: TargetTypeDef = TargetTypeDef {
+    section: TargetSection::Envelope,
+    name: "Arm/disarm",
+    short_name: "Envelope arm",
+    hint: AUTOMATIC_FEEDBACK_VIA_POLLING_ONLY,
+    supports_track: true,
+    supports_fx: true,
+    supports_fx_parameter: true,
+    ..DEFAULT_TARGET
+};
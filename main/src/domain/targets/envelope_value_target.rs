@@ -0,0 +1,230 @@
+use crate::domain::ui_util::{
+    format_value_as_db, format_value_as_db_without_unit, parse_unit_value_from_percentage,
+    parse_value_from_db, volume_unit_value,
+};
+use crate::domain::{
+    AutomationEnvelope, CompartmentKind, ControlContext, EnvelopeRef, EnvelopeValueRange,
+    ExtendedProcessorContext, FeedbackResolution, FxParameterDescriptor, HitResponse,
+    MappingControlContext, RealearnTarget, ReaperTarget, ReaperTargetType, TargetCharacter,
+    TargetSection, TargetTypeDef, TrackDescriptor, UnresolvedReaperTargetDef, VirtualEnvelope,
+    AUTOMATIC_FEEDBACK_VIA_POLLING_ONLY, DEFAULT_TARGET,
+};
+use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, NumericValue, Target, UnitValue};
+use helgobox_api::persistence::EnvelopePosition;
+use reaper_high::{Fx, Project, SliderVolume, Track};
+use reaper_medium::ReaperVolumeValue;
+use std::borrow::Cow;
+
+#[derive(Debug)]
+pub struct UnresolvedEnvelopeValueTarget {
+    pub envelope: VirtualEnvelope,
+    pub position: EnvelopePosition,
+}
+
+impl UnresolvedReaperTargetDef for UnresolvedEnvelopeValueTarget {
+    fn resolve(
+        &self,
+        context: ExtendedProcessorContext,
+        compartment: CompartmentKind,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        let targets = self
+            .envelope
+            .resolve(context, compartment)?
+            .into_iter()
+            .map(|envelope| {
+                ReaperTarget::EnvelopeValue(EnvelopeValueTarget {
+                    envelope,
+                    position: self.position,
+                })
+            })
+            .collect();
+        Ok(targets)
+    }
+
+    fn feedback_resolution(&self) -> Option<FeedbackResolution> {
+        Some(FeedbackResolution::High)
+    }
+
+    fn track_descriptor(&self) -> Option<&TrackDescriptor> {
+        self.envelope.track_descriptor()
+    }
+
+    fn fx_parameter_descriptor(&self) -> Option<&FxParameterDescriptor> {
+        self.envelope.fx_parameter_descriptor()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EnvelopeValueTarget {
+    pub envelope: EnvelopeRef,
+    pub position: EnvelopePosition,
+}
+
+impl EnvelopeValueTarget {
+    /// Returns the position in seconds at which this target reads and writes the envelope value.
+    fn time(&self) -> f64 {
+        let Some(project) = self.envelope.project() else {
+            return 0.0;
+        };
+        let pos = match self.position {
+            EnvelopePosition::EditCursor => project.edit_cursor_position(),
+            EnvelopePosition::PlayPosition => project.play_or_edit_cursor_position(),
+        };
+        pos.unwrap_or_default().get()
+    }
+
+    fn is_volume(&self) -> bool {
+        self.envelope
+            .envelope()
+            .is_some_and(|e| e.range() == EnvelopeValueRange::Volume)
+    }
+
+    fn current_unit_value(&self, envelope: &AutomationEnvelope) -> UnitValue {
+        let value = envelope.value_at(self.time());
+        match envelope.range() {
+            EnvelopeValueRange::Volume => volume_unit_value(envelope_volume(value)),
+            EnvelopeValueRange::Linear { min, max } => {
+                UnitValue::new_clamped((value - min) / (max - min))
+            }
+        }
+    }
+}
+
+impl RealearnTarget for EnvelopeValueTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        let control_type = match self.position {
+            EnvelopePosition::EditCursor => ControlType::AbsoluteContinuous,
+            // Inserting a point at the play position should also work if the value doesn't change.
+            EnvelopePosition::PlayPosition => ControlType::AbsoluteContinuousRetriggerable,
+        };
+        (control_type, TargetCharacter::Continuous)
+    }
+
+    fn parse_as_value(&self, text: &str, _: ControlContext) -> Result<UnitValue, &'static str> {
+        if self.is_volume() {
+            parse_value_from_db(text)
+        } else {
+            parse_unit_value_from_percentage(text)
+        }
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue, context: ControlContext) -> String {
+        if self.is_volume() {
+            format_value_as_db_without_unit(value)
+        } else {
+            self.format_as_discrete_or_percentage(value, context)
+        }
+    }
+
+    fn hide_formatted_value(&self, _: ControlContext) -> bool {
+        self.is_volume()
+    }
+
+    fn value_unit(&self, context: ControlContext) -> &'static str {
+        if self.is_volume() {
+            "dB"
+        } else {
+            self.value_unit_default(context)
+        }
+    }
+
+    fn format_value(&self, value: UnitValue, context: ControlContext) -> String {
+        if self.is_volume() {
+            format_value_as_db(value)
+        } else {
+            self.format_value_generic(value, context)
+        }
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        _: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        let value = value.to_unit_value()?;
+        let envelope = self.envelope.envelope().ok_or("envelope not found")?;
+        let envelope_value = match envelope.range() {
+            EnvelopeValueRange::Volume => {
+                let volume = SliderVolume::try_from_normalized_slider_value(value.get())
+                    .unwrap_or(SliderVolume::MIN);
+                volume.reaper_value().get()
+            }
+            EnvelopeValueRange::Linear { min, max } => min + value.get() * (max - min),
+        };
+        envelope.set_value_at(self.time(), envelope_value);
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        self.envelope.envelope().is_some()
+    }
+
+    fn project(&self) -> Option<Project> {
+        self.envelope.project()
+    }
+
+    fn track(&self) -> Option<&Track> {
+        self.envelope.track()
+    }
+
+    fn fx(&self) -> Option<&Fx> {
+        self.envelope.fx()
+    }
+
+    fn text_value(&self, context: ControlContext) -> Option<Cow<'static, str>> {
+        let envelope = self.envelope.envelope()?;
+        let text = match envelope.range() {
+            EnvelopeValueRange::Volume => {
+                envelope_volume(envelope.value_at(self.time())).to_string()
+            }
+            EnvelopeValueRange::Linear { .. } => {
+                self.format_value(self.current_unit_value(&envelope), context)
+            }
+        };
+        Some(text.into())
+    }
+
+    fn numeric_value(&self, _: ControlContext) -> Option<NumericValue> {
+        let envelope = self.envelope.envelope()?;
+        let value = envelope.value_at(self.time());
+        let value = match envelope.range() {
+            EnvelopeValueRange::Volume => envelope_volume(value).db().get(),
+            EnvelopeValueRange::Linear { .. } => value,
+        };
+        Some(NumericValue::Decimal(value))
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::EnvelopeValue)
+    }
+}
+
+impl<'a> Target<'a> for EnvelopeValueTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        let envelope = self.envelope.envelope()?;
+        Some(AbsoluteValue::Continuous(
+            self.current_unit_value(&envelope),
+        ))
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+fn envelope_volume(value: f64) -> SliderVolume {
+    SliderVolume::from_reaper_value(ReaperVolumeValue::new_panic(value.max(0.0)))
+}
+
+pub const ENVELOPE_VALUE_TARGET: TargetTypeDef = TargetTypeDef {
+    section: TargetSection::Envelope,
+    name: "Set value",
+    short_name: "Envelope value",
+    hint: AUTOMATIC_FEEDBACK_VIA_POLLING_ONLY,
+    supports_track: true,
+    supports_fx: true,
+    supports_fx_parameter: true,
+    ..DEFAULT_TARGET
+};
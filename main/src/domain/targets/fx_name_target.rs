@@ -0,0 +1,162 @@
+use crate::domain::ui_util::convert_bool_to_unit_value;
+use crate::domain::{
+    activate_fx_preset_by_name, format_value_as_on_off, fx_alias, set_fx_alias, CompartmentKind,
+    CompoundChangeEvent, ControlContext, ExtendedProcessorContext, FeedbackResolution,
+    FxDescriptor, HitResponse, MappingControlContext, RealearnTarget, ReaperTarget,
+    ReaperTargetType, TargetCharacter, TargetSection, TargetTypeDef, UnresolvedReaperTargetDef,
+    DEFAULT_TARGET,
+};
+use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, Target, UnitValue};
+use helgobox_api::persistence::FxNameKind;
+use reaper_high::{ChangeEvent, Fx, Project, Track};
+use std::borrow::Cow;
+
+#[derive(Debug)]
+pub struct UnresolvedFxNameTarget {
+    pub fx_descriptor: FxDescriptor,
+    pub kind: FxNameKind,
+}
+
+impl UnresolvedReaperTargetDef for UnresolvedFxNameTarget {
+    fn resolve(
+        &self,
+        context: ExtendedProcessorContext,
+        compartment: CompartmentKind,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        Ok(self
+            .fx_descriptor
+            .resolve(context, compartment)?
+            .into_iter()
+            .map(|fx| {
+                ReaperTarget::FxName(FxNameTarget {
+                    fx,
+                    kind: self.kind,
+                })
+            })
+            .collect())
+    }
+
+    fn fx_descriptor(&self) -> Option<&FxDescriptor> {
+        Some(&self.fx_descriptor)
+    }
+
+    fn feedback_resolution(&self) -> Option<FeedbackResolution> {
+        // REAPER doesn't notify us when an FX gets renamed
+        match self.kind {
+            FxNameKind::Alias => Some(FeedbackResolution::High),
+            FxNameKind::Preset => None,
+        }
+    }
+}
+
+/// Sets the alias of an FX or activates an FX preset by name, using the text which comes with the
+/// control value (e.g. an OSC string argument).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FxNameTarget {
+    pub fx: Fx,
+    pub kind: FxNameKind,
+}
+
+impl RealearnTarget for FxNameTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        (
+            ControlType::AbsoluteContinuousRetriggerable,
+            TargetCharacter::Trigger,
+        )
+    }
+
+    fn format_value(&self, value: UnitValue, _: ControlContext) -> String {
+        format_value_as_on_off(value).to_string()
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        context: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        if value.to_unit_value()?.is_zero() {
+            return Ok(HitResponse::ignored());
+        }
+        let name = context.incoming_text.ok_or("no text received")?;
+        match self.kind {
+            FxNameKind::Alias => set_fx_alias(&self.fx, name)?,
+            FxNameKind::Preset => activate_fx_preset_by_name(&self.fx, name)?,
+        }
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn consumes_incoming_control_data(&self) -> bool {
+        true
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        self.fx.is_available()
+    }
+
+    fn project(&self) -> Option<Project> {
+        self.fx.project()
+    }
+
+    fn track(&self) -> Option<&Track> {
+        self.fx.track()
+    }
+
+    fn fx(&self) -> Option<&Fx> {
+        Some(&self.fx)
+    }
+
+    fn process_change_event(
+        &self,
+        evt: CompoundChangeEvent,
+        _: ControlContext,
+    ) -> (bool, Option<AbsoluteValue>) {
+        match evt {
+            CompoundChangeEvent::Reaper(ChangeEvent::FxPresetChanged(e))
+                if self.kind == FxNameKind::Preset && e.fx == self.fx =>
+            {
+                (true, None)
+            }
+            _ => (false, None),
+        }
+    }
+
+    fn text_value(&self, _: ControlContext) -> Option<Cow<'static, str>> {
+        let name = match self.kind {
+            FxNameKind::Alias => fx_alias(&self.fx)?,
+            FxNameKind::Preset => self.fx.preset_name()?.into_string(),
+        };
+        Some(name.into())
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::FxName)
+    }
+}
+
+impl<'a> Target<'a> for FxNameTarget {
+    type Context = ControlContext<'a>;
+
+    /// "On" if the FX has an alias or a preset loaded, respectively.
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        let has_name = match self.kind {
+            FxNameKind::Alias => fx_alias(&self.fx).is_some(),
+            FxNameKind::Preset => self.fx.preset_name().is_some(),
+        };
+        Some(AbsoluteValue::Continuous(convert_bool_to_unit_value(
+            has_name,
+        )))
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+pub const FX_NAME_TARGET: TargetTypeDef = TargetTypeDef {
+    section: TargetSection::Fx,
+    name: "Set name",
+    short_name: "FX name",
+    supports_track: true,
+    supports_fx: true,
+    ..DEFAULT_TARGET
+};
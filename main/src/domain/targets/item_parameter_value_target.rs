@@ -0,0 +1,297 @@
+use crate::domain::ui_util::{
+    format_value_as_db, format_value_as_db_without_unit, parse_unit_value_from_percentage,
+    parse_value_from_db, volume_unit_value,
+};
+use crate::domain::{
+    format_value_as_on_off, low_reaper, mute_unit_value, CompartmentKind, ControlContext,
+    ExtendedProcessorContext, FeedbackResolution, HitResponse, Item, ItemRef,
+    MappingControlContext, RealearnTarget, ReaperTarget, ReaperTargetType, TargetCharacter,
+    TargetSection, TargetTypeDef, TrackDescriptor, UnresolvedReaperTargetDef, VirtualItem,
+    AUTOMATIC_FEEDBACK_VIA_POLLING_ONLY, DEFAULT_TARGET,
+};
+use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, NumericValue, Target, UnitValue};
+use helgobox_api::persistence::ItemParameter;
+use reaper_high::{Project, SliderVolume, Track};
+use reaper_medium::ReaperVolumeValue;
+use std::borrow::Cow;
+use std::ptr::{null, null_mut};
+
+#[derive(Debug)]
+pub struct UnresolvedItemParameterValueTarget {
+    pub track_descriptor: TrackDescriptor,
+    pub item: VirtualItem,
+    pub parameter: ItemParameter,
+}
+
+impl UnresolvedReaperTargetDef for UnresolvedItemParameterValueTarget {
+    fn resolve(
+        &self,
+        context: ExtendedProcessorContext,
+        compartment: CompartmentKind,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        let targets = self
+            .item
+            .resolve(&self.track_descriptor, context, compartment)?
+            .into_iter()
+            .map(|item| {
+                ReaperTarget::ItemParameterValue(ItemParameterValueTarget {
+                    item,
+                    parameter: self.parameter,
+                })
+            })
+            .collect();
+        Ok(targets)
+    }
+
+    fn feedback_resolution(&self) -> Option<FeedbackResolution> {
+        Some(FeedbackResolution::High)
+    }
+
+    fn track_descriptor(&self) -> Option<&TrackDescriptor> {
+        Some(&self.track_descriptor)
+    }
+
+    fn virtual_item(&self) -> Option<&VirtualItem> {
+        Some(&self.item)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ItemParameterValueTarget {
+    pub item: ItemRef,
+    pub parameter: ItemParameter,
+}
+
+impl RealearnTarget for ItemParameterValueTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        match self.parameter {
+            // There's no natural maximum item position, so we only support moving the item
+            ItemParameter::Position => (ControlType::Relative, TargetCharacter::Discrete),
+            ItemParameter::Mute => (ControlType::AbsoluteContinuous, TargetCharacter::Switch),
+            _ => (ControlType::AbsoluteContinuous, TargetCharacter::Continuous),
+        }
+    }
+
+    fn parse_as_value(&self, text: &str, _: ControlContext) -> Result<UnitValue, &'static str> {
+        match self.parameter {
+            ItemParameter::Volume => parse_value_from_db(text),
+            _ => parse_unit_value_from_percentage(text),
+        }
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue, context: ControlContext) -> String {
+        match self.parameter {
+            ItemParameter::Volume => format_value_as_db_without_unit(value),
+            _ => self.format_as_discrete_or_percentage(value, context),
+        }
+    }
+
+    fn hide_formatted_value(&self, _: ControlContext) -> bool {
+        self.parameter == ItemParameter::Volume
+    }
+
+    fn value_unit(&self, context: ControlContext) -> &'static str {
+        match self.parameter {
+            ItemParameter::Volume => "dB",
+            _ => self.value_unit_default(context),
+        }
+    }
+
+    fn format_value(&self, value: UnitValue, context: ControlContext) -> String {
+        match self.parameter {
+            ItemParameter::Volume => format_value_as_db(value),
+            ItemParameter::Mute => format_value_as_on_off(value).to_string(),
+            _ => self.format_value_generic(value, context),
+        }
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        _: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        let item = self.item.item().ok_or("item not found")?;
+        let unit_value = value.to_unit_value();
+        match self.parameter {
+            ItemParameter::Position => {
+                let beats = position_increment_in_beats(value)?;
+                item.set_position(move_by_beats(
+                    self.item.project(),
+                    item.position(),
+                    beats as f64,
+                ));
+            }
+            ItemParameter::Volume => {
+                let volume = SliderVolume::try_from_normalized_slider_value(unit_value?.get())
+                    .unwrap_or(SliderVolume::MIN);
+                item.set_volume(volume.reaper_value().get());
+            }
+            ItemParameter::Mute => {
+                item.set_muted(!unit_value?.is_zero());
+            }
+            ItemParameter::FadeInLength => {
+                item.set_fade_in_length(fade_length_from_unit_value(item.length(), unit_value?));
+            }
+            ItemParameter::FadeOutLength => {
+                item.set_fade_out_length(fade_length_from_unit_value(item.length(), unit_value?));
+            }
+        }
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        self.item.item().is_some()
+    }
+
+    fn project(&self) -> Option<Project> {
+        Some(self.item.project())
+    }
+
+    fn track(&self) -> Option<&Track> {
+        self.item.track()
+    }
+
+    fn text_value(&self, _: ControlContext) -> Option<Cow<'static, str>> {
+        let item = self.item.item()?;
+        let text = match self.parameter {
+            ItemParameter::Position => format!("{} s", format_seconds(item.position())),
+            ItemParameter::Volume => item_volume(&item).to_string(),
+            ItemParameter::Mute => format_value_as_on_off(mute_unit_value(item.is_muted())).into(),
+            ItemParameter::FadeInLength => format!("{} s", format_seconds(item.fade_in_length())),
+            ItemParameter::FadeOutLength => {
+                format!("{} s", format_seconds(item.fade_out_length()))
+            }
+        };
+        Some(text.into())
+    }
+
+    fn numeric_value(&self, _: ControlContext) -> Option<NumericValue> {
+        let item = self.item.item()?;
+        let value = match self.parameter {
+            ItemParameter::Position => item.position(),
+            ItemParameter::Volume => item_volume(&item).db().get(),
+            ItemParameter::Mute => return None,
+            ItemParameter::FadeInLength => item.fade_in_length(),
+            ItemParameter::FadeOutLength => item.fade_out_length(),
+        };
+        Some(NumericValue::Decimal(value))
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::ItemParameterValue)
+    }
+}
+
+impl<'a> Target<'a> for ItemParameterValueTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        let item = self.item.item()?;
+        let val = match self.parameter {
+            ItemParameter::Position => return None,
+            ItemParameter::Volume => volume_unit_value(item_volume(&item)),
+            ItemParameter::Mute => mute_unit_value(item.is_muted()),
+            ItemParameter::FadeInLength => fade_unit_value(&item, item.fade_in_length()),
+            ItemParameter::FadeOutLength => fade_unit_value(&item, item.fade_out_length()),
+        };
+        Some(AbsoluteValue::Continuous(val))
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+fn item_volume(item: &Item) -> SliderVolume {
+    SliderVolume::from_reaper_value(ReaperVolumeValue::new_panic(item.volume().max(0.0)))
+}
+
+/// Each increment moves the item by one beat.
+fn position_increment_in_beats(value: ControlValue) -> Result<i32, &'static str> {
+    match value {
+        ControlValue::RelativeDiscrete(inc) => Ok(inc.get()),
+        ControlValue::RelativeContinuous(inc) => Ok(inc.to_discrete_increment().get()),
+        _ => Err("needs to be controlled relatively"),
+    }
+}
+
+/// Returns the position which is the given number of beats away from the given position,
+/// taking the tempo map into account.
+fn move_by_beats(project: Project, seconds: f64, beats: f64) -> f64 {
+    let raw_project = project.raw().as_ptr();
+    let mut full_beats = 0.0;
+    unsafe {
+        low_reaper().TimeMap2_timeToBeats(
+            raw_project,
+            seconds,
+            null_mut(),
+            null_mut(),
+            &mut full_beats,
+            null_mut(),
+        );
+    }
+    let new_seconds =
+        unsafe { low_reaper().TimeMap2_beatsToTime(raw_project, full_beats + beats, null()) };
+    new_seconds.max(0.0)
+}
+
+/// Fade lengths are expressed relative to the item length.
+fn fade_unit_value(item: &Item, seconds: f64) -> UnitValue {
+    fade_length_to_unit_value(item.length(), seconds)
+}
+
+fn fade_length_to_unit_value(item_length: f64, seconds: f64) -> UnitValue {
+    if item_length <= 0.0 {
+        return UnitValue::MIN;
+    }
+    UnitValue::new_clamped(seconds / item_length)
+}
+
+fn fade_length_from_unit_value(item_length: f64, value: UnitValue) -> f64 {
+    value.get() * item_length
+}
+
+fn format_seconds(seconds: f64) -> String {
+    format!("{seconds:.3}")
+}
+
+pub const ITEM_PARAMETER_VALUE_TARGET: TargetTypeDef = TargetTypeDef {
+    section: TargetSection::Item,
+    name: "Set parameter value",
+    short_name: "Item parameter",
+    hint: AUTOMATIC_FEEDBACK_VIA_POLLING_ONLY,
+    supports_track: true,
+    ..DEFAULT_TARGET
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helgoboss_learn::DiscreteIncrement;
+
+    #[test]
+    fn move_position_by_one_beat_per_increment() {
+        // Given
+        let discrete = ControlValue::RelativeDiscrete(DiscreteIncrement::new(-3));
+        let absolute = ControlValue::AbsoluteContinuous(UnitValue::new(0.5));
+        // When
+        // Then
+        assert_eq!(position_increment_in_beats(discrete), Ok(-3));
+        assert!(position_increment_in_beats(absolute).is_err());
+    }
+
+    #[test]
+    fn convert_fade_length_relative_to_item_length() {
+        // Given
+        let item_length = 8.0;
+        // When
+        let unit_value = fade_length_to_unit_value(item_length, 2.0);
+        let too_long = fade_length_to_unit_value(item_length, 10.0);
+        let empty_item = fade_length_to_unit_value(0.0, 2.0);
+        // Then
+        assert_eq!(unit_value, UnitValue::new(0.25));
+        assert_eq!(too_long, UnitValue::MAX);
+        assert_eq!(empty_item, UnitValue::MIN);
+        assert_eq!(fade_length_from_unit_value(item_length, unit_value), 2.0);
+    }
+}
@@ -160,6 +160,9 @@ pub use load_mapping_snapshot_target::*;
 mod take_mapping_snapshot_target;
 pub use take_mapping_snapshot_target::*;
 
+mod morph_mapping_snapshots_target;
+pub use morph_mapping_snapshots_target::*;
+
 mod enable_mappings_target;
 pub use enable_mappings_target::*;
 
@@ -201,3 +204,48 @@ pub use compartment_parameter_value_target::*;
 
 mod stream_deck_brightness_target;
 pub use stream_deck_brightness_target::*;
+
+mod item_parameter_value_target;
+pub use item_parameter_value_target::*;
+
+mod take_parameter_value_target;
+pub use take_parameter_value_target::*;
+
+mod browse_takes_target;
+pub use browse_takes_target::*;
+
+mod envelope_value_target;
+pub use envelope_value_target::*;
+
+mod browse_envelope_points_target;
+pub use browse_envelope_points_target::*;
+
+mod envelope_arm_state_target;
+pub use envelope_arm_state_target::*;
+
+mod add_bookmark_target;
+pub use add_bookmark_target::*;
+
+mod rename_bookmark_target;
+pub use rename_bookmark_target::*;
+
+mod delete_bookmark_target;
+pub use delete_bookmark_target::*;
+
+mod track_record_input_target;
+pub use track_record_input_target::*;
+
+mod track_record_mode_target;
+pub use track_record_mode_target::*;
+
+mod track_input_fx_enable_target;
+pub use track_input_fx_enable_target::*;
+
+mod track_name_target;
+pub use track_name_target::*;
+
+mod track_color_target;
+pub use track_color_target::*;
+
+mod fx_name_target;
+pub use fx_name_target::*;
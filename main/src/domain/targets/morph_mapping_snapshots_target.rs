@@ -0,0 +1,161 @@
+use crate::domain::{
+    morph_target_values, CompartmentKind, ControlContext, ControlLogContext,
+    ExtendedProcessorContext, HitInstruction, HitInstructionContext, HitInstructionResponse,
+    HitResponse, MappingControlContext, MappingSnapshotId, RealearnTarget, ReaperTarget,
+    ReaperTargetType, TagScope, TargetCharacter, TargetSection, TargetTypeDef,
+    UnresolvedReaperTargetDef, DEFAULT_TARGET,
+};
+use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, Target, UnitValue};
+
+#[derive(Debug)]
+pub struct UnresolvedMorphMappingSnapshotsTarget {
+    pub compartment: CompartmentKind,
+    /// Mappings which are in the snapshots but not in the tag scope will be ignored.
+    pub scope: TagScope,
+    /// If `false`, mappings which are contained in the snapshots but are now inactive
+    /// due to conditional activation will be ignored.
+    pub active_mappings_only: bool,
+    pub snapshot_a: MappingSnapshotId,
+    pub snapshot_b: MappingSnapshotId,
+}
+
+impl UnresolvedReaperTargetDef for UnresolvedMorphMappingSnapshotsTarget {
+    fn resolve(
+        &self,
+        _: ExtendedProcessorContext,
+        _: CompartmentKind,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        Ok(vec![ReaperTarget::MorphMappingSnapshots(
+            MorphMappingSnapshotsTarget {
+                compartment: self.compartment,
+                scope: self.scope.clone(),
+                active_mappings_only: self.active_mappings_only,
+                snapshot_a: self.snapshot_a.clone(),
+                snapshot_b: self.snapshot_b.clone(),
+            },
+        )])
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MorphMappingSnapshotsTarget {
+    pub compartment: CompartmentKind,
+    pub scope: TagScope,
+    pub active_mappings_only: bool,
+    pub snapshot_a: MappingSnapshotId,
+    pub snapshot_b: MappingSnapshotId,
+}
+
+impl RealearnTarget for MorphMappingSnapshotsTarget {
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::MorphMappingSnapshots)
+    }
+
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        (ControlType::AbsoluteContinuous, TargetCharacter::Continuous)
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        _: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        let instruction = MorphMappingSnapshotsInstruction {
+            // Cloning is not ideal because morphing can happen quite often (fader movements).
+            // But the tag scope and snapshot IDs are small, so it should be okay.
+            compartment: self.compartment,
+            scope: self.scope.clone(),
+            active_mappings_only: self.active_mappings_only,
+            snapshot_a: self.snapshot_a.clone(),
+            snapshot_b: self.snapshot_b.clone(),
+            position: value.to_unit_value()?,
+        };
+        Ok(HitResponse::hit_instruction(Box::new(instruction)))
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        true
+    }
+}
+
+impl<'a> Target<'a> for MorphMappingSnapshotsTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        // The morph position is not memorized. We could try to derive it from the current target
+        // values of the affected mappings, but that would be ambiguous.
+        None
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+pub const MORPH_MAPPING_SNAPSHOTS_TARGET: TargetTypeDef = TargetTypeDef {
+    section: TargetSection::ReaLearn,
+    name: "Morph between mapping snapshots",
+    short_name: "Morph mapping snapshots",
+    supports_tags: true,
+    ..DEFAULT_TARGET
+};
+
+struct MorphMappingSnapshotsInstruction {
+    compartment: CompartmentKind,
+    scope: TagScope,
+    active_mappings_only: bool,
+    snapshot_a: MappingSnapshotId,
+    snapshot_b: MappingSnapshotId,
+    position: UnitValue,
+}
+
+impl HitInstruction for MorphMappingSnapshotsInstruction {
+    fn execute(self: Box<Self>, context: HitInstructionContext) -> HitInstructionResponse {
+        let unit = context.control_context.unit.borrow();
+        let snapshot_container = unit.mapping_snapshot_container(self.compartment);
+        let (Some(snapshot_a), Some(snapshot_b)) = (
+            snapshot_container.find_snapshot_by_id(&self.snapshot_a),
+            snapshot_container.find_snapshot_by_id(&self.snapshot_b),
+        ) else {
+            return HitInstructionResponse::Ignored;
+        };
+        let results = context
+            .mappings
+            .values_mut()
+            .filter_map(|m| {
+                // Same filter rules as for loading a snapshot
+                if !m.control_is_enabled() {
+                    return None;
+                }
+                if self.scope.has_tags() && !m.has_any_tag(&self.scope.tags) {
+                    return None;
+                }
+                if self.active_mappings_only && !m.is_active() {
+                    return None;
+                }
+                // Mappings which are not contained in both snapshots are left alone
+                let value_a = snapshot_a.find_target_value_by_mapping_id(m.id())?;
+                let value_b = snapshot_b.find_target_value_by_mapping_id(m.id())?;
+                let morphed_value = morph_target_values(value_a, value_b, self.position);
+                context
+                    .domain_event_handler
+                    .notify_mapping_matched(m.compartment(), m.id());
+                let res = m.control_from_target_directly(
+                    context.control_context,
+                    context.processor_context,
+                    ControlValue::from_absolute(morphed_value),
+                    context.basic_settings.target_control_logger(
+                        context.processor_context.control_context.unit,
+                        ControlLogContext::LoadingMappingSnapshot,
+                        m.qualified_id(),
+                    ),
+                );
+                if res.at_least_one_target_was_reached {
+                    m.update_last_non_performance_target_value(morphed_value);
+                }
+                Some(res)
+            })
+            .collect();
+        HitInstructionResponse::CausedEffect(results)
+    }
+}
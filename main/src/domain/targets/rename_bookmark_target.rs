@@ -0,0 +1,123 @@
+use crate::domain::ui_util::convert_bool_to_unit_value;
+use crate::domain::{
+    bookmark_is_at_play_position, format_value_as_on_off, rename_bookmark, BookmarkAddress,
+    CompartmentKind, ControlContext, ExtendedProcessorContext, FeedbackResolution, HitResponse,
+    MappingControlContext, RealearnTarget, ReaperTarget, ReaperTargetType, TargetCharacter,
+    TargetSection, TargetTypeDef, UnresolvedBookmarkAddress, UnresolvedReaperTargetDef,
+    AUTOMATIC_FEEDBACK_VIA_POLLING_ONLY, DEFAULT_TARGET,
+};
+use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, Target, UnitValue};
+use reaper_high::Project;
+use std::borrow::Cow;
+use std::time::Duration;
+
+#[derive(Debug)]
+pub struct UnresolvedRenameBookmarkTarget {
+    pub bookmark: UnresolvedBookmarkAddress,
+    pub tolerance: Duration,
+}
+
+impl UnresolvedReaperTargetDef for UnresolvedRenameBookmarkTarget {
+    fn resolve(
+        &self,
+        context: ExtendedProcessorContext,
+        _: CompartmentKind,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        let project = context.context().project_or_current_project();
+        Ok(vec![ReaperTarget::RenameBookmark(RenameBookmarkTarget {
+            project,
+            address: self.bookmark.resolve(project)?,
+            tolerance: self.tolerance,
+        })])
+    }
+
+    fn feedback_resolution(&self) -> Option<FeedbackResolution> {
+        Some(FeedbackResolution::High)
+    }
+}
+
+/// Renames a marker or region, using the text which comes with the control value (e.g. an OSC
+/// string argument).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RenameBookmarkTarget {
+    pub project: Project,
+    pub address: BookmarkAddress,
+    pub tolerance: Duration,
+}
+
+impl RealearnTarget for RenameBookmarkTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        (
+            ControlType::AbsoluteContinuousRetriggerable,
+            TargetCharacter::Trigger,
+        )
+    }
+
+    fn format_value(&self, value: UnitValue, _: ControlContext) -> String {
+        format_value_as_on_off(value).to_string()
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        context: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        if value.to_unit_value()?.is_zero() {
+            return Ok(HitResponse::ignored());
+        }
+        let name = context.incoming_text.ok_or("no text received")?;
+        let index = self
+            .address
+            .find_index(self.project, self.tolerance)
+            .ok_or("bookmark not found")?;
+        rename_bookmark(self.project, index, name)?;
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn consumes_incoming_control_data(&self) -> bool {
+        true
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        self.project.is_available()
+    }
+
+    fn project(&self) -> Option<Project> {
+        Some(self.project)
+    }
+
+    fn text_value(&self, _: ControlContext) -> Option<Cow<'static, str>> {
+        let index = self.address.find_index(self.project, self.tolerance)?;
+        let bookmark = self.project.find_bookmark_by_index(index)?;
+        Some(bookmark.name().into())
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::RenameBookmark)
+    }
+}
+
+impl<'a> Target<'a> for RenameBookmarkTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        let val = convert_bool_to_unit_value(bookmark_is_at_play_position(
+            self.project,
+            self.address,
+            self.tolerance,
+        ));
+        Some(AbsoluteValue::Continuous(val))
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+pub const RENAME_BOOKMARK_TARGET: TargetTypeDef = TargetTypeDef {
+    section: TargetSection::Bookmark,
+    name: "Rename",
+    short_name: "Rename bookmark",
+    hint: AUTOMATIC_FEEDBACK_VIA_POLLING_ONLY,
+    ..DEFAULT_TARGET
+};
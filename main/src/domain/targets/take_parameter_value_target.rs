@@ -0,0 +1,287 @@
+use crate::domain::ui_util::{
+    format_value_as_db, format_value_as_db_without_unit, parse_unit_value_from_percentage,
+    parse_value_from_db, volume_unit_value,
+};
+use crate::domain::{
+    format_step_size_as_playback_speed_factor_without_unit,
+    format_value_as_playback_speed_factor_without_unit, parse_step_size_from_playback_speed_factor,
+    parse_value_from_playback_speed_factor, playrate_unit_value, CompartmentKind, ControlContext,
+    ExtendedProcessorContext, FeedbackResolution, HitResponse, MappingControlContext,
+    RealearnTarget, ReaperTarget, ReaperTargetType, Take, TakeRef, TargetCharacter, TargetSection,
+    TargetTypeDef, TrackDescriptor, UnresolvedReaperTargetDef, VirtualItem, VirtualTake,
+    AUTOMATIC_FEEDBACK_VIA_POLLING_ONLY, DEFAULT_TARGET,
+};
+use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, NumericValue, Target, UnitValue};
+use helgobox_api::persistence::TakeParameter;
+use reaper_high::{PlayRate, Project, SliderVolume, Track};
+use reaper_medium::{NormalizedPlayRate, PlaybackSpeedFactor, ReaperVolumeValue};
+use std::borrow::Cow;
+
+/// Take pitch can be adjusted within this range (in semitones) in both directions.
+const PITCH_SPAN: f64 = 24.0;
+
+#[derive(Debug)]
+pub struct UnresolvedTakeParameterValueTarget {
+    pub track_descriptor: TrackDescriptor,
+    pub item: VirtualItem,
+    pub take: VirtualTake,
+    pub parameter: TakeParameter,
+}
+
+impl UnresolvedReaperTargetDef for UnresolvedTakeParameterValueTarget {
+    fn resolve(
+        &self,
+        context: ExtendedProcessorContext,
+        compartment: CompartmentKind,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        let take_index = self.take.resolve(context, compartment)?;
+        let targets = self
+            .item
+            .resolve(&self.track_descriptor, context, compartment)?
+            .into_iter()
+            .map(|item| {
+                ReaperTarget::TakeParameterValue(TakeParameterValueTarget {
+                    take: TakeRef {
+                        item,
+                        index: take_index,
+                    },
+                    parameter: self.parameter,
+                })
+            })
+            .collect();
+        Ok(targets)
+    }
+
+    fn feedback_resolution(&self) -> Option<FeedbackResolution> {
+        Some(FeedbackResolution::High)
+    }
+
+    fn track_descriptor(&self) -> Option<&TrackDescriptor> {
+        Some(&self.track_descriptor)
+    }
+
+    fn virtual_item(&self) -> Option<&VirtualItem> {
+        Some(&self.item)
+    }
+
+    fn virtual_take(&self) -> Option<&VirtualTake> {
+        Some(&self.take)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TakeParameterValueTarget {
+    pub take: TakeRef,
+    pub parameter: TakeParameter,
+}
+
+impl RealearnTarget for TakeParameterValueTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        (ControlType::AbsoluteContinuous, TargetCharacter::Continuous)
+    }
+
+    fn parse_as_value(&self, text: &str, _: ControlContext) -> Result<UnitValue, &'static str> {
+        match self.parameter {
+            TakeParameter::Volume => parse_value_from_db(text),
+            TakeParameter::Pitch => {
+                let semitones: f64 = text.parse().map_err(|_| "not a decimal value")?;
+                Ok(pitch_unit_value(semitones))
+            }
+            TakeParameter::PlayRate => parse_value_from_playback_speed_factor(text),
+        }
+    }
+
+    fn parse_as_step_size(&self, text: &str, _: ControlContext) -> Result<UnitValue, &'static str> {
+        match self.parameter {
+            TakeParameter::Volume => parse_unit_value_from_percentage(text),
+            TakeParameter::Pitch => {
+                let semitones: f64 = text.parse().map_err(|_| "not a decimal value")?;
+                if !(0.0..=2.0 * PITCH_SPAN).contains(&semitones) {
+                    return Err("not in semitone increment range");
+                }
+                Ok(UnitValue::new(semitones / (2.0 * PITCH_SPAN)))
+            }
+            TakeParameter::PlayRate => parse_step_size_from_playback_speed_factor(text),
+        }
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue, _: ControlContext) -> String {
+        match self.parameter {
+            TakeParameter::Volume => format_value_as_db_without_unit(value),
+            TakeParameter::Pitch => format_semitones(pitch_from_unit_value(value)),
+            TakeParameter::PlayRate => format_value_as_playback_speed_factor_without_unit(value),
+        }
+    }
+
+    fn format_step_size_without_unit(
+        &self,
+        step_size: UnitValue,
+        context: ControlContext,
+    ) -> String {
+        match self.parameter {
+            TakeParameter::Volume => self.format_as_discrete_or_percentage(step_size, context),
+            TakeParameter::Pitch => format_semitones(step_size.get() * 2.0 * PITCH_SPAN),
+            TakeParameter::PlayRate => {
+                format_step_size_as_playback_speed_factor_without_unit(step_size)
+            }
+        }
+    }
+
+    fn hide_formatted_value(&self, _: ControlContext) -> bool {
+        true
+    }
+
+    fn hide_formatted_step_size(&self, _: ControlContext) -> bool {
+        !matches!(self.parameter, TakeParameter::Volume)
+    }
+
+    fn value_unit(&self, _: ControlContext) -> &'static str {
+        match self.parameter {
+            TakeParameter::Volume => "dB",
+            TakeParameter::Pitch => "st",
+            TakeParameter::PlayRate => "x",
+        }
+    }
+
+    fn step_size_unit(&self, _: ControlContext) -> &'static str {
+        match self.parameter {
+            TakeParameter::Volume => "%",
+            TakeParameter::Pitch => "st",
+            TakeParameter::PlayRate => "x",
+        }
+    }
+
+    fn format_value(&self, value: UnitValue, context: ControlContext) -> String {
+        match self.parameter {
+            TakeParameter::Volume => format_value_as_db(value),
+            _ => self.format_value_generic(value, context),
+        }
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        _: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        let value = value.to_unit_value()?;
+        let take = self.take.take().ok_or("take not found")?;
+        match self.parameter {
+            TakeParameter::Volume => {
+                let volume = SliderVolume::try_from_normalized_slider_value(value.get())
+                    .unwrap_or(SliderVolume::MIN);
+                take.set_volume(volume.reaper_value().get());
+            }
+            TakeParameter::Pitch => {
+                take.set_pitch(pitch_from_unit_value(value));
+            }
+            TakeParameter::PlayRate => {
+                let play_rate =
+                    PlayRate::from_normalized_value(NormalizedPlayRate::new(value.get()));
+                take.set_playrate(play_rate.playback_speed_factor().get());
+            }
+        }
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        self.take.take().is_some()
+    }
+
+    fn project(&self) -> Option<Project> {
+        Some(self.take.item.project())
+    }
+
+    fn track(&self) -> Option<&Track> {
+        self.take.item.track()
+    }
+
+    fn text_value(&self, _: ControlContext) -> Option<Cow<'static, str>> {
+        let take = self.take.take()?;
+        let text = match self.parameter {
+            TakeParameter::Volume => take_volume(&take).to_string(),
+            TakeParameter::Pitch => format!("{} st", format_semitones(take.pitch())),
+            TakeParameter::PlayRate => format!("{:.4}x", take.playrate()),
+        };
+        Some(text.into())
+    }
+
+    fn numeric_value(&self, _: ControlContext) -> Option<NumericValue> {
+        let take = self.take.take()?;
+        let value = match self.parameter {
+            TakeParameter::Volume => take_volume(&take).db().get(),
+            TakeParameter::Pitch => take.pitch(),
+            TakeParameter::PlayRate => take.playrate(),
+        };
+        Some(NumericValue::Decimal(value))
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::TakeParameterValue)
+    }
+}
+
+impl<'a> Target<'a> for TakeParameterValueTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        let take = self.take.take()?;
+        let val = match self.parameter {
+            TakeParameter::Volume => volume_unit_value(take_volume(&take)),
+            TakeParameter::Pitch => pitch_unit_value(take.pitch()),
+            TakeParameter::PlayRate => {
+                // Takes support a wider playrate range than the project, we can't represent that.
+                let factor: PlaybackSpeedFactor = take.playrate().try_into().ok()?;
+                playrate_unit_value(PlayRate::from_playback_speed_factor(factor))
+            }
+        };
+        Some(AbsoluteValue::Continuous(val))
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+fn take_volume(take: &Take) -> SliderVolume {
+    SliderVolume::from_reaper_value(ReaperVolumeValue::new_panic(take.volume()))
+}
+
+fn pitch_unit_value(semitones: f64) -> UnitValue {
+    UnitValue::new_clamped((semitones + PITCH_SPAN) / (2.0 * PITCH_SPAN))
+}
+
+fn pitch_from_unit_value(value: UnitValue) -> f64 {
+    value.get() * 2.0 * PITCH_SPAN - PITCH_SPAN
+}
+
+fn format_semitones(semitones: f64) -> String {
+    format!("{semitones:.2}")
+}
+
+pub const TAKE_PARAMETER_VALUE_TARGET: TargetTypeDef = TargetTypeDef {
+    section: TargetSection::Item,
+    name: "Set take parameter value",
+    short_name: "Take parameter",
+    hint: AUTOMATIC_FEEDBACK_VIA_POLLING_ONLY,
+    supports_track: true,
+    ..DEFAULT_TARGET
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_pitch_symmetrically_around_zero() {
+        // Given
+        let up_one_octave = 12.0;
+        // When
+        let unit_value = pitch_unit_value(up_one_octave);
+        // Then
+        assert_eq!(pitch_unit_value(0.0), UnitValue::new(0.5));
+        assert_eq!(unit_value, UnitValue::new(0.75));
+        assert_eq!(pitch_from_unit_value(unit_value), up_one_octave);
+        assert_eq!(pitch_unit_value(-PITCH_SPAN - 5.0), UnitValue::MIN);
+        assert_eq!(pitch_from_unit_value(UnitValue::MAX), PITCH_SPAN);
+    }
+}
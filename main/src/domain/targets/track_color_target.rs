@@ -0,0 +1,194 @@
+use crate::domain::{
+    convert_count_to_step_size, convert_discrete_to_unit_value, convert_unit_to_discrete_value,
+    format_color, get_effective_tracks, parse_color, set_track_color, track_color,
+    track_color_from_palette, track_color_palette, track_color_palette_index, CompartmentKind,
+    ControlContext, ExtendedProcessorContext, FeedbackResolution, HitResponse,
+    MappingControlContext, RealearnTarget, ReaperTarget, ReaperTargetType, TargetCharacter,
+    TargetSection, TargetTypeDef, TrackDescriptor, UnresolvedReaperTargetDef,
+    AUTOMATIC_FEEDBACK_VIA_POLLING_ONLY, DEFAULT_TARGET,
+};
+use helgoboss_learn::{
+    AbsoluteValue, ControlType, ControlValue, Fraction, NumericValue, RgbColor, Target, UnitValue,
+};
+use reaper_high::{Project, Track};
+use std::borrow::Cow;
+
+#[derive(Debug)]
+pub struct UnresolvedTrackColorTarget {
+    pub track_descriptor: TrackDescriptor,
+}
+
+impl UnresolvedReaperTargetDef for UnresolvedTrackColorTarget {
+    fn resolve(
+        &self,
+        context: ExtendedProcessorContext,
+        compartment: CompartmentKind,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        Ok(
+            get_effective_tracks(context, &self.track_descriptor.track, compartment)?
+                .into_iter()
+                .map(|track| ReaperTarget::TrackColor(TrackColorTarget { track }))
+                .collect(),
+        )
+    }
+
+    fn track_descriptor(&self) -> Option<&TrackDescriptor> {
+        Some(&self.track_descriptor)
+    }
+
+    fn feedback_resolution(&self) -> Option<FeedbackResolution> {
+        Some(FeedbackResolution::High)
+    }
+}
+
+/// Sets the color of a track.
+///
+/// Takes the color from the color argument or the `#RRGGBB` string argument of an incoming OSC
+/// message. If there's none, the control value picks a color from the track color palette (see
+/// [`track_color_palette`]).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TrackColorTarget {
+    pub track: Track,
+}
+
+impl RealearnTarget for TrackColorTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        (
+            ControlType::AbsoluteDiscrete {
+                atomic_step_size: convert_count_to_step_size(palette_count()),
+                is_retriggerable: true,
+            },
+            TargetCharacter::Discrete,
+        )
+    }
+
+    fn parse_as_value(
+        &self,
+        text: &str,
+        context: ControlContext,
+    ) -> Result<UnitValue, &'static str> {
+        self.parse_value_from_discrete_value(text, context)
+    }
+
+    fn parse_as_step_size(
+        &self,
+        text: &str,
+        context: ControlContext,
+    ) -> Result<UnitValue, &'static str> {
+        self.parse_value_from_discrete_value(text, context)
+    }
+
+    fn convert_unit_value_to_discrete_value(
+        &self,
+        input: UnitValue,
+        _: ControlContext,
+    ) -> Result<u32, &'static str> {
+        Ok(convert_unit_to_discrete_value(input, palette_count()))
+    }
+
+    fn convert_discrete_value_to_unit_value(
+        &self,
+        value: u32,
+        _: ControlContext,
+    ) -> Result<UnitValue, &'static str> {
+        Ok(convert_discrete_to_unit_value(value, palette_count()))
+    }
+
+    fn format_value(&self, value: UnitValue, _: ControlContext) -> String {
+        let palette = track_color_palette();
+        let index = convert_unit_to_discrete_value(value, palette.len() as u32);
+        format_optional_color(track_color_from_palette(&palette, index))
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        context: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        let color = if let Some(color) = context.incoming_color {
+            Some(color)
+        } else if let Some(text) = context.incoming_text {
+            Some(parse_color(text).ok_or("text is not a color in the form of #RRGGBB")?)
+        } else {
+            let palette = track_color_palette();
+            let count = palette.len() as u32;
+            let index = match value.to_absolute_value()? {
+                AbsoluteValue::Continuous(v) => convert_unit_to_discrete_value(v, count),
+                AbsoluteValue::Discrete(f) => f.actual(),
+            };
+            if index >= count {
+                return Err("palette index out of range");
+            }
+            track_color_from_palette(&palette, index)
+        };
+        set_track_color(&self.track, color);
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn consumes_incoming_control_data(&self) -> bool {
+        true
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        self.track.is_available()
+    }
+
+    fn project(&self) -> Option<Project> {
+        Some(self.track.project())
+    }
+
+    fn track(&self) -> Option<&Track> {
+        Some(&self.track)
+    }
+
+    fn text_value(&self, _: ControlContext) -> Option<Cow<'static, str>> {
+        Some(format_optional_color(track_color(&self.track)).into())
+    }
+
+    fn numeric_value(&self, _: ControlContext) -> Option<NumericValue> {
+        let index = track_color_palette_index(&track_color_palette(), track_color(&self.track))?;
+        Some(NumericValue::Discrete(index as i32))
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::TrackColor)
+    }
+}
+
+impl<'a> Target<'a> for TrackColorTarget {
+    type Context = ControlContext<'a>;
+
+    /// Only available if the current color is part of the palette.
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        let palette = track_color_palette();
+        let index = track_color_palette_index(&palette, track_color(&self.track))?;
+        Some(AbsoluteValue::Discrete(Fraction::new(
+            index,
+            palette.len() as u32 - 1,
+        )))
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+pub const TRACK_COLOR_TARGET: TargetTypeDef = TargetTypeDef {
+    section: TargetSection::Track,
+    name: "Set color",
+    short_name: "Track color",
+    hint: AUTOMATIC_FEEDBACK_VIA_POLLING_ONLY,
+    supports_track: true,
+    ..DEFAULT_TARGET
+};
+
+fn palette_count() -> u32 {
+    track_color_palette().len() as u32
+}
+
+fn format_optional_color(color: Option<RgbColor>) -> String {
+    match color {
+        None => "Default".to_string(),
+        Some(c) => format_color(c),
+    }
+}
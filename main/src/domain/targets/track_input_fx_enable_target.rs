@@ -0,0 +1,150 @@
+use crate::domain::ui_util::convert_bool_to_unit_value;
+use crate::domain::{
+    change_track_prop, format_value_as_on_off,
+    get_control_type_and_character_for_track_exclusivity, get_effective_tracks,
+    track_input_fx_are_enabled, AdditionalFeedbackEvent, Backbone, CompartmentKind,
+    CompoundChangeEvent, ControlContext, ExtendedProcessorContext, HitResponse,
+    MappingControlContext, RealearnTarget, ReaperTarget, ReaperTargetType, TargetCharacter,
+    TargetSection, TargetTypeDef, TrackDescriptor, TrackExclusivity, UnresolvedReaperTargetDef,
+    DEFAULT_TARGET,
+};
+use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, Target, UnitValue};
+use reaper_high::{Project, Track};
+use std::borrow::Cow;
+
+#[derive(Debug)]
+pub struct UnresolvedTrackInputFxEnableTarget {
+    pub track_descriptor: TrackDescriptor,
+    pub exclusivity: TrackExclusivity,
+}
+
+impl UnresolvedReaperTargetDef for UnresolvedTrackInputFxEnableTarget {
+    fn resolve(
+        &self,
+        context: ExtendedProcessorContext,
+        compartment: CompartmentKind,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        Ok(
+            get_effective_tracks(context, &self.track_descriptor.track, compartment)?
+                .into_iter()
+                .map(|track| {
+                    ReaperTarget::TrackInputFxEnable(TrackInputFxEnableTarget {
+                        track,
+                        exclusivity: self.exclusivity,
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    fn track_descriptor(&self) -> Option<&TrackDescriptor> {
+        Some(&self.track_descriptor)
+    }
+}
+
+/// Bypasses or unbypasses the input FX chain of a track as a whole.
+///
+/// Disabling bypasses all input FX. Enabling unbypasses only those input FX which were enabled
+/// before disabling them via this target.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TrackInputFxEnableTarget {
+    pub track: Track,
+    pub exclusivity: TrackExclusivity,
+}
+
+impl RealearnTarget for TrackInputFxEnableTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        get_control_type_and_character_for_track_exclusivity(self.exclusivity)
+    }
+
+    fn format_value(&self, value: UnitValue, _: ControlContext) -> String {
+        format_value_as_on_off(value).to_string()
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        _: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        change_track_prop(
+            &self.track,
+            self.exclusivity,
+            value.to_unit_value()?,
+            |t| {
+                Backbone::target_state()
+                    .borrow_mut()
+                    .enable_track_input_fx(t)
+            },
+            |t| {
+                Backbone::target_state()
+                    .borrow_mut()
+                    .disable_track_input_fx(t)
+            },
+        );
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        self.track.is_available()
+    }
+
+    fn project(&self) -> Option<Project> {
+        Some(self.track.project())
+    }
+
+    fn track(&self) -> Option<&Track> {
+        Some(&self.track)
+    }
+
+    fn track_exclusivity(&self) -> Option<TrackExclusivity> {
+        Some(self.exclusivity)
+    }
+
+    fn process_change_event(
+        &self,
+        evt: CompoundChangeEvent,
+        _: ControlContext,
+    ) -> (bool, Option<AbsoluteValue>) {
+        match evt {
+            CompoundChangeEvent::Additional(
+                AdditionalFeedbackEvent::TrackInputFxEnabledChanged(e),
+            ) if e.track == self.track => (
+                true,
+                Some(AbsoluteValue::Continuous(convert_bool_to_unit_value(
+                    e.new_value,
+                ))),
+            ),
+            _ => (false, None),
+        }
+    }
+
+    fn text_value(&self, context: ControlContext) -> Option<Cow<'static, str>> {
+        Some(format_value_as_on_off(self.current_value(context)?.to_unit_value()).into())
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::TrackInputFxEnable)
+    }
+}
+
+impl<'a> Target<'a> for TrackInputFxEnableTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        let val = convert_bool_to_unit_value(track_input_fx_are_enabled(&self.track));
+        Some(AbsoluteValue::Continuous(val))
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+pub const TRACK_INPUT_FX_ENABLE_TARGET: TargetTypeDef = TargetTypeDef {
+    section: TargetSection::Track,
+    name: "Enable/disable input FX",
+    short_name: "Enable/disable track input FX",
+    supports_track: true,
+    supports_track_exclusivity: true,
+    ..DEFAULT_TARGET
+};
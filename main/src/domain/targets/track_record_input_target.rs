@@ -0,0 +1,162 @@
+use crate::domain::ui_util::convert_bool_to_unit_value;
+use crate::domain::{
+    change_track_prop, format_recording_input, format_value_as_on_off, get_effective_tracks,
+    CompartmentKind, CompoundChangeEvent, ControlContext, ExtendedProcessorContext, HitResponse,
+    MappingControlContext, RealearnTarget, ReaperTarget, ReaperTargetType, TargetCharacter,
+    TargetSection, TargetTypeDef, TrackDescriptor, TrackExclusivity, UnresolvedReaperTargetDef,
+    DEFAULT_TARGET,
+};
+use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, Target, UnitValue};
+use reaper_high::{ChangeEvent, Project, Track};
+use reaper_medium::RecordingInput;
+use std::borrow::Cow;
+
+#[derive(Debug)]
+pub struct UnresolvedTrackRecordInputTarget {
+    pub track_descriptor: TrackDescriptor,
+    pub exclusivity: TrackExclusivity,
+    pub input: Option<RecordingInput>,
+}
+
+impl UnresolvedReaperTargetDef for UnresolvedTrackRecordInputTarget {
+    fn resolve(
+        &self,
+        context: ExtendedProcessorContext,
+        compartment: CompartmentKind,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        Ok(
+            get_effective_tracks(context, &self.track_descriptor.track, compartment)?
+                .into_iter()
+                .map(|track| {
+                    ReaperTarget::TrackRecordInput(TrackRecordInputTarget {
+                        track,
+                        exclusivity: self.exclusivity,
+                        input: self.input,
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    fn track_descriptor(&self) -> Option<&TrackDescriptor> {
+        Some(&self.track_descriptor)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TrackRecordInputTarget {
+    pub track: Track,
+    pub exclusivity: TrackExclusivity,
+    /// `None` means no input.
+    pub input: Option<RecordingInput>,
+}
+
+impl RealearnTarget for TrackRecordInputTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        if self.exclusivity == TrackExclusivity::NonExclusive {
+            (
+                ControlType::AbsoluteContinuousRetriggerable,
+                TargetCharacter::Switch,
+            )
+        } else {
+            (
+                ControlType::AbsoluteContinuousRetriggerable,
+                TargetCharacter::Trigger,
+            )
+        }
+    }
+
+    fn format_value(&self, value: UnitValue, _: ControlContext) -> String {
+        format_value_as_on_off(value).to_string()
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        _: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        change_track_prop(
+            &self.track,
+            self.exclusivity,
+            value.to_unit_value()?,
+            |t| t.set_recording_input(self.input),
+            |t| t.set_recording_input(None),
+        );
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        self.track.is_available()
+    }
+
+    fn project(&self) -> Option<Project> {
+        Some(self.track.project())
+    }
+
+    fn track(&self) -> Option<&Track> {
+        Some(&self.track)
+    }
+
+    fn track_exclusivity(&self) -> Option<TrackExclusivity> {
+        Some(self.exclusivity)
+    }
+
+    fn process_change_event(
+        &self,
+        evt: CompoundChangeEvent,
+        _: ControlContext,
+    ) -> (bool, Option<AbsoluteValue>) {
+        match evt {
+            CompoundChangeEvent::Reaper(ChangeEvent::TrackInputChanged(e))
+                if e.track == self.track =>
+            {
+                (
+                    true,
+                    Some(AbsoluteValue::Continuous(recording_input_unit_value(
+                        self.input,
+                        e.track.recording_input(),
+                    ))),
+                )
+            }
+            _ => (false, None),
+        }
+    }
+
+    /// Shows the currently active input, not just whether it's the desired one.
+    fn text_value(&self, _: ControlContext) -> Option<Cow<'static, str>> {
+        Some(format_recording_input(self.track.recording_input()).into())
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::TrackRecordInput)
+    }
+}
+
+impl<'a> Target<'a> for TrackRecordInputTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        let val = recording_input_unit_value(self.input, self.track.recording_input());
+        Some(AbsoluteValue::Continuous(val))
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+pub const TRACK_RECORD_INPUT_TARGET: TargetTypeDef = TargetTypeDef {
+    section: TargetSection::Track,
+    name: "Set record input",
+    short_name: "Track record input",
+    supports_track: true,
+    supports_track_exclusivity: true,
+    ..DEFAULT_TARGET
+};
+
+fn recording_input_unit_value(
+    desired_input: Option<RecordingInput>,
+    actual_input: Option<RecordingInput>,
+) -> UnitValue {
+    convert_bool_to_unit_value(desired_input == actual_input)
+}
@@ -0,0 +1,149 @@
+use crate::domain::ui_util::convert_bool_to_unit_value;
+use crate::domain::{
+    change_track_prop, format_value_as_on_off, get_effective_tracks, set_track_record_mode,
+    track_record_mode, AdditionalFeedbackEvent, CompartmentKind, CompoundChangeEvent,
+    ControlContext, ExtendedProcessorContext, HitResponse, MappingControlContext, RealearnTarget,
+    ReaperTarget, ReaperTargetType, TargetCharacter, TargetSection, TargetTypeDef, TrackDescriptor,
+    TrackExclusivity, UnresolvedReaperTargetDef, DEFAULT_TARGET,
+};
+use helgoboss_learn::{AbsoluteValue, ControlType, ControlValue, Target, UnitValue};
+use helgobox_api::persistence::RecordMode;
+use reaper_high::{Project, Track};
+use std::borrow::Cow;
+
+#[derive(Debug)]
+pub struct UnresolvedTrackRecordModeTarget {
+    pub track_descriptor: TrackDescriptor,
+    pub exclusivity: TrackExclusivity,
+    pub mode: RecordMode,
+}
+
+impl UnresolvedReaperTargetDef for UnresolvedTrackRecordModeTarget {
+    fn resolve(
+        &self,
+        context: ExtendedProcessorContext,
+        compartment: CompartmentKind,
+    ) -> Result<Vec<ReaperTarget>, &'static str> {
+        Ok(
+            get_effective_tracks(context, &self.track_descriptor.track, compartment)?
+                .into_iter()
+                .map(|track| {
+                    ReaperTarget::TrackRecordMode(TrackRecordModeTarget {
+                        track,
+                        exclusivity: self.exclusivity,
+                        mode: self.mode,
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    fn track_descriptor(&self) -> Option<&TrackDescriptor> {
+        Some(&self.track_descriptor)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TrackRecordModeTarget {
+    pub track: Track,
+    pub exclusivity: TrackExclusivity,
+    pub mode: RecordMode,
+}
+
+impl RealearnTarget for TrackRecordModeTarget {
+    fn control_type_and_character(&self, _: ControlContext) -> (ControlType, TargetCharacter) {
+        if self.exclusivity == TrackExclusivity::NonExclusive {
+            (
+                ControlType::AbsoluteContinuousRetriggerable,
+                TargetCharacter::Switch,
+            )
+        } else {
+            (
+                ControlType::AbsoluteContinuousRetriggerable,
+                TargetCharacter::Trigger,
+            )
+        }
+    }
+
+    fn format_value(&self, value: UnitValue, _: ControlContext) -> String {
+        format_value_as_on_off(value).to_string()
+    }
+
+    fn hit(
+        &mut self,
+        value: ControlValue,
+        _: MappingControlContext,
+    ) -> Result<HitResponse, &'static str> {
+        change_track_prop(
+            &self.track,
+            self.exclusivity,
+            value.to_unit_value()?,
+            |t| set_track_record_mode(t, self.mode),
+            |t| set_track_record_mode(t, RecordMode::Disabled),
+        );
+        Ok(HitResponse::processed_with_effect())
+    }
+
+    fn is_available(&self, _: ControlContext) -> bool {
+        self.track.is_available()
+    }
+
+    fn project(&self) -> Option<Project> {
+        Some(self.track.project())
+    }
+
+    fn track(&self) -> Option<&Track> {
+        Some(&self.track)
+    }
+
+    fn track_exclusivity(&self) -> Option<TrackExclusivity> {
+        Some(self.exclusivity)
+    }
+
+    fn process_change_event(
+        &self,
+        evt: CompoundChangeEvent,
+        _: ControlContext,
+    ) -> (bool, Option<AbsoluteValue>) {
+        match evt {
+            CompoundChangeEvent::Additional(AdditionalFeedbackEvent::TrackRecordModeChanged(e))
+                if e.track == self.track =>
+            {
+                let val = convert_bool_to_unit_value(e.new_value == self.mode);
+                (true, Some(AbsoluteValue::Continuous(val)))
+            }
+            _ => (false, None),
+        }
+    }
+
+    /// Shows the currently active record mode, not just whether it's the desired one.
+    fn text_value(&self, _: ControlContext) -> Option<Cow<'static, str>> {
+        Some(track_record_mode(&self.track)?.to_string().into())
+    }
+
+    fn reaper_target_type(&self) -> Option<ReaperTargetType> {
+        Some(ReaperTargetType::TrackRecordMode)
+    }
+}
+
+impl<'a> Target<'a> for TrackRecordModeTarget {
+    type Context = ControlContext<'a>;
+
+    fn current_value(&self, _: Self::Context) -> Option<AbsoluteValue> {
+        let val = convert_bool_to_unit_value(track_record_mode(&self.track)? == self.mode);
+        Some(AbsoluteValue::Continuous(val))
+    }
+
+    fn control_type(&self, context: Self::Context) -> ControlType {
+        self.control_type_and_character(context).0
+    }
+}
+
+pub const TRACK_RECORD_MODE_TARGET: TargetTypeDef = TargetTypeDef {
+    section: TargetSection::Track,
+    name: "Set record mode",
+    short_name: "Track record mode",
+    supports_track: true,
+    supports_track_exclusivity: true,
+    ..DEFAULT_TARGET
+};
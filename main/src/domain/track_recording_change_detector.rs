@@ -0,0 +1,83 @@
+use crate::domain::{
+    track_input_fx_are_enabled, track_record_mode, AdditionalFeedbackEvent,
+    TrackInputFxEnabledChangedEvent, TrackRecordModeChangedEvent,
+};
+use base::hash_util::NonCryptoHashMap;
+use helgobox_api::persistence::RecordMode;
+use reaper_high::Reaper;
+use reaper_medium::MediaTrack;
+
+/// REAPER doesn't inform about changes of the track record mode via control surface callback
+/// methods. It informs about single input FX being enabled or disabled, but not about the input
+/// FX chain as a whole being switched on or off.
+///
+/// We detect both by polling the tracks of the current project. The caller decides how often
+/// that's necessary (e.g. only if there are targets interested in these changes).
+#[derive(Debug, Default)]
+pub struct TrackRecordingChangeDetector {
+    items: NonCryptoHashMap<MediaTrack, Item>,
+    poll_count: u64,
+}
+
+#[derive(Debug)]
+struct Item {
+    record_mode: Option<RecordMode>,
+    input_fx_enabled: bool,
+    /// Value of `poll_count` when this track was seen the last time.
+    last_seen: u64,
+}
+
+impl TrackRecordingChangeDetector {
+    /// Forgets all previously seen track states.
+    ///
+    /// Should be called when polling is suspended. Otherwise, changes which happened in the
+    /// meantime would be reported as if they had happened just now.
+    pub fn reset(&mut self) {
+        self.items.clear();
+    }
+
+    pub fn poll_for_changes(&mut self) -> Vec<AdditionalFeedbackEvent> {
+        self.poll_count += 1;
+        let mut change_events = vec![];
+        for track in Reaper::get().current_project().tracks() {
+            let Ok(raw_track) = track.raw() else {
+                continue;
+            };
+            let record_mode = track_record_mode(&track);
+            let input_fx_enabled = track_input_fx_are_enabled(&track);
+            let Some(item) = self.items.get_mut(&raw_track) else {
+                // Tracks which we see for the first time don't count as changed
+                let item = Item {
+                    record_mode,
+                    input_fx_enabled,
+                    last_seen: self.poll_count,
+                };
+                self.items.insert(raw_track, item);
+                continue;
+            };
+            if let Some(new_value) = record_mode {
+                if item.record_mode != record_mode {
+                    let event = TrackRecordModeChangedEvent {
+                        track: track.clone(),
+                        new_value,
+                    };
+                    change_events.push(AdditionalFeedbackEvent::TrackRecordModeChanged(event));
+                }
+            }
+            if item.input_fx_enabled != input_fx_enabled {
+                let event = TrackInputFxEnabledChangedEvent {
+                    track: track.clone(),
+                    new_value: input_fx_enabled,
+                };
+                change_events.push(AdditionalFeedbackEvent::TrackInputFxEnabledChanged(event));
+            }
+            item.record_mode = record_mode;
+            item.input_fx_enabled = input_fx_enabled;
+            item.last_seen = self.poll_count;
+        }
+        // Forget tracks which don't exist anymore
+        let poll_count = self.poll_count;
+        self.items.retain(|_, item| item.last_seen == poll_count);
+        change_events
+    }
+}
@@ -0,0 +1,156 @@
+use helgobox_api::persistence::RecordMode;
+use reaper_high::{Reaper, Track};
+use reaper_medium::{RecordingInput, TrackAttributeKey};
+
+/// Returns the record mode of the given track.
+pub fn track_record_mode(track: &Track) -> Option<RecordMode> {
+    let raw_track = track.raw().ok()?;
+    let value = unsafe {
+        Reaper::get()
+            .medium_reaper()
+            .get_media_track_info_value(raw_track, TrackAttributeKey::RecMode)
+    };
+    record_mode_from_reaper_value(value)
+}
+
+/// Sets the record mode of the given track.
+pub fn set_track_record_mode(track: &Track, mode: RecordMode) {
+    let Ok(raw_track) = track.raw() else {
+        return;
+    };
+    let _ = unsafe {
+        Reaper::get().medium_reaper().set_media_track_info_value(
+            raw_track,
+            TrackAttributeKey::RecMode,
+            record_mode_to_reaper_value(mode),
+        )
+    };
+}
+
+/// Converts the given `I_RECMODE` value to a record mode.
+fn record_mode_from_reaper_value(value: f64) -> Option<RecordMode> {
+    if value < 0.0 {
+        return None;
+    }
+    RecordMode::try_from(value as usize).ok()
+}
+
+/// Converts the given record mode to an `I_RECMODE` value.
+fn record_mode_to_reaper_value(mode: RecordMode) -> f64 {
+    let value: usize = mode.into();
+    value as f64
+}
+
+/// Returns whether the input FX chain of the given track is active, that is, whether at least one
+/// of its FX is enabled.
+pub fn track_input_fx_are_enabled(track: &Track) -> bool {
+    track.input_fx_chain().fxs().any(|fx| fx.is_enabled())
+}
+
+/// Formats the given record input similar to how REAPER displays it in the track input menu.
+pub fn format_recording_input(input: Option<RecordingInput>) -> String {
+    match input {
+        None => "None".to_string(),
+        Some(RecordingInput::Mono(index)) => format!("In {}", index + 1),
+        Some(RecordingInput::Stereo(index)) => format!("In {}/{}", index + 1, index + 2),
+        Some(RecordingInput::Midi { device_id, channel }) => {
+            let device = match device_id {
+                None => "All devices".to_string(),
+                Some(id) => format!("Device {}", id.get()),
+            };
+            let channel = match channel {
+                None => "all channels".to_string(),
+                Some(ch) => format!("Ch {}", ch.get() + 1),
+            };
+            format!("MIDI: {device}, {channel}")
+        }
+        Some(_) => "Other".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helgoboss_midi::Channel;
+    use reaper_medium::MidiInputDeviceId;
+
+    #[test]
+    fn record_mode_matches_reaper_value_order() {
+        // Given
+        let expected = [
+            (0, RecordMode::Input),
+            (1, RecordMode::StereoOutput),
+            (2, RecordMode::Disabled),
+            (3, RecordMode::StereoOutputLatencyCompensated),
+            (4, RecordMode::MidiOutput),
+            (5, RecordMode::MonoOutput),
+            (6, RecordMode::MonoOutputLatencyCompensated),
+            (7, RecordMode::MidiOverdub),
+            (8, RecordMode::MidiReplace),
+            (9, RecordMode::MidiTouchReplace),
+            (10, RecordMode::MultichannelOutput),
+            (11, RecordMode::MultichannelOutputLatencyCompensated),
+            (12, RecordMode::InputForceMono),
+            (13, RecordMode::InputForceStereo),
+            (14, RecordMode::InputForceMultichannel),
+            (15, RecordMode::InputForceMidi),
+            (16, RecordMode::MidiLatchReplace),
+        ];
+        for (value, mode) in expected {
+            // When
+            let converted_mode = record_mode_from_reaper_value(value as f64);
+            let converted_value = record_mode_to_reaper_value(mode);
+            // Then
+            assert_eq!(converted_mode, Some(mode));
+            assert_eq!(converted_value, value as f64);
+        }
+    }
+
+    #[test]
+    fn record_mode_from_unknown_reaper_value() {
+        // Given
+        let values = [-1.0, 17.0];
+        for value in values {
+            // When
+            let mode = record_mode_from_reaper_value(value);
+            // Then
+            assert_eq!(mode, None);
+        }
+    }
+
+    #[test]
+    fn format_audio_recording_inputs() {
+        // Given
+        let inputs = [
+            (None, "None"),
+            (Some(RecordingInput::Mono(0)), "In 1"),
+            (Some(RecordingInput::Stereo(2)), "In 3/4"),
+        ];
+        for (input, expected) in inputs {
+            // When
+            let text = format_recording_input(input);
+            // Then
+            assert_eq!(text, expected);
+        }
+    }
+
+    #[test]
+    fn format_midi_recording_inputs() {
+        // Given
+        let inputs = [
+            (None, None, "MIDI: All devices, all channels"),
+            (
+                Some(MidiInputDeviceId::new(3)),
+                Some(Channel::new(9)),
+                "MIDI: Device 3, Ch 10",
+            ),
+        ];
+        for (device_id, channel, expected) in inputs {
+            let input = RecordingInput::Midi { device_id, channel };
+            // When
+            let text = format_recording_input(Some(input));
+            // Then
+            assert_eq!(text, expected);
+        }
+    }
+}
@@ -2,29 +2,35 @@ use crate::application::BookmarkAnchorType;
 use crate::domain::realearn_target::RealearnTarget;
 use crate::domain::{
     scoped_track_index, Backbone, CompartmentKind, CompartmentParamIndex, CompartmentParams,
-    ControlContext, ExtendedProcessorContext, FeedbackResolution, ReaperTarget,
-    UnresolvedActionTarget, UnresolvedAllTrackFxEnableTarget, UnresolvedAnyOnTarget,
-    UnresolvedAutomationModeOverrideTarget, UnresolvedBrowseFxsTarget, UnresolvedBrowseGroupTarget,
-    UnresolvedBrowsePotFilterItemsTarget, UnresolvedBrowsePotPresetsTarget,
-    UnresolvedBrowseTracksTarget, UnresolvedCompartmentParameterValueTarget, UnresolvedDummyTarget,
-    UnresolvedEnableInstancesTarget, UnresolvedEnableMappingsTarget, UnresolvedEnableUnitsTarget,
-    UnresolvedFxEnableTarget, UnresolvedFxOnlineTarget, UnresolvedFxOpenTarget,
-    UnresolvedFxParameterTarget, UnresolvedFxParameterTouchStateTarget, UnresolvedFxPresetTarget,
-    UnresolvedFxToolTarget, UnresolvedGoToBookmarkTarget, UnresolvedLastTouchedTarget,
+    ControlContext, EnvelopeRef, ExtendedProcessorContext, FeedbackResolution, ItemRef,
+    ReaperTarget, UnresolvedActionTarget, UnresolvedAddBookmarkTarget,
+    UnresolvedAllTrackFxEnableTarget, UnresolvedAnyOnTarget,
+    UnresolvedAutomationModeOverrideTarget, UnresolvedBrowseEnvelopePointsTarget,
+    UnresolvedBrowseFxsTarget, UnresolvedBrowseGroupTarget, UnresolvedBrowsePotFilterItemsTarget,
+    UnresolvedBrowsePotPresetsTarget, UnresolvedBrowseTakesTarget, UnresolvedBrowseTracksTarget,
+    UnresolvedCompartmentParameterValueTarget, UnresolvedDeleteBookmarkTarget,
+    UnresolvedDummyTarget, UnresolvedEnableInstancesTarget, UnresolvedEnableMappingsTarget,
+    UnresolvedEnableUnitsTarget, UnresolvedEnvelopeArmStateTarget, UnresolvedEnvelopeValueTarget,
+    UnresolvedFxEnableTarget, UnresolvedFxNameTarget, UnresolvedFxOnlineTarget,
+    UnresolvedFxOpenTarget, UnresolvedFxParameterTarget, UnresolvedFxParameterTouchStateTarget,
+    UnresolvedFxPresetTarget, UnresolvedFxToolTarget, UnresolvedGoToBookmarkTarget,
+    UnresolvedItemParameterValueTarget, UnresolvedLastTouchedTarget,
     UnresolvedLoadFxSnapshotTarget, UnresolvedLoadMappingSnapshotTarget,
     UnresolvedLoadPotPresetTarget, UnresolvedMidiSendTarget, UnresolvedModifyMappingTarget,
-    UnresolvedMouseTarget, UnresolvedOscSendTarget, UnresolvedPlayrateTarget,
-    UnresolvedPreviewPotPresetTarget, UnresolvedRouteAutomationModeTarget,
-    UnresolvedRouteMonoTarget, UnresolvedRouteMuteTarget, UnresolvedRoutePanTarget,
-    UnresolvedRoutePhaseTarget, UnresolvedRouteTouchStateTarget, UnresolvedRouteVolumeTarget,
-    UnresolvedSeekTarget, UnresolvedStreamDeckBrightnessTarget,
-    UnresolvedTakeMappingSnapshotTarget, UnresolvedTempoTarget, UnresolvedTrackArmTarget,
-    UnresolvedTrackAutomationModeTarget, UnresolvedTrackMonitoringModeTarget,
-    UnresolvedTrackMuteTarget, UnresolvedTrackPanTarget, UnresolvedTrackParentSendTarget,
-    UnresolvedTrackPeakTarget, UnresolvedTrackPhaseTarget, UnresolvedTrackSelectionTarget,
-    UnresolvedTrackShowTarget, UnresolvedTrackSoloTarget, UnresolvedTrackToolTarget,
-    UnresolvedTrackTouchStateTarget, UnresolvedTrackVolumeTarget, UnresolvedTrackWidthTarget,
-    UnresolvedTransportTarget,
+    UnresolvedMorphMappingSnapshotsTarget, UnresolvedMouseTarget, UnresolvedOscSendTarget,
+    UnresolvedPlayrateTarget, UnresolvedPreviewPotPresetTarget, UnresolvedRenameBookmarkTarget,
+    UnresolvedRouteAutomationModeTarget, UnresolvedRouteMonoTarget, UnresolvedRouteMuteTarget,
+    UnresolvedRoutePanTarget, UnresolvedRoutePhaseTarget, UnresolvedRouteTouchStateTarget,
+    UnresolvedRouteVolumeTarget, UnresolvedSeekTarget, UnresolvedStreamDeckBrightnessTarget,
+    UnresolvedTakeMappingSnapshotTarget, UnresolvedTakeParameterValueTarget, UnresolvedTempoTarget,
+    UnresolvedTrackArmTarget, UnresolvedTrackAutomationModeTarget, UnresolvedTrackColorTarget,
+    UnresolvedTrackInputFxEnableTarget, UnresolvedTrackMonitoringModeTarget,
+    UnresolvedTrackMuteTarget, UnresolvedTrackNameTarget, UnresolvedTrackPanTarget,
+    UnresolvedTrackParentSendTarget, UnresolvedTrackPeakTarget, UnresolvedTrackPhaseTarget,
+    UnresolvedTrackRecordInputTarget, UnresolvedTrackRecordModeTarget,
+    UnresolvedTrackSelectionTarget, UnresolvedTrackShowTarget, UnresolvedTrackSoloTarget,
+    UnresolvedTrackToolTarget, UnresolvedTrackTouchStateTarget, UnresolvedTrackVolumeTarget,
+    UnresolvedTrackWidthTarget, UnresolvedTransportTarget,
 };
 use derive_more::{Display, Error};
 use enum_dispatch::enum_dispatch;
@@ -73,6 +79,11 @@ pub enum UnresolvedReaperTarget {
     TrackSolo(UnresolvedTrackSoloTarget),
     TrackAutomationMode(UnresolvedTrackAutomationModeTarget),
     TrackMonitoringMode(UnresolvedTrackMonitoringModeTarget),
+    TrackRecordInput(UnresolvedTrackRecordInputTarget),
+    TrackRecordMode(UnresolvedTrackRecordModeTarget),
+    TrackInputFxEnable(UnresolvedTrackInputFxEnableTarget),
+    TrackName(UnresolvedTrackNameTarget),
+    TrackColor(UnresolvedTrackColorTarget),
     RoutePan(UnresolvedRoutePanTarget),
     RouteMute(UnresolvedRouteMuteTarget),
     RoutePhase(UnresolvedRoutePhaseTarget),
@@ -86,6 +97,7 @@ pub enum UnresolvedReaperTarget {
     FxEnable(UnresolvedFxEnableTarget),
     FxOnline(UnresolvedFxOnlineTarget),
     FxOpen(UnresolvedFxOpenTarget),
+    FxName(UnresolvedFxNameTarget),
     FxPreset(UnresolvedFxPresetTarget),
     SelectedTrack(UnresolvedBrowseTracksTarget),
     BrowseFxs(UnresolvedBrowseFxsTarget),
@@ -94,6 +106,9 @@ pub enum UnresolvedReaperTarget {
     LoadFxPreset(UnresolvedLoadFxSnapshotTarget),
     TrackTouchState(UnresolvedTrackTouchStateTarget),
     GoToBookmark(UnresolvedGoToBookmarkTarget),
+    AddBookmark(UnresolvedAddBookmarkTarget),
+    RenameBookmark(UnresolvedRenameBookmarkTarget),
+    DeleteBookmark(UnresolvedDeleteBookmarkTarget),
     Seek(UnresolvedSeekTarget),
     SendMidi(UnresolvedMidiSendTarget),
     SendOsc(UnresolvedOscSendTarget),
@@ -109,6 +124,7 @@ pub enum UnresolvedReaperTarget {
     PlaytimeBrowseCells(crate::domain::UnresolvedPlaytimeBrowseCellsTarget),
     LoadMappingSnapshot(UnresolvedLoadMappingSnapshotTarget),
     TakeMappingSnapshot(UnresolvedTakeMappingSnapshotTarget),
+    MorphMappingSnapshots(UnresolvedMorphMappingSnapshotsTarget),
     EnableMappings(UnresolvedEnableMappingsTarget),
     ModifyMapping(UnresolvedModifyMappingTarget),
     BrowseGroup(UnresolvedBrowseGroupTarget),
@@ -122,6 +138,12 @@ pub enum UnresolvedReaperTarget {
     LoadPotPreset(UnresolvedLoadPotPresetTarget),
     CompartmentParameterValue(UnresolvedCompartmentParameterValueTarget),
     StreamDeckBrightness(UnresolvedStreamDeckBrightnessTarget),
+    ItemParameterValue(UnresolvedItemParameterValueTarget),
+    TakeParameterValue(UnresolvedTakeParameterValueTarget),
+    BrowseTakes(UnresolvedBrowseTakesTarget),
+    EnvelopeValue(UnresolvedEnvelopeValueTarget),
+    BrowseEnvelopePoints(UnresolvedBrowseEnvelopePointsTarget),
+    EnvelopeArmState(UnresolvedEnvelopeArmStateTarget),
 }
 
 impl UnresolvedReaperTarget {
@@ -194,6 +216,16 @@ impl UnresolvedReaperTarget {
                 return true;
             }
         }
+        if let Some(item) = self.virtual_item() {
+            if item.can_be_affected_by_parameters() {
+                return true;
+            }
+        }
+        if let Some(take) = self.virtual_take() {
+            if take.can_be_affected_by_parameters() {
+                return true;
+            }
+        }
         false
     }
 
@@ -950,6 +982,203 @@ impl VirtualPlaytimeRow {
     }
 }
 
+#[derive(Debug)]
+pub enum VirtualItem {
+    Selected,
+    UnderEditCursor,
+    ByIndex(u32),
+    Dynamic(Box<ExpressionEvaluator>),
+}
+
+impl Default for VirtualItem {
+    fn default() -> Self {
+        Self::Selected
+    }
+}
+
+impl VirtualItem {
+    pub fn from_descriptor(
+        descriptor: &helgobox_api::persistence::ItemDescriptor,
+    ) -> Result<VirtualItem, &'static str> {
+        use helgobox_api::persistence::ItemDescriptor::*;
+        let item = match descriptor {
+            Selected => VirtualItem::Selected,
+            UnderEditCursor => VirtualItem::UnderEditCursor,
+            ByIndex { index } => VirtualItem::ByIndex(*index),
+            Dynamic { expression } => {
+                let evaluator = ExpressionEvaluator::compile(expression)
+                    .map_err(|_| "couldn't evaluate item index")?;
+                VirtualItem::Dynamic(Box::new(evaluator))
+            }
+        };
+        Ok(item)
+    }
+
+    /// Resolves to item references, one for each resolved track.
+    ///
+    /// The track is only relevant if the item is not addressed as selected item.
+    pub fn resolve(
+        &self,
+        track_descriptor: &TrackDescriptor,
+        context: ExtendedProcessorContext,
+        compartment: CompartmentKind,
+    ) -> Result<Vec<ItemRef>, &'static str> {
+        let index = match self {
+            VirtualItem::Selected => {
+                let project = context.context().project_or_current_project();
+                return Ok(vec![ItemRef::Selected(project)]);
+            }
+            VirtualItem::UnderEditCursor => None,
+            VirtualItem::ByIndex(index) => Some(*index),
+            VirtualItem::Dynamic(evaluator) => Some(evaluate_to_item_or_take_index(
+                evaluator,
+                context,
+                compartment,
+            )?),
+        };
+        let item_refs = get_effective_tracks(context, &track_descriptor.track, compartment)?
+            .into_iter()
+            .map(|track| match index {
+                None => ItemRef::UnderEditCursor(track),
+                Some(i) => ItemRef::ByIndex(track, i),
+            })
+            .collect();
+        Ok(item_refs)
+    }
+
+    pub fn can_be_affected_by_parameters(&self) -> bool {
+        matches!(self, VirtualItem::Dynamic(_))
+    }
+}
+
+#[derive(Debug)]
+pub enum VirtualTake {
+    Active,
+    ByIndex(u32),
+    Dynamic(Box<ExpressionEvaluator>),
+}
+
+impl Default for VirtualTake {
+    fn default() -> Self {
+        Self::Active
+    }
+}
+
+impl VirtualTake {
+    pub fn from_descriptor(
+        descriptor: &helgobox_api::persistence::TakeDescriptor,
+    ) -> Result<VirtualTake, &'static str> {
+        use helgobox_api::persistence::TakeDescriptor::*;
+        let take = match descriptor {
+            Active => VirtualTake::Active,
+            ByIndex { index } => VirtualTake::ByIndex(*index),
+            Dynamic { expression } => {
+                let evaluator = ExpressionEvaluator::compile(expression)
+                    .map_err(|_| "couldn't evaluate take index")?;
+                VirtualTake::Dynamic(Box::new(evaluator))
+            }
+        };
+        Ok(take)
+    }
+
+    /// Resolves to the take index within the item. `None` means the active take.
+    pub fn resolve(
+        &self,
+        context: ExtendedProcessorContext,
+        compartment: CompartmentKind,
+    ) -> Result<Option<u32>, &'static str> {
+        let index = match self {
+            VirtualTake::Active => None,
+            VirtualTake::ByIndex(index) => Some(*index),
+            VirtualTake::Dynamic(evaluator) => Some(evaluate_to_item_or_take_index(
+                evaluator,
+                context,
+                compartment,
+            )?),
+        };
+        Ok(index)
+    }
+
+    pub fn can_be_affected_by_parameters(&self) -> bool {
+        matches!(self, VirtualTake::Dynamic(_))
+    }
+}
+
+#[derive(Debug)]
+pub enum VirtualEnvelope {
+    /// Track envelope with the given name.
+    Track {
+        track_descriptor: TrackDescriptor,
+        name: String,
+    },
+    /// Automation envelope of an FX parameter.
+    FxParameter(FxParameterDescriptor),
+}
+
+impl VirtualEnvelope {
+    /// Resolves to envelope references, one for each resolved track or FX parameter.
+    ///
+    /// Doesn't check whether the envelope actually exists because it can come and go at any time.
+    pub fn resolve(
+        &self,
+        context: ExtendedProcessorContext,
+        compartment: CompartmentKind,
+    ) -> Result<Vec<EnvelopeRef>, &'static str> {
+        let envelope_refs = match self {
+            VirtualEnvelope::Track {
+                track_descriptor,
+                name,
+            } => get_effective_tracks(context, &track_descriptor.track, compartment)?
+                .into_iter()
+                .map(|track| EnvelopeRef::Track {
+                    track,
+                    name: name.clone(),
+                })
+                .collect(),
+            VirtualEnvelope::FxParameter(descriptor) => {
+                get_fx_params(context, descriptor, compartment)?
+                    .into_iter()
+                    .map(EnvelopeRef::FxParameter)
+                    .collect()
+            }
+        };
+        Ok(envelope_refs)
+    }
+
+    pub fn track_descriptor(&self) -> Option<&TrackDescriptor> {
+        match self {
+            VirtualEnvelope::Track {
+                track_descriptor, ..
+            } => Some(track_descriptor),
+            VirtualEnvelope::FxParameter(_) => None,
+        }
+    }
+
+    pub fn fx_parameter_descriptor(&self) -> Option<&FxParameterDescriptor> {
+        match self {
+            VirtualEnvelope::Track { .. } => None,
+            VirtualEnvelope::FxParameter(descriptor) => Some(descriptor),
+        }
+    }
+}
+
+fn evaluate_to_item_or_take_index(
+    evaluator: &ExpressionEvaluator,
+    context: ExtendedProcessorContext,
+    compartment: CompartmentKind,
+) -> Result<u32, &'static str> {
+    let compartment_params = context.params().compartment_params(compartment);
+    to_item_or_take_index(evaluator.evaluate_with_params(compartment_params))
+}
+
+fn to_item_or_take_index(eval_result: Result<f64, fasteval::Error>) -> Result<u32, &'static str> {
+    let result = eval_result.map_err(|_| "couldn't evaluate index")?.round() as i32;
+    if result < 0 {
+        return Err("negative index");
+    }
+    Ok(result as u32)
+}
+
 fn to_slot_coordinate(eval_result: Result<f64, fasteval::Error>) -> Result<usize, &'static str> {
     let res = eval_result.map_err(|_| "couldn't evaluate clip slot coordinate")?;
     if res < 0.0 {
@@ -2223,6 +2452,14 @@ pub trait UnresolvedReaperTargetDef {
     fn clip_row_descriptor(&self) -> Option<&VirtualPlaytimeRow> {
         None
     }
+
+    fn virtual_item(&self) -> Option<&VirtualItem> {
+        None
+    }
+
+    fn virtual_take(&self) -> Option<&VirtualTake> {
+        None
+    }
 }
 
 /// Special: Index -1 means master track.
@@ -2316,3 +2553,24 @@ fn additional_playtime_vars(context: ControlContext) -> impl Fn(&str, &[f64]) ->
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evaluate_dynamic_item_or_take_index() {
+        // Given
+        let params = CompartmentParams::default();
+        let evaluate = |expression: &str| {
+            let evaluator = ExpressionEvaluator::compile(expression).unwrap();
+            to_item_or_take_index(evaluator.evaluate_with_params(&params))
+        };
+        // When
+        // Then
+        assert_eq!(evaluate("p1 + 2.6"), Ok(3));
+        assert_eq!(evaluate("0"), Ok(0));
+        assert_eq!(evaluate("p1 - 1"), Err("negative index"));
+        assert_eq!(evaluate("unknown_var"), Err("couldn't evaluate index"));
+    }
+}
@@ -0,0 +1,288 @@
+use base64::Engine as _;
+use helgoboss_learn::{
+    AbsoluteValue, FeedbackValue, MidiSourceAddress, MidiSourceScript, MidiSourceScriptOutcome,
+    RawMidiEvent,
+};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::fmt::{Debug, Formatter};
+use wasmi::{
+    Config, Engine, Linker, Memory, Module, Store, StoreLimits, StoreLimitsBuilder, TypedFunc,
+};
+
+/// Size of the buffer which the module must reserve at the offset returned by its `buffer`
+/// export. It's used for passing text feedback values in and MIDI messages out.
+pub const WASM_MIDI_SCRIPT_BUFFER_SIZE: usize = 4096;
+
+/// Maximum amount of fuel (roughly the number of executed instructions) per execution.
+///
+/// Exceeding it aborts the execution, so a buggy module can't block the main thread.
+const FUEL_PER_EXECUTION: u64 = 1_000_000;
+
+/// Maximum size of the linear memory of a module (16 MiB).
+const MAX_MEMORY_SIZE: usize = 16 * 1024 * 1024;
+
+/// Feedback value kinds as passed to the `feedback` export.
+const KIND_OFF: i32 = 0;
+const KIND_CONTINUOUS: i32 = 1;
+const KIND_DISCRETE: i32 = 2;
+const KIND_TEXT: i32 = 3;
+const KIND_COMPLEX: i32 = 4;
+
+struct WasmState {
+    limits: StoreLimits,
+}
+
+struct WasmUnit {
+    store: Store<WasmState>,
+    memory: Memory,
+    buffer_offset: usize,
+    feedback: TypedFunc<(i32, f64, i32), i32>,
+    address: Option<TypedFunc<(), i64>>,
+}
+
+/// A MIDI source script implemented as precompiled WebAssembly module.
+///
+/// The module must export:
+///
+/// - `memory`: Its linear memory.
+/// - `buffer() -> i32`: Offset of a buffer with [`WASM_MIDI_SCRIPT_BUFFER_SIZE`] bytes.
+/// - `feedback(kind: i32, value: f64, text_len: i32) -> i32`: Invoked for each feedback value.
+///   For text and complex (JSON) values, the UTF-8 text is written to the buffer before. Must
+///   write the resulting MIDI messages to the buffer, each one prefixed with its length as single
+///   byte, and return the number of written bytes (or a negative number to indicate an error).
+///
+/// Optionally, it can export `address() -> i64`, which is invoked after `feedback` and returns
+/// the feedback address (0 means no address).
+pub struct WasmMidiSourceScript {
+    // RefCell because executing a function needs mutable access to the store
+    unit: RefCell<WasmUnit>,
+}
+
+impl Debug for WasmMidiSourceScript {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WasmMidiSourceScript").finish()
+    }
+}
+
+impl WasmMidiSourceScript {
+    /// Compiles a module given as Base64-encoded WASM binary.
+    pub fn compile(encoded_module: &str) -> anyhow::Result<Self> {
+        let wasm = decode_wasm_module(encoded_module)?;
+        Self::compile_binary(&wasm)
+    }
+
+    pub fn compile_binary(wasm: &[u8]) -> anyhow::Result<Self> {
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, wasm)?;
+        let state = WasmState {
+            limits: StoreLimitsBuilder::new()
+                .memory_size(MAX_MEMORY_SIZE)
+                .instances(1)
+                .build(),
+        };
+        let mut store = Store::new(&engine, state);
+        store.limiter(|state| &mut state.limits);
+        // Also limit the execution time of the module's start function
+        store.set_fuel(FUEL_PER_EXECUTION)?;
+        // No imports. Modules can't interact with anything else than their own memory.
+        let linker = Linker::<WasmState>::new(&engine);
+        let instance = linker.instantiate(&mut store, &module)?.start(&mut store)?;
+        let memory = instance
+            .get_memory(&store, "memory")
+            .ok_or_else(|| anyhow::anyhow!("module doesn't export memory"))?;
+        let buffer = instance.get_typed_func::<(), i32>(&store, "buffer")?;
+        let feedback = instance.get_typed_func::<(i32, f64, i32), i32>(&store, "feedback")?;
+        let address = instance.get_typed_func::<(), i64>(&store, "address").ok();
+        store.set_fuel(FUEL_PER_EXECUTION)?;
+        let buffer_offset = buffer.call(&mut store, ())?;
+        let buffer_offset =
+            usize::try_from(buffer_offset).map_err(|_| anyhow::anyhow!("invalid buffer offset"))?;
+        if buffer_offset + WASM_MIDI_SCRIPT_BUFFER_SIZE > memory.data(&store).len() {
+            anyhow::bail!("buffer exceeds memory");
+        }
+        let unit = WasmUnit {
+            store,
+            memory,
+            buffer_offset,
+            feedback,
+            address,
+        };
+        Ok(Self {
+            unit: RefCell::new(unit),
+        })
+    }
+}
+
+impl MidiSourceScript<'_> for WasmMidiSourceScript {
+    type AdditionalInput = ();
+
+    fn execute(
+        &self,
+        input_value: FeedbackValue,
+        _additional_input: (),
+    ) -> Result<MidiSourceScriptOutcome, Cow<'static, str>> {
+        let (kind, value, text) = match input_value {
+            FeedbackValue::Off => (KIND_OFF, 0.0, None),
+            FeedbackValue::Numeric(v) => match v.value {
+                AbsoluteValue::Continuous(v) => (KIND_CONTINUOUS, v.get(), None),
+                AbsoluteValue::Discrete(f) => (KIND_DISCRETE, f.actual() as f64, None),
+            },
+            FeedbackValue::Textual(v) => (KIND_TEXT, 0.0, Some(v.text.to_string())),
+            FeedbackValue::Complex(v) => {
+                let json = serde_json::to_string(&v.value)
+                    .map_err(|_| "couldn't convert complex value to JSON")?;
+                (KIND_COMPLEX, 0.0, Some(json))
+            }
+        };
+        let mut unit = self.unit.borrow_mut();
+        let unit = &mut *unit;
+        let buffer_range = unit.buffer_offset..unit.buffer_offset + WASM_MIDI_SCRIPT_BUFFER_SIZE;
+        let text_len = match text {
+            None => 0,
+            Some(text) => {
+                let bytes = text.as_bytes();
+                if bytes.len() > WASM_MIDI_SCRIPT_BUFFER_SIZE {
+                    return Err("text too long".into());
+                }
+                unit.memory
+                    .write(&mut unit.store, unit.buffer_offset, bytes)
+                    .map_err(|_| "couldn't write text to buffer")?;
+                bytes.len() as i32
+            }
+        };
+        unit.store
+            .set_fuel(FUEL_PER_EXECUTION)
+            .map_err(|_| "couldn't set fuel")?;
+        let output_len = unit
+            .feedback
+            .call(&mut unit.store, (kind, value, text_len))
+            .map_err(|e| {
+                let error = e.to_string();
+                tracing::debug!(msg = "Failed to execute WASM MIDI source script", %error);
+                error
+            })?;
+        let output_len = usize::try_from(output_len).map_err(|_| "module reported error")?;
+        if output_len > WASM_MIDI_SCRIPT_BUFFER_SIZE {
+            return Err("invalid output length".into());
+        }
+        let address = match &unit.address {
+            None => 0,
+            Some(f) => f
+                .call(&mut unit.store, ())
+                .map_err(|_| "couldn't query address")?,
+        };
+        let memory = unit.memory.data(&unit.store);
+        let output = &memory[buffer_range][..output_len];
+        let outcome = MidiSourceScriptOutcome {
+            address: if address == 0 {
+                None
+            } else {
+                Some(MidiSourceAddress::Script {
+                    bytes: address as u64,
+                })
+            },
+            events: parse_messages(output)?.into_iter().collect(),
+        };
+        Ok(outcome)
+    }
+}
+
+/// Parses length-prefixed MIDI messages.
+fn parse_messages(mut output: &[u8]) -> Result<Vec<RawMidiEvent>, &'static str> {
+    let mut events = vec![];
+    while let Some((&len, rest)) = output.split_first() {
+        let len = len as usize;
+        if len > rest.len() {
+            return Err("truncated message");
+        }
+        let (msg, rest) = rest.split_at(len);
+        let event = RawMidiEvent::try_from_slice(0, msg).map_err(|_| "invalid message")?;
+        events.push(event);
+        output = rest;
+    }
+    Ok(events)
+}
+
+pub fn encode_wasm_module(wasm: &[u8]) -> String {
+    base64::engine::general_purpose::STANDARD.encode(wasm)
+}
+
+pub fn decode_wasm_module(encoded_module: &str) -> anyhow::Result<Vec<u8>> {
+    let encoded_module: String = encoded_module
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    if encoded_module.is_empty() {
+        anyhow::bail!("module empty");
+    }
+    let wasm = base64::engine::general_purpose::STANDARD.decode(encoded_module)?;
+    Ok(wasm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helgoboss_learn::{FeedbackStyle, NumericFeedbackValue, UnitValue};
+
+    #[test]
+    fn basics() {
+        // Given
+        let wat = r#"
+            (module
+                (memory (export "memory") 1)
+                (func (export "buffer") (result i32) i32.const 0)
+                (func (export "address") (result i64) i64.const 0x4bb0)
+                (func (export "feedback") (param $kind i32) (param $value f64) (param $text_len i32) (result i32)
+                    (i32.store8 (i32.const 0) (i32.const 3))
+                    (i32.store8 (i32.const 1) (i32.const 0xb0))
+                    (i32.store8 (i32.const 2) (i32.const 0x4b))
+                    (i32.store8 (i32.const 3) (i32.trunc_f64_u (f64.mul (local.get $value) (f64.const 10))))
+                    i32.const 4
+                )
+            )
+        "#;
+        let wasm = wat::parse_str(wat).unwrap();
+        let script = WasmMidiSourceScript::compile(&encode_wasm_module(&wasm)).unwrap();
+        // When
+        let fb_value = NumericFeedbackValue::new(
+            FeedbackStyle::default(),
+            AbsoluteValue::Continuous(UnitValue::new(0.5)),
+        );
+        let outcome = script
+            .execute(FeedbackValue::Numeric(fb_value), ())
+            .unwrap();
+        // Then
+        assert_eq!(
+            outcome.address,
+            Some(MidiSourceAddress::Script { bytes: 0x4bb0 })
+        );
+        assert_eq!(
+            outcome.events,
+            vec![RawMidiEvent::try_from_slice(0, &[0xb0, 0x4b, 5]).unwrap()]
+        );
+    }
+
+    #[test]
+    fn endless_loop_runs_out_of_fuel() {
+        // Given
+        let wat = r#"
+            (module
+                (memory (export "memory") 1)
+                (func (export "buffer") (result i32) i32.const 0)
+                (func (export "feedback") (param i32 f64 i32) (result i32)
+                    (loop $forever (br $forever))
+                    i32.const 0
+                )
+            )
+        "#;
+        let wasm = wat::parse_str(wat).unwrap();
+        let script = WasmMidiSourceScript::compile_binary(&wasm).unwrap();
+        // When
+        let result = script.execute(FeedbackValue::Off, ());
+        // Then
+        assert!(result.is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::domain::{DEFAULT_CHORD_WINDOW, DEFAULT_MUSICAL_TIMER_INTERVAL};
 use helgobox_api::persistence::Interval;
 
 pub const MAPPING_CONTROL_ENABLED: bool = true;
@@ -11,6 +12,9 @@ pub const GROUP_FEEDBACK_ENABLED: bool = true;
 pub const SOURCE_OSC_IS_RELATIVE: bool = false;
 pub const SOURCE_MACKIE_LCD_EXTENDER_INDEX: u8 = 0;
 pub const SOURCE_X_TOUCH_MACKIE_LCD_EXTENDER_INDEX: u8 = 0;
+pub const SOURCE_MUSICAL_TIMER_INTERVAL: f64 = DEFAULT_MUSICAL_TIMER_INTERVAL;
+pub const SOURCE_MUSICAL_TIMER_ONLY_WHILE_PLAYING: bool = false;
+pub const SOURCE_CHORD_WINDOW_MILLIS: u64 = DEFAULT_CHORD_WINDOW.as_millis() as _;
 
 pub const UNIT_INTERVAL: Interval<f64> = Interval(0.0, 1.0);
 pub const GLUE_STEP_SIZE_INTERVAL: Interval<f64> = Interval(0.01, 0.01);
@@ -46,6 +50,7 @@ pub const TARGET_SEEK_MOVE_VIEW: bool = true;
 pub const TARGET_SEEK_SEEK_PLAY: bool = true;
 pub const TARGET_LOAD_MAPPING_SNAPSHOT_ACTIVE_MAPPINGS_ONLY: bool = false;
 pub const TARGET_SAVE_MAPPING_SNAPSHOT_ACTIVE_MAPPINGS_ONLY: bool = false;
+pub const TARGET_MORPH_MAPPING_SNAPSHOTS_ACTIVE_MAPPINGS_ONLY: bool = false;
 pub const TARGET_STOP_COLUMN_IF_SLOT_EMPTY: bool = false;
 pub const TARGET_USE_SELECTION_GANGING: bool = false;
 pub const TARGET_USE_TRACK_GROUPING: bool = false;
@@ -189,6 +189,7 @@ pub fn convert_glue(
             style.required_value(v)
         },
         feedback_value_table: data.feedback_value_table,
+        feedback_color_table: data.feedback_color_table,
     };
     Ok(glue)
 }
@@ -1,5 +1,6 @@
 use crate::application::{
-    LifecycleMidiMessageModel, LifecycleModel, MappingExtensionModel, RawMidiMessage,
+    LifecycleActionModel, LifecycleMidiMessageModel, LifecycleModel, MappingExtensionModel,
+    OscArgValueModel, RawMidiMessage,
 };
 use crate::infrastructure::api::convert::from_data::{
     convert_activation_condition, convert_glue, convert_source, convert_tags, convert_target,
@@ -123,10 +124,67 @@ fn convert_lifecycle_model(
                 .collect();
             style.required_value(actions?)
         },
+        actions: {
+            let actions: Vec<_> = lifecycle_model
+                .actions
+                .into_iter()
+                .map(convert_lifecycle_action_model)
+                .collect();
+            style.required_value(actions)
+        },
     };
     Ok(style.required_value(hook))
 }
 
+fn convert_lifecycle_action_model(model: LifecycleActionModel) -> persistence::LifecycleAction {
+    match model {
+        LifecycleActionModel::SendOsc {
+            address,
+            args,
+            device_id,
+        } => persistence::LifecycleAction::SendOsc {
+            address,
+            arguments: {
+                let args: Vec<_> = args.into_iter().map(convert_osc_arg_value_model).collect();
+                if args.is_empty() {
+                    None
+                } else {
+                    Some(args)
+                }
+            },
+            destination: device_id
+                .map(|id| persistence::OscDestination::Device { id: id.to_string() }),
+        },
+        LifecycleActionModel::SetParameter { index, value } => {
+            persistence::LifecycleAction::SetParameter {
+                parameter: persistence::CompartmentParameterDescriptor::ById { index: index.get() },
+                value,
+            }
+        }
+        LifecycleActionModel::InvokeAction { command_name } => {
+            let command = match command_name.parse::<u32>() {
+                Ok(id) => persistence::ReaperCommand::Id(id),
+                Err(_) => persistence::ReaperCommand::Name(command_name),
+            };
+            persistence::LifecycleAction::InvokeAction { command }
+        }
+    }
+}
+
+fn convert_osc_arg_value_model(model: OscArgValueModel) -> persistence::OscArgValue {
+    use persistence::OscArgValue as T;
+    match model {
+        OscArgValueModel::Float(value) => T::Float { value },
+        OscArgValueModel::Double(value) => T::Double { value },
+        OscArgValueModel::Int(value) => T::Int { value },
+        OscArgValueModel::Long(value) => T::Long { value },
+        OscArgValueModel::Bool(value) => T::Bool { value },
+        OscArgValueModel::String(value) => T::String { value },
+        OscArgValueModel::Nil => T::Nil,
+        OscArgValueModel::Inf => T::Inf,
+    }
+}
+
 fn convert_lifecycle_midi_message_model(
     model: LifecycleMidiMessageModel,
 ) -> ConversionResult<persistence::SendMidiFeedbackAction> {
@@ -13,7 +13,7 @@ pub use compartment::*;
 mod target;
 
 use crate::application::ActivationType;
-use crate::domain::{Keystroke, Tag};
+use crate::domain::{BooleanOperator, Keystroke, Tag};
 use crate::infrastructure::data::{
     ActivationConditionData, OscValueRange, VirtualControlElementIdData,
 };
@@ -193,5 +193,26 @@ fn convert_activation_condition(
             };
             Some(T::TargetValue(condition))
         }
+        Composite => {
+            let condition = persistence::CompositeActivationCondition {
+                conditions: condition_data
+                    .conditions
+                    .into_iter()
+                    // Operands which are "Always" don't have any API representation. Represent
+                    // them as empty "And" (which is always fulfilled).
+                    .map(|c| {
+                        convert_activation_condition(c).unwrap_or(T::And(
+                            persistence::CompositeActivationCondition { conditions: vec![] },
+                        ))
+                    })
+                    .collect(),
+            };
+            let condition = match condition_data.operator {
+                BooleanOperator::And => T::And(condition),
+                BooleanOperator::Or => T::Or(condition),
+                BooleanOperator::Not => T::Not(condition),
+            };
+            Some(condition)
+        }
     }
 }
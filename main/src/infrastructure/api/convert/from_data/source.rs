@@ -239,6 +239,20 @@ pub fn convert_source(
                 Timer => persistence::Source::Timer(persistence::TimerSource {
                     duration: data.timer_millis,
                 }),
+                MusicalTimer => {
+                    persistence::Source::MusicalTimer(persistence::MusicalTimerSource {
+                        unit: style.required_value(data.musical_timer_unit),
+                        interval: style.required_value_with_default(
+                            data.musical_timer_interval,
+                            defaults::SOURCE_MUSICAL_TIMER_INTERVAL,
+                        ),
+                        offset: style.required_value(data.musical_timer_offset),
+                        only_while_playing: style.required_value_with_default(
+                            data.musical_timer_only_while_playing,
+                            defaults::SOURCE_MUSICAL_TIMER_ONLY_WHILE_PLAYING,
+                        ),
+                    })
+                }
                 RealearnParameter => {
                     persistence::Source::RealearnParameter(persistence::RealearnParameterSource {
                         parameter_index: data.parameter_index.get(),
@@ -267,6 +281,34 @@ pub fn convert_source(
             };
             persistence::Source::StreamDeck(s)
         }
+        Evdev => {
+            let s = persistence::EvdevSource {
+                event_kind: style.required_value(data.evdev_event_kind),
+                code: data.evdev_code,
+            };
+            persistence::Source::Evdev(s)
+        }
+        Chord => {
+            let members = data
+                .chord_members
+                .into_iter()
+                .map(|m| {
+                    let props = NewSourceProps {
+                        prevent_echo_feedback: false,
+                        send_feedback_after_control: false,
+                    };
+                    convert_source(m, props, style)
+                })
+                .collect::<ConversionResult<_>>()?;
+            let s = persistence::ChordSource {
+                members,
+                window_millis: style.required_value_with_default(
+                    data.chord_window_millis,
+                    defaults::SOURCE_CHORD_WINDOW_MILLIS,
+                ),
+            };
+            persistence::Source::Chord(s)
+        }
     };
     Ok(source)
 }
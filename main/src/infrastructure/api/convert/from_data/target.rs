@@ -14,30 +14,35 @@ use crate::infrastructure::api::convert::from_data::{
 use crate::infrastructure::api::convert::{defaults, ConversionResult};
 use crate::infrastructure::data::{
     deserialize_fx, deserialize_fx_parameter, deserialize_track, deserialize_track_route,
-    MigrationDescriptor, TargetModelData, TrackData, TrackDeserializationInput,
+    BookmarkData, MigrationDescriptor, TargetModelData, TrackData, TrackDeserializationInput,
 };
 use base::hash_util::convert_into_other_hash_set;
 use helgobox_api::persistence;
 use helgobox_api::persistence::{
-    AllTrackFxOnOffStateTarget, AnyOnTarget, AutomationModeOverrideTarget,
+    AddBookmarkTarget, AllTrackFxOnOffStateTarget, AnyOnTarget, AutomationModeOverrideTarget,
     BackwardCompatibleMappingSnapshotDescForTake, BookmarkDescriptor, BookmarkRef,
-    BrowseFxChainTarget, BrowseFxPresetsTarget, BrowseGroupMappingsTarget,
-    BrowsePotFilterItemsTarget, BrowsePotPresetsTarget, BrowseTracksTarget,
-    CompartmentParameterDescriptor, CompartmentParameterValueTarget, DummyTarget,
-    EnableInstancesTarget, EnableMappingsTarget, EnableUnitsTarget, FxOnOffStateTarget,
-    FxOnlineOfflineStateTarget, FxParameterAutomationTouchStateTarget, FxParameterValueTarget,
-    FxToolTarget, FxVisibilityTarget, GoToBookmarkTarget, InputDeviceMidiDestination,
+    BrowseEnvelopePointsTarget, BrowseFxChainTarget, BrowseFxPresetsTarget,
+    BrowseGroupMappingsTarget, BrowsePotFilterItemsTarget, BrowsePotPresetsTarget,
+    BrowseTakesTarget, BrowseTracksTarget, CompartmentParameterDescriptor,
+    CompartmentParameterValueTarget, DeleteBookmarkTarget, DummyTarget, EnableInstancesTarget,
+    EnableMappingsTarget, EnableUnitsTarget, EnvelopeArmStateTarget, EnvelopeValueTarget,
+    FxNameTarget, FxOnOffStateTarget, FxOnlineOfflineStateTarget,
+    FxParameterAutomationTouchStateTarget, FxParameterValueTarget, FxToolTarget,
+    FxVisibilityTarget, GoToBookmarkTarget, InputDeviceMidiDestination, ItemParameterValueTarget,
     LastTouchedTarget, LearnTargetMappingModification, LoadFxSnapshotTarget,
     LoadMappingSnapshotTarget, LoadPotPresetTarget, MappingModification, ModifyMappingTarget,
-    MouseTarget, PlayRateTarget, PreviewPotPresetTarget, ReaperActionTarget,
-    RouteAutomationModeTarget, RouteMonoStateTarget, RouteMuteStateTarget, RoutePanTarget,
-    RoutePhaseTarget, RouteTouchStateTarget, RouteVolumeTarget, SeekTarget, SendMidiTarget,
-    SendOscTarget, SetTargetToLastTouchedMappingModification, StreamDeckBrightnessTarget,
-    TakeMappingSnapshotTarget, TempoTarget, TrackArmStateTarget, TrackAutomationModeTarget,
-    TrackAutomationTouchStateTarget, TrackMonitoringModeTarget, TrackMuteStateTarget,
+    MorphMappingSnapshotsTarget, MouseTarget, PlayRateTarget, PreviewPotPresetTarget,
+    ReaperActionTarget, RenameBookmarkTarget, RouteAutomationModeTarget, RouteMonoStateTarget,
+    RouteMuteStateTarget, RoutePanTarget, RoutePhaseTarget, RouteTouchStateTarget,
+    RouteVolumeTarget, SeekTarget, SendMidiTarget, SendOscTarget,
+    SetTargetToLastTouchedMappingModification, StreamDeckBrightnessTarget,
+    TakeMappingSnapshotTarget, TakeParameterValueTarget, TempoTarget, TrackArmStateTarget,
+    TrackAutomationModeTarget, TrackAutomationTouchStateTarget, TrackColorTarget,
+    TrackInputFxOnOffStateTarget, TrackMonitoringModeTarget, TrackMuteStateTarget, TrackNameTarget,
     TrackPanTarget, TrackParentSendStateTarget, TrackPeakTarget, TrackPhaseTarget,
-    TrackSelectionStateTarget, TrackSoloStateTarget, TrackToolTarget, TrackVisibilityTarget,
-    TrackVolumeTarget, TrackWidthTarget, TransportActionTarget,
+    TrackRecordInputTarget, TrackRecordModeTarget, TrackSelectionStateTarget, TrackSoloStateTarget,
+    TrackToolTarget, TrackVisibilityTarget, TrackVolumeTarget, TrackWidthTarget,
+    TransportActionTarget,
 };
 
 pub fn convert_target(
@@ -124,21 +129,7 @@ fn convert_real_target(
         }),
         GoToBookmark => T::GoToBookmark(GoToBookmarkTarget {
             commons,
-            bookmark: {
-                let bookmark_ref = match data.bookmark_data.anchor {
-                    BookmarkAnchorType::Id => BookmarkRef::ById {
-                        id: data.bookmark_data.r#ref,
-                    },
-                    BookmarkAnchorType::Index => BookmarkRef::ByIndex {
-                        index: data.bookmark_data.r#ref,
-                    },
-                };
-                if data.bookmark_data.is_region {
-                    BookmarkDescriptor::Region(bookmark_ref)
-                } else {
-                    BookmarkDescriptor::Marker(bookmark_ref)
-                }
-            },
+            bookmark: convert_bookmark_descriptor(&data.bookmark_data),
             set_time_selection: style.required_value_with_default(
                 data.seek_options.use_time_selection,
                 defaults::TARGET_BOOKMARK_SET_TIME_SELECTION,
@@ -175,6 +166,38 @@ fn convert_real_target(
                 defaults::TARGET_USE_SELECTION_GANGING,
             ),
         }),
+        TrackRecordInput => T::TrackRecordInput(TrackRecordInputTarget {
+            commons,
+            track: convert_track_descriptor(
+                data.track_data,
+                data.enable_only_if_track_is_selected,
+                &data.clip_column,
+                style,
+            ),
+            exclusivity: convert_track_exclusivity(data.track_exclusivity),
+            input: data.track_record_input,
+        }),
+        TrackRecordMode => T::TrackRecordMode(TrackRecordModeTarget {
+            commons,
+            track: convert_track_descriptor(
+                data.track_data,
+                data.enable_only_if_track_is_selected,
+                &data.clip_column,
+                style,
+            ),
+            exclusivity: convert_track_exclusivity(data.track_exclusivity),
+            mode: data.track_record_mode,
+        }),
+        TrackInputFxEnable => T::TrackInputFxOnOffState(TrackInputFxOnOffStateTarget {
+            commons,
+            track: convert_track_descriptor(
+                data.track_data,
+                data.enable_only_if_track_is_selected,
+                &data.clip_column,
+                style,
+            ),
+            exclusivity: convert_track_exclusivity(data.track_exclusivity),
+        }),
         TrackTouchState => T::TrackAutomationTouchState(TrackAutomationTouchStateTarget {
             commons,
             track: convert_track_descriptor(
@@ -300,6 +323,67 @@ fn convert_real_target(
             },
             route: convert_route_descriptor(data, style),
         }),
+        ItemParameterValue => T::ItemParameterValue(ItemParameterValueTarget {
+            commons,
+            track: convert_track_descriptor(
+                data.track_data,
+                data.enable_only_if_track_is_selected,
+                &data.clip_column,
+                style,
+            ),
+            item: Some(data.item),
+            parameter: Some(data.item_parameter),
+        }),
+        TakeParameterValue => T::TakeParameterValue(TakeParameterValueTarget {
+            commons,
+            track: convert_track_descriptor(
+                data.track_data,
+                data.enable_only_if_track_is_selected,
+                &data.clip_column,
+                style,
+            ),
+            item: Some(data.item),
+            take: Some(data.take),
+            parameter: Some(data.take_parameter),
+        }),
+        BrowseTakes => T::BrowseTakes(BrowseTakesTarget {
+            commons,
+            track: convert_track_descriptor(
+                data.track_data,
+                data.enable_only_if_track_is_selected,
+                &data.clip_column,
+                style,
+            ),
+            item: Some(data.item),
+        }),
+        AddBookmark => T::AddBookmark(AddBookmarkTarget {
+            commons,
+            region: style.required_value(data.bookmark_data.is_region),
+            tolerance: data.bookmark_tolerance_millis.map(|ms| ms as _),
+        }),
+        RenameBookmark => T::RenameBookmark(RenameBookmarkTarget {
+            commons,
+            bookmark: convert_editable_bookmark_descriptor(&data),
+            tolerance: data.bookmark_tolerance_millis.map(|ms| ms as _),
+        }),
+        DeleteBookmark => T::DeleteBookmark(DeleteBookmarkTarget {
+            commons,
+            bookmark: convert_editable_bookmark_descriptor(&data),
+            tolerance: data.bookmark_tolerance_millis.map(|ms| ms as _),
+        }),
+        EnvelopeValue => T::EnvelopeValue(EnvelopeValueTarget {
+            commons,
+            position: style.required_value(data.envelope_position),
+            envelope: convert_envelope_descriptor(data, style),
+        }),
+        BrowseEnvelopePoints => T::BrowseEnvelopePoints(BrowseEnvelopePointsTarget {
+            commons,
+            envelope: convert_envelope_descriptor(data, style),
+        }),
+        EnvelopeArmState => T::EnvelopeArmState(EnvelopeArmStateTarget {
+            commons,
+            envelope: convert_envelope_descriptor(data, style),
+        }),
         PlaytimeSlotTransportAction => T::PlaytimeSlotTransportAction(
             helgobox_api::persistence::PlaytimeSlotTransportActionTarget {
                 commons,
@@ -498,6 +582,24 @@ fn convert_real_target(
                 style,
             ),
         }),
+        TrackName => T::TrackName(TrackNameTarget {
+            commons,
+            track: convert_track_descriptor(
+                data.track_data,
+                data.enable_only_if_track_is_selected,
+                &data.clip_column,
+                style,
+            ),
+        }),
+        TrackColor => T::TrackColor(TrackColorTarget {
+            commons,
+            track: convert_track_descriptor(
+                data.track_data,
+                data.enable_only_if_track_is_selected,
+                &data.clip_column,
+                style,
+            ),
+        }),
         TrackPhase => T::TrackPhase(TrackPhaseTarget {
             commons,
             track: convert_track_descriptor(
@@ -667,6 +769,11 @@ fn convert_real_target(
             display_kind: convert_fx_display_kind(data.fx_display_type, style),
             fx: convert_fx_descriptor(data, style),
         }),
+        FxName => T::FxName(FxNameTarget {
+            commons,
+            name_kind: style.optional_value(Some(data.fx_name_kind)),
+            fx: convert_fx_descriptor(data, style),
+        }),
         SendOsc => T::SendOsc(SendOscTarget {
             commons,
             address: style.required_value(data.osc_address_pattern),
@@ -764,6 +871,17 @@ fn convert_real_target(
                 .map(BackwardCompatibleMappingSnapshotDescForTake::New)
                 .unwrap_or_default(),
         }),
+        MorphMappingSnapshots => T::MorphMappingSnapshots(MorphMappingSnapshotsTarget {
+            commons,
+            tags: convert_tags(&data.tags, style),
+            active_mappings_only: Some(data.active_mappings_only),
+            snapshot_a: data
+                .mapping_snapshot
+                .id()
+                .filter(|id| !id.is_empty())
+                .map(|id| id.to_string()),
+            snapshot_b: data.second_mapping_snapshot_id,
+        }),
         BrowseGroup => T::BrowseGroupMappings(BrowseGroupMappingsTarget {
             commons,
             exclusivity: {
@@ -1009,6 +1127,48 @@ fn convert_fx_parameter_descriptor(
     }
 }
 
+fn convert_bookmark_descriptor(data: &BookmarkData) -> BookmarkDescriptor {
+    let bookmark_ref = match data.anchor {
+        BookmarkAnchorType::Id => BookmarkRef::ById { id: data.r#ref },
+        BookmarkAnchorType::Index => BookmarkRef::ByIndex { index: data.r#ref },
+    };
+    if data.is_region {
+        BookmarkDescriptor::Region(bookmark_ref)
+    } else {
+        BookmarkDescriptor::Marker(bookmark_ref)
+    }
+}
+
+/// Returns `None` if the marker nearest to the play position should be edited.
+fn convert_editable_bookmark_descriptor(data: &TargetModelData) -> Option<BookmarkDescriptor> {
+    if data.use_nearest_marker {
+        return None;
+    }
+    Some(convert_bookmark_descriptor(&data.bookmark_data))
+}
+
+fn convert_envelope_descriptor(
+    data: TargetModelData,
+    style: ConversionStyle,
+) -> persistence::EnvelopeDescriptor {
+    use persistence::EnvelopeDescriptor as T;
+    if data.envelope_name.is_empty() {
+        T::FxParameter {
+            parameter: convert_fx_parameter_descriptor(data, style),
+        }
+    } else {
+        T::Track {
+            name: data.envelope_name,
+            track: convert_track_descriptor(
+                data.track_data,
+                data.enable_only_if_track_is_selected,
+                &data.clip_column,
+                style,
+            ),
+        }
+    }
+}
+
 fn convert_route_descriptor(
     data: TargetModelData,
     style: ConversionStyle,
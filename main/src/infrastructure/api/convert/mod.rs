@@ -10,3 +10,63 @@ fn convert_multiple<A, B>(
 ) -> ConversionResult<Vec<B>> {
     input.into_iter().map(f).collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helgobox_api::persistence::{
+        EnvelopeArmStateTarget, EnvelopeDescriptor, FxParameterDescriptor, Target,
+    };
+
+    #[test]
+    fn convert_track_envelope_back_and_forth() {
+        // Given
+        let target = envelope_arm_state_target(EnvelopeDescriptor::Track {
+            track: None,
+            name: "Pan".to_string(),
+        });
+        // When
+        let data = to_data::convert_target(target).unwrap();
+        let converted_target =
+            from_data::convert_target(data.clone(), from_data::ConversionStyle::Minimal).unwrap();
+        // Then
+        assert_eq!(data.envelope_name, "Pan");
+        assert!(matches!(
+            converted_target,
+            Target::EnvelopeArmState(EnvelopeArmStateTarget {
+                envelope: EnvelopeDescriptor::Track { name, .. },
+                ..
+            }) if name == "Pan"
+        ));
+    }
+
+    #[test]
+    fn convert_fx_parameter_envelope_back_and_forth() {
+        // Given
+        let target = envelope_arm_state_target(EnvelopeDescriptor::FxParameter {
+            parameter: FxParameterDescriptor::ByIndex { fx: None, index: 3 },
+        });
+        // When
+        let data = to_data::convert_target(target).unwrap();
+        let converted_target =
+            from_data::convert_target(data.clone(), from_data::ConversionStyle::Minimal).unwrap();
+        // Then
+        assert!(data.envelope_name.is_empty());
+        assert!(matches!(
+            converted_target,
+            Target::EnvelopeArmState(EnvelopeArmStateTarget {
+                envelope: EnvelopeDescriptor::FxParameter {
+                    parameter: FxParameterDescriptor::ByIndex { index: 3, .. }
+                },
+                ..
+            })
+        ));
+    }
+
+    fn envelope_arm_state_target(envelope: EnvelopeDescriptor) -> Target {
+        Target::EnvelopeArmState(EnvelopeArmStateTarget {
+            commons: Default::default(),
+            envelope,
+        })
+    }
+}
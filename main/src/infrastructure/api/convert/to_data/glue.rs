@@ -211,6 +211,7 @@ pub fn convert_glue(g: Glue) -> ConversionResult<ModeModelData> {
         },
         feedback_type: fb_data.feedback_type,
         feedback_value_table: g.feedback_value_table,
+        feedback_color_table: g.feedback_color_table,
     };
     Ok(data)
 }
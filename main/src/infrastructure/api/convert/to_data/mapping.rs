@@ -1,9 +1,10 @@
 use super::convert_source;
 use crate::application;
 use crate::application::{
-    LifecycleMidiMessageModel, LifecycleModel, MappingExtensionModel, RawByteArrayMidiMessage,
+    LifecycleActionModel, LifecycleMidiMessageModel, LifecycleModel, MappingExtensionModel,
+    OscArgValueModel, RawByteArrayMidiMessage,
 };
-use crate::domain::Tag;
+use crate::domain::{CompartmentParamIndex, Tag};
 use crate::infrastructure::api::convert::to_data::glue::convert_glue;
 use crate::infrastructure::api::convert::to_data::target::convert_target;
 use crate::infrastructure::api::convert::to_data::{
@@ -118,10 +119,10 @@ fn convert_advanced(
 }
 
 fn convert_lifecycle_hook(hook: Option<LifecycleHook>) -> ConversionResult<LifecycleModel> {
+    let hook = hook.unwrap_or_default();
     let v = LifecycleModel {
         send_midi_feedback: {
             let actions: Result<Vec<_>, _> = hook
-                .unwrap_or_default()
                 .send_midi_feedback
                 .unwrap_or_default()
                 .into_iter()
@@ -129,10 +130,69 @@ fn convert_lifecycle_hook(hook: Option<LifecycleHook>) -> ConversionResult<Lifec
                 .collect();
             actions?
         },
+        actions: {
+            let actions: Result<Vec<_>, _> = hook
+                .actions
+                .unwrap_or_default()
+                .into_iter()
+                .map(convert_lifecycle_action)
+                .collect();
+            actions?
+        },
+    };
+    Ok(v)
+}
+
+fn convert_lifecycle_action(action: LifecycleAction) -> ConversionResult<LifecycleActionModel> {
+    let v = match action {
+        LifecycleAction::SendOsc {
+            address,
+            arguments,
+            destination,
+        } => LifecycleActionModel::SendOsc {
+            address,
+            args: arguments
+                .unwrap_or_default()
+                .into_iter()
+                .map(convert_osc_arg_value)
+                .collect(),
+            device_id: match destination.unwrap_or_default() {
+                OscDestination::FeedbackOutput => None,
+                OscDestination::Device { id } => Some(id.parse()?),
+            },
+        },
+        LifecycleAction::SetParameter { parameter, value } => {
+            let index = match parameter {
+                CompartmentParameterDescriptor::ById { index } => index,
+            };
+            LifecycleActionModel::SetParameter {
+                index: CompartmentParamIndex::try_from(index).map_err(anyhow::Error::msg)?,
+                value,
+            }
+        }
+        LifecycleAction::InvokeAction { command } => LifecycleActionModel::InvokeAction {
+            command_name: match command {
+                ReaperCommand::Id(id) => id.to_string(),
+                ReaperCommand::Name(n) => n,
+            },
+        },
     };
     Ok(v)
 }
 
+fn convert_osc_arg_value(value: OscArgValue) -> OscArgValueModel {
+    match value {
+        OscArgValue::Float { value } => OscArgValueModel::Float(value),
+        OscArgValue::Double { value } => OscArgValueModel::Double(value),
+        OscArgValue::Int { value } => OscArgValueModel::Int(value),
+        OscArgValue::Long { value } => OscArgValueModel::Long(value),
+        OscArgValue::Bool { value } => OscArgValueModel::Bool(value),
+        OscArgValue::String { value } => OscArgValueModel::String(value),
+        OscArgValue::Nil => OscArgValueModel::Nil,
+        OscArgValue::Inf => OscArgValueModel::Inf,
+    }
+}
+
 fn convert_send_midi_feedback_action(
     action: SendMidiFeedbackAction,
 ) -> ConversionResult<LifecycleMidiMessageModel> {
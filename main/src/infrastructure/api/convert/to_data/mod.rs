@@ -1,5 +1,5 @@
 use crate::application::{BankConditionModel, ModifierConditionModel};
-use crate::domain::{CompartmentKind, CompartmentParamIndex};
+use crate::domain::{BooleanOperator, CompartmentKind, CompartmentParamIndex};
 use crate::infrastructure::api::convert::ConversionResult;
 use crate::infrastructure::data;
 use crate::infrastructure::data::{ActivationConditionData, OscValueRange};
@@ -7,13 +7,15 @@ use crate::{application, domain};
 use anyhow::anyhow;
 pub use compartment::*;
 use enumflags2::BitFlags;
+pub use glue::convert_glue;
 use helgobox_api::persistence::{
-    ActivationCondition, Interval, Keystroke, ModifierState, OscArgKind, ParamRef,
-    VirtualControlElementId,
+    ActivationCondition, CompositeActivationCondition, Interval, Keystroke, ModifierState,
+    OscArgKind, ParamRef, VirtualControlElementId,
 };
 pub use mapping::*;
 use reaper_medium::AcceleratorKeyCode;
 use source::*;
+pub use target::convert_target;
 
 mod compartment;
 mod glue;
@@ -132,6 +134,28 @@ fn convert_activation(
             eel_condition: c.condition,
             ..Default::default()
         },
+        And(c) => convert_composite_activation(BooleanOperator::And, c, param_index_by_key)?,
+        Or(c) => convert_composite_activation(BooleanOperator::Or, c, param_index_by_key)?,
+        Not(c) => convert_composite_activation(BooleanOperator::Not, c, param_index_by_key)?,
+    };
+    Ok(data)
+}
+
+fn convert_composite_activation(
+    operator: BooleanOperator,
+    c: CompositeActivationCondition,
+    param_index_by_key: &impl Fn(&str) -> Option<CompartmentParamIndex>,
+) -> ConversionResult<ActivationConditionData> {
+    let conditions: ConversionResult<Vec<_>> = c
+        .conditions
+        .into_iter()
+        .map(|c| convert_activation(c, param_index_by_key))
+        .collect();
+    let data = ActivationConditionData {
+        activation_type: application::ActivationType::Composite,
+        operator,
+        conditions: conditions?,
+        ..Default::default()
     };
     Ok(data)
 }
@@ -11,7 +11,7 @@ use helgobox_api::persistence::*;
 use std::convert::TryInto;
 
 pub fn convert_source(s: Source) -> ConversionResult<SourceModelData> {
-    let data = SourceModelData {
+    let mut data = SourceModelData {
         category: convert_category(&s),
         r#type: convert_midi_source_type(&s),
         channel: convert_midi_channel(&s)?,
@@ -133,6 +133,14 @@ pub fn convert_source(s: Source) -> ConversionResult<SourceModelData> {
             Source::StreamDeck(s) => s.button_design.clone(),
             _ => Default::default(),
         },
+        evdev_event_kind: match &s {
+            Source::Evdev(s) => s.event_kind.unwrap_or_default(),
+            _ => Default::default(),
+        },
+        evdev_code: match &s {
+            Source::Evdev(s) => s.code,
+            _ => Default::default(),
+        },
         control_element_type: match &s {
             Source::Virtual(s) => s.character.unwrap_or_default(),
             _ => Default::default(),
@@ -145,6 +153,7 @@ pub fn convert_source(s: Source) -> ConversionResult<SourceModelData> {
             Source::MidiDeviceChanges => ReaperSourceType::MidiDeviceChanges,
             Source::RealearnInstanceStart => ReaperSourceType::RealearnUnitStart,
             Source::Timer(_) => ReaperSourceType::Timer,
+            Source::MusicalTimer(_) => ReaperSourceType::MusicalTimer,
             Source::RealearnParameter(_) => ReaperSourceType::RealearnParameter,
             Source::RealearnCompartmentLoaded => ReaperSourceType::RealearnCompartmentLoaded,
             _ => Default::default(),
@@ -153,13 +162,48 @@ pub fn convert_source(s: Source) -> ConversionResult<SourceModelData> {
             Source::Timer(t) => t.duration,
             _ => Default::default(),
         },
+        musical_timer_unit: match &s {
+            Source::MusicalTimer(t) => t.unit.unwrap_or_default(),
+            _ => Default::default(),
+        },
+        musical_timer_interval: match &s {
+            Source::MusicalTimer(t) => t
+                .interval
+                .unwrap_or(defaults::SOURCE_MUSICAL_TIMER_INTERVAL),
+            _ => Default::default(),
+        },
+        musical_timer_offset: match &s {
+            Source::MusicalTimer(t) => t.offset.unwrap_or_default(),
+            _ => Default::default(),
+        },
+        musical_timer_only_while_playing: match &s {
+            Source::MusicalTimer(t) => t
+                .only_while_playing
+                .unwrap_or(defaults::SOURCE_MUSICAL_TIMER_ONLY_WHILE_PLAYING),
+            _ => Default::default(),
+        },
         parameter_index: match &s {
             Source::RealearnParameter(s) => {
                 s.parameter_index.try_into().map_err(anyhow::Error::msg)?
             }
             _ => Default::default(),
         },
+        chord_members: vec![],
+        chord_window_millis: Default::default(),
     };
+    if let Source::Chord(s) = s {
+        if s.members.iter().any(|m| matches!(m, Source::Chord(_))) {
+            bail!("chords can't be nested");
+        }
+        data.chord_members = s
+            .members
+            .into_iter()
+            .map(convert_source)
+            .collect::<ConversionResult<_>>()?;
+        data.chord_window_millis = s
+            .window_millis
+            .unwrap_or(defaults::SOURCE_CHORD_WINDOW_MILLIS);
+    }
     Ok(data)
 }
 
@@ -171,6 +215,7 @@ fn convert_category(s: &Source) -> SourceCategory {
         | RealearnInstanceStart
         | RealearnCompartmentLoaded
         | Timer(_)
+        | MusicalTimer(_)
         | RealearnParameter(_)
         | Speech => SourceCategory::Reaper,
         MidiNoteVelocity(_)
@@ -195,6 +240,8 @@ fn convert_category(s: &Source) -> SourceCategory {
         Osc(_) => SourceCategory::Osc,
         Key(_) => SourceCategory::Keyboard,
         StreamDeck(_) => SourceCategory::StreamDeck,
+        Evdev(_) => SourceCategory::Evdev,
+        Chord(_) => SourceCategory::Chord,
         Virtual(_) => SourceCategory::Virtual,
     }
 }
@@ -155,26 +155,7 @@ pub fn convert_target(t: Target) -> ConversionResult<TargetModelData> {
         Target::GoToBookmark(d) => TargetModelData {
             category: TargetCategory::Reaper,
             r#type: ReaperTargetType::GoToBookmark,
-            bookmark_data: {
-                match d.bookmark {
-                    BookmarkDescriptor::Marker(r) => {
-                        let (anchor, r#ref) = convert_bookmark_ref(r);
-                        BookmarkData {
-                            anchor,
-                            r#ref,
-                            is_region: false,
-                        }
-                    }
-                    BookmarkDescriptor::Region(r) => {
-                        let (anchor, r#ref) = convert_bookmark_ref(r);
-                        BookmarkData {
-                            anchor,
-                            r#ref,
-                            is_region: true,
-                        }
-                    }
-                }
-            },
+            bookmark_data: convert_bookmark_descriptor(d.bookmark),
             seek_options: SeekOptions {
                 use_time_selection: d
                     .set_time_selection
@@ -188,6 +169,38 @@ pub fn convert_target(t: Target) -> ConversionResult<TargetModelData> {
             seek_behavior: d.seek_behavior,
             ..init(d.commons)
         },
+        Target::AddBookmark(d) => TargetModelData {
+            category: TargetCategory::Reaper,
+            r#type: ReaperTargetType::AddBookmark,
+            bookmark_data: BookmarkData {
+                is_region: d.region.unwrap_or_default(),
+                ..Default::default()
+            },
+            bookmark_tolerance_millis: d.tolerance.map(u64::from),
+            ..init(d.commons)
+        },
+        Target::RenameBookmark(d) => TargetModelData {
+            category: TargetCategory::Reaper,
+            r#type: ReaperTargetType::RenameBookmark,
+            use_nearest_marker: d.bookmark.is_none(),
+            bookmark_data: d
+                .bookmark
+                .map(convert_bookmark_descriptor)
+                .unwrap_or_default(),
+            bookmark_tolerance_millis: d.tolerance.map(u64::from),
+            ..init(d.commons)
+        },
+        Target::DeleteBookmark(d) => TargetModelData {
+            category: TargetCategory::Reaper,
+            r#type: ReaperTargetType::DeleteBookmark,
+            use_nearest_marker: d.bookmark.is_none(),
+            bookmark_data: d
+                .bookmark
+                .map(convert_bookmark_descriptor)
+                .unwrap_or_default(),
+            bookmark_tolerance_millis: d.tolerance.map(u64::from),
+            ..init(d.commons)
+        },
         Target::TrackArmState(d) => {
             let track_desc = convert_track_desc(d.track.unwrap_or_default())?;
             TargetModelData {
@@ -266,6 +279,28 @@ pub fn convert_target(t: Target) -> ConversionResult<TargetModelData> {
                 ..init(d.commons)
             }
         }
+        Target::TrackName(d) => {
+            let track_desc = convert_track_desc(d.track.unwrap_or_default())?;
+            TargetModelData {
+                category: TargetCategory::Reaper,
+                r#type: ReaperTargetType::TrackName,
+                track_data: track_desc.track_data,
+                enable_only_if_track_is_selected: track_desc.track_must_be_selected,
+                clip_column: track_desc.clip_column.unwrap_or_default(),
+                ..init(d.commons)
+            }
+        }
+        Target::TrackColor(d) => {
+            let track_desc = convert_track_desc(d.track.unwrap_or_default())?;
+            TargetModelData {
+                category: TargetCategory::Reaper,
+                r#type: ReaperTargetType::TrackColor,
+                track_data: track_desc.track_data,
+                enable_only_if_track_is_selected: track_desc.track_must_be_selected,
+                clip_column: track_desc.clip_column.unwrap_or_default(),
+                ..init(d.commons)
+            }
+        }
         Target::TrackPhase(d) => {
             let track_desc = convert_track_desc(d.track.unwrap_or_default())?;
             TargetModelData {
@@ -337,6 +372,44 @@ pub fn convert_target(t: Target) -> ConversionResult<TargetModelData> {
                 ..init(d.commons)
             }
         }
+        Target::TrackRecordInput(d) => {
+            let track_desc = convert_track_desc(d.track.unwrap_or_default())?;
+            TargetModelData {
+                category: TargetCategory::Reaper,
+                r#type: ReaperTargetType::TrackRecordInput,
+                track_data: track_desc.track_data,
+                enable_only_if_track_is_selected: track_desc.track_must_be_selected,
+                clip_column: track_desc.clip_column.unwrap_or_default(),
+                track_exclusivity: convert_track_exclusivity(d.exclusivity),
+                track_record_input: d.input,
+                ..init(d.commons)
+            }
+        }
+        Target::TrackRecordMode(d) => {
+            let track_desc = convert_track_desc(d.track.unwrap_or_default())?;
+            TargetModelData {
+                category: TargetCategory::Reaper,
+                r#type: ReaperTargetType::TrackRecordMode,
+                track_data: track_desc.track_data,
+                enable_only_if_track_is_selected: track_desc.track_must_be_selected,
+                clip_column: track_desc.clip_column.unwrap_or_default(),
+                track_exclusivity: convert_track_exclusivity(d.exclusivity),
+                track_record_mode: d.mode,
+                ..init(d.commons)
+            }
+        }
+        Target::TrackInputFxOnOffState(d) => {
+            let track_desc = convert_track_desc(d.track.unwrap_or_default())?;
+            TargetModelData {
+                category: TargetCategory::Reaper,
+                r#type: ReaperTargetType::TrackInputFxEnable,
+                track_data: track_desc.track_data,
+                enable_only_if_track_is_selected: track_desc.track_must_be_selected,
+                clip_column: track_desc.clip_column.unwrap_or_default(),
+                track_exclusivity: convert_track_exclusivity(d.exclusivity),
+                ..init(d.commons)
+            }
+        }
         Target::TrackAutomationTouchState(d) => {
             let track_desc = convert_track_desc(d.track.unwrap_or_default())?;
             TargetModelData {
@@ -596,6 +669,21 @@ pub fn convert_target(t: Target) -> ConversionResult<TargetModelData> {
                 ..init(d.commons)
             }
         }
+        Target::FxName(d) => {
+            let fx_desc = convert_fx_desc(d.fx.unwrap_or_default())?;
+            let track_desc = fx_desc.chain_desc.track_desc;
+            TargetModelData {
+                category: TargetCategory::Reaper,
+                r#type: ReaperTargetType::FxName,
+                track_data: track_desc.track_data,
+                enable_only_if_track_is_selected: track_desc.track_must_be_selected,
+                clip_column: track_desc.clip_column.unwrap_or_default(),
+                fx_data: fx_desc.fx_data,
+                enable_only_if_fx_has_focus: fx_desc.fx_must_have_focus,
+                fx_name_kind: d.name_kind.unwrap_or_default(),
+                ..init(d.commons)
+            }
+        }
         Target::FxParameterValue(d) => {
             let fx_parameter_desc = convert_fx_parameter_desc(d.parameter)?;
             let fx_desc = fx_parameter_desc.fx_desc;
@@ -754,6 +842,100 @@ pub fn convert_target(t: Target) -> ConversionResult<TargetModelData> {
                 ..init(d.commons)
             }
         }
+        Target::ItemParameterValue(d) => {
+            let track_desc = convert_track_desc(d.track.unwrap_or_default())?;
+            TargetModelData {
+                category: TargetCategory::Reaper,
+                r#type: ReaperTargetType::ItemParameterValue,
+                track_data: track_desc.track_data,
+                enable_only_if_track_is_selected: track_desc.track_must_be_selected,
+                clip_column: track_desc.clip_column.unwrap_or_default(),
+                item: d.item.unwrap_or_default(),
+                item_parameter: d.parameter.unwrap_or_default(),
+                ..init(d.commons)
+            }
+        }
+        Target::TakeParameterValue(d) => {
+            let track_desc = convert_track_desc(d.track.unwrap_or_default())?;
+            TargetModelData {
+                category: TargetCategory::Reaper,
+                r#type: ReaperTargetType::TakeParameterValue,
+                track_data: track_desc.track_data,
+                enable_only_if_track_is_selected: track_desc.track_must_be_selected,
+                clip_column: track_desc.clip_column.unwrap_or_default(),
+                item: d.item.unwrap_or_default(),
+                take: d.take.unwrap_or_default(),
+                take_parameter: d.parameter.unwrap_or_default(),
+                ..init(d.commons)
+            }
+        }
+        Target::BrowseTakes(d) => {
+            let track_desc = convert_track_desc(d.track.unwrap_or_default())?;
+            TargetModelData {
+                category: TargetCategory::Reaper,
+                r#type: ReaperTargetType::BrowseTakes,
+                track_data: track_desc.track_data,
+                enable_only_if_track_is_selected: track_desc.track_must_be_selected,
+                clip_column: track_desc.clip_column.unwrap_or_default(),
+                item: d.item.unwrap_or_default(),
+                ..init(d.commons)
+            }
+        }
+        Target::EnvelopeValue(d) => {
+            let envelope_desc = convert_envelope_desc(d.envelope)?;
+            let fx_parameter_desc = envelope_desc.fx_parameter_desc;
+            let fx_desc = fx_parameter_desc.fx_desc;
+            let track_desc = fx_desc.chain_desc.track_desc;
+            TargetModelData {
+                category: TargetCategory::Reaper,
+                r#type: ReaperTargetType::EnvelopeValue,
+                track_data: track_desc.track_data,
+                enable_only_if_track_is_selected: track_desc.track_must_be_selected,
+                clip_column: track_desc.clip_column.unwrap_or_default(),
+                fx_data: fx_desc.fx_data,
+                enable_only_if_fx_has_focus: fx_desc.fx_must_have_focus,
+                fx_parameter_data: fx_parameter_desc.fx_parameter_data,
+                envelope_name: envelope_desc.envelope_name,
+                envelope_position: d.position.unwrap_or_default(),
+                ..init(d.commons)
+            }
+        }
+        Target::BrowseEnvelopePoints(d) => {
+            let envelope_desc = convert_envelope_desc(d.envelope)?;
+            let fx_parameter_desc = envelope_desc.fx_parameter_desc;
+            let fx_desc = fx_parameter_desc.fx_desc;
+            let track_desc = fx_desc.chain_desc.track_desc;
+            TargetModelData {
+                category: TargetCategory::Reaper,
+                r#type: ReaperTargetType::BrowseEnvelopePoints,
+                track_data: track_desc.track_data,
+                enable_only_if_track_is_selected: track_desc.track_must_be_selected,
+                clip_column: track_desc.clip_column.unwrap_or_default(),
+                fx_data: fx_desc.fx_data,
+                enable_only_if_fx_has_focus: fx_desc.fx_must_have_focus,
+                fx_parameter_data: fx_parameter_desc.fx_parameter_data,
+                envelope_name: envelope_desc.envelope_name,
+                ..init(d.commons)
+            }
+        }
+        Target::EnvelopeArmState(d) => {
+            let envelope_desc = convert_envelope_desc(d.envelope)?;
+            let fx_parameter_desc = envelope_desc.fx_parameter_desc;
+            let fx_desc = fx_parameter_desc.fx_desc;
+            let track_desc = fx_desc.chain_desc.track_desc;
+            TargetModelData {
+                category: TargetCategory::Reaper,
+                r#type: ReaperTargetType::EnvelopeArmState,
+                track_data: track_desc.track_data,
+                enable_only_if_track_is_selected: track_desc.track_must_be_selected,
+                clip_column: track_desc.clip_column.unwrap_or_default(),
+                fx_data: fx_desc.fx_data,
+                enable_only_if_fx_has_focus: fx_desc.fx_must_have_focus,
+                fx_parameter_data: fx_parameter_desc.fx_parameter_data,
+                envelope_name: envelope_desc.envelope_name,
+                ..init(d.commons)
+            }
+        }
         Target::PlaytimeSlotTransportAction(d) => TargetModelData {
             category: TargetCategory::Reaper,
             r#type: ReaperTargetType::PlaytimeSlotTransportAction,
@@ -965,6 +1147,19 @@ pub fn convert_target(t: Target) -> ConversionResult<TargetModelData> {
             },
             ..init(d.commons)
         },
+        Target::MorphMappingSnapshots(d) => TargetModelData {
+            category: TargetCategory::Reaper,
+            r#type: ReaperTargetType::MorphMappingSnapshots,
+            tags: convert_tags(d.tags.unwrap_or_default())?,
+            active_mappings_only: d
+                .active_mappings_only
+                .unwrap_or(defaults::TARGET_MORPH_MAPPING_SNAPSHOTS_ACTIVE_MAPPINGS_ONLY),
+            mapping_snapshot: MappingSnapshotDescForLoad::ById {
+                id: d.snapshot_a.unwrap_or_default(),
+            },
+            second_mapping_snapshot_id: d.snapshot_b,
+            ..init(d.commons)
+        },
         Target::BrowseGroupMappings(d) => TargetModelData {
             category: TargetCategory::Reaper,
             r#type: ReaperTargetType::BrowseGroup,
@@ -1084,6 +1279,13 @@ struct FxParameterDesc {
     fx_parameter_data: FxParameterData,
 }
 
+#[derive(Default)]
+struct EnvelopeDesc {
+    fx_parameter_desc: FxParameterDesc,
+    /// Empty if the envelope of the FX parameter is meant.
+    envelope_name: String,
+}
+
 fn convert_track_desc(t: TrackDescriptor) -> ConversionResult<TrackDesc> {
     use TrackDescriptor::*;
     let (props, track_must_be_selected) = match t {
@@ -1449,6 +1651,29 @@ fn convert_fx_parameter_desc(t: FxParameterDescriptor) -> ConversionResult<FxPar
     Ok(desc)
 }
 
+fn convert_envelope_desc(t: EnvelopeDescriptor) -> ConversionResult<EnvelopeDesc> {
+    let desc = match t {
+        EnvelopeDescriptor::Track { track, name } => EnvelopeDesc {
+            fx_parameter_desc: FxParameterDesc {
+                fx_desc: FxDesc {
+                    chain_desc: FxChainDesc {
+                        track_desc: convert_track_desc(track.unwrap_or_default())?,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            envelope_name: name,
+        },
+        EnvelopeDescriptor::FxParameter { parameter } => EnvelopeDesc {
+            fx_parameter_desc: convert_fx_parameter_desc(parameter)?,
+            envelope_name: String::new(),
+        },
+    };
+    Ok(desc)
+}
+
 fn convert_transport_action(transport_action: TransportAction) -> domain::TransportAction {
     use domain::TransportAction as T;
     use TransportAction::*;
@@ -1482,6 +1707,27 @@ fn convert_feedback_resolution(r: FeedbackResolution) -> domain::FeedbackResolut
     }
 }
 
+fn convert_bookmark_descriptor(d: BookmarkDescriptor) -> BookmarkData {
+    match d {
+        BookmarkDescriptor::Marker(r) => {
+            let (anchor, r#ref) = convert_bookmark_ref(r);
+            BookmarkData {
+                anchor,
+                r#ref,
+                is_region: false,
+            }
+        }
+        BookmarkDescriptor::Region(r) => {
+            let (anchor, r#ref) = convert_bookmark_ref(r);
+            BookmarkData {
+                anchor,
+                r#ref,
+                is_region: true,
+            }
+        }
+    }
+}
+
 fn convert_bookmark_ref(r: BookmarkRef) -> (BookmarkAnchorType, u32) {
     use BookmarkAnchorType as T;
     match r {
@@ -6,7 +6,7 @@ use helgoboss_learn::{
     GroupInteraction, Interval, OutOfRangeBehavior, SoftSymmetricUnitValue, TakeoverMode,
     UnitValue, ValueSequence, VirtualColor,
 };
-use helgobox_api::persistence::FeedbackValueTable;
+use helgobox_api::persistence::{FeedbackColorTable, FeedbackValueTable};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use tracing::debug;
@@ -208,6 +208,12 @@ pub struct ModeModelData {
         skip_serializing_if = "is_default"
     )]
     pub feedback_value_table: Option<FeedbackValueTable>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub feedback_color_table: Option<FeedbackColorTable>,
 }
 
 fn default_step_size() -> SoftSymmetricUnitValue {
@@ -266,6 +272,7 @@ impl ModeModelData {
             target_value_sequence: model.target_value_sequence().clone(),
             feedback_type: model.feedback_type(),
             feedback_value_table: model.feedback_value_table().cloned(),
+            feedback_color_table: model.feedback_color_table().cloned(),
         }
     }
 
@@ -407,6 +414,7 @@ impl ModeModelData {
         ));
         model.change(P::SetFeedbackType(self.feedback_type));
         model.change(P::SetFeedbackValueTable(self.feedback_value_table.clone()));
+        model.change(P::SetFeedbackColorTable(self.feedback_color_table.clone()));
     }
 }
 
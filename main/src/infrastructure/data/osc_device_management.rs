@@ -1,5 +1,7 @@
 use crate::base::AsyncNotifier;
-use crate::domain::{OscDeviceId, OscInputDevice, OscOutputDevice};
+use crate::domain::{
+    OscDeviceId, OscInputDevice, OscOutputDevice, OscOutputPolicy, OscStreamFraming, OscTransport,
+};
 use base::default_util::{bool_true, deserialize_null_default, is_bool_true, is_default};
 use camino::Utf8PathBuf;
 use derive_more::Display;
@@ -9,7 +11,8 @@ use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::error::Error;
 use std::fs;
-use std::net::{Ipv4Addr, SocketAddrV4, UdpSocket};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, UdpSocket};
+use std::num::NonZeroU32;
 use std::rc::Rc;
 use strum::EnumIs;
 
@@ -131,6 +134,9 @@ pub struct OscDevice {
     name: String,
     #[serde(default = "bool_true", skip_serializing_if = "is_bool_true")]
     is_enabled_for_control: bool,
+    /// Used for both control and feedback.
+    #[serde(default, skip_serializing_if = "is_default")]
+    transport: OscTransport,
     /// For receiving control messages.
     #[serde(
         default,
@@ -148,7 +154,9 @@ pub struct OscDevice {
         deserialize_with = "deserialize_null_default",
         skip_serializing_if = "is_default"
     )]
-    device_host: Option<Ipv4Addr>,
+    ///
+    /// Can be an IPv4 or IPv6 address.
+    device_host: Option<IpAddr>,
     /// For sending feedback messages.
     #[serde(
         default,
@@ -158,6 +166,9 @@ pub struct OscDevice {
     device_port: Option<u16>,
     #[serde(default = "bool_true", skip_serializing_if = "is_bool_true")]
     can_deal_with_bundles: bool,
+    /// For sending feedback messages.
+    #[serde(default, skip_serializing_if = "is_default")]
+    output_policy: OscOutputPolicy,
     #[serde(skip)]
     has_output_connection_problem: bool,
 }
@@ -169,10 +180,12 @@ impl Default for OscDevice {
             name: "".to_string(),
             is_enabled_for_control: true,
             is_enabled_for_feedback: true,
+            transport: Default::default(),
             local_port: None,
             device_host: None,
             device_port: None,
             can_deal_with_bundles: true,
+            output_policy: Default::default(),
             has_input_connection_problem: false,
             has_output_connection_problem: false,
         }
@@ -186,15 +199,18 @@ impl OscDevice {
         if !self.is_enabled_for_control && !self.is_enabled_for_feedback {
             return Err("neither control nor feedback enabled".into());
         }
-        let ip = Ipv4Addr::UNSPECIFIED;
+        let ip = self.local_ip();
         let bind_address = if self.is_enabled_for_control {
             // Control. We need to bind to the defined local port.
-            SocketAddrV4::new(ip, self.local_port.ok_or("local port not specified")?)
+            SocketAddr::new(ip, self.local_port.ok_or("local port not specified")?)
         } else {
             // Feedback only. We don't care to which port to connect locally because we don't
             // want to receive control messages.
-            SocketAddrV4::new(ip, 0)
+            SocketAddr::new(ip, 0)
         };
+        if let Some(framing) = self.transport.stream_framing() {
+            return self.connect_via_tcp(bind_address, framing);
+        }
         let socket = UdpSocket::bind(bind_address)?;
         let input_dev = if self.is_enabled_for_control {
             let result = self.connect_input_internal(socket.try_clone()?);
@@ -213,6 +229,47 @@ impl OscDevice {
         Ok((input_dev, output_dev))
     }
 
+    fn connect_via_tcp(
+        &mut self,
+        bind_address: SocketAddr,
+        framing: OscStreamFraming,
+    ) -> Result<(Option<OscInputDevice>, Option<OscOutputDevice>), Box<dyn Error>> {
+        let input_dev = if self.is_enabled_for_control {
+            let result = self.connect_tcp_input_internal(bind_address, framing);
+            self.has_input_connection_problem = result.is_err();
+            Some(result?)
+        } else {
+            None
+        };
+        let output_dev = if self.is_enabled_for_feedback {
+            let result = self.connect_tcp_output_internal(framing);
+            self.has_output_connection_problem = result.is_err();
+            Some(result?)
+        } else {
+            None
+        };
+        Ok((input_dev, output_dev))
+    }
+
+    /// The local IP to bind to.
+    ///
+    /// We bind to the unspecified address of the same family as the device host because a UDP
+    /// socket can't send to an address of a different family.
+    fn local_ip(&self) -> IpAddr {
+        match self.device_host {
+            Some(IpAddr::V6(_)) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+            _ => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        }
+    }
+
+    fn dest_address(&self) -> Result<SocketAddr, Box<dyn Error>> {
+        let dest_addr = SocketAddr::new(
+            self.device_host.ok_or("device host not specified")?,
+            self.device_port.ok_or("local port not specified")?,
+        );
+        Ok(dest_addr)
+    }
+
     fn connect_input_internal(&self, socket: UdpSocket) -> Result<OscInputDevice, Box<dyn Error>> {
         socket.set_nonblocking(true)?;
         OscInputDevice::bind(self.id, socket)
@@ -222,11 +279,39 @@ impl OscDevice {
         &self,
         socket: UdpSocket,
     ) -> Result<OscOutputDevice, Box<dyn Error>> {
-        let dest_addr = SocketAddrV4::new(
-            self.device_host.ok_or("device host not specified")?,
-            self.device_port.ok_or("local port not specified")?,
+        let dest_addr = self.dest_address()?;
+        let dev = OscOutputDevice::new(
+            self.id,
+            socket,
+            dest_addr,
+            self.can_deal_with_bundles,
+            self.output_policy,
+        );
+        Ok(dev)
+    }
+
+    fn connect_tcp_input_internal(
+        &self,
+        bind_address: SocketAddr,
+        framing: OscStreamFraming,
+    ) -> Result<OscInputDevice, Box<dyn Error>> {
+        let listener = TcpListener::bind(bind_address)?;
+        listener.set_nonblocking(true)?;
+        OscInputDevice::listen(self.id, listener, framing)
+    }
+
+    fn connect_tcp_output_internal(
+        &self,
+        framing: OscStreamFraming,
+    ) -> Result<OscOutputDevice, Box<dyn Error>> {
+        let dest_addr = self.dest_address()?;
+        let dev = OscOutputDevice::new_tcp(
+            self.id,
+            dest_addr,
+            framing,
+            self.can_deal_with_bundles,
+            self.output_policy,
         );
-        let dev = OscOutputDevice::new(self.id, socket, dest_addr, self.can_deal_with_bundles);
         Ok(dev)
     }
 
@@ -250,7 +335,11 @@ impl OscDevice {
         self.local_port
     }
 
-    pub fn device_host(&self) -> Option<Ipv4Addr> {
+    pub fn transport(&self) -> OscTransport {
+        self.transport
+    }
+
+    pub fn device_host(&self) -> Option<IpAddr> {
         self.device_host
     }
 
@@ -270,6 +359,10 @@ impl OscDevice {
         self.can_deal_with_bundles
     }
 
+    pub fn output_policy(&self) -> OscOutputPolicy {
+        self.output_policy
+    }
+
     pub fn input_status(&self) -> OscDeviceStatus {
         use OscDeviceStatus::*;
         if !self.is_configured_for_input() {
@@ -306,7 +399,11 @@ impl OscDevice {
         self.local_port = local_port;
     }
 
-    pub fn set_device_host(&mut self, device_host: Option<Ipv4Addr>) {
+    pub fn set_transport(&mut self, transport: OscTransport) {
+        self.transport = transport;
+    }
+
+    pub fn set_device_host(&mut self, device_host: Option<IpAddr>) {
         self.device_host = device_host;
     }
 
@@ -326,6 +423,14 @@ impl OscDevice {
         self.can_deal_with_bundles = !self.can_deal_with_bundles;
     }
 
+    pub fn set_max_feedback_messages_per_second(&mut self, value: Option<NonZeroU32>) {
+        self.output_policy.max_messages_per_second = value;
+    }
+
+    pub fn toggle_coalesce_feedback_by_address(&mut self) {
+        self.output_policy.coalesce_by_address = !self.output_policy.coalesce_by_address;
+    }
+
     pub fn get_list_label(&self, is_output: bool) -> String {
         format!(
             "{}{}",
@@ -3,8 +3,8 @@ use crate::application::{CompartmentPresetManager, CompartmentPresetModel};
 use crate::base::notification;
 use crate::base::notification::{warn_user_about_anyhow_error, warn_user_on_anyhow_error};
 use crate::domain::{
-    CompartmentKind, FsDirLuaModuleFinder, IncludedDirLuaModuleFinder, LuaModuleContainer,
-    LuaModuleFinder, SafeLua,
+    user_preset_lua_module_root, CompartmentKind, FsDirLuaModuleFinder, IncludedDirLuaModuleFinder,
+    LuaModuleContainer, LuaModuleFinder, SafeLua,
 };
 use crate::infrastructure::api::convert::to_data::convert_compartment;
 use crate::infrastructure::data::CompartmentPresetData;
@@ -481,26 +481,13 @@ impl<S: SpecificPresetMetaData> FileBasedCompartmentPresetManager<S> {
                 let module_finder: Result<Rc<dyn LuaModuleFinder>, _> = match origin {
                     PresetOrigin::User {
                         absolute_file_path: _,
-                    } => {
-                        let relative_path = Utf8Path::new(&preset_info.common.id);
-                        let mut components = relative_path.components();
-                        let first_component = components
-                            .next()
-                            .expect("user preset with empty path shouldn't happen here");
-                        if components.next().is_some() {
-                            // That means the user preset is in a subdirectory of the preset folder.
-                            // This is our root for resolving Lua modules (the subdirectory serves as namespace).
-                            let module_root = self.preset_dir_path.join(first_component);
-                            Ok(Rc::new(FsDirLuaModuleFinder::new(module_root)))
-                        } else {
-                            // The preset resides in the root of the preset folder. This is discouraged nowadays
-                            // because it makes sharing presets more difficult (conflicting file names etc.).
-                            // That's why we don't allow using require in this case!
-                            Err(
-                                r#"Using "require" in Lua presets is only supported if they are located in a subfolder of the main or controller preset folder."#,
-                            )
-                        }
-                    }
+                    } => user_preset_lua_module_root(
+                        &self.preset_dir_path,
+                        Utf8Path::new(&preset_info.common.id),
+                    )
+                    .map(|module_root| {
+                        Rc::new(FsDirLuaModuleFinder::new(module_root)) as Rc<dyn LuaModuleFinder>
+                    }),
                     PresetOrigin::Factory { compartment, .. } => {
                         let module_root = get_factory_preset_dir(*compartment).clone();
                         Ok(Rc::new(IncludedDirLuaModuleFinder::new(module_root)))
@@ -10,8 +10,8 @@ use base::default_util::{deserialize_null_default, is_default};
 use helgoboss_learn::{DisplayType, MidiClockTransportMessage, OscTypeTag, SourceCharacter};
 use helgoboss_midi::{Channel, U14, U7};
 use helgobox_api::persistence::{
-    MidiScriptKind, StreamDeckButtonBackground, StreamDeckButtonDesign, StreamDeckButtonForeground,
-    VirtualControlElementCharacter,
+    EvdevEventKind, MidiScriptKind, MusicalTimerUnit, StreamDeckButtonBackground,
+    StreamDeckButtonDesign, StreamDeckButtonForeground, VirtualControlElementCharacter,
 };
 use semver::Version;
 use serde::{Deserialize, Serialize};
@@ -157,6 +157,19 @@ pub struct SourceModelData {
     pub button_index: u32,
     #[serde(default)]
     pub button_design: StreamDeckButtonDesign,
+    // Evdev
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub evdev_event_kind: EvdevEventKind,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub evdev_code: u16,
     // Virtual
     #[serde(
         default,
@@ -188,11 +201,51 @@ pub struct SourceModelData {
         deserialize_with = "deserialize_null_default",
         skip_serializing_if = "is_default"
     )]
+    pub musical_timer_unit: MusicalTimerUnit,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub musical_timer_interval: f64,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub musical_timer_offset: f64,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub musical_timer_only_while_playing: bool,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
     pub parameter_index: CompartmentParamIndex,
+    // Chord
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub chord_members: Vec<SourceModelData>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub chord_window_millis: u64,
 }
 
 impl SourceModelData {
     pub fn from_model(model: &SourceModel) -> Self {
+        // Only persist musical timer settings if they are relevant
+        let is_musical_timer = model.category() == SourceCategory::Reaper
+            && model.reaper_source_type() == ReaperSourceType::MusicalTimer;
         Self {
             category: model.category(),
             r#type: model.midi_source_type(),
@@ -221,13 +274,38 @@ impl SourceModelData {
             keystroke: model.keystroke(),
             button_index: model.button_index(),
             button_design: model.create_stream_deck_button_design(),
+            evdev_event_kind: model.evdev_event_kind(),
+            evdev_code: model.evdev_code(),
             control_element_type: model.control_element_character(),
             control_element_index: VirtualControlElementIdData::from_model(
                 model.control_element_id(),
             ),
             reaper_source_type: model.reaper_source_type(),
             timer_millis: model.timer_millis(),
+            musical_timer_unit: if is_musical_timer {
+                model.musical_timer_unit()
+            } else {
+                Default::default()
+            },
+            musical_timer_interval: if is_musical_timer {
+                model.musical_timer_interval()
+            } else {
+                Default::default()
+            },
+            musical_timer_offset: if is_musical_timer {
+                model.musical_timer_offset()
+            } else {
+                Default::default()
+            },
+            musical_timer_only_while_playing: is_musical_timer
+                && model.musical_timer_only_while_playing(),
             parameter_index: model.parameter_index(),
+            chord_members: model
+                .chord_members()
+                .iter()
+                .map(SourceModelData::from_model)
+                .collect(),
+            chord_window_millis: model.chord_window_millis(),
         }
     }
 
@@ -337,6 +415,31 @@ impl SourceModelData {
         model.change(P::SetButtonStaticText(
             self.button_design.static_text.clone(),
         ));
+        model.change(P::SetEvdevEventKind(self.evdev_event_kind));
+        model.change(P::SetEvdevCode(self.evdev_code));
+        if self.category == SourceCategory::Reaper
+            && self.reaper_source_type == ReaperSourceType::MusicalTimer
+        {
+            model.change(P::SetMusicalTimerUnit(self.musical_timer_unit));
+            model.change(P::SetMusicalTimerInterval(self.musical_timer_interval));
+            model.change(P::SetMusicalTimerOffset(self.musical_timer_offset));
+            model.change(P::SetMusicalTimerOnlyWhilePlaying(
+                self.musical_timer_only_while_playing,
+            ));
+        }
+        if self.category == SourceCategory::Chord {
+            let members = self
+                .chord_members
+                .iter()
+                .map(|d| {
+                    let mut member = SourceModel::new();
+                    d.apply_to_model_flexible(&mut member, compartment, preset_version);
+                    member
+                })
+                .collect();
+            model.change(P::SetChordMembers(members));
+            model.change(P::SetChordWindowMillis(self.chord_window_millis));
+        }
     }
 }
 
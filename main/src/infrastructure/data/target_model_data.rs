@@ -12,7 +12,7 @@ use crate::domain::{
     ExtendedProcessorContext, FxDisplayType, GroupKey, MappingKey, OscDeviceId, ReaperTargetType,
     SeekOptions, SendMidiDestinationType, SoloBehavior, Tag, TouchedRouteParameterType,
     TouchedTrackParameterType, TrackExclusivity, TrackGangBehavior, TrackRouteType,
-    TransportAction, VirtualTrack,
+    TransportAction, VirtualTrack, DEFAULT_BOOKMARK_TOLERANCE,
 };
 use crate::infrastructure::data::common::OscValueRange;
 use crate::infrastructure::data::{
@@ -27,10 +27,11 @@ use base::default_util::{
 use base::hash_util::NonCryptoHashSet;
 use helgoboss_learn::{AbsoluteValue, Fraction, OscTypeTag, UnitValue};
 use helgobox_api::persistence::{
-    ActionScope, Axis, BrowseTracksMode, FxToolAction, InstanceTagKind, LearnableTargetKind,
+    ActionScope, Axis, BrowseTracksMode, EnvelopePosition, FxNameKind, FxToolAction,
+    InstanceTagKind, ItemDescriptor, ItemParameter, LearnableTargetKind,
     MappingSnapshotDescForLoad, MappingSnapshotDescForTake, MonitoringMode, MouseAction,
-    PotFilterKind, SeekBehavior, TargetTouchCause, TargetValue, TrackScope, TrackToolAction,
-    VirtualControlElementCharacter,
+    PotFilterKind, RecordInput, RecordMode, SeekBehavior, TakeDescriptor, TakeParameter,
+    TargetTouchCause, TargetValue, TrackScope, TrackToolAction, VirtualControlElementCharacter,
 };
 use helgobox_api::persistence::{
     ClipColumnTrackContext, PlaytimeColumnAction, PlaytimeColumnDescriptor, PlaytimeMatrixAction,
@@ -42,6 +43,7 @@ use reaper_medium::MidiInputDeviceId;
 use semver::Version;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::time::Duration;
 
 #[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -171,6 +173,12 @@ pub struct TargetModelData {
         skip_serializing_if = "is_default"
     )]
     pub fx_tool_action: FxToolAction,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub fx_name_kind: FxNameKind,
     // Transport target
     #[serde(
         default,
@@ -244,6 +252,20 @@ pub struct TargetModelData {
         skip_serializing_if = "is_default"
     )]
     pub track_monitoring_mode: MonitoringMode,
+    // Track record input target
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub track_record_input: RecordInput,
+    // Track record mode target
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub track_record_mode: RecordMode,
     // Automation mode override target
     #[serde(
         default,
@@ -369,6 +391,14 @@ pub struct TargetModelData {
         skip_serializing_if = "is_default"
     )]
     pub take_mapping_snapshot: Option<MappingSnapshotDescForTake>,
+    /// Snapshot B of the "Morph between mapping snapshots" target. Snapshot A is encoded as
+    /// part of "mapping_snapshot".
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub second_mapping_snapshot_id: Option<String>,
     #[serde(
         default,
         deserialize_with = "deserialize_null_default",
@@ -535,6 +565,55 @@ pub struct TargetModelData {
         skip_serializing_if = "is_default"
     )]
     pub touch_cause: TargetTouchCause,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub item: ItemDescriptor,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub take: TakeDescriptor,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub item_parameter: ItemParameter,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub take_parameter: TakeParameter,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub envelope_name: String,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub envelope_position: EnvelopePosition,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub use_nearest_marker: bool,
+    /// `None` means the default tolerance.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    pub bookmark_tolerance_millis: Option<u64>,
 }
 
 impl TargetModelData {
@@ -579,6 +658,7 @@ impl TargetModelData {
             track_exclusivity: model.track_exclusivity(),
             track_tool_action: model.track_tool_action(),
             fx_tool_action: model.fx_tool_action(),
+            fx_name_kind: model.fx_name_kind(),
             transport_action: model.transport_action(),
             any_on_parameter: model.any_on_parameter(),
             control_element_type: model.control_element_character(),
@@ -598,6 +678,8 @@ impl TargetModelData {
             track_area: model.track_area(),
             track_automation_mode: model.automation_mode(),
             track_monitoring_mode: model.monitoring_mode(),
+            track_record_input: model.record_input(),
+            track_record_mode: model.record_mode(),
             automation_mode_override_type: model.automation_mode_override_type(),
             browse_tracks_mode: model.browse_tracks_mode(),
             fx_display_type: model.fx_display_type(),
@@ -622,6 +704,7 @@ impl TargetModelData {
             tags: model.tags().to_vec(),
             mapping_snapshot: model.mapping_snapshot_desc_for_load(),
             take_mapping_snapshot: Some(model.mapping_snapshot_desc_for_take()),
+            second_mapping_snapshot_id: model.second_mapping_snapshot_id().map(|id| id.to_string()),
             mapping_snapshot_default_value: model
                 .mapping_snapshot_default_value()
                 .map(convert_target_value_to_api),
@@ -662,6 +745,17 @@ impl TargetModelData {
                 None
             },
             touch_cause: model.touch_cause(),
+            item: model.item().clone(),
+            take: model.take().clone(),
+            item_parameter: model.item_parameter(),
+            take_parameter: model.take_parameter(),
+            envelope_name: model.envelope_name().to_owned(),
+            envelope_position: model.envelope_position(),
+            use_nearest_marker: model.use_nearest_marker(),
+            bookmark_tolerance_millis: {
+                let tolerance = model.bookmark_tolerance();
+                (tolerance != DEFAULT_BOOKMARK_TOLERANCE).then_some(tolerance.as_millis() as _)
+            },
         }
     }
 
@@ -833,10 +927,18 @@ impl TargetModelData {
         model.change(C::SetBookmarkType(bookmark_type));
         model.change(C::SetBookmarkAnchorType(self.bookmark_data.anchor));
         model.change(C::SetBookmarkRef(self.bookmark_data.r#ref));
+        model.change(C::SetUseNearestMarker(self.use_nearest_marker));
+        let bookmark_tolerance = self
+            .bookmark_tolerance_millis
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_BOOKMARK_TOLERANCE);
+        model.change(C::SetBookmarkTolerance(bookmark_tolerance));
         let _ = model.set_seek_options(self.seek_options);
         model.change(C::SetTrackArea(self.track_area));
         model.change(C::SetAutomationMode(self.track_automation_mode));
         model.change(C::SetMonitoringMode(self.track_monitoring_mode));
+        model.change(C::SetRecordInput(self.track_record_input));
+        model.change(C::SetRecordMode(self.track_record_mode));
         model.change(C::SetAutomationModeOverrideType(
             self.automation_mode_override_type,
         ));
@@ -920,6 +1022,7 @@ impl TargetModelData {
         }
         model.change(C::SetTrackToolAction(self.track_tool_action));
         model.change(C::SetFxToolAction(self.fx_tool_action));
+        model.change(C::SetFxNameKind(self.fx_name_kind));
         // "Load mapping snapshot" stuff
         let mapping_snapshot_id_for_load = {
             let (mapping_snapshot_type, mapping_snapshot_id) = match &self.mapping_snapshot {
@@ -964,6 +1067,11 @@ impl TargetModelData {
         model.change(C::SetMappingSnapshotId(
             mapping_snapshot_id_for_load.or(mapping_snapshot_id_for_take),
         ));
+        model.change(C::SetSecondMappingSnapshotId(
+            self.second_mapping_snapshot_id
+                .as_ref()
+                .and_then(|id| id.parse().ok()),
+        ));
         if self.category == TargetCategory::Reaper && self.r#type == ReaperTargetType::Mouse {
             // We set this only when we actually have the mouse target. Because the axis model
             // property is also used for other things.
@@ -1015,6 +1123,12 @@ impl TargetModelData {
         });
         model.change(C::SetLearnableTargetKinds(target_kinds));
         model.change(C::SetTouchCause(self.touch_cause));
+        model.change(C::SetItem(self.item.clone()));
+        model.change(C::SetTake(self.take.clone()));
+        model.change(C::SetItemParameter(self.item_parameter));
+        model.change(C::SetTakeParameter(self.take_parameter));
+        model.change(C::SetEnvelopeName(self.envelope_name.clone()));
+        model.change(C::SetEnvelopePosition(self.envelope_position));
         Ok(())
     }
 }
@@ -6,7 +6,7 @@ use crate::application::{
 };
 use crate::domain::{
     compartment_param_index_iter, CompartmentKind, CompartmentParamIndex, CompartmentParams,
-    ControlInput, FeedbackOutput, GroupId, GroupKey, MappingId, MappingKey,
+    ControlInput, EvdevDeviceId, FeedbackOutput, GroupId, GroupKey, MappingId, MappingKey,
     MappingSnapshotContainer, MappingSnapshotId, MidiControlInput, MidiDestination, OscDeviceId,
     Param, PluginParams, StayActiveWhenProjectInBackground, StreamDeckDeviceId, Tag, Unit,
 };
@@ -115,6 +115,12 @@ pub struct UnitData {
         skip_serializing_if = "is_default"
     )]
     stream_deck_device_id: Option<StreamDeckDeviceId>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_null_default",
+        skip_serializing_if = "is_default"
+    )]
+    evdev_device_id: Option<EvdevDeviceId>,
     ///
     /// - `None` means "\<None>"
     /// - `Some("fx-output")` means "\<FX output>"
@@ -424,6 +430,7 @@ impl Default for UnitData {
             wants_keyboard_input: unit_defaults::WANTS_KEYBOARD_INPUT,
             match_even_inactive_mappings: unit_defaults::MATCH_EVEN_INACTIVE_MAPPINGS,
             stream_deck_device_id: None,
+            evdev_device_id: None,
             feedback_device_id: None,
             default_group: None,
             default_controller_group: None,
@@ -517,6 +524,7 @@ impl UnitData {
             wants_keyboard_input: session.wants_keyboard_input(),
             match_even_inactive_mappings: session.match_even_inactive_mappings(),
             stream_deck_device_id: session.stream_deck_device_id(),
+            evdev_device_id: session.evdev_device_id(),
             feedback_device_id: {
                 session.feedback_output().map(|output| match output {
                     FeedbackOutput::Midi(MidiDestination::FxOutput) => {
@@ -712,6 +720,7 @@ impl UnitData {
             self.wants_keyboard_input || wants_keyboard_input_legacy,
         ));
         let _ = session.change(UnitCommand::SetStreamDeckDevice(self.stream_deck_device_id));
+        let _ = session.change(UnitCommand::SetEvdevDevice(self.evdev_device_id));
         let _ = session.change(UnitCommand::SetMatchEvenInactiveMappings(
             self.match_even_inactive_mappings,
         ));
@@ -1103,22 +1112,35 @@ fn convert_mapping_snapshots_to_api_internal(
 ) -> Vec<MappingSnapshot> {
     container
         .snapshots()
-        .map(|(snapshot_id, snapshot)| MappingSnapshot {
-            id: snapshot_id.to_string(),
-            mappings: snapshot
-                .target_values()
-                .filter_map(|(mapping_id, target_value)| {
-                    let m = MappingInSnapshot {
-                        id: conversion_context.mapping_key_by_id(mapping_id)?.into(),
-                        target_value: convert_target_value_to_api(target_value),
-                    };
-                    Some(m)
-                })
-                .collect(),
+        .map(|(snapshot_id, snapshot)| {
+            convert_mapping_snapshot_to_api(snapshot_id, snapshot, conversion_context)
         })
         .collect()
 }
 
+/// Converts a single mapping snapshot to its persistent representation.
+///
+/// Also used for exporting a snapshot as standalone file.
+pub(crate) fn convert_mapping_snapshot_to_api(
+    snapshot_id: &MappingSnapshotId,
+    snapshot: &crate::domain::MappingSnapshot,
+    conversion_context: &impl ModelToDataConversionContext,
+) -> MappingSnapshot {
+    MappingSnapshot {
+        id: snapshot_id.to_string(),
+        mappings: snapshot
+            .target_values()
+            .filter_map(|(mapping_id, target_value)| {
+                let m = MappingInSnapshot {
+                    id: conversion_context.mapping_key_by_id(mapping_id)?.into(),
+                    target_value: convert_target_value_to_api(target_value),
+                };
+                Some(m)
+            })
+            .collect(),
+    }
+}
+
 fn convert_mapping_snapshots_to_model(
     api_snapshots: &[MappingSnapshot],
     active_snapshot_id_by_tag: &NonCryptoHashMap<Tag, MappingSnapshotId>,
@@ -1129,21 +1151,11 @@ fn convert_mapping_snapshots_to_model(
     > = api_snapshots
         .iter()
         .map(|api_snapshot| {
-            let id: MappingSnapshotId = api_snapshot.id.parse().map_err(anyhow::Error::msg)?;
-            let target_values: anyhow::Result<NonCryptoHashMap<_, _>> = api_snapshot
-                .mappings
-                .iter()
-                .map(|api_mapping| {
-                    let mapping_key: MappingKey = api_mapping.id.clone().into();
-                    let id: MappingId = conversion_context
-                        .mapping_id_by_key(&mapping_key)
-                        .context("couldn't find mapping with key")?;
-                    let absolute_value = convert_target_value_to_model(&api_mapping.target_value)?;
-                    Ok((id, absolute_value))
-                })
-                .collect();
-            let snapshot = crate::domain::MappingSnapshot::new(target_values?);
-            Ok((id, snapshot))
+            let converted = convert_mapping_snapshot_to_model(api_snapshot, conversion_context)?;
+            if let Some(key) = converted.unknown_mapping_keys.first() {
+                anyhow::bail!("couldn't find mapping with key {key}");
+            }
+            Ok((converted.id, converted.snapshot))
         })
         .collect();
     Ok(MappingSnapshotContainer::new(
@@ -1151,3 +1163,38 @@ fn convert_mapping_snapshots_to_model(
         active_snapshot_id_by_tag.clone(),
     ))
 }
+
+pub(crate) struct ConvertedMappingSnapshot {
+    pub id: MappingSnapshotId,
+    pub snapshot: crate::domain::MappingSnapshot,
+    /// Keys of mappings which are contained in the persistent snapshot but couldn't be found.
+    pub unknown_mapping_keys: Vec<MappingKey>,
+}
+
+/// Converts a single persistent mapping snapshot to the model.
+///
+/// Target values of mappings that don't exist are skipped. The caller decides whether that's
+/// an error or not.
+pub(crate) fn convert_mapping_snapshot_to_model(
+    api_snapshot: &MappingSnapshot,
+    conversion_context: &impl DataToModelConversionContext,
+) -> anyhow::Result<ConvertedMappingSnapshot> {
+    let id: MappingSnapshotId = api_snapshot.id.parse().map_err(anyhow::Error::msg)?;
+    let mut target_values = NonCryptoHashMap::default();
+    let mut unknown_mapping_keys = vec![];
+    for api_mapping in &api_snapshot.mappings {
+        let mapping_key: MappingKey = api_mapping.id.clone().into();
+        let Some(mapping_id) = conversion_context.mapping_id_by_key(&mapping_key) else {
+            unknown_mapping_keys.push(mapping_key);
+            continue;
+        };
+        let absolute_value = convert_target_value_to_model(&api_mapping.target_value)?;
+        target_values.insert(mapping_id, absolute_value);
+    }
+    let converted = ConvertedMappingSnapshot {
+        id,
+        snapshot: crate::domain::MappingSnapshot::new(target_values),
+        unknown_mapping_keys,
+    };
+    Ok(converted)
+}
@@ -312,6 +312,8 @@ impl BackboneShell {
             debug!("{}", e);
             Default::default()
         });
+        #[cfg(target_os = "linux")]
+        crate::domain::set_linux_speech_command(config.speech_command());
         // Init error reporting
         Reaper::get().set_report_crashes_to_sentry(config.send_errors_to_dev());
         Reaper::get().set_log_crashes_to_console(config.show_errors_in_console());
@@ -1322,6 +1324,29 @@ impl BackboneShell {
         Self::realearn_preset_dir_path().join(sub_dir)
     }
 
+    pub fn realearn_mapping_snapshot_dir_path() -> Utf8PathBuf {
+        Self::realearn_data_dir_path().join("mapping-snapshots")
+    }
+
+    /// Snapshots are exported per project and compartment because snapshot IDs are only unique
+    /// within one compartment.
+    pub fn realearn_compartment_mapping_snapshot_dir_path(
+        project: Project,
+        compartment: CompartmentKind,
+    ) -> Utf8PathBuf {
+        let project_dir = project
+            .file()
+            .and_then(|f| Some(f.file_stem()?.to_string()))
+            .unwrap_or_else(|| "unsaved-project".to_string());
+        let compartment_dir = match compartment {
+            CompartmentKind::Controller => "controller",
+            CompartmentKind::Main => "main",
+        };
+        Self::realearn_mapping_snapshot_dir_path()
+            .join(project_dir)
+            .join(compartment_dir)
+    }
+
     pub fn realearn_auto_load_configs_dir_path() -> Utf8PathBuf {
         Self::realearn_data_dir_path().join("auto-load-configs")
     }
@@ -2374,6 +2399,10 @@ impl BackboneConfig {
         };
     }
 
+    pub fn speech_command(&self) -> &str {
+        &self.main.speech_command
+    }
+
     pub fn toolbar_button_is_enabled(&self, command_name: &str) -> bool {
         self.toolbar.get(command_name).is_some_and(|v| *v != 0)
     }
@@ -2428,6 +2457,9 @@ struct MainConfig {
         skip_serializing_if = "is_default_notify_about_updates"
     )]
     notify_about_updates: u8,
+    /// Command used by the speech source on Linux. Empty means default.
+    #[serde(default, skip_serializing_if = "is_default")]
+    speech_command: String,
 }
 
 const DEFAULT_SERVER_HTTP_PORT: u16 = 39080;
@@ -2521,6 +2553,7 @@ impl Default for MainConfig {
             // For new installations, this is an opt-out. The welcome screen will be shown for sure, so that's okay.
             send_errors_to_dev: 1,
             notify_about_updates: default_notify_about_updates(),
+            speech_command: String::new(),
         }
     }
 }
@@ -1,22 +1,38 @@
 //! Contains the actual application interface and implementation without any HTTP-specific stuff.
 
 use crate::application::{
-    CompartmentPresetManager, CompartmentPresetModel, SourceCategory, TargetCategory, UnitModel,
+    CompartmentPresetManager, CompartmentPresetModel, MappingCommand, SourceCategory,
+    StreamedControlEvent, TargetCategory, UnitModel,
 };
-use crate::domain::{CompartmentKind, MappingKey, ProjectionFeedbackValue};
-use crate::infrastructure::data::CompartmentPresetData;
+use crate::domain::{CompartmentKind, MappingId, MappingKey, ProjectionFeedbackValue};
+use crate::infrastructure::api::convert::{from_data, to_data};
+use crate::infrastructure::data::{CompartmentPresetData, MappingModelData, ModeModelData};
 use crate::infrastructure::plugin::BackboneShell;
 use base::hash_util::{NonCryptoHashMap, NonCryptoHashSet};
+use base::serde_json_util;
 use helgoboss_learn::UnitValue;
+use helgobox_api::persistence;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
-// Right now just a placeholder
-pub struct SessionResponseData {}
+pub struct SessionResponseData {
+    /// IDs of the compartments, as used in the paths of the compartment and mapping endpoints.
+    compartments: Vec<&'static str>,
+}
+
+impl Default for SessionResponseData {
+    fn default() -> Self {
+        Self {
+            compartments: CompartmentKind::enum_iter().map(compartment_id).collect(),
+        }
+    }
+}
 
 pub enum DataError {
     SessionNotFound,
@@ -25,6 +41,12 @@ pub enum DataError {
     OnlyPatchReplaceIsSupported,
     OnlyCustomDataKeyIsSupportedAsPatchPath,
     ControllerUpdateFailed,
+    CompartmentNotFound,
+    MappingNotFound,
+    UnsupportedMappingPatchPath,
+    InvalidPatchValue,
+    MappingConversionFailed,
+    MappingUpdateFailed,
 }
 
 pub enum DataErrorCategory {
@@ -46,18 +68,32 @@ impl DataError {
                 "only '/customData/{key}' is supported as path"
             }
             ControllerUpdateFailed => "couldn't update controller",
+            CompartmentNotFound => "compartment not found (must be 'controller' or 'main')",
+            MappingNotFound => "mapping not found",
+            UnsupportedMappingPatchPath => {
+                "only '/enabled', '/name', '/tags', '/glue' and '/target' are supported as path"
+            }
+            InvalidPatchValue => "patch value doesn't have the expected format",
+            MappingConversionFailed => "couldn't convert mapping",
+            MappingUpdateFailed => "couldn't update mapping",
         }
     }
 
     pub fn category(&self) -> DataErrorCategory {
         use DataError::*;
         match self {
-            SessionNotFound | SessionHasNoActiveController | ControllerNotFound => {
-                DataErrorCategory::NotFound
-            }
+            SessionNotFound
+            | SessionHasNoActiveController
+            | ControllerNotFound
+            | CompartmentNotFound
+            | MappingNotFound => DataErrorCategory::NotFound,
             OnlyPatchReplaceIsSupported => DataErrorCategory::MethodNotAllowed,
-            OnlyCustomDataKeyIsSupportedAsPatchPath => DataErrorCategory::BadRequest,
-            ControllerUpdateFailed => DataErrorCategory::InternalServerError,
+            OnlyCustomDataKeyIsSupportedAsPatchPath
+            | UnsupportedMappingPatchPath
+            | InvalidPatchValue => DataErrorCategory::BadRequest,
+            ControllerUpdateFailed | MappingConversionFailed | MappingUpdateFailed => {
+                DataErrorCategory::InternalServerError
+            }
         }
     }
 }
@@ -95,11 +131,33 @@ struct TargetDescriptor {
     label: String,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompartmentResponseData {
+    id: &'static str,
+    mappings: Vec<MappingSummary>,
+}
+
+/// Lightweight description of a mapping, for listing purposes.
+///
+/// The complete mapping can be queried separately.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MappingSummary {
+    id: MappingKey,
+    name: String,
+    tags: Vec<String>,
+    enabled: bool,
+    /// Whether the mapping is currently active (e.g. not switched off by its activation condition
+    /// or because the target can't be resolved).
+    is_on: bool,
+}
+
 pub fn get_session_data(session_id: String) -> Result<SessionResponseData, DataError> {
     let _ = BackboneShell::get()
         .find_unit_model_by_key(&session_id)
         .ok_or(DataError::SessionNotFound)?;
-    Ok(SessionResponseData {})
+    Ok(SessionResponseData::default())
 }
 
 pub fn get_controller_routing_by_session_id(
@@ -176,6 +234,167 @@ pub fn get_controller_routing(session: &UnitModel) -> ControllerRouting {
     }
 }
 
+pub fn get_compartments_data(
+    session_id: String,
+) -> Result<Vec<CompartmentResponseData>, DataError> {
+    let session = BackboneShell::get()
+        .find_unit_model_by_key(&session_id)
+        .ok_or(DataError::SessionNotFound)?;
+    let session = session.borrow();
+    let compartments = CompartmentKind::enum_iter()
+        .map(|compartment| CompartmentResponseData {
+            id: compartment_id(compartment),
+            mappings: get_mapping_summaries(&session, compartment),
+        })
+        .collect();
+    Ok(compartments)
+}
+
+pub fn get_mappings_data(
+    session_id: String,
+    compartment_id: String,
+) -> Result<Vec<MappingSummary>, DataError> {
+    let session = BackboneShell::get()
+        .find_unit_model_by_key(&session_id)
+        .ok_or(DataError::SessionNotFound)?;
+    let compartment = parse_compartment_id(&compartment_id)?;
+    let summaries = get_mapping_summaries(&session.borrow(), compartment);
+    Ok(summaries)
+}
+
+pub fn get_mapping_data(
+    session_id: String,
+    compartment_id: String,
+    mapping_key: String,
+) -> Result<persistence::Mapping, DataError> {
+    let session = BackboneShell::get()
+        .find_unit_model_by_key(&session_id)
+        .ok_or(DataError::SessionNotFound)?;
+    let session = session.borrow();
+    let compartment = parse_compartment_id(&compartment_id)?;
+    let mapping = session
+        .find_mapping_by_key(compartment, &mapping_key.into())
+        .ok_or(DataError::MappingNotFound)?;
+    let compartment_in_session = session.compartment_in_unit(compartment);
+    let mapping_data = MappingModelData::from_model(&mapping.borrow(), &compartment_in_session);
+    from_data::convert_mapping(mapping_data, from_data::ConversionStyle::Minimal)
+        .map_err(|_| DataError::MappingConversionFailed)
+}
+
+/// Changes one property of a mapping.
+///
+/// All properties are replaced as a whole, except for `/glue`. The glue value is merged into the
+/// current glue (like a JSON merge patch), so a client can change single glue properties without
+/// knowing the others. Setting a glue property to `null` resets it to its default.
+pub fn patch_mapping(
+    session_id: String,
+    compartment_id: String,
+    mapping_key: String,
+    req: PatchRequest,
+) -> Result<(), DataError> {
+    if req.op != PatchRequestOp::Replace {
+        return Err(DataError::OnlyPatchReplaceIsSupported);
+    }
+    let shared_session = BackboneShell::get()
+        .find_unit_model_by_key(&session_id)
+        .ok_or(DataError::SessionNotFound)?;
+    let compartment = parse_compartment_id(&compartment_id)?;
+    let mut session = shared_session.borrow_mut();
+    let mapping = session
+        .find_mapping_by_key(compartment, &mapping_key.into())
+        .ok_or(DataError::MappingNotFound)?;
+    let mut mapping = mapping.borrow_mut();
+    match req.path.as_str() {
+        "/enabled" => {
+            let enabled: bool = parse_patch_value(req.value)?;
+            mapping.change(MappingCommand::SetIsEnabled(enabled));
+        }
+        "/name" => {
+            let name: String = parse_patch_value(req.value)?;
+            mapping.change(MappingCommand::SetName(name));
+        }
+        "/tags" => {
+            let tags: Vec<String> = parse_patch_value(req.value)?;
+            let tags = to_data::convert_tags(tags).map_err(|_| DataError::InvalidPatchValue)?;
+            mapping.change(MappingCommand::SetTags(tags));
+        }
+        "/glue" => {
+            let current_glue = from_data::convert_glue(
+                ModeModelData::from_model(&mapping.mode_model),
+                from_data::ConversionStyle::Minimal,
+            )
+            .map_err(|_| DataError::MappingConversionFailed)?;
+            let glue = merge_glue_patch(current_glue, req.value)?;
+            let mode_data =
+                to_data::convert_glue(glue).map_err(|_| DataError::InvalidPatchValue)?;
+            mode_data.apply_to_model(&mut mapping.mode_model);
+        }
+        "/target" => {
+            let target: persistence::Target = parse_patch_value(req.value)?;
+            let target_data =
+                to_data::convert_target(target).map_err(|_| DataError::InvalidPatchValue)?;
+            let compartment_in_session = session.compartment_in_unit(compartment);
+            target_data
+                .apply_to_model(
+                    &mut mapping.target_model,
+                    compartment,
+                    session.extended_context(),
+                    &compartment_in_session,
+                )
+                .map_err(|_| DataError::MappingUpdateFailed)?;
+        }
+        _ => return Err(DataError::UnsupportedMappingPatchPath),
+    }
+    session.notify_mapping_has_changed(mapping.qualified_id(), Rc::downgrade(&shared_session));
+    Ok(())
+}
+
+fn merge_glue_patch(
+    glue: persistence::Glue,
+    patch: serde_json::Value,
+) -> Result<persistence::Glue, DataError> {
+    if !patch.is_object() {
+        return Err(DataError::InvalidPatchValue);
+    }
+    let mut value = serde_json::to_value(glue).map_err(|_| DataError::MappingConversionFailed)?;
+    serde_json_util::merge(&mut value, patch);
+    parse_patch_value(value)
+}
+
+fn parse_patch_value<T: DeserializeOwned>(value: serde_json::Value) -> Result<T, DataError> {
+    serde_json::from_value(value).map_err(|_| DataError::InvalidPatchValue)
+}
+
+fn get_mapping_summaries(session: &UnitModel, compartment: CompartmentKind) -> Vec<MappingSummary> {
+    let instance_state = session.unit().borrow();
+    session
+        .mappings(compartment)
+        .map(|m| {
+            let m = m.borrow();
+            MappingSummary {
+                id: m.key().clone(),
+                name: m.effective_name(),
+                tags: m.tags().iter().map(|t| t.to_string()).collect(),
+                enabled: m.is_enabled(),
+                is_on: instance_state.mapping_is_on(m.qualified_id()),
+            }
+        })
+        .collect()
+}
+
+fn compartment_id(compartment: CompartmentKind) -> &'static str {
+    match compartment {
+        CompartmentKind::Controller => "controller",
+        CompartmentKind::Main => "main",
+    }
+}
+
+fn parse_compartment_id(id: &str) -> Result<CompartmentKind, DataError> {
+    CompartmentKind::enum_iter()
+        .find(|c| compartment_id(*c) == id)
+        .ok_or(DataError::CompartmentNotFound)
+}
+
 pub fn patch_controller(controller_id: String, req: PatchRequest) -> Result<(), DataError> {
     if req.op != PatchRequestOp::Replace {
         return Err(DataError::OnlyPatchReplaceIsSupported);
@@ -236,6 +455,7 @@ pub enum Topic {
     ActiveController { session_id: String },
     ControllerRouting { session_id: String },
     Feedback { session_id: String },
+    ControlEvents { session_id: String },
 }
 
 impl TryFrom<&str> for Topic {
@@ -253,6 +473,9 @@ impl TryFrom<&str> for Topic {
             ["realearn", "session", id, "feedback"] => Topic::Feedback {
                 session_id: id.to_string(),
             },
+            ["realearn", "session", id, "control-events"] => Topic::ControlEvents {
+                session_id: id.to_string(),
+            },
             ["realearn", "session", id] => Topic::Session {
                 session_id: id.to_string(),
             },
@@ -287,6 +510,76 @@ pub fn get_projection_feedback_event(
     Event::patch(format!("/realearn/session/{session_id}/feedback"), map)
 }
 
+pub fn get_control_event(
+    session: &UnitModel,
+    event: StreamedControlEvent,
+) -> Event<ControlEventData> {
+    use StreamedControlEvent::*;
+    let (mapping, payload) = match event {
+        SourceControl(e) => (
+            None,
+            ControlEventPayload::SourceControl {
+                message: e.message.clone(),
+                match_outcome: e.match_outcome.map(|o| o.to_string()),
+            },
+        ),
+        MappingMatched(e) => (
+            Some((e.compartment, e.mapping_id)),
+            ControlEventPayload::MappingMatched,
+        ),
+        TargetControl(e) => (
+            Some((e.id.compartment, e.id.id)),
+            ControlEventPayload::TargetControl {
+                outcome: e.log_entry.kind.to_string(),
+                control_value: e.log_entry.control_value.map(|v| v.to_string()),
+                target_index: e.log_entry.target_index,
+                invocation_count: e.log_entry.invocation_count,
+                error: Some(e.log_entry.error).filter(|err| !err.is_empty()),
+            },
+        ),
+        TargetValueChanged(e) => (
+            Some((e.compartment, e.mapping_id)),
+            ControlEventPayload::TargetValueChanged {
+                target_value: e.new_value.to_unit_value().get(),
+            },
+        ),
+        SourceFeedback(e) => (
+            Some((e.id.compartment, e.id.id)),
+            ControlEventPayload::SourceFeedback {
+                feedback_value: e.log_entry.to_string(),
+            },
+        ),
+    };
+    let data = ControlEventData {
+        timestamp: unix_timestamp_millis(),
+        compartment: mapping.map(|(compartment, _)| compartment_id(compartment)),
+        mapping_key: mapping
+            .and_then(|(compartment, id)| get_mapping_key(session, compartment, id)),
+        payload,
+    };
+    Event::patch(
+        format!("/realearn/session/{}/control-events", session.unit_key()),
+        data,
+    )
+}
+
+fn get_mapping_key(
+    session: &UnitModel,
+    compartment: CompartmentKind,
+    mapping_id: MappingId,
+) -> Option<MappingKey> {
+    let mapping = session.find_mapping_by_id(compartment, mapping_id)?;
+    let key = mapping.try_borrow().ok()?.key().clone();
+    Some(key)
+}
+
+fn unix_timestamp_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
 pub fn get_session_updated_event(
     session_id: &str,
     session_data: Option<SessionResponseData>,
@@ -304,6 +597,51 @@ pub fn get_controller_routing_updated_event(
     )
 }
 
+/// One entry of the live stream of control and feedback events of a session.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ControlEventData {
+    /// Wall-clock time in milliseconds since the UNIX epoch.
+    timestamp: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    compartment: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mapping_key: Option<MappingKey>,
+    #[serde(flatten)]
+    payload: ControlEventPayload,
+}
+
+#[derive(Serialize)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum ControlEventPayload {
+    /// A message arrived from a real input device.
+    SourceControl {
+        message: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        match_outcome: Option<String>,
+    },
+    /// The source of a mapping matched an incoming message.
+    MappingMatched,
+    /// A mapping attempted to control its target.
+    TargetControl {
+        outcome: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        control_value: Option<String>,
+        target_index: usize,
+        invocation_count: u32,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<&'static str>,
+    },
+    /// The value of a mapping target changed (normalized to 0.0 - 1.0).
+    TargetValueChanged { target_value: f64 },
+    /// A mapping sent feedback to its source.
+    SourceFeedback { feedback_value: String },
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Event<T> {
@@ -371,3 +709,32 @@ fn get_controller_preset_data_internal(
     );
     Ok(CompartmentPresetData::from_model(&preset))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn merge_glue_patch_keeps_other_properties() {
+        // Given
+        let glue = persistence::Glue {
+            reverse: Some(true),
+            control_transformation: Some("y = x".to_string()),
+            ..Default::default()
+        };
+        let patch = json!({ "wrap": true, "control_transformation": null });
+        // When
+        let glue = merge_glue_patch(glue, patch).ok().unwrap();
+        // Then
+        assert_eq!(glue.reverse, Some(true));
+        assert_eq!(glue.wrap, Some(true));
+        assert_eq!(glue.control_transformation, None);
+    }
+
+    #[test]
+    fn reject_glue_patch_which_is_not_an_object() {
+        let result = merge_glue_patch(persistence::Glue::default(), json!(true));
+        assert!(matches!(result, Err(DataError::InvalidPatchValue)));
+    }
+}
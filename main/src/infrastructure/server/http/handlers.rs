@@ -1,9 +1,13 @@
 use crate::infrastructure::data::CompartmentPresetData;
 use crate::infrastructure::server::data::{
-    get_controller_preset_data, get_controller_routing_by_session_id, patch_controller,
-    ControllerRouting, DataError, DataErrorCategory, PatchRequest, SessionResponseData, Topics,
+    get_compartments_data, get_controller_preset_data, get_controller_routing_by_session_id,
+    get_mapping_data, get_mappings_data, patch_controller, patch_mapping, CompartmentResponseData,
+    ControllerRouting, DataError, DataErrorCategory, MappingSummary, PatchRequest,
+    SessionResponseData, Topics,
+};
+use crate::infrastructure::server::http::{
+    send_initial_events, sync_control_event_streaming_for_topics, ServerClients, WebSocketClient,
 };
-use crate::infrastructure::server::http::{send_initial_events, ServerClients, WebSocketClient};
 use crate::infrastructure::server::MetricsReporter;
 use axum::body::Body;
 use axum::extract::ws::{Message, WebSocket};
@@ -57,6 +61,41 @@ pub async fn patch_controller_handler(
     Ok(StatusCode::OK)
 }
 
+/// Needs to be executed in the main thread!
+pub async fn session_compartments_handler(
+    Path(session_id): Path<String>,
+) -> Result<Json<Vec<CompartmentResponseData>>, SimpleResponse> {
+    let compartments = get_compartments_data(session_id).map_err(translate_data_error)?;
+    Ok(Json(compartments))
+}
+
+/// Needs to be executed in the main thread!
+pub async fn session_mappings_handler(
+    Path((session_id, compartment_id)): Path<(String, String)>,
+) -> Result<Json<Vec<MappingSummary>>, SimpleResponse> {
+    let mappings = get_mappings_data(session_id, compartment_id).map_err(translate_data_error)?;
+    Ok(Json(mappings))
+}
+
+/// Needs to be executed in the main thread!
+pub async fn session_mapping_handler(
+    Path((session_id, compartment_id, mapping_key)): Path<(String, String, String)>,
+) -> Result<Json<helgobox_api::persistence::Mapping>, SimpleResponse> {
+    let mapping =
+        get_mapping_data(session_id, compartment_id, mapping_key).map_err(translate_data_error)?;
+    Ok(Json(mapping))
+}
+
+/// Needs to be executed in the main thread!
+pub async fn patch_session_mapping_handler(
+    Path((session_id, compartment_id, mapping_key)): Path<(String, String, String)>,
+    Json(patch_request): Json<PatchRequest>,
+) -> Result<StatusCode, SimpleResponse> {
+    patch_mapping(session_id, compartment_id, mapping_key, patch_request)
+        .map_err(translate_data_error)?;
+    Ok(StatusCode::OK)
+}
+
 pub fn create_cert_response(cert: String, cert_file_name: &str) -> Response<Body> {
     Response::builder()
         .status(StatusCode::OK)
@@ -123,7 +162,15 @@ pub async fn handle_websocket_upgrade(socket: WebSocket, topics: Topics, clients
         };
     }
     // Stream closed up, so remove from the client list
-    clients.write().unwrap().remove(&client_id);
+    let removed_client = clients.write().unwrap().remove(&client_id);
+    // Sessions might not need to stream control events anymore
+    if let Some(client) = removed_client {
+        Global::task_support()
+            .do_later_in_main_thread_asap(move || {
+                sync_control_event_streaming_for_topics(&client.topics);
+            })
+            .unwrap();
+    }
 }
 
 fn translate_data_error(e: DataError) -> SimpleResponse {
@@ -1,12 +1,12 @@
 //! Contains functions for sending data to WebSocket clients.
-use crate::application::{SharedUnitModel, UnitModel};
+use crate::application::{SharedUnitModel, StreamedControlEvent, UnitModel};
 use crate::base::when;
 use crate::domain::ProjectionFeedbackValue;
 use crate::infrastructure::plugin::BackboneShell;
 use crate::infrastructure::server::data::{
-    get_active_controller_updated_event, get_controller_routing_updated_event,
+    get_active_controller_updated_event, get_control_event, get_controller_routing_updated_event,
     get_projection_feedback_event, get_session_updated_event, send_initial_feedback,
-    SessionResponseData, Topic,
+    SessionResponseData, Topic, Topics,
 };
 use crate::infrastructure::server::http::client::WebSocketClient;
 use base::Global;
@@ -33,6 +33,10 @@ fn send_initial_events_for_topic(
             send_initial_feedback(session_id);
             Ok(())
         }
+        ControlEvents { session_id } => {
+            // There's nothing to send initially. But the session needs to start streaming.
+            sync_control_event_streaming_by_key(session_id)
+        }
     }
 }
 
@@ -44,7 +48,7 @@ pub fn send_initial_session(
         .find_unit_model_by_key(session_id)
         .is_some()
     {
-        get_session_updated_event(session_id, Some(SessionResponseData {}))
+        get_session_updated_event(session_id, Some(SessionResponseData::default()))
     } else {
         get_session_updated_event(session_id, None)
     };
@@ -115,6 +119,75 @@ pub fn send_projection_feedback_to_subscribed_clients(
     )
 }
 
+pub fn send_control_event_to_subscribed_clients(
+    session: &UnitModel,
+    event: StreamedControlEvent,
+) -> Result<(), &'static str> {
+    send_to_clients_subscribed_to(
+        &Topic::ControlEvents {
+            session_id: session.unit_key().to_string(),
+        },
+        || Some(get_control_event(session, event)),
+    )
+}
+
+/// Makes sure that the sessions affected by the given topics stream control events if and only if
+/// at least one client is subscribed to them.
+///
+/// Must be called whenever clients subscribe to or unsubscribe from control events.
+pub fn sync_control_event_streaming_for_topics(topics: &Topics) {
+    for topic in topics {
+        if let Topic::ControlEvents { session_id } = topic {
+            let _ = sync_control_event_streaming_by_key(session_id);
+        }
+    }
+}
+
+fn sync_control_event_streaming_by_key(session_id: &str) -> Result<(), &'static str> {
+    let shared_session = BackboneShell::get()
+        .find_unit_model_by_key(session_id)
+        .ok_or("session not found")?;
+    let Ok(mut session) = shared_session.try_borrow_mut() else {
+        // The session is busy at the moment. Skipping the sync would leave the session streaming
+        // (or not streaming) until the subscriptions change again, so we retry later.
+        tracing::debug!(
+            "Session {session_id} is busy, retrying control event streaming sync in next cycle"
+        );
+        let session_id = session_id.to_string();
+        Global::task_support()
+            .do_later_in_main_thread_asap(move || {
+                let _ = sync_control_event_streaming_by_key(&session_id);
+            })
+            .map_err(|_| "couldn't schedule control event streaming sync")?;
+        return Ok(());
+    };
+    sync_control_event_streaming(&mut session)
+}
+
+fn sync_control_event_streaming(session: &mut UnitModel) -> Result<(), &'static str> {
+    let topic = Topic::ControlEvents {
+        session_id: session.unit_key().to_string(),
+    };
+    let enabled = at_least_one_client_is_subscribed_to(&topic)?;
+    session.set_control_event_streaming_enabled(enabled);
+    Ok(())
+}
+
+fn at_least_one_client_is_subscribed_to(topic: &Topic) -> Result<bool, &'static str> {
+    let server = BackboneShell::get().server().borrow();
+    if !server.is_running() {
+        return Ok(false);
+    }
+    let clients = server.clients()?.clone();
+    let clients = clients
+        .read()
+        .map_err(|_| "couldn't get read lock for client")?;
+    let subscribed = clients
+        .values()
+        .any(|client| client.is_subscribed_to(topic));
+    Ok(subscribed)
+}
+
 fn send_to_clients_subscribed_to<T: Serialize>(
     topic: &Topic,
     create_message: impl FnOnce() -> Option<T>,
@@ -205,6 +278,8 @@ pub fn keep_informing_clients_about_session_events(shared_session: &SharedUnitMo
         .with(Rc::downgrade(shared_session))
         .do_async(|session, _| {
             send_sessions_to_subscribed_clients();
+            // The session key might have changed
+            let _ = sync_control_event_streaming(&mut session.borrow_mut());
             let session = session.borrow();
             let _ = send_updated_active_controller(&session);
             let _ = send_updated_controller_routing(&session);
@@ -75,6 +75,19 @@ fn create_router(
             "/realearn/session/:id/controller-routing",
             get(controller_routing_handler.layer(MainThreadLayer)),
         )
+        .route(
+            "/realearn/session/:id/compartments",
+            get(session_compartments_handler.layer(MainThreadLayer)),
+        )
+        .route(
+            "/realearn/session/:id/compartment/:compartment/mappings",
+            get(session_mappings_handler.layer(MainThreadLayer)),
+        )
+        .route(
+            "/realearn/session/:id/compartment/:compartment/mapping/:mapping",
+            get(session_mapping_handler.layer(MainThreadLayer))
+                .patch(patch_session_mapping_handler.layer(MainThreadLayer)),
+        )
         .route(
             "/realearn/controller/:id",
             patch(patch_controller_handler.layer(MainThreadLayer)),
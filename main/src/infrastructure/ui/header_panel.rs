@@ -11,10 +11,10 @@ use swell_ui::{DeviceContext, Pixels, Point, SharedView, View, ViewContext, Weak
 
 use crate::application::{
     reaper_supports_global_midi_filter, Affected, AutoLoadMode, CompartmentCommand,
-    CompartmentPresetManager, CompartmentPresetModel, CompartmentProp, FxId, FxPresetLinkConfig,
-    InstanceCommand, MakeFxNonStickyMode, MakeTrackNonStickyMode, MappingCommand, MappingModel,
-    PresetLinkMutator, SharedInstanceModel, SharedMapping, SharedUnitModel, UnitCommand, UnitProp,
-    WeakUnitModel,
+    CompartmentInUnit, CompartmentPresetManager, CompartmentPresetModel, CompartmentProp, FxId,
+    FxPresetLinkConfig, InstanceCommand, MakeFxNonStickyMode, MakeTrackNonStickyMode,
+    MappingCommand, MappingModel, PresetLinkMutator, SharedInstanceModel, SharedMapping,
+    SharedUnitModel, UnitCommand, UnitProp, WeakUnitModel,
 };
 use crate::base::when;
 use crate::domain::{
@@ -24,6 +24,7 @@ use crate::domain::{
 };
 use crate::domain::{MidiControlInput, MidiDestination};
 use crate::infrastructure::data::{
+    convert_mapping_snapshot_to_api, convert_mapping_snapshot_to_model,
     CommonCompartmentPresetManager, CommonPresetInfo, CompartmentModelData,
     FileBasedMainPresetManager, MappingModelData, OscDevice, PresetFileType, PresetOrigin,
     UnitData,
@@ -64,13 +65,15 @@ use crate::infrastructure::ui::{
 };
 use crate::infrastructure::ui::{dialog_util, CompanionAppPresenter};
 use anyhow::{bail, Context};
-use helgobox_api::persistence::{Envelope, VirtualControlElementCharacter};
+use helgobox_api::persistence::{Envelope, MappingSnapshot, VirtualControlElementCharacter};
 use itertools::Itertools;
 use reaper_medium::Hbrush;
 use semver::Version;
 use std::cell::{Cell, RefCell};
 use std::error::Error;
-use std::net::Ipv4Addr;
+use std::fs;
+use std::net::IpAddr;
+use std::num::NonZeroU32;
 use std::ops::{DerefMut, RangeInclusive};
 use strum::IntoEnumIterator;
 use tracing::debug;
@@ -229,6 +232,9 @@ impl HeaderPanel {
             One(StreamDeckDeviceId) => {
                 self.invalidate_control_input_button();
             }
+            One(EvdevDeviceId) => {
+                self.invalidate_control_input_button();
+            }
             One(InCompartment(compartment, One(InGroup(_, _))))
                 if *compartment == self.active_compartment() =>
             {
@@ -412,6 +418,11 @@ impl HeaderPanel {
             }
             ToggleOscDeviceBundles(dev_id) => BackboneShell::get()
                 .do_with_osc_device(dev_id, |d| d.toggle_can_deal_with_bundles()),
+            ToggleOscDeviceFeedbackCoalescing(dev_id) => BackboneShell::get()
+                .do_with_osc_device(dev_id, |d| d.toggle_coalesce_feedback_by_address()),
+            SetOscDeviceTransport(dev_id, transport) => {
+                BackboneShell::get().do_with_osc_device(dev_id, |d| d.set_transport(transport))
+            }
         }
     }
 
@@ -615,6 +626,14 @@ impl HeaderPanel {
                 menu(
                     "Compartment tools",
                     vec![
+                        item(
+                            "Export mapping snapshots to files",
+                            MainMenuAction::ExportMappingSnapshots,
+                        ),
+                        item(
+                            "Import mapping snapshot from file...",
+                            MainMenuAction::ImportMappingSnapshot,
+                        ),
                         menu(
                             "Convert toolbar to Stream Deck mappings",
                             ["Main toolbar".to_string()].into_iter().chain((0..32).map(|i| format!("Floating toolbar {}", i + 1))).map(|toolbar_name| {
@@ -973,6 +992,12 @@ impl HeaderPanel {
                     self.convert_toolbar_to_stream_deck_mappings(&toolbar_name),
                 );
             }
+            MainMenuAction::ExportMappingSnapshots => {
+                self.notify_user_on_anyhow_error(self.export_mapping_snapshots());
+            }
+            MainMenuAction::ImportMappingSnapshot => {
+                self.notify_user_on_anyhow_error(self.import_mapping_snapshot());
+            }
         };
         Ok(())
     }
@@ -1798,6 +1823,9 @@ impl HeaderPanel {
         if unit.stream_deck_device_id().is_some() {
             text.insert_str(0, "[Stream Deck] + ");
         }
+        if unit.evdev_device_id().is_some() {
+            text.insert_str(0, "[Evdev] + ");
+        }
         self.view
             .require_control(root::ID_CONTROL_INPUT_BUTTON)
             .set_text(text);
@@ -1850,13 +1878,19 @@ impl HeaderPanel {
     }
 
     fn pick_control_input(&self) {
-        let (current_control_input, current_wants_keyboard_input, current_stream_deck_dev_id) = {
+        let (
+            current_control_input,
+            current_wants_keyboard_input,
+            current_stream_deck_dev_id,
+            current_evdev_dev_id,
+        ) = {
             let session = self.session();
             let session = session.borrow();
             (
                 session.control_input(),
                 session.wants_keyboard_input(),
                 session.stream_deck_device_id(),
+                session.evdev_device_id(),
             )
         };
         let result = self.view.require_window().open_popup_menu(
@@ -1864,6 +1898,7 @@ impl HeaderPanel {
                 current_control_input,
                 current_wants_keyboard_input,
                 current_stream_deck_dev_id,
+                current_evdev_dev_id,
             ),
             Window::cursor_pos(),
         );
@@ -1897,6 +1932,15 @@ impl HeaderPanel {
                         )
                     }
                 }
+                ControlInputMenuAction::SelectEvdevDevice(dev) => {
+                    if let Some(session) = self.session.clone().upgrade() {
+                        session.borrow_mut().change_with_notification(
+                            UnitCommand::SetEvdevDevice(dev),
+                            None,
+                            self.session.clone(),
+                        )
+                    }
+                }
             }
         }
     }
@@ -2841,6 +2885,116 @@ impl HeaderPanel {
         self.activate_compartment(compartment_kind);
         Ok(())
     }
+
+    /// Writes each mapping snapshot of the active compartment to a separate JSON file in a
+    /// directory specific to the project and compartment.
+    ///
+    /// Asks before overwriting existing snapshot files.
+    fn export_mapping_snapshots(&self) -> anyhow::Result<()> {
+        let compartment = self.active_compartment();
+        // Don't keep the session borrowed while showing the confirmation dialog
+        let (api_snapshots, project): (Vec<_>, _) = {
+            let session = self.session();
+            let session = session.borrow();
+            let unit = session.unit().borrow();
+            let conversion_context = CompartmentInUnit::new(&session, compartment);
+            let api_snapshots = unit
+                .mapping_snapshot_container(compartment)
+                .snapshots()
+                .map(|(id, snapshot)| {
+                    convert_mapping_snapshot_to_api(id, snapshot, &conversion_context)
+                })
+                .collect();
+            let project = session.processor_context().project_or_current_project();
+            (api_snapshots, project)
+        };
+        if api_snapshots.is_empty() {
+            bail!("There are no mapping snapshots in the {compartment} compartment yet.");
+        }
+        let dir_path =
+            BackboneShell::realearn_compartment_mapping_snapshot_dir_path(project, compartment);
+        let files: Vec<_> = api_snapshots
+            .iter()
+            .map(|s| (dir_path.join(format!("{}.json", s.id)), s))
+            .collect();
+        let existing_file_names: Vec<_> = files
+            .iter()
+            .filter(|(path, _)| path.exists())
+            .filter_map(|(path, _)| path.file_name())
+            .collect();
+        if !existing_file_names.is_empty() {
+            let msg = format!(
+                "The following mapping snapshot files already exist in {dir_path} and will be overwritten: {}\n\nDo you want to continue?",
+                existing_file_names.iter().join(", ")
+            );
+            if !self.view.require_window().confirm("ReaLearn", msg) {
+                return Ok(());
+            }
+        }
+        fs::create_dir_all(&dir_path)?;
+        for (path, api_snapshot) in &files {
+            let json = serde_json::to_string_pretty(api_snapshot)?;
+            fs::write(path, json)?;
+        }
+        open_in_file_manager(dir_path.as_std_path()).map_err(anyhow::Error::msg)?;
+        Ok(())
+    }
+
+    /// Lets the user pick a mapping snapshot file and adds the contained snapshot to the active
+    /// compartment, replacing a snapshot with the same ID.
+    fn import_mapping_snapshot(&self) -> anyhow::Result<()> {
+        let compartment = self.active_compartment();
+        let project = self
+            .session()
+            .borrow()
+            .processor_context()
+            .project_or_current_project();
+        // Start in the directory to which snapshots of this project and compartment are exported
+        let dir_path = {
+            let compartment_dir_path =
+                BackboneShell::realearn_compartment_mapping_snapshot_dir_path(project, compartment);
+            if compartment_dir_path.exists() {
+                compartment_dir_path
+            } else {
+                BackboneShell::realearn_mapping_snapshot_dir_path()
+            }
+        };
+        let Some(file_path) = Reaper::get().medium_reaper().get_user_file_name_for_read(
+            &dir_path,
+            "Import mapping snapshot",
+            "json",
+        ) else {
+            return Ok(());
+        };
+        let json = fs::read_to_string(&file_path)?;
+        let api_snapshot: MappingSnapshot =
+            serde_json::from_str(&json).context("file doesn't contain a valid mapping snapshot")?;
+        let session = self.session();
+        let session = session.borrow();
+        let converted = {
+            let conversion_context = CompartmentInUnit::new(&session, compartment);
+            convert_mapping_snapshot_to_model(&api_snapshot, &conversion_context)?
+        };
+        session
+            .unit()
+            .borrow_mut()
+            .mapping_snapshot_container_mut(compartment)
+            .update_snapshot(converted.id.clone(), converted.snapshot);
+        let msg = if converted.unknown_mapping_keys.is_empty() {
+            format!(
+                "Successfully imported mapping snapshot \"{}\".",
+                converted.id
+            )
+        } else {
+            format!(
+                "Imported mapping snapshot \"{}\". The following mappings don't exist in this compartment and were skipped: {}",
+                converted.id,
+                converted.unknown_mapping_keys.iter().join(", ")
+            )
+        };
+        self.view.require_window().alert("ReaLearn", msg);
+        Ok(())
+    }
 }
 
 fn build_create_compartment_preset_workspace_label(include_factory_presets: bool) -> String {
@@ -3193,24 +3347,33 @@ fn edit_osc_device(mut dev: OscDevice) -> Result<OscDevice, EditOscDevError> {
         .medium_reaper()
         .get_user_inputs(
             "ReaLearn",
-            4,
-            "Name,Local port (e.g. 7878),Device host (e.g. 192.168.x.y),Device port (e.g. 7878),separator=;,extrawidth=80",
+            5,
+            "Name,Local port (e.g. 7878),Device host (e.g. 192.168.x.y),Device port (e.g. 7878),Max feedback messages/s (optional),separator=;,extrawidth=80",
             format!(
-                "{};{};{};{}",
+                "{};{};{};{};{}",
                 dev.name(),
                 dev.local_port().map(|p| p.to_string()).unwrap_or_default(),
                 dev.device_host().map(|a| a.to_string()).unwrap_or_default(),
                 dev.device_port().map(|p| p.to_string()).unwrap_or_default(),
+                dev.output_policy()
+                    .max_messages_per_second
+                    .map(|v| v.to_string())
+                    .unwrap_or_default(),
             ),
             512,
         )
         .ok_or(EditOscDevError::Cancelled)?;
     let splitted: Vec<_> = csv.to_str().split(';').collect();
-    if let [name, local_port, device_host, device_port] = splitted.as_slice() {
+    if let [name, local_port, device_host, device_port, max_feedback_messages_per_second] =
+        splitted.as_slice()
+    {
         dev.set_name(name.to_string());
         dev.set_local_port(local_port.parse::<u16>().ok());
-        dev.set_device_host(device_host.parse::<Ipv4Addr>().ok());
+        dev.set_device_host(device_host.parse::<IpAddr>().ok());
         dev.set_device_port(device_port.parse::<u16>().ok());
+        dev.set_max_feedback_messages_per_second(
+            max_feedback_messages_per_second.parse::<NonZeroU32>().ok(),
+        );
         Ok(dev)
     } else {
         Err(EditOscDevError::Unexpected("couldn't split"))
@@ -3272,6 +3435,8 @@ enum MainMenuAction {
     CreateCompartmentPresetWorkspace,
     CreateCompartmentPresetWorkspaceIncludingFactoryPresets,
     ConvertToolbarToStreamDeckMappings(String),
+    ExportMappingSnapshots,
+    ImportMappingSnapshot,
 }
 
 enum HelpMenuAction {
@@ -12,7 +12,7 @@ use crate::application::{
     SharedUnitModel, UnitModel, WeakUnitModel,
 };
 use crate::domain::ui_util::format_tags_as_csv;
-use crate::domain::{CompartmentKind, MappingId, Tag};
+use crate::domain::{BooleanOperator, CompartmentKind, MappingId, Tag};
 use crate::infrastructure::ui::menus;
 use derivative::Derivative;
 use std::fmt::Debug;
@@ -57,6 +57,9 @@ pub trait Item: Debug {
     fn set_script(&mut self, session: WeakUnitModel, value: String, initiator: u32);
     fn mapping_id(&self) -> Option<MappingId>;
     fn set_mapping_id(&mut self, session: WeakUnitModel, value: Option<MappingId>);
+    fn operator(&self) -> BooleanOperator;
+    fn set_operator(&mut self, session: WeakUnitModel, value: BooleanOperator);
+    fn composite_summary(&self) -> String;
 }
 
 pub enum ItemProp {
@@ -70,6 +73,8 @@ pub enum ItemProp {
     BankCondition,
     Script,
     MappingId,
+    Operator,
+    Conditions,
 }
 
 impl ItemProp {
@@ -82,6 +87,8 @@ impl ItemProp {
             S::BankCondition => Self::BankCondition,
             S::Script => Self::Script,
             S::MappingId => Self::MappingId,
+            S::Operator => Self::Operator,
+            S::Conditions => Self::Conditions,
         }
     }
 }
@@ -269,6 +276,12 @@ impl MappingHeaderPanel {
                 button.set_text(text);
                 Some("Mapping")
             }
+            Composite => {
+                button.show();
+                check_box.hide();
+                button.set_text(item.operator().to_string());
+                Some("Operator")
+            }
             _ => {
                 button.hide();
                 check_box.hide();
@@ -295,6 +308,7 @@ impl MappingHeaderPanel {
         let edit_control = self
             .view
             .require_control(root::ID_MAPPING_ACTIVATION_EDIT_CONTROL);
+        let composite_summary;
         use ActivationType::*;
         let label = match item.activation_type() {
             Modifiers => {
@@ -343,6 +357,14 @@ impl MappingHeaderPanel {
                 edit_control.set_text(item.script());
                 Some("Ex: p[0] == 2")
             }
+            Composite => {
+                // Nested conditions can only be edited via Lua/JSON (e.g. copy & paste)
+                button.hide();
+                check_box.hide();
+                edit_control.hide();
+                composite_summary = item.composite_summary();
+                Some(composite_summary.as_str())
+            }
             Always => {
                 button.hide();
                 check_box.hide();
@@ -495,6 +517,16 @@ impl MappingHeaderPanel {
                     item.borrow_mut().set_mapping_id(session, mapping_id);
                 }
             }
+            Composite => {
+                let menu = menus::menu_containing_boolean_operators(item.borrow().operator());
+                let result = self
+                    .view
+                    .require_window()
+                    .open_popup_menu(menu, Window::cursor_pos());
+                if let Some(operator) = result {
+                    item.borrow_mut().set_operator(session, operator);
+                }
+            }
             _ => {}
         }
     }
@@ -605,6 +637,11 @@ impl MappingHeaderPanel {
                     }
                     Script => self.invalidate_activation_setting_2_controls(item, initiator),
                     MappingId => self.invalidate_activation_setting_1_controls(item),
+                    Operator => {
+                        self.invalidate_activation_setting_1_controls(item);
+                        self.invalidate_activation_setting_2_controls(item, initiator);
+                    }
+                    Conditions => self.invalidate_activation_setting_2_controls(item, initiator),
                 };
             });
         });
@@ -877,6 +914,25 @@ impl Item for MappingModel {
             None,
         );
     }
+
+    fn operator(&self) -> BooleanOperator {
+        self.activation_condition_model().operator()
+    }
+
+    fn set_operator(&mut self, session: WeakUnitModel, value: BooleanOperator) {
+        UnitModel::change_mapping_from_ui_simple(
+            session,
+            self,
+            MappingCommand::ChangeActivationCondition(ActivationConditionCommand::SetOperator(
+                value,
+            )),
+            None,
+        );
+    }
+
+    fn composite_summary(&self) -> String {
+        self.activation_condition_model().composite_summary()
+    }
 }
 
 impl Item for GroupModel {
@@ -1027,4 +1083,21 @@ impl Item for GroupModel {
             None,
         );
     }
+
+    fn operator(&self) -> BooleanOperator {
+        self.activation_condition_model().operator()
+    }
+
+    fn set_operator(&mut self, session: WeakUnitModel, value: BooleanOperator) {
+        UnitModel::change_group_from_ui_simple(
+            session,
+            self,
+            GroupCommand::ChangeActivationCondition(ActivationConditionCommand::SetOperator(value)),
+            None,
+        );
+    }
+
+    fn composite_summary(&self) -> String {
+        self.activation_condition_model().composite_summary()
+    }
 }
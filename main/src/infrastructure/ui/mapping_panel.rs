@@ -14,6 +14,7 @@ use rxrust::prelude::*;
 use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::convert::TryInto;
+use std::fs;
 use std::ptr::null;
 use std::rc::Rc;
 use std::time::Duration;
@@ -28,12 +29,15 @@ use helgoboss_learn::{
     ValueSequence, VirtualColor, DEFAULT_OSC_ARG_VALUE_RANGE,
 };
 use helgobox_api::persistence::{
-    ActionScope, Axis, BrowseTracksMode, FxDescriptor, FxToolAction, InstanceTagKind,
-    LearnableTargetKind, MidiScriptKind, MonitoringMode, MouseButton, PlaytimeColumnAction,
-    PlaytimeColumnDescriptor, PlaytimeColumnDescriptorKind, PlaytimeMatrixAction,
-    PlaytimeRowAction, PlaytimeRowDescriptor, PlaytimeRowDescriptorKind, PlaytimeSlotDescriptor,
-    PlaytimeSlotDescriptorKind, PlaytimeSlotManagementAction, PlaytimeSlotTransportAction,
-    PotFilterKind, SeekBehavior, TrackToolAction, VirtualControlElementCharacter,
+    ActionScope, Axis, BrowseTracksMode, EnvelopePosition, EvdevEventKind, FxDescriptor,
+    FxNameKind, FxToolAction, InstanceTagKind, ItemDescriptor, ItemDescriptorKind, ItemParameter,
+    LearnableTargetKind, MidiScriptKind, MonitoringMode, MouseButton, MusicalTimerUnit,
+    PlaytimeColumnAction, PlaytimeColumnDescriptor, PlaytimeColumnDescriptorKind,
+    PlaytimeMatrixAction, PlaytimeRowAction, PlaytimeRowDescriptor, PlaytimeRowDescriptorKind,
+    PlaytimeSlotDescriptor, PlaytimeSlotDescriptorKind, PlaytimeSlotManagementAction,
+    PlaytimeSlotTransportAction, PotFilterKind, RecordInput, RecordMode, SeekBehavior,
+    TakeDescriptor, TakeDescriptorKind, TakeParameter, TrackToolAction,
+    VirtualControlElementCharacter,
 };
 use swell_ui::{
     DeviceContext, DialogUnits, Point, SharedView, SwellStringArg, View, ViewContext, WeakView,
@@ -48,10 +52,10 @@ use crate::application::{
     MappingChangeContext, MappingCommand, MappingModel, MappingModificationKind, MappingProp,
     MappingRefModel, MappingSnapshotTypeForLoad, MappingSnapshotTypeForTake, MidiSourceType,
     ModeCommand, ModeModel, ModeProp, RealearnAutomationMode, RealearnTrackArea, ReaperSourceType,
-    SharedMapping, SharedUnitModel, SourceCategory, SourceCommand, SourceModel, SourceProp,
-    StreamDeckButtonBackgroundType, StreamDeckButtonForegroundType, TargetCategory, TargetCommand,
-    TargetModel, TargetModelFormatVeryShort, TargetModelWithContext, TargetProp, TargetUnit,
-    TrackRouteSelectorType, UnitModel, UnitProp, VirtualFxParameterType, VirtualFxType,
+    RecordInputKind, SharedMapping, SharedUnitModel, SourceCategory, SourceCommand, SourceModel,
+    SourceProp, StreamDeckButtonBackgroundType, StreamDeckButtonForegroundType, TargetCategory,
+    TargetCommand, TargetModel, TargetModelFormatVeryShort, TargetModelWithContext, TargetProp,
+    TargetUnit, TrackRouteSelectorType, UnitModel, UnitProp, VirtualFxParameterType, VirtualFxType,
     VirtualTrackType, WeakUnitModel, KEY_UNDEFINED_LABEL,
 };
 use crate::base::{notification, when, Prop};
@@ -65,11 +69,12 @@ use crate::domain::{
     TargetControlEvent, TouchedRouteParameterType, TrackGangBehavior, WithControlContext,
 };
 use crate::domain::{
-    get_non_present_virtual_route_label, get_non_present_virtual_track_label,
-    resolve_track_route_by_index, ActionInvocationType, CompartmentKind, CompoundMappingTarget,
-    ExtendedProcessorContext, FeedbackResolution, FxDisplayType, QualifiedMappingId,
-    RealearnTarget, SoloBehavior, TargetCharacter, TouchedTrackParameterType, TrackExclusivity,
-    TrackRouteType, TransportAction, VirtualControlElement, VirtualControlElementId, VirtualFx,
+    decode_wasm_module, encode_wasm_module, get_non_present_virtual_route_label,
+    get_non_present_virtual_track_label, resolve_track_route_by_index, ActionInvocationType,
+    CompartmentKind, CompoundMappingTarget, ExtendedProcessorContext, FeedbackResolution,
+    FxDisplayType, QualifiedMappingId, RealearnTarget, SoloBehavior, TargetCharacter,
+    TouchedTrackParameterType, TrackExclusivity, TrackRouteType, TransportAction,
+    VirtualControlElement, VirtualControlElementId, VirtualFx, WasmMidiSourceScript,
 };
 use crate::infrastructure::plugin::BackboneShell;
 use crate::infrastructure::ui::bindings::root;
@@ -312,9 +317,15 @@ impl MappingPanel {
                                             P::MidiClockTransportMessage => {
                                                 view.invalidate_source_line_3_combo_box();
                                             }
-                                            P::IsRegistered => {
+                                            P::IsRegistered | P::MusicalTimerOnlyWhilePlaying => {
                                                 view.invalidate_source_line_4_check_box();
                                             }
+                                            P::MusicalTimerUnit => {
+                                                view.invalidate_source_line_4_combo_box_2();
+                                            }
+                                            P::MusicalTimerOffset => {
+                                                view.invalidate_source_line_5(initiator);
+                                            }
                                             P::Is14Bit => {
                                                 view.invalidate_source_controls();
                                                 view.invalidate_mode_controls();
@@ -328,7 +339,7 @@ impl MappingPanel {
                                             P::Line => {
                                                 view.invalidate_source_line_5_combo_box();
                                             }
-                                            P::OscAddressPattern | P::TimerMillis => {
+                                            P::OscAddressPattern | P::TimerMillis | P::MusicalTimerInterval | P::ChordWindowMillis => {
                                                 view.invalidate_source_line_3_edit_control(initiator);
                                             }
                                             P::RawMidiPattern => {
@@ -339,6 +350,7 @@ impl MappingPanel {
                                             }
                                             P::MidiScriptKind => {
                                                 view.invalidate_source_line_3(initiator);
+                                                view.invalidate_source_line_7(initiator);
                                             }
                                             P::MidiScript | P::OscFeedbackArgs => {
                                                 view.invalidate_source_line_7_edit_control(initiator);
@@ -363,6 +375,16 @@ impl MappingPanel {
                                             P::ButtonStaticText => {
                                                 view.invalidate_source_line_7_edit_control(initiator);
                                             }
+                                            P::EvdevEventKind => {
+                                                view.invalidate_source_line_3_combo_box();
+                                                view.invalidate_mode_controls();
+                                            }
+                                            P::EvdevCode => {
+                                                view.invalidate_source_line_4_edit_control(initiator);
+                                            }
+                                            P::ChordMembers => {
+                                                view.invalidate_source_line_7_edit_control(initiator);
+                                            }
                                         }
                                     }
                                 }
@@ -438,7 +460,7 @@ impl MappingPanel {
                                             P::FeedbackColor | P::FeedbackBackgroundColor => {
                                                 view.invalidate_mode_feedback_type_button();
                                             }
-                                            P::FeedbackValueTable => {
+                                            P::FeedbackValueTable | P::FeedbackColorTable => {
                                                 // No representation in GUI at the moment.
                                             }
                                             P::LegacyJumpInterval => {
@@ -463,7 +485,8 @@ impl MappingPanel {
                                             }
                                             P::TrackType | P::TrackIndex | P::TrackId | P::TrackName
                                             | P::TrackExpression | P::BookmarkType | P::BookmarkAnchorType
-                                            | P::BookmarkRef | P::TransportAction | P::AnyOnParameter
+                                            | P::BookmarkRef | P::UseNearestMarker | P::BookmarkTolerance
+                                            | P::TransportAction | P::AnyOnParameter
                                             | P::SmartCommandName | P::ActionScope => {
                                                 view.invalidate_window_title();
                                                 view.invalidate_target_controls(initiator);
@@ -475,7 +498,7 @@ impl MappingPanel {
                                             P::MappingSnapshotTypeForLoad | P::MappingSnapshotTypeForTake | P::MappingSnapshotId => {
                                                 view.invalidate_target_line_2(initiator);
                                             }
-                                            P::MappingSnapshotDefaultValue => {
+                                            P::MappingSnapshotDefaultValue | P::SecondMappingSnapshotId => {
                                                 view.invalidate_target_line_3(initiator);
                                             }
                                             P::ControlElementId => {
@@ -513,9 +536,13 @@ impl MappingPanel {
                                                 view.invalidate_target_value_controls();
                                                 view.invalidate_mode_controls();
                                             }
-                                            P::SeekBehavior | P::TouchedTrackParameterType | P::AutomationMode | P::MonitoringMode | P::TrackArea => {
+                                            P::SeekBehavior | P::TouchedTrackParameterType | P::AutomationMode | P::MonitoringMode | P::RecordMode | P::TrackArea => {
                                                 view.invalidate_target_line_3(None);
                                             }
+                                            P::RecordInput => {
+                                                view.invalidate_target_line_3(initiator);
+                                                view.invalidate_target_line_5(initiator);
+                                            }
                                             P::SoloBehavior => {
                                                 view.invalidate_target_line_3(None);
                                                 view.invalidate_target_check_boxes();
@@ -534,7 +561,7 @@ impl MappingPanel {
                                                 view.invalidate_target_value_controls();
                                                 view.invalidate_mode_controls();
                                             }
-                                            P::TrackToolAction | P::FxToolAction => {
+                                            P::TrackToolAction | P::FxToolAction | P::FxNameKind => {
                                                 view.invalidate_target_line_4(initiator);
                                                 view.invalidate_target_value_controls();
                                                 view.invalidate_mode_controls();
@@ -632,6 +659,14 @@ impl MappingPanel {
                                             P::TouchedRouteParameterType => {
                                                 view.invalidate_target_line_3_combo_box_2();
                                             }
+                                            P::Item | P::Take | P::ItemParameter | P::TakeParameter => {
+                                                view.invalidate_target_controls(initiator);
+                                                view.invalidate_mode_controls();
+                                            }
+                                            P::EnvelopeName | P::EnvelopePosition => {
+                                                view.invalidate_target_controls(initiator);
+                                                view.invalidate_mode_controls();
+                                            }
                                             P::MappingModificationKind => {
                                                 view.invalidate_target_line_2(initiator);
                                             }
@@ -1257,6 +1292,13 @@ impl MappingPanel {
                             },
                         );
                     }
+                    MidiSourceType::Script
+                        if mapping.borrow().source_model.midi_script_kind()
+                            == MidiScriptKind::Wasm =>
+                    {
+                        let result = self.pick_wasm_midi_source_script();
+                        self.notify_user_on_anyhow_error(result);
+                    }
                     MidiSourceType::Script => {
                         let session = self.session.clone();
                         self.edit_midi_source_script_internal(
@@ -1416,11 +1458,31 @@ impl MappingPanel {
         let engine: Box<dyn ScriptEngine> = match mapping.borrow().source_model.midi_script_kind() {
             MidiScriptKind::Eel => Box::new(EelMidiScriptEngine),
             MidiScriptKind::Lua => Box::new(LuaMidiScriptEngine::new()),
+            // WASM modules are binary, they are picked from a file instead
+            MidiScriptKind::Wasm => return,
         };
         let help_url = "https://docs.helgoboss.org/realearn/goto#source-midi-script";
         self.edit_script_in_simple_editor(engine, help_url, get_initial_value, apply);
     }
 
+    fn pick_wasm_midi_source_script(&self) -> anyhow::Result<()> {
+        let Some(file) = Reaper::get().medium_reaper().get_user_file_name_for_read(
+            &Reaper::get().resource_path(),
+            "Pick WebAssembly module",
+            "wasm",
+        ) else {
+            return Ok(());
+        };
+        let wasm = fs::read(&file).with_context(|| format!("couldn't read {file}"))?;
+        // Fail early instead of silently not sending any feedback
+        WasmMidiSourceScript::compile_binary(&wasm)
+            .with_context(|| format!("{file} is not a valid MIDI source script module"))?;
+        self.change_mapping(MappingCommand::ChangeSource(SourceCommand::SetMidiScript(
+            encode_wasm_module(&wasm),
+        )));
+        Ok(())
+    }
+
     fn edit_script_in_simple_editor(
         &self,
         engine: Box<dyn ScriptEngine>,
@@ -1988,7 +2050,7 @@ impl<'a> MutableMappingPanel<'a> {
     fn handle_target_line_2_button_press_internal(&mut self) {
         match self.target_category() {
             TargetCategory::Reaper => match self.reaper_target_type() {
-                ReaperTargetType::GoToBookmark => {
+                _ if self.mapping.target_model.addresses_particular_bookmark() => {
                     let project = self
                         .session
                         .processor_context()
@@ -2090,9 +2152,12 @@ impl<'a> MutableMappingPanel<'a> {
             .view
             .require_control(root::ID_SOURCE_RPN_CHECK_BOX)
             .is_checked();
-        self.change_mapping(MappingCommand::ChangeSource(
-            SourceCommand::SetIsRegistered(Some(checked)),
-        ));
+        let cmd = if self.mapping.source_model.is_musical_timer() {
+            SourceCommand::SetMusicalTimerOnlyWhilePlaying(checked)
+        } else {
+            SourceCommand::SetIsRegistered(Some(checked))
+        };
+        self.change_mapping(MappingCommand::ChangeSource(cmd));
     }
 
     fn handle_source_check_box_2_change(&mut self) {
@@ -2112,7 +2177,7 @@ impl<'a> MutableMappingPanel<'a> {
                     SourceCommand::SetOscArgIsRelative(checked),
                 ));
             }
-            Reaper | Virtual | Never | Keyboard | StreamDeck => {}
+            Reaper | Virtual | Never | Keyboard | StreamDeck | Evdev | Chord => {}
         };
     }
 
@@ -2170,6 +2235,13 @@ impl<'a> MutableMappingPanel<'a> {
                     index,
                 )));
             }
+            Evdev => {
+                let i = b.selected_combo_box_item_index();
+                let kind = i.try_into().expect("invalid evdev event kind");
+                self.change_mapping(MappingCommand::ChangeSource(
+                    SourceCommand::SetEvdevEventKind(kind),
+                ));
+            }
             _ => {}
         };
     }
@@ -2234,6 +2306,13 @@ impl<'a> MutableMappingPanel<'a> {
                     SourceCommand::SetOscArgTypeTag(tag),
                 ));
             }
+            Reaper if self.mapping.source_model.is_musical_timer() => {
+                let i = b.selected_combo_box_item_index();
+                let unit = i.try_into().expect("invalid musical timer unit");
+                self.change_mapping(MappingCommand::ChangeSource(
+                    SourceCommand::SetMusicalTimerUnit(unit),
+                ));
+            }
             _ => {}
         }
     }
@@ -2340,7 +2419,15 @@ impl<'a> MutableMappingPanel<'a> {
                     Some(edit_control_id),
                 );
             }
-            Reaper | Never | Keyboard | Osc => {}
+            Evdev => {
+                if let Ok(code) = text.parse() {
+                    self.change_mapping_with_initiator(
+                        MappingCommand::ChangeSource(SourceCommand::SetEvdevCode(code)),
+                        Some(edit_control_id),
+                    );
+                }
+            }
+            Reaper | Never | Keyboard | Osc | Chord => {}
         };
     }
 
@@ -2365,6 +2452,13 @@ impl<'a> MutableMappingPanel<'a> {
                     Some(edit_control_id),
                 );
             }
+            Reaper if self.mapping.source_model.is_musical_timer() => {
+                let value = text.parse().unwrap_or_default();
+                self.change_mapping_with_initiator(
+                    MappingCommand::ChangeSource(SourceCommand::SetMusicalTimerOffset(value)),
+                    Some(edit_control_id),
+                );
+            }
             _ => {}
         };
     }
@@ -2389,9 +2483,25 @@ impl<'a> MutableMappingPanel<'a> {
                             Some(edit_control_id),
                         )
                     }
+                    ReaperSourceType::MusicalTimer => {
+                        let value = value.parse().unwrap_or_default();
+                        self.change_mapping_with_initiator(
+                            MappingCommand::ChangeSource(SourceCommand::SetMusicalTimerInterval(
+                                value,
+                            )),
+                            Some(edit_control_id),
+                        )
+                    }
                     _ => {}
                 },
-                Midi | Virtual | Never | Keyboard | StreamDeck => {}
+                Chord => {
+                    let value = value.parse().unwrap_or_default();
+                    self.change_mapping_with_initiator(
+                        MappingCommand::ChangeSource(SourceCommand::SetChordWindowMillis(value)),
+                        Some(edit_control_id),
+                    )
+                }
+                Midi | Virtual | Never | Keyboard | StreamDeck | Evdev => {}
             }
         }
     }
@@ -2889,7 +2999,9 @@ impl<'a> MutableMappingPanel<'a> {
                         is_checked,
                     )));
                 }
-                ReaperTargetType::GoToBookmark => {
+                t if t == ReaperTargetType::AddBookmark
+                    || self.mapping.target_model.addresses_particular_bookmark() =>
+                {
                     let bookmark_type = if is_checked {
                         BookmarkType::Region
                     } else {
@@ -2951,7 +3063,12 @@ impl<'a> MutableMappingPanel<'a> {
                         is_checked,
                     )));
                 }
-                ReaperTargetType::LoadMappingSnapshot => {
+                ReaperTargetType::RenameBookmark | ReaperTargetType::DeleteBookmark => {
+                    self.change_mapping(MappingCommand::ChangeTarget(
+                        TargetCommand::SetUseNearestMarker(is_checked),
+                    ));
+                }
+                ReaperTargetType::LoadMappingSnapshot | ReaperTargetType::MorphMappingSnapshots => {
                     self.change_mapping(MappingCommand::ChangeTarget(
                         TargetCommand::SetActiveMappingsOnly(is_checked),
                     ));
@@ -3010,6 +3127,16 @@ impl<'a> MutableMappingPanel<'a> {
                         TargetCommand::SetUseRegions(is_checked),
                     ));
                 }
+                ReaperTargetType::EnvelopeValue => {
+                    let position = if is_checked {
+                        EnvelopePosition::PlayPosition
+                    } else {
+                        EnvelopePosition::EditCursor
+                    };
+                    self.change_mapping(MappingCommand::ChangeTarget(
+                        TargetCommand::SetEnvelopePosition(position),
+                    ));
+                }
                 t if t.supports_poll_for_feedback() => {
                     self.change_mapping(MappingCommand::ChangeTarget(
                         TargetCommand::SetPollForFeedback(is_checked),
@@ -3105,7 +3232,7 @@ impl<'a> MutableMappingPanel<'a> {
         let combo = self.view.require_control(combo_id);
         match self.target_category() {
             TargetCategory::Reaper => match self.reaper_target_type() {
-                ReaperTargetType::GoToBookmark => {
+                _ if self.mapping.target_model.addresses_particular_bookmark() => {
                     let bookmark_anchor_type = combo
                         .selected_combo_box_item_index()
                         .try_into()
@@ -3162,6 +3289,15 @@ impl<'a> MutableMappingPanel<'a> {
             .require_control(root::ID_TARGET_LINE_3_COMBO_BOX_1);
         match self.target_category() {
             TargetCategory::Reaper => match self.reaper_target_type() {
+                ReaperTargetType::TrackRecordInput => {
+                    let kind: RecordInputKind = combo
+                        .selected_combo_box_item_index()
+                        .try_into()
+                        .expect("invalid record input kind");
+                    self.change_mapping(MappingCommand::ChangeTarget(
+                        TargetCommand::SetRecordInput(kind.first_input()),
+                    ));
+                }
                 ReaperTargetType::Action => {
                     let scope: ActionScope = combo
                         .selected_combo_box_item_index()
@@ -3200,6 +3336,16 @@ impl<'a> MutableMappingPanel<'a> {
                         TargetCommand::SetPlaytimeRow(desc),
                     ));
                 }
+                ReaperTargetType::ItemParameterValue
+                | ReaperTargetType::TakeParameterValue
+                | ReaperTargetType::BrowseTakes => {
+                    let kind = combo
+                        .selected_combo_box_item_index()
+                        .try_into()
+                        .unwrap_or_default();
+                    let desc = ItemDescriptor::from_kind(kind);
+                    self.change_mapping(MappingCommand::ChangeTarget(TargetCommand::SetItem(desc)));
+                }
                 ReaperTargetType::PlaytimeSlotManagementAction
                 | ReaperTargetType::PlaytimeSlotTransportAction
                 | ReaperTargetType::PlaytimeSlotSeek
@@ -3278,6 +3424,23 @@ impl<'a> MutableMappingPanel<'a> {
                         TargetCommand::SetFxToolAction(action),
                     ));
                 }
+                ReaperTargetType::FxName => {
+                    let kind: FxNameKind = combo
+                        .selected_combo_box_item_index()
+                        .try_into()
+                        .unwrap_or_default();
+                    self.change_mapping(MappingCommand::ChangeTarget(
+                        TargetCommand::SetFxNameKind(kind),
+                    ));
+                }
+                ReaperTargetType::TakeParameterValue => {
+                    let kind = combo
+                        .selected_combo_box_item_index()
+                        .try_into()
+                        .unwrap_or_default();
+                    let desc = TakeDescriptor::from_kind(kind);
+                    self.change_mapping(MappingCommand::ChangeTarget(TargetCommand::SetTake(desc)));
+                }
                 t if t.supports_fx_parameter() => {
                     let param_type = combo
                         .selected_combo_box_item_index()
@@ -3316,7 +3479,7 @@ impl<'a> MutableMappingPanel<'a> {
         let combo = self.view.require_control(combo_id);
         match self.target_category() {
             TargetCategory::Reaper => match self.reaper_target_type() {
-                ReaperTargetType::GoToBookmark => {
+                _ if self.mapping.target_model.addresses_particular_bookmark() => {
                     let value: u32 = combo.selected_combo_box_item_data() as _;
                     self.change_mapping(MappingCommand::ChangeTarget(
                         TargetCommand::SetBookmarkRef(value),
@@ -3529,6 +3692,13 @@ impl<'a> MutableMappingPanel<'a> {
                         TargetCommand::SetMonitoringMode(v),
                     ));
                 }
+                ReaperTargetType::TrackRecordMode => {
+                    let i = combo.selected_combo_box_item_index();
+                    let v = i.try_into().expect("invalid record mode");
+                    self.change_mapping(MappingCommand::ChangeTarget(
+                        TargetCommand::SetRecordMode(v),
+                    ));
+                }
                 ReaperTargetType::TrackTouchState => {
                     let i = combo.selected_combo_box_item_index();
                     let v = i.try_into().expect("invalid touched track parameter type");
@@ -3543,6 +3713,20 @@ impl<'a> MutableMappingPanel<'a> {
                         TargetCommand::SetTouchedRouteParameterType(v),
                     ));
                 }
+                ReaperTargetType::ItemParameterValue => {
+                    let i = combo.selected_combo_box_item_index();
+                    let v = i.try_into().expect("invalid item parameter");
+                    self.change_mapping(MappingCommand::ChangeTarget(
+                        TargetCommand::SetItemParameter(v),
+                    ));
+                }
+                ReaperTargetType::TakeParameterValue => {
+                    let i = combo.selected_combo_box_item_index();
+                    let v = i.try_into().expect("invalid take parameter");
+                    self.change_mapping(MappingCommand::ChangeTarget(
+                        TargetCommand::SetTakeParameter(v),
+                    ));
+                }
                 _ if self.mapping.target_model.supports_axis() => {
                     let i = combo.selected_combo_box_item_index();
                     let v = i.try_into().expect("invalid axis type");
@@ -3651,7 +3835,7 @@ impl<'a> MutableMappingPanel<'a> {
         let control = self.view.require_control(edit_control_id);
         match self.target_category() {
             TargetCategory::Reaper => match self.reaper_target_type() {
-                ReaperTargetType::GoToBookmark => {
+                _ if self.mapping.target_model.addresses_particular_bookmark() => {
                     let human_value: u32 = control
                         .text()
                         .unwrap_or_default()
@@ -3716,6 +3900,32 @@ impl<'a> MutableMappingPanel<'a> {
         let control = self.view.require_control(edit_control_id);
         match self.target_category() {
             TargetCategory::Reaper => match self.reaper_target_type() {
+                ReaperTargetType::TrackRecordInput => {
+                    let text = control.text().unwrap_or_default();
+                    let input = match self.mapping.target_model.record_input() {
+                        RecordInput::None => RecordInput::None,
+                        RecordInput::Mono { .. } => {
+                            let position: u32 = text.trim().parse().unwrap_or_default();
+                            RecordInput::Mono {
+                                index: position.saturating_sub(1),
+                            }
+                        }
+                        RecordInput::Stereo { .. } => {
+                            let position: u32 = text.trim().parse().unwrap_or_default();
+                            RecordInput::Stereo {
+                                index: position.saturating_sub(1),
+                            }
+                        }
+                        RecordInput::Midi { channel, .. } => RecordInput::Midi {
+                            device_id: text.trim().parse().ok(),
+                            channel,
+                        },
+                    };
+                    self.change_mapping_with_initiator(
+                        MappingCommand::ChangeTarget(TargetCommand::SetRecordInput(input)),
+                        Some(edit_control_id),
+                    );
+                }
                 ReaperTargetType::SendOsc => {
                     let pattern = control.text().unwrap_or_default();
                     self.change_mapping_with_initiator(
@@ -3735,6 +3945,13 @@ impl<'a> MutableMappingPanel<'a> {
                         Some(edit_control_id),
                     );
                 }
+                ReaperTargetType::MorphMappingSnapshots => {
+                    let id = control.text().unwrap_or_default().parse().ok();
+                    self.change_mapping_with_initiator(
+                        MappingCommand::ChangeTarget(TargetCommand::SetSecondMappingSnapshotId(id)),
+                        Some(edit_control_id),
+                    );
+                }
                 ReaperTargetType::PlaytimeColumnAction => {
                     let text = control.text().unwrap_or_default();
                     match self.mapping.target_model.playtime_column() {
@@ -3785,6 +4002,27 @@ impl<'a> MutableMappingPanel<'a> {
                         }
                     }
                 }
+                ReaperTargetType::ItemParameterValue
+                | ReaperTargetType::TakeParameterValue
+                | ReaperTargetType::BrowseTakes => {
+                    let text = control.text().unwrap_or_default();
+                    let desc = match self.mapping.target_model.item() {
+                        ItemDescriptor::Selected | ItemDescriptor::UnderEditCursor => return,
+                        ItemDescriptor::ByIndex { .. } => {
+                            let position: u32 = text.parse().unwrap_or_default();
+                            ItemDescriptor::ByIndex {
+                                index: position.saturating_sub(1),
+                            }
+                        }
+                        ItemDescriptor::Dynamic { .. } => {
+                            ItemDescriptor::Dynamic { expression: text }
+                        }
+                    };
+                    self.change_mapping_with_initiator(
+                        MappingCommand::ChangeTarget(TargetCommand::SetItem(desc)),
+                        Some(edit_control_id),
+                    );
+                }
                 ReaperTargetType::PlaytimeSlotManagementAction
                 | ReaperTargetType::PlaytimeSlotTransportAction
                 | ReaperTargetType::PlaytimeSlotVolume
@@ -3887,6 +4125,25 @@ impl<'a> MutableMappingPanel<'a> {
                         Some(edit_control_id),
                     );
                 }
+                ReaperTargetType::TakeParameterValue => {
+                    let text = control.text().unwrap_or_default();
+                    let desc = match self.mapping.target_model.take() {
+                        TakeDescriptor::Active => return,
+                        TakeDescriptor::ByIndex { .. } => {
+                            let position: u32 = text.parse().unwrap_or_default();
+                            TakeDescriptor::ByIndex {
+                                index: position.saturating_sub(1),
+                            }
+                        }
+                        TakeDescriptor::Dynamic { .. } => {
+                            TakeDescriptor::Dynamic { expression: text }
+                        }
+                    };
+                    self.change_mapping_with_initiator(
+                        MappingCommand::ChangeTarget(TargetCommand::SetTake(desc)),
+                        Some(edit_control_id),
+                    );
+                }
                 ReaperTargetType::PlaytimeSlotManagementAction
                 | ReaperTargetType::PlaytimeSlotTransportAction
                 | ReaperTargetType::PlaytimeSlotVolume
@@ -3966,6 +4223,22 @@ impl<'a> MutableMappingPanel<'a> {
         let control = self.view.require_control(edit_control_id);
         match self.target_category() {
             TargetCategory::Reaper => match self.reaper_target_type() {
+                ReaperTargetType::TrackRecordInput => {
+                    if let RecordInput::Midi { device_id, .. } =
+                        self.mapping.target_model.record_input()
+                    {
+                        let position: Option<u8> =
+                            control.text().unwrap_or_default().trim().parse().ok();
+                        let input = RecordInput::Midi {
+                            device_id,
+                            channel: position.map(|p| p.saturating_sub(1).min(15)),
+                        };
+                        self.change_mapping_with_initiator(
+                            MappingCommand::ChangeTarget(TargetCommand::SetRecordInput(input)),
+                            Some(edit_control_id),
+                        );
+                    }
+                }
                 ReaperTargetType::SendOsc => {
                     let text = control.text().unwrap_or_default();
                     let v = parse_osc_arg_value_range(&text);
@@ -3974,6 +4247,33 @@ impl<'a> MutableMappingPanel<'a> {
                         Some(edit_control_id),
                     );
                 }
+                ReaperTargetType::EnvelopeValue
+                | ReaperTargetType::BrowseEnvelopePoints
+                | ReaperTargetType::EnvelopeArmState => {
+                    let text = control.text().unwrap_or_default();
+                    self.change_mapping_with_initiator(
+                        MappingCommand::ChangeTarget(TargetCommand::SetEnvelopeName(
+                            text.trim().to_owned(),
+                        )),
+                        Some(edit_control_id),
+                    );
+                }
+                ReaperTargetType::AddBookmark
+                | ReaperTargetType::RenameBookmark
+                | ReaperTargetType::DeleteBookmark => {
+                    let millis: u64 = control
+                        .text()
+                        .unwrap_or_default()
+                        .trim()
+                        .parse()
+                        .unwrap_or_default();
+                    self.change_mapping_with_initiator(
+                        MappingCommand::ChangeTarget(TargetCommand::SetBookmarkTolerance(
+                            Duration::from_millis(millis),
+                        )),
+                        Some(edit_control_id),
+                    );
+                }
                 _ => {}
             },
             TargetCategory::Virtual => {}
@@ -4360,11 +4660,14 @@ impl<'a> ImmutableMappingPanel<'a> {
             Osc => Some("Address"),
             Reaper => match self.source.reaper_source_type() {
                 ReaperSourceType::Timer => Some("Millis"),
+                ReaperSourceType::MusicalTimer => Some("Every"),
                 ReaperSourceType::RealearnParameter => Some("Param"),
                 _ => None,
             },
             Keyboard => Some("Key"),
             StreamDeck => Some("Button"),
+            Evdev => Some("Event"),
+            Chord => Some("Millis"),
             _ => None,
         };
         self.view
@@ -4443,6 +4746,11 @@ impl<'a> ImmutableMappingPanel<'a> {
                 b.select_combo_box_item_by_index(self.source.button_index() as _);
                 b.show();
             }
+            Evdev => {
+                b.fill_combo_box_indexed(EvdevEventKind::iter());
+                b.select_combo_box_item_by_index(self.source.evdev_event_kind().into());
+                b.show();
+            }
             _ => {
                 b.hide();
             }
@@ -4487,6 +4795,10 @@ impl<'a> ImmutableMappingPanel<'a> {
                     _ => None,
                 }
             }
+            Reaper if self.source.is_musical_timer() => Some((
+                "Only while playing",
+                self.source.musical_timer_only_while_playing(),
+            )),
             _ => None,
         };
         self.invalidate_check_box(root::ID_SOURCE_RPN_CHECK_BOX, state);
@@ -4538,6 +4850,8 @@ impl<'a> ImmutableMappingPanel<'a> {
             Virtual => Some("ID"),
             Osc => Some("Argument"),
             StreamDeck => Some("Back"),
+            Evdev => Some("Code"),
+            Reaper if self.source.is_musical_timer() => Some("Unit"),
             _ => None,
         };
         self.view
@@ -4619,6 +4933,11 @@ impl<'a> ImmutableMappingPanel<'a> {
                 let tag = self.source.osc_arg_type_tag();
                 invalidate_with_osc_arg_type_tag(b, tag);
             }
+            Reaper if self.source.is_musical_timer() => {
+                b.show();
+                b.fill_combo_box_indexed(MusicalTimerUnit::iter());
+                b.select_combo_box_item_by_index(self.source.musical_timer_unit().into());
+            }
             _ => {
                 b.hide();
             }
@@ -4644,6 +4963,7 @@ impl<'a> ImmutableMappingPanel<'a> {
                 Some(self.source.button_background_image_path().to_string())
             }
             Virtual => Some(self.source.control_element_id().to_string()),
+            Evdev => Some(self.source.evdev_code().to_string()),
             _ => None,
         };
         self.view
@@ -4680,11 +5000,15 @@ impl<'a> ImmutableMappingPanel<'a> {
         let text = match self.source.category() {
             Midi => match self.source.midi_source_type() {
                 MidiSourceType::Raw => Some("Pattern"),
-                MidiSourceType::Script => Some("Script"),
+                MidiSourceType::Script => match self.source.midi_script_kind() {
+                    MidiScriptKind::Wasm => Some("Module"),
+                    _ => Some("Script"),
+                },
                 _ => None,
             },
             Osc => Some("Feedback arguments"),
             StreamDeck => Some("Default text"),
+            Chord => Some("Members"),
             _ => None,
         };
         self.view
@@ -4703,6 +5027,9 @@ impl<'a> ImmutableMappingPanel<'a> {
             Osc => Some((self.source.osc_address_pattern().to_owned(), true)),
             Reaper => match self.source.reaper_source_type() {
                 ReaperSourceType::Timer => Some((self.source.timer_millis().to_string(), true)),
+                ReaperSourceType::MusicalTimer => {
+                    Some((self.source.musical_timer_interval().to_string(), true))
+                }
                 _ => None,
             },
             Keyboard => {
@@ -4729,6 +5056,7 @@ impl<'a> ImmutableMappingPanel<'a> {
                     .unwrap_or_else(|| KEY_UNDEFINED_LABEL.to_string());
                 Some((text, false))
             }
+            Chord => Some((self.source.chord_window_millis().to_string(), true)),
             _ => None,
         };
         if let Some((value_text, enabled)) = content {
@@ -4755,10 +5083,19 @@ impl<'a> ImmutableMappingPanel<'a> {
                 }
                 MidiSourceType::Script => {
                     let text = self.source.midi_script();
-                    (
-                        Some(extract_first_line(text).to_owned()),
-                        has_multiple_lines(text),
-                    )
+                    if self.source.midi_script_kind() == MidiScriptKind::Wasm {
+                        // Showing Base64 doesn't make sense
+                        let summary = match decode_wasm_module(text) {
+                            Ok(wasm) => format!("{} bytes", wasm.len()),
+                            Err(_) => "<None>".to_string(),
+                        };
+                        (Some(summary), true)
+                    } else {
+                        (
+                            Some(extract_first_line(text).to_owned()),
+                            has_multiple_lines(text),
+                        )
+                    }
                 }
                 _ => (None, false),
             },
@@ -4770,6 +5107,16 @@ impl<'a> ImmutableMappingPanel<'a> {
                 let text = self.source.button_static_text();
                 (Some(text.to_string()), has_multiple_lines(text))
             }
+            // Members can only be defined via API at the moment
+            Chord => {
+                let text = self
+                    .source
+                    .chord_members()
+                    .iter()
+                    .map(|m| m.to_string().replace('\n', " "))
+                    .join(" + ");
+                (Some(text), true)
+            }
             _ => (None, false),
         };
         c.set_text_or_hide(value_text);
@@ -4802,6 +5149,7 @@ impl<'a> ImmutableMappingPanel<'a> {
             }
             Osc if self.source.supports_osc_arg_value_range() => Some("Range"),
             StreamDeck => Some("Front"),
+            Reaper if self.source.is_musical_timer() => Some("Offset"),
             _ => None,
         };
         self.view
@@ -4881,6 +5229,9 @@ impl<'a> ImmutableMappingPanel<'a> {
             StreamDeck if self.source.button_foreground_type().wants_image() => {
                 Some(self.source.button_foreground_image_path().to_string())
             }
+            Reaper if self.source.is_musical_timer() => {
+                Some(self.source.musical_timer_offset().to_string())
+            }
             _ => None,
         };
         self.view
@@ -4952,14 +5303,17 @@ impl<'a> ImmutableMappingPanel<'a> {
                 ReaperTargetType::Transport => Some("Action"),
                 ReaperTargetType::AnyOn => Some("Parameter"),
                 ReaperTargetType::AutomationModeOverride => Some("Behavior"),
-                ReaperTargetType::GoToBookmark => match self.target.bookmark_type() {
-                    BookmarkType::Marker => Some("Marker"),
-                    BookmarkType::Region => Some("Region"),
-                },
+                _ if self.target.addresses_particular_bookmark() => {
+                    match self.target.bookmark_type() {
+                        BookmarkType::Marker => Some("Marker"),
+                        BookmarkType::Region => Some("Region"),
+                    }
+                }
                 ReaperTargetType::SendMidi => Some("Output"),
                 ReaperTargetType::SendOsc => Some("Output"),
                 ReaperTargetType::LoadMappingSnapshot => Some("Snapshot"),
                 ReaperTargetType::TakeMappingSnapshot => Some("Snapshot ID"),
+                ReaperTargetType::MorphMappingSnapshots => Some("Snapshot A"),
                 ReaperTargetType::BrowseGroup => Some("Group"),
                 ReaperTargetType::BrowseTracks => Some("Scope"),
                 ReaperTargetType::ModifyMapping => Some("Kind"),
@@ -5023,7 +5377,7 @@ impl<'a> ImmutableMappingPanel<'a> {
                     combo.fill_combo_box_indexed(VirtualTrackType::iter());
                     combo.select_combo_box_item_by_index(self.target.track_type().into());
                 }
-                ReaperTargetType::GoToBookmark => {
+                _ if self.target.addresses_particular_bookmark() => {
                     combo.show();
                     combo.fill_combo_box_indexed(BookmarkAnchorType::iter());
                     combo.select_combo_box_item_by_index(self.target.bookmark_anchor_type().into());
@@ -5104,8 +5458,8 @@ impl<'a> ImmutableMappingPanel<'a> {
                             .into(),
                     );
                 }
-                ReaperTargetType::GoToBookmark
-                    if self.target.bookmark_anchor_type() == BookmarkAnchorType::Id =>
+                _ if self.target.addresses_particular_bookmark()
+                    && self.target.bookmark_anchor_type() == BookmarkAnchorType::Id =>
                 {
                     combo.show();
                     let project = self.target_with_context().project();
@@ -5306,8 +5660,8 @@ impl<'a> ImmutableMappingPanel<'a> {
                     };
                     control.set_text(text);
                 }
-                ReaperTargetType::GoToBookmark
-                    if self.target.bookmark_anchor_type() == BookmarkAnchorType::Index =>
+                _ if self.target.addresses_particular_bookmark()
+                    && self.target.bookmark_anchor_type() == BookmarkAnchorType::Index =>
                 {
                     control.show();
                     let text = (self.target.bookmark_ref() + 1).to_string();
@@ -5347,7 +5701,7 @@ impl<'a> ImmutableMappingPanel<'a> {
     fn invalidate_target_line_2_button(&self) {
         let text = match self.target_category() {
             TargetCategory::Reaper => match self.reaper_target_type() {
-                ReaperTargetType::GoToBookmark => Some("Now!"),
+                _ if self.target.addresses_particular_bookmark() => Some("Now!"),
                 ReaperTargetType::LastTouched => Some("Pick!"),
                 ReaperTargetType::CompartmentParameterValue => Some("Pick!"),
                 ReaperTargetType::ModifyMapping => match self.target.mapping_modification_kind() {
@@ -5370,6 +5724,19 @@ impl<'a> ImmutableMappingPanel<'a> {
     }
 
     fn invalidate_target_line_3(&self, initiator: Option<u32>) {
+        // Track envelopes don't need the FX line
+        if self.target.uses_track_envelope() {
+            self.hide_target_line(&[
+                root::ID_TARGET_LINE_3_LABEL_1,
+                root::ID_TARGET_LINE_3_LABEL_2,
+                root::ID_TARGET_LINE_3_LABEL_3,
+                root::ID_TARGET_LINE_3_COMBO_BOX_1,
+                root::ID_TARGET_LINE_3_COMBO_BOX_2,
+                root::ID_TARGET_LINE_3_EDIT_CONTROL,
+                root::ID_TARGET_LINE_3_BUTTON,
+            ]);
+            return;
+        }
         self.invalidate_target_line_3_label_1();
         self.invalidate_target_line_3_label_2();
         self.invalidate_target_line_3_label_3();
@@ -5380,6 +5747,19 @@ impl<'a> ImmutableMappingPanel<'a> {
     }
 
     fn invalidate_target_line_4(&self, initiator: Option<u32>) {
+        // Track envelopes don't need the FX parameter line
+        if self.target.uses_track_envelope() {
+            self.hide_target_line(&[
+                root::ID_TARGET_LINE_4_LABEL_1,
+                root::ID_TARGET_LINE_4_LABEL_2,
+                root::ID_TARGET_LINE_4_LABEL_3,
+                root::ID_TARGET_LINE_4_COMBO_BOX_1,
+                root::ID_TARGET_LINE_4_COMBO_BOX_2,
+                root::ID_TARGET_LINE_4_EDIT_CONTROL,
+                root::ID_TARGET_LINE_4_BUTTON,
+            ]);
+            return;
+        }
         self.invalidate_target_line_4_label_1();
         self.invalidate_target_line_4_label_2();
         self.invalidate_target_line_4_label_3();
@@ -5394,6 +5774,12 @@ impl<'a> ImmutableMappingPanel<'a> {
         self.invalidate_target_line_5_edit_control(initiator);
     }
 
+    fn hide_target_line(&self, control_ids: &[u32]) {
+        for id in control_ids {
+            self.view.require_control(*id).hide();
+        }
+    }
+
     fn invalidate_target_line_3_button(&self) {
         let text = match self.target_category() {
             TargetCategory::Reaper => match self.reaper_target_type() {
@@ -5453,6 +5839,12 @@ impl<'a> ImmutableMappingPanel<'a> {
         }
         let text = match self.target_category() {
             TargetCategory::Reaper => match self.reaper_target_type() {
+                ReaperTargetType::TrackRecordInput => match self.target.record_input() {
+                    RecordInput::Midi { channel, .. } => {
+                        Some(channel.map(|ch| (ch + 1).to_string()).unwrap_or_default())
+                    }
+                    _ => None,
+                },
                 ReaperTargetType::SendOsc if self.target.supports_osc_arg_value_range() => {
                     let text = format_osc_arg_value_range(
                         self.target.osc_arg_value_range(),
@@ -5460,6 +5852,16 @@ impl<'a> ImmutableMappingPanel<'a> {
                     );
                     Some(text)
                 }
+                ReaperTargetType::EnvelopeValue
+                | ReaperTargetType::BrowseEnvelopePoints
+                | ReaperTargetType::EnvelopeArmState => {
+                    Some(self.target.envelope_name().to_owned())
+                }
+                ReaperTargetType::AddBookmark
+                | ReaperTargetType::RenameBookmark
+                | ReaperTargetType::DeleteBookmark => {
+                    Some(self.target.bookmark_tolerance().as_millis().to_string())
+                }
                 _ => None,
             },
             TargetCategory::Virtual => None,
@@ -5483,6 +5885,14 @@ impl<'a> ImmutableMappingPanel<'a> {
                     let read_only = text.chars().count() > 30;
                     (Some(text), read_only)
                 }
+                ReaperTargetType::TakeParameterValue => {
+                    let text = match self.target.take() {
+                        TakeDescriptor::Active => None,
+                        TakeDescriptor::ByIndex { index } => Some((index + 1).to_string()),
+                        TakeDescriptor::Dynamic { expression } => Some(expression.clone()),
+                    };
+                    (text, false)
+                }
                 ReaperTargetType::PlaytimeSlotManagementAction
                 | ReaperTargetType::PlaytimeSlotTransportAction
                 | ReaperTargetType::PlaytimeSlotSeek
@@ -5545,6 +5955,16 @@ impl<'a> ImmutableMappingPanel<'a> {
             .require_control(root::ID_TARGET_LINE_3_EDIT_CONTROL);
         let (value_text, read_only) = match self.target_category() {
             TargetCategory::Reaper => match self.reaper_target_type() {
+                ReaperTargetType::TrackRecordInput => match self.target.record_input() {
+                    RecordInput::None => (None, false),
+                    RecordInput::Mono { index } | RecordInput::Stereo { index } => {
+                        (Some((index + 1).to_string()), false)
+                    }
+                    RecordInput::Midi { device_id, .. } => {
+                        let text = device_id.map(|id| id.to_string()).unwrap_or_default();
+                        (Some(text), false)
+                    }
+                },
                 ReaperTargetType::SendOsc => {
                     let text = self.target.osc_address_pattern().to_owned();
                     (Some(text), false)
@@ -5559,6 +5979,14 @@ impl<'a> ImmutableMappingPanel<'a> {
                         .unwrap_or_default();
                     (Some(text), false)
                 }
+                ReaperTargetType::MorphMappingSnapshots => {
+                    let text = self
+                        .target
+                        .second_mapping_snapshot_id()
+                        .map(|id| id.to_string())
+                        .unwrap_or_default();
+                    (Some(text), false)
+                }
                 ReaperTargetType::PlaytimeColumnAction => {
                     let text = match self.target.playtime_column() {
                         PlaytimeColumnDescriptor::Active => None,
@@ -5577,6 +6005,16 @@ impl<'a> ImmutableMappingPanel<'a> {
                     };
                     (text, false)
                 }
+                ReaperTargetType::ItemParameterValue
+                | ReaperTargetType::TakeParameterValue
+                | ReaperTargetType::BrowseTakes => {
+                    let text = match self.target.item() {
+                        ItemDescriptor::Selected | ItemDescriptor::UnderEditCursor => None,
+                        ItemDescriptor::ByIndex { index } => Some((index + 1).to_string()),
+                        ItemDescriptor::Dynamic { expression } => Some(expression.clone()),
+                    };
+                    (text, false)
+                }
                 ReaperTargetType::PlaytimeSlotManagementAction
                 | ReaperTargetType::PlaytimeSlotTransportAction
                 | ReaperTargetType::PlaytimeSlotSeek
@@ -5623,7 +6061,10 @@ impl<'a> ImmutableMappingPanel<'a> {
                 ReaperTargetType::TrackTouchState => Some("Type"),
                 ReaperTargetType::SendOsc => Some("Address"),
                 ReaperTargetType::TrackMonitoringMode => Some("Mode"),
+                ReaperTargetType::TrackRecordInput => Some("Input"),
+                ReaperTargetType::TrackRecordMode => Some("Mode"),
                 ReaperTargetType::LoadMappingSnapshot => Some("Default"),
+                ReaperTargetType::MorphMappingSnapshots => Some("Snapshot B"),
                 ReaperTargetType::ModifyMapping => Some("Unit"),
                 ReaperTargetType::EnableInstances => Some("Tag kind"),
                 ReaperTargetType::SendMidi
@@ -5634,6 +6075,9 @@ impl<'a> ImmutableMappingPanel<'a> {
                 }
                 ReaperTargetType::PlaytimeColumnAction => Some("Column"),
                 ReaperTargetType::PlaytimeRowAction => Some("Row"),
+                ReaperTargetType::ItemParameterValue
+                | ReaperTargetType::TakeParameterValue
+                | ReaperTargetType::BrowseTakes => Some("Item"),
                 ReaperTargetType::PlaytimeSlotManagementAction
                 | ReaperTargetType::PlaytimeSlotTransportAction
                 | ReaperTargetType::PlaytimeSlotSeek
@@ -5658,6 +6102,17 @@ impl<'a> ImmutableMappingPanel<'a> {
                 ReaperTargetType::SendOsc if self.target.supports_osc_arg_value_range() => {
                     Some("Range")
                 }
+                ReaperTargetType::EnvelopeValue
+                | ReaperTargetType::BrowseEnvelopePoints
+                | ReaperTargetType::EnvelopeArmState => Some("Envelope"),
+                ReaperTargetType::AddBookmark
+                | ReaperTargetType::RenameBookmark
+                | ReaperTargetType::DeleteBookmark => Some("Tolerance (ms)"),
+                ReaperTargetType::TrackRecordInput
+                    if self.target.record_input_kind() == RecordInputKind::Midi =>
+                {
+                    Some("Channel")
+                }
                 _ => None,
             },
             TargetCategory::Virtual => None,
@@ -5677,8 +6132,10 @@ impl<'a> ImmutableMappingPanel<'a> {
                 ReaperTargetType::LoadFxSnapshot => Some("Snapshot"),
                 ReaperTargetType::SendOsc => Some("Argument"),
                 ReaperTargetType::TrackTool | ReaperTargetType::FxTool => Some("Act/Tags"),
+                ReaperTargetType::FxName => Some("Name"),
                 ReaperTargetType::ModifyMapping => Some("Mapping"),
                 ReaperTargetType::SendMidi => Some("Pattern"),
+                ReaperTargetType::TakeParameterValue => Some("Take"),
                 t if t.supports_fx_parameter() => Some("Parameter"),
                 t if t.supports_track_exclusivity() => Some("Exclusive"),
                 t if t.supports_fx_display_type() => Some("Display"),
@@ -5797,6 +6254,11 @@ impl<'a> ImmutableMappingPanel<'a> {
             .require_control(root::ID_TARGET_LINE_3_COMBO_BOX_1);
         match self.target_category() {
             TargetCategory::Reaper => match self.target.target_type() {
+                ReaperTargetType::TrackRecordInput => {
+                    combo.show();
+                    combo.fill_combo_box_indexed(RecordInputKind::iter());
+                    combo.select_combo_box_item_by_index(self.target.record_input_kind().into());
+                }
                 ReaperTargetType::Action => {
                     combo.show();
                     combo.fill_combo_box_indexed(ActionScope::iter());
@@ -5819,6 +6281,13 @@ impl<'a> ImmutableMappingPanel<'a> {
                     combo.fill_combo_box_indexed(PlaytimeRowDescriptorKind::iter());
                     combo.select_combo_box_item_by_index(self.target.playtime_row().kind().into());
                 }
+                ReaperTargetType::ItemParameterValue
+                | ReaperTargetType::TakeParameterValue
+                | ReaperTargetType::BrowseTakes => {
+                    combo.show();
+                    combo.fill_combo_box_indexed(ItemDescriptorKind::iter());
+                    combo.select_combo_box_item_by_index(self.target.item().kind().into());
+                }
                 ReaperTargetType::PlaytimeSlotManagementAction
                 | ReaperTargetType::PlaytimeSlotTransportAction
                 | ReaperTargetType::PlaytimeSlotSeek
@@ -5872,6 +6341,16 @@ impl<'a> ImmutableMappingPanel<'a> {
                     let action: FxToolAction = self.target.fx_tool_action();
                     combo.select_combo_box_item_by_index(action.into());
                 }
+                ReaperTargetType::FxName => {
+                    combo.show();
+                    combo.fill_combo_box_indexed(FxNameKind::iter());
+                    combo.select_combo_box_item_by_index(self.target.fx_name_kind().into());
+                }
+                ReaperTargetType::TakeParameterValue => {
+                    combo.show();
+                    combo.fill_combo_box_indexed(TakeDescriptorKind::iter());
+                    combo.select_combo_box_item_by_index(self.target.take().kind().into());
+                }
                 t if t.supports_fx_parameter() => {
                     combo.show();
                     combo.fill_combo_box_indexed(VirtualFxParameterType::iter());
@@ -5980,11 +6459,26 @@ impl<'a> ImmutableMappingPanel<'a> {
                         self.target.touched_route_parameter_type().into(),
                     );
                 }
+                ReaperTargetType::ItemParameterValue => {
+                    combo.show();
+                    combo.fill_combo_box_indexed(ItemParameter::iter());
+                    combo.select_combo_box_item_by_index(self.target.item_parameter().into());
+                }
+                ReaperTargetType::TakeParameterValue => {
+                    combo.show();
+                    combo.fill_combo_box_indexed(TakeParameter::iter());
+                    combo.select_combo_box_item_by_index(self.target.take_parameter().into());
+                }
                 ReaperTargetType::TrackMonitoringMode => {
                     combo.show();
                     combo.fill_combo_box_indexed(MonitoringMode::iter());
                     combo.select_combo_box_item_by_index(self.target.monitoring_mode().into());
                 }
+                ReaperTargetType::TrackRecordMode => {
+                    combo.show();
+                    combo.fill_combo_box_indexed(RecordMode::iter());
+                    combo.select_combo_box_item_by_index(self.target.record_mode().into());
+                }
                 _ if self.target.supports_automation_mode() => {
                     combo.show();
                     combo.fill_combo_box_indexed(RealearnAutomationMode::iter());
@@ -6118,7 +6612,9 @@ impl<'a> ImmutableMappingPanel<'a> {
         let state = match self.target.category() {
             TargetCategory::Reaper => match self.target.target_type() {
                 ReaperTargetType::Action => Some(("With track", self.target.with_track())),
-                ReaperTargetType::GoToBookmark => {
+                t if t == ReaperTargetType::AddBookmark
+                    || self.target.addresses_particular_bookmark() =>
+                {
                     let is_regions = self.target.bookmark_type() == BookmarkType::Region;
                     Some(("Regions", is_regions))
                 }
@@ -6148,7 +6644,7 @@ impl<'a> ImmutableMappingPanel<'a> {
     fn invalidate_target_check_box_2(&self) {
         let state = match self.target.category() {
             TargetCategory::Reaper => match self.target.target_type() {
-                ReaperTargetType::LoadMappingSnapshot => {
+                ReaperTargetType::LoadMappingSnapshot | ReaperTargetType::MorphMappingSnapshots => {
                     Some(("Active mappings only", self.target.active_mappings_only()))
                 }
                 _ if self.mapping.target_model.supports_track_must_be_selected() => {
@@ -6169,6 +6665,9 @@ impl<'a> ImmutableMappingPanel<'a> {
                     Some(("Scroll MCP", self.target.scroll_mixer()))
                 }
                 ReaperTargetType::Seek => Some(("Move view", self.target.move_view())),
+                ReaperTargetType::RenameBookmark | ReaperTargetType::DeleteBookmark => {
+                    Some(("Nearest marker", self.target.use_nearest_marker()))
+                }
                 _ => None,
             },
             TargetCategory::Virtual => None,
@@ -6205,6 +6704,10 @@ impl<'a> ImmutableMappingPanel<'a> {
         let state = match self.target.category() {
             TargetCategory::Reaper => match self.target.target_type() {
                 ReaperTargetType::Seek => Some(("Use regions", self.target.use_regions())),
+                ReaperTargetType::EnvelopeValue => Some((
+                    "At play position",
+                    self.target.envelope_position() == EnvelopePosition::PlayPosition,
+                )),
                 t if t.supports_poll_for_feedback() => {
                     Some(("Poll for feedback", self.target.poll_for_feedback()))
                 }
@@ -7067,7 +7570,7 @@ impl<'a> ImmutableMappingPanel<'a> {
             Midi => b.fill_combo_box_indexed(MidiSourceType::iter()),
             Reaper => b.fill_combo_box_indexed(ReaperSourceType::iter()),
             Virtual => b.fill_combo_box_indexed(VirtualControlElementCharacter::iter()),
-            Osc | Never | Keyboard | StreamDeck => {}
+            Osc | Never | Keyboard | StreamDeck | Evdev | Chord => {}
         };
     }
 
@@ -1,9 +1,9 @@
 use crate::application::{UnitModel, WeakUnitModel};
 use crate::domain::{
-    compartment_param_index_iter, probe_stream_deck_devices, CompartmentKind,
-    CompartmentParamIndex, CompartmentParams, ControlInput, FeedbackOutput, MappingId,
-    MidiControlInput, MidiDestination, OscDeviceId, ProbedStreamDeckDevice, ReaperTargetType,
-    StreamDeckDeviceId, TargetSection,
+    compartment_param_index_iter, probe_evdev_devices, probe_stream_deck_devices, BooleanOperator,
+    CompartmentKind, CompartmentParamIndex, CompartmentParams, ControlInput, EvdevDevice,
+    EvdevDeviceId, FeedbackOutput, MappingId, MidiControlInput, MidiDestination, OscDeviceId,
+    OscTransport, ProbedStreamDeckDevice, ReaperTargetType, StreamDeckDeviceId, TargetSection,
 };
 use crate::infrastructure::data::{CommonPresetInfo, OscDevice};
 use crate::infrastructure::plugin::{ActionSection, BackboneShell, ACTION_DEFS};
@@ -30,6 +30,7 @@ pub enum ControlInputMenuAction {
     ManageOsc(OscDeviceManagementAction),
     ToggleWantsKeyboardInput,
     SelectStreamDeckDevice(Option<StreamDeckDeviceId>),
+    SelectEvdevDevice(Option<EvdevDeviceId>),
 }
 
 pub fn midi_device_input_menu(
@@ -72,6 +73,7 @@ pub fn control_input_menu(
     current_value: ControlInput,
     current_wants_keyboard_input: bool,
     current_stream_deck_dev_id: Option<StreamDeckDeviceId>,
+    current_evdev_dev_id: Option<EvdevDeviceId>,
 ) -> Menu<ControlInputMenuAction> {
     let fx_input = ControlInput::Midi(MidiControlInput::FxInput);
     let (open_midi_devs, closed_midi_devs) = get_open_and_closed_midi_input_devs();
@@ -100,6 +102,9 @@ pub fn control_input_menu(
         .iter()
         .filter(|d| d.available)
         .map(|dev| build_stream_deck_dev_menu_item(dev, current_stream_deck_dev_id));
+    let evdev_devs = probe_evdev_devices()
+        .into_iter()
+        .map(|dev| build_evdev_dev_menu_item(dev, current_evdev_dev_id));
     let entries = [item_with_opts(
         CONTROL_INPUT_MIDI_FX_INPUT_LABEL,
         ItemOpts {
@@ -147,6 +152,16 @@ pub fn control_input_menu(
         create_category_menu("Unavailable Stream Decks", unavailable_stream_deck_devs),
         separator(),
     ])
+    .chain([item_with_opts(
+        "Evdev: <None>",
+        ItemOpts {
+            enabled: true,
+            checked: current_evdev_dev_id.is_none(),
+        },
+        ControlInputMenuAction::SelectEvdevDevice(None),
+    )])
+    .chain(evdev_devs)
+    .chain([separator()])
     .chain([item_with_opts(
         CONTROL_INPUT_KEYBOARD_LABEL,
         ItemOpts {
@@ -339,6 +354,20 @@ fn build_stream_deck_dev_menu_item(
     )
 }
 
+fn build_evdev_dev_menu_item(
+    dev: EvdevDevice,
+    current: Option<EvdevDeviceId>,
+) -> Entry<ControlInputMenuAction> {
+    item_with_opts(
+        format!("Evdev: {} ({})", dev.name, dev.id),
+        ItemOpts {
+            enabled: true,
+            checked: current == Some(dev.id),
+        },
+        ControlInputMenuAction::SelectEvdevDevice(Some(dev.id)),
+    )
+}
+
 pub fn get_osc_device_list_label(dev: &OscDevice, is_output: bool) -> String {
     format!("OSC: {}", dev.get_list_label(is_output))
 }
@@ -654,6 +683,22 @@ pub fn get_bank_name(
     }
 }
 
+pub fn menu_containing_boolean_operators(current_value: BooleanOperator) -> Menu<BooleanOperator> {
+    let entries = BooleanOperator::iter()
+        .map(|op| {
+            item_with_opts(
+                op.to_string(),
+                ItemOpts {
+                    enabled: true,
+                    checked: op == current_value,
+                },
+                op,
+            )
+        })
+        .collect();
+    anonymous_menu(entries)
+}
+
 fn bank_item(text: String, bank_index: usize, current_bank_index: u32) -> Entry<u32> {
     item_with_opts(
         text,
@@ -795,6 +840,8 @@ pub enum OscDeviceManagementAction {
     ToggleOscDeviceControl(OscDeviceId),
     ToggleOscDeviceFeedback(OscDeviceId),
     ToggleOscDeviceBundles(OscDeviceId),
+    ToggleOscDeviceFeedbackCoalescing(OscDeviceId),
+    SetOscDeviceTransport(OscDeviceId, OscTransport),
 }
 
 fn osc_device_management_menu_entries<P>(
@@ -843,6 +890,33 @@ fn osc_device_management_menu_entries<P>(
                     },
                     build_payload(OscDeviceManagementAction::ToggleOscDeviceBundles(dev_id)),
                 ),
+                item_with_opts(
+                    "Coalesce feedback messages with same address",
+                    ItemOpts {
+                        enabled: true,
+                        checked: dev.output_policy().coalesce_by_address,
+                    },
+                    build_payload(
+                        OscDeviceManagementAction::ToggleOscDeviceFeedbackCoalescing(dev_id),
+                    ),
+                ),
+                menu(
+                    "Transport",
+                    OscTransport::iter()
+                        .map(|transport| {
+                            item_with_opts(
+                                transport.to_string(),
+                                ItemOpts {
+                                    enabled: true,
+                                    checked: dev.transport() == transport,
+                                },
+                                build_payload(OscDeviceManagementAction::SetOscDeviceTransport(
+                                    dev_id, transport,
+                                )),
+                            )
+                        })
+                        .collect(),
+                ),
             ],
         )
     }))
@@ -6,8 +6,8 @@ use crate::infrastructure::ui::{
 use reaper_high::Reaper;
 
 use crate::application::{
-    get_virtual_fx_label, get_virtual_track_label, Affected, CompartmentProp, UnitCommand,
-    UnitModel, UnitProp, UnitUi, VirtualFxType, WeakUnitModel,
+    get_virtual_fx_label, get_virtual_track_label, Affected, CompartmentProp, StreamedControlEvent,
+    UnitCommand, UnitModel, UnitProp, UnitUi, VirtualFxType, WeakUnitModel,
 };
 use crate::base::when;
 use crate::domain::ui_util::format_tags_as_csv;
@@ -18,8 +18,8 @@ use crate::domain::{
 };
 use crate::infrastructure::plugin::{update_auto_units_async, BackboneShell};
 use crate::infrastructure::server::http::{
-    send_projection_feedback_to_subscribed_clients, send_sessions_to_subscribed_clients,
-    send_updated_controller_routing,
+    send_control_event_to_subscribed_clients, send_projection_feedback_to_subscribed_clients,
+    send_sessions_to_subscribed_clients, send_updated_controller_routing,
 };
 use crate::infrastructure::ui::instance_panel::InstancePanel;
 use crate::infrastructure::ui::util::{header_panel_height, parse_tags_from_csv};
@@ -617,6 +617,10 @@ impl UnitUi for Weak<UnitPanel> {
         upgrade_panel(self).handle_source_feedback_event(event);
     }
 
+    fn send_control_event(&self, session: &UnitModel, event: StreamedControlEvent) {
+        let _ = send_control_event_to_subscribed_clients(session, event);
+    }
+
     fn handle_affected(
         &self,
         session: &UnitModel,
@@ -93,6 +93,13 @@ pub enum Fil {
     ///
     /// Not suitable for persistence because project IDs are created at runtime.
     Project(ProjectId),
+    /// Refers to a metadata value (e.g. a category) of a preset in a directory database.
+    ///
+    /// Only makes sense for the pot filter kinds "Sub bank", "Category", "Sub category" and "Mode".
+    ///
+    /// The IDs are shared by all directory databases, so the same value has the same ID in each
+    /// of them. Not suitable for persistence because metadata value IDs are created at runtime.
+    DirectoryMetadata(u32),
 }
 
 /// Runtime ID for a [`Product`].
@@ -223,19 +230,15 @@ impl Filters {
         }
     }
 
-    pub fn favorite_matches(
-        &self,
-        favorites: &NonCryptoHashSet<InnerPresetId>,
-        preset_id: InnerPresetId,
-    ) -> bool {
+    pub fn favorite_status_matches(&self, is_favorite: bool) -> bool {
         match self.get(PotFilterKind::IsFavorite) {
             None => true,
             Some(FilterItemId(None)) => false,
             Some(FilterItemId(Some(fil))) => {
                 if fil == FIL_IS_FAVORITE_TRUE {
-                    favorites.contains(&preset_id)
+                    is_favorite
                 } else {
-                    !favorites.contains(&preset_id)
+                    !is_favorite
                 }
             }
         }
@@ -0,0 +1,215 @@
+use crate::PersistentDatabaseId;
+use base::hash_util::NonCryptoHashMap;
+use camino::{Utf8Path, Utf8PathBuf};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::fs::Metadata;
+use std::time::UNIX_EPOCH;
+
+/// Persistent on-disk index of the files scanned by a file-based database.
+///
+/// Each entry is keyed by the path of the scanned file (relative to the database root directory)
+/// and remembers the modification time and size of the file at the time it was scanned. On
+/// refresh, only files whose modification time or size changed need to be scanned again.
+///
+/// The whole index is discarded if it was written with another format version, so providers must
+/// increase their format version whenever the structure or meaning of `T` changes.
+pub struct IndexCache<T> {
+    file_path: Utf8PathBuf,
+    format_version: u32,
+    previous_entries: NonCryptoHashMap<String, IndexCacheEntry<T>>,
+    current_entries: NonCryptoHashMap<String, IndexCacheEntry<T>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct IndexCacheFile<T> {
+    format_version: u32,
+    entries: NonCryptoHashMap<String, IndexCacheEntry<T>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct IndexCacheEntry<T> {
+    /// `None` if the file system doesn't provide a modification time. Such entries never match.
+    fingerprint: Option<FileFingerprint>,
+    value: T,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+struct FileFingerprint {
+    modified_secs: u64,
+    modified_nanos: u32,
+    size: u64,
+}
+
+impl FileFingerprint {
+    fn from_metadata(metadata: &Metadata) -> Option<Self> {
+        let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+        let fingerprint = Self {
+            modified_secs: modified.as_secs(),
+            modified_nanos: modified.subsec_nanos(),
+            size: metadata.len(),
+        };
+        Some(fingerprint)
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> IndexCache<T> {
+    /// Loads the index of the given database from the given cache directory.
+    ///
+    /// Starts with an empty index if there's none yet, if it can't be read or if it has been
+    /// written with a different format version.
+    pub fn load(cache_dir: &Utf8Path, db_id: &PersistentDatabaseId, format_version: u32) -> Self {
+        let file_path = cache_dir.join(format!(
+            "{}.msgpack",
+            sanitize_filename::sanitize(db_id.get())
+        ));
+        let previous_entries = match read_index_cache_file::<T>(&file_path) {
+            Ok(file) if file.format_version == format_version => file.entries,
+            Ok(_) => {
+                tracing::debug!("Discarding Pot index cache {file_path} because of format change");
+                Default::default()
+            }
+            Err(e) => {
+                if file_path.exists() {
+                    tracing::warn!("Couldn't read Pot index cache {file_path}: {e}");
+                }
+                Default::default()
+            }
+        };
+        Self {
+            file_path,
+            format_version,
+            previous_entries,
+            current_entries: Default::default(),
+        }
+    }
+
+    /// Returns the cached value for the given file if the file hasn't changed since it was
+    /// scanned, otherwise scans it using the given function.
+    ///
+    /// Only entries requested via this method during a refresh will be kept when saving. That
+    /// way, entries of deleted files disappear automatically.
+    pub fn get_or_scan(
+        &mut self,
+        relative_path: &str,
+        metadata: &Metadata,
+        scan: impl FnOnce() -> Option<T>,
+    ) -> Option<&T> {
+        let fingerprint = FileFingerprint::from_metadata(metadata);
+        let value = match self.previous_entries.remove(relative_path) {
+            Some(entry) if fingerprint.is_some() && entry.fingerprint == fingerprint => entry.value,
+            _ => scan()?,
+        };
+        let entry = IndexCacheEntry { fingerprint, value };
+        self.current_entries
+            .insert(relative_path.to_string(), entry);
+        self.current_entries.get(relative_path).map(|e| &e.value)
+    }
+
+    /// Writes all entries which have been requested since loading to disk.
+    pub fn save(self) -> Result<(), Box<dyn Error>> {
+        let file = IndexCacheFile {
+            format_version: self.format_version,
+            entries: self.current_entries,
+        };
+        let bytes = rmp_serde::to_vec(&file)?;
+        if let Some(parent) = self.file_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.file_path, bytes)?;
+        Ok(())
+    }
+}
+
+fn read_index_cache_file<T: DeserializeOwned>(
+    file_path: &Utf8Path,
+) -> Result<IndexCacheFile<T>, Box<dyn Error>> {
+    let bytes = fs::read(file_path)?;
+    let file = rmp_serde::from_slice(&bytes)?;
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn reuse_value_of_unchanged_file() {
+        // Given
+        let dir = tempfile::tempdir().unwrap();
+        let preset_path = dir.path().join("preset.txt");
+        fs::write(&preset_path, "foo").unwrap();
+        scan_and_save(dir.path(), &preset_path, 1, "first");
+        // When
+        let value = scan_and_save(dir.path(), &preset_path, 1, "second");
+        // Then
+        assert_eq!(value, "first");
+    }
+
+    #[test]
+    fn rescan_file_with_stale_fingerprint() {
+        // Given
+        let dir = tempfile::tempdir().unwrap();
+        let preset_path = dir.path().join("preset.txt");
+        fs::write(&preset_path, "foo").unwrap();
+        scan_and_save(dir.path(), &preset_path, 1, "first");
+        // When
+        fs::write(&preset_path, "foo bar").unwrap();
+        let value = scan_and_save(dir.path(), &preset_path, 1, "second");
+        // Then
+        assert_eq!(value, "second");
+    }
+
+    #[test]
+    fn discard_cache_with_different_format_version() {
+        // Given
+        let dir = tempfile::tempdir().unwrap();
+        let preset_path = dir.path().join("preset.txt");
+        fs::write(&preset_path, "foo").unwrap();
+        scan_and_save(dir.path(), &preset_path, 1, "first");
+        // When
+        let value = scan_and_save(dir.path(), &preset_path, 2, "second");
+        // Then
+        assert_eq!(value, "second");
+    }
+
+    #[test]
+    fn drop_entries_of_files_not_requested_anymore() {
+        // Given
+        let dir = tempfile::tempdir().unwrap();
+        let preset_path = dir.path().join("preset.txt");
+        fs::write(&preset_path, "foo").unwrap();
+        scan_and_save(dir.path(), &preset_path, 1, "first");
+        // When
+        load_cache(dir.path(), 1).save().unwrap();
+        let value = scan_and_save(dir.path(), &preset_path, 1, "second");
+        // Then
+        assert_eq!(value, "second");
+    }
+
+    /// Requests the given file from the cache, saves the cache and returns the value.
+    fn scan_and_save(
+        cache_dir: &Path,
+        preset_path: &Path,
+        format_version: u32,
+        scanned_value: &str,
+    ) -> String {
+        let mut cache = load_cache(cache_dir, format_version);
+        let metadata = fs::metadata(preset_path).unwrap();
+        let value = cache
+            .get_or_scan("preset.txt", &metadata, || Some(scanned_value.to_string()))
+            .unwrap()
+            .clone();
+        cache.save().unwrap();
+        value
+    }
+
+    fn load_cache(cache_dir: &Path, format_version: u32) -> IndexCache<String> {
+        let cache_dir = Utf8Path::from_path(cache_dir).unwrap();
+        let db_id = PersistentDatabaseId::new("test".to_string());
+        IndexCache::load(cache_dir, &db_id, format_version)
+    }
+}
@@ -51,6 +51,7 @@ pub use worker::*;
 mod escape_catcher;
 pub mod preset_crawler;
 pub mod preview_recorder;
+mod index_cache;
 use crate::preset_crawler::get_shim_file_path;
 use crate::preview_recorder::get_preview_file_path_from_hash;
 use base::hash_util::{NonCryptoHashSet, NonCryptoIndexSet, PersistentHash};
@@ -321,6 +322,16 @@ impl<'a> FilterInput<'a> {
         &self,
         plugin: Option<&PluginCore>,
         preset_id: InnerPresetId,
+    ) -> bool {
+        self.everything_matches_with_favorite_status(plugin, self.db_favorites.contains(&preset_id))
+    }
+
+    /// Like [`Self::everything_matches`] but for databases which know on their own whether a
+    /// preset is a favorite.
+    pub fn everything_matches_with_favorite_status(
+        &self,
+        plugin: Option<&PluginCore>,
+        is_favorite: bool,
     ) -> bool {
         let availability_matches = || {
             let fil = if plugin.is_some() {
@@ -350,7 +361,7 @@ impl<'a> FilterInput<'a> {
             let product_id = plugin.map(|p| p.product_id);
             !self.excludes.contains_product(product_id)
         };
-        let favorite_matches = || self.filters.favorite_matches(self.db_favorites, preset_id);
+        let favorite_matches = || self.filters.favorite_status_matches(is_favorite);
         // Combine
         availability_matches()
             && support_matches()
@@ -399,12 +410,20 @@ pub enum SearchField {
     ProductName,
     #[strum(serialize = "Extension")]
     FileExtension,
+    /// Names of tracks and plug-ins contained in the preset.
+    #[strum(serialize = "Contents")]
+    Contents,
 }
 
 pub trait SearchInput {
     fn preset_name(&self) -> &str;
     fn product_name(&self) -> Option<Cow<str>>;
     fn file_extension(&self) -> Option<&str>;
+
+    /// Additional searchable names of things contained in the preset, e.g. tracks or plug-ins.
+    fn contents(&self) -> &[String] {
+        &[]
+    }
 }
 
 impl SearchEvaluator {
@@ -456,6 +475,10 @@ impl SearchEvaluator {
                         false
                     }
                 }
+                SearchField::Contents => input
+                    .contents()
+                    .iter()
+                    .any(|text| self.matches_internal(text)),
             })
     }
 
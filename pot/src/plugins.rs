@@ -1,4 +1,5 @@
-use crate::{parse_vst2_magic_number, parse_vst3_uid, PluginId, ProductId};
+use crate::index_cache::IndexCache;
+use crate::{parse_vst2_magic_number, parse_vst3_uid, PersistentDatabaseId, PluginId, ProductId};
 use base::file_util;
 use base::hash_util::NonCryptoHashMap;
 use camino::Utf8Path;
@@ -71,8 +72,16 @@ impl ProductAccumulator {
     }
 }
 
+/// Increase whenever the cached JS description changes.
+const JS_INDEX_CACHE_FORMAT_VERSION: u32 = 1;
+
 impl PluginDatabase {
-    pub fn crawl(reaper_resource_dir: &Utf8Path) -> Self {
+    /// Crawls all plug-ins known to REAPER.
+    ///
+    /// The descriptions of JS plug-ins are cached in the given index cache directory because
+    /// reading them requires opening each JS file. The other plug-ins are read from REAPER's own
+    /// scan caches (INI files), so there's no need to cache them once more.
+    pub fn crawl(reaper_resource_dir: &Utf8Path, index_cache_dir: &Utf8Path) -> Self {
         let mut product_accumulator = ProductAccumulator::default();
         let mut detected_legacy_vst3_scan = false;
         let shared_library_plugins = crawl_shared_library_plugins(
@@ -81,7 +90,16 @@ impl PluginDatabase {
             &mut detected_legacy_vst3_scan,
         );
         let js_root_dir = reaper_resource_dir.join("Effects");
-        let js_plugins = crawl_js_plugins(&mut product_accumulator, &js_root_dir);
+        let mut js_index_cache = IndexCache::load(
+            index_cache_dir,
+            &PersistentDatabaseId::new("js-plugins".to_string()),
+            JS_INDEX_CACHE_FORMAT_VERSION,
+        );
+        let js_plugins =
+            crawl_js_plugins(&mut product_accumulator, &js_root_dir, &mut js_index_cache);
+        if let Err(e) = js_index_cache.save() {
+            tracing::warn!("Couldn't save index cache of JS plug-ins: {e}");
+        }
         let plugin_map = shared_library_plugins
             .into_iter()
             .chain(js_plugins)
@@ -118,10 +136,7 @@ impl PluginDatabase {
     }
 
     pub fn detect_plugin_from_rxml_line(&self, line: &str) -> Option<&Plugin> {
-        let is_fx_line = ["<VST ", "<CLAP ", "<JS "]
-            .into_iter()
-            .any(|suffix| line.starts_with(suffix));
-        if !is_fx_line {
+        if !is_plugin_rxml_line(line) {
             return None;
         }
         let plugin_id = PluginId::parse_from_rxml_line(line).ok()?;
@@ -129,6 +144,13 @@ impl PluginDatabase {
     }
 }
 
+/// Returns whether the given (trimmed) line of a REAPER-XML-like file declares a plug-in.
+pub fn is_plugin_rxml_line(line: &str) -> bool {
+    ["<VST ", "<CLAP ", "<JS "]
+        .into_iter()
+        .any(|prefix| line.starts_with(prefix))
+}
+
 /// A product - an abstraction over related plug-ins.
 ///
 /// Example: The product "Zebra2 (u-he)". This ignores architecture and plug-in framework.
@@ -264,6 +286,7 @@ impl ProductKind {
 fn crawl_js_plugins(
     product_accumulator: &mut ProductAccumulator,
     js_root_dir: &Utf8Path,
+    index_cache: &mut IndexCache<Option<String>>,
 ) -> Vec<Plugin> {
     WalkDir::new(js_root_dir)
         .follow_links(true)
@@ -277,7 +300,13 @@ fn crawl_js_plugins(
             let relative_path = entry.path().strip_prefix(js_root_dir).ok()?;
             let relative_path = relative_path.to_str()?;
             let product_kind = Some(ProductKind::Effect);
-            let js_desc = read_js_desc_from_file(entry.path())?;
+            let metadata = entry.metadata().ok()?;
+            // Files without description are cached as well, so we don't read them again
+            let js_desc = index_cache
+                .get_or_scan(relative_path, &metadata, || {
+                    Some(read_js_desc_from_file(entry.path()))
+                })?
+                .clone()?;
             let plugin = Plugin {
                 common: PluginCommon {
                     name: js_desc.clone(),
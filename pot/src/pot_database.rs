@@ -5,7 +5,7 @@ use crate::provider_database::{
     FIL_IS_USER_PRESET_FALSE, FIL_IS_USER_PRESET_TRUE, FIL_PRODUCT_KIND_EFFECT,
     FIL_PRODUCT_KIND_INSTRUMENT, FIL_PRODUCT_KIND_LOOP, FIL_PRODUCT_KIND_ONE_SHOT,
 };
-use crate::providers::directory::{DirectoryDatabase, DirectoryDbConfig};
+use crate::providers::directory::{DirectoryDatabase, DirectoryDbConfig, DirectoryMetadataValues};
 use crate::providers::komplete::KompleteDatabase;
 use crate::{
     preview_exists, BuildInput, Fil, FilterItem, FilterItemCollections, FilterItemId, Filters,
@@ -17,6 +17,7 @@ use base::{blocking_read_lock, blocking_write_lock};
 use crate::plugins::PluginDatabase;
 use crate::providers::defaults::DefaultsDatabase;
 use crate::providers::ini::IniDatabase;
+use crate::providers::track_templates::parse_track_template;
 
 use enumset::{enum_set, EnumSet};
 use helgobox_api::persistence::PotFilterKind;
@@ -27,8 +28,9 @@ use std::fmt::Debug;
 use std::ops::Deref;
 
 use base::hash_util::NonCryptoIndexSet;
+use camino::Utf8PathBuf;
 use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
-use std::sync::{RwLock, RwLockReadGuard};
+use std::sync::{Arc, RwLock, RwLockReadGuard};
 use std::time::{Duration, Instant};
 
 pub fn pot_db() -> &'static PotDatabase {
@@ -60,6 +62,7 @@ type DatabaseOpeningResult = Result<BoxedDatabase, PotDatabaseError>;
 // be read-only. The synchronous refresh would still need mutable access but we wouldn't be able to
 // get one directly within an Arc.
 pub struct PotDatabase {
+    index_cache_dir: Utf8PathBuf,
     plugin_db: RwLock<PluginDatabase>,
     databases: RwLock<Databases>,
     revision: AtomicU8,
@@ -84,6 +87,7 @@ impl PotDatabase {
     pub fn open() -> Self {
         let resource_path = Reaper::get().resource_path();
         let komplete_db = KompleteDatabase::open();
+        let directory_metadata_values = Arc::new(DirectoryMetadataValues::default());
         let rfx_chain_db = {
             let config = DirectoryDbConfig {
                 persistent_id: PersistentDatabaseId::new("fx-chains".to_string()),
@@ -91,6 +95,8 @@ impl PotDatabase {
                 valid_extensions: &["RfxChain"],
                 name: "FX chains",
                 description: "All the RfxChain files in your FXChains directory",
+                content_parser: None,
+                metadata_values: directory_metadata_values.clone(),
             };
             DirectoryDatabase::open(config)
         };
@@ -102,6 +108,8 @@ impl PotDatabase {
                 name: "Track templates",
                 description: "All the RTrackTemplate files in your TrackTemplates directory.\n\
                 Doesn't load the complete track, only its FX chain!",
+                content_parser: Some(parse_track_template),
+                metadata_values: directory_metadata_values,
             };
             DirectoryDatabase::open(config)
         };
@@ -124,6 +132,7 @@ impl PotDatabase {
             .map(|(i, db)| (DatabaseId(i as _), RwLock::new(db)))
             .collect();
         Self {
+            index_cache_dir: resource_path.join("Helgoboss/Pot/index-cache"),
             plugin_db: Default::default(),
             databases: RwLock::new(databases),
             revision: Default::default(),
@@ -140,12 +149,12 @@ impl PotDatabase {
         // Build provider context
         let resource_path = Reaper::get().resource_path();
         // Crawl plug-ins
-        let plugin_db = PluginDatabase::crawl(&resource_path);
+        let plugin_db = PluginDatabase::crawl(&resource_path, &self.index_cache_dir);
         // In order to be able to query the legacy-vst3-scan result without having to lock the
         // plug-in DB (which could lead to unresponsive UI), we save it as atomic bool right here.
         self.detected_legacy_vst3_scan
             .store(plugin_db.detected_legacy_vst3_scan(), Ordering::Relaxed);
-        let provider_context = ProviderContext::new(&plugin_db);
+        let provider_context = ProviderContext::new(&plugin_db, &self.index_cache_dir);
         // Refresh databases
         for db in self.read_lock_databases().values() {
             let mut db = blocking_write_lock(db, "pot db refresh provider db");
@@ -185,7 +194,7 @@ impl PotDatabase {
         // TODO-high-pot Implement correctly as soon as favorites writable
         let favorites = PotFavorites::default();
         let plugin_db = self.read_lock_plugin_db();
-        let provider_context = ProviderContext::new(&plugin_db);
+        let provider_context = ProviderContext::new(&plugin_db, &self.index_cache_dir);
         // Build constant filter collections
         let mut total_output = BuildOutput {
             supported_filter_kinds: enum_set!(
@@ -201,6 +210,7 @@ impl PotDatabase {
             // Let all databases build filter collections and accumulate them
             let mut database_filter_items = Vec::new();
             let mut used_product_ids = HashSet::new();
+            let mut used_shared_filter_item_ids = HashSet::new();
             for (db_id, db) in self.read_lock_databases().deref() {
                 // If the database is on the exclude list, we don't even want it to appear in the
                 // database list.
@@ -233,11 +243,15 @@ impl PotDatabase {
                 else {
                     continue;
                 };
-                // Add unique filter items directly to the list of filters. Gather shared filter
-                // items so we can deduplicate them later.
+                // Add unique filter items directly to the list of filters. Add shared filter
+                // items only if no other database added them already. Gather products so we can
+                // deduplicate them later.
                 for (kind, items) in filter_collections.into_iter() {
                     let final_filter_items = items.into_iter().filter_map(|i| match i {
                         InnerFilterItem::Unique(i) => Some(i),
+                        InnerFilterItem::Shared(i) => used_shared_filter_item_ids
+                            .insert((kind, i.id))
+                            .then_some(i),
                         InnerFilterItem::Product(pid) => {
                             used_product_ids.insert(pid);
                             None
@@ -334,7 +348,7 @@ impl PotDatabase {
         // TODO-high-pot Implement correctly as soon as favorites writable
         let favorites = PotFavorites::default();
         let plugin_db = self.read_lock_plugin_db();
-        let provider_context = ProviderContext::new(&plugin_db);
+        let provider_context = ProviderContext::new(&plugin_db, &self.index_cache_dir);
         self.gather_preset_ids_internal(&input, &provider_context, &favorites)
             .into_iter()
             .filter_map(|(db_id, sortable_preset_id)| {
@@ -382,7 +396,7 @@ impl PotDatabase {
 
     pub fn find_preset_by_id(&self, preset_id: PresetId) -> Option<PotPreset> {
         let plugin_db = self.read_lock_plugin_db();
-        let provider_context = ProviderContext::new(&plugin_db);
+        let provider_context = ProviderContext::new(&plugin_db, &self.index_cache_dir);
         let databases = self.read_lock_databases();
         let db = databases.get(&preset_id.database_id)?;
         let db = blocking_read_lock(db, "pot db find_preset_by_id 1");
@@ -415,7 +429,7 @@ impl PotDatabase {
             .plugin_db
             .try_read()
             .map_err(|_| "couldn't acquire plugin db lock")?;
-        let provider_context = ProviderContext::new(&plugin_db);
+        let provider_context = ProviderContext::new(&plugin_db, &self.index_cache_dir);
         let databases = self.read_lock_databases();
         let db = databases
             .get(&preset_id.database_id)
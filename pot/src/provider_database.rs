@@ -3,6 +3,7 @@ use crate::{
     Fil, FilterItem, FilterItemId, GenericFilterItemCollections, HasFilterItemId, InnerBuildInput,
     InnerPresetId, PersistentDatabaseId, PotPreset, ProductId,
 };
+use camino::Utf8Path;
 use enumset::{enum_set, EnumSet};
 use helgobox_api::persistence::PotFilterKind;
 use std::borrow::Cow;
@@ -68,6 +69,10 @@ pub enum InnerFilterItem {
     /// A unique final filter item. Only makes sense within a specific database and within the
     /// context of a specific pot filter item kind. Not deduplicated.
     Unique(FilterItem),
+    /// A final filter item whose ID has the same meaning in all databases which emit it.
+    ///
+    /// Will be deduplicated by the pot database!
+    Shared(FilterItem),
     /// A filter item representing a particular product (product for which the preset is made).
     ///
     /// Will be deduplicated by the pot database!
@@ -77,7 +82,7 @@ pub enum InnerFilterItem {
 impl HasFilterItemId for InnerFilterItem {
     fn id(&self) -> FilterItemId {
         match self {
-            InnerFilterItem::Unique(i) => i.id,
+            InnerFilterItem::Unique(i) | InnerFilterItem::Shared(i) => i.id,
             InnerFilterItem::Product(i) => FilterItemId(Some(Fil::Product(*i))),
         }
     }
@@ -100,11 +105,16 @@ impl SortablePresetId {
 #[derive(Copy, Clone)]
 pub struct ProviderContext<'a> {
     pub plugin_db: &'a PluginDatabase,
+    /// Directory in which databases can persist their index caches.
+    pub index_cache_dir: &'a Utf8Path,
 }
 
 impl<'a> ProviderContext<'a> {
-    pub fn new(plugin_db: &'a PluginDatabase) -> Self {
-        Self { plugin_db }
+    pub fn new(plugin_db: &'a PluginDatabase, index_cache_dir: &'a Utf8Path) -> Self {
+        Self {
+            plugin_db,
+            index_cache_dir,
+        }
     }
 }
 
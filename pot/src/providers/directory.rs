@@ -2,26 +2,32 @@ use crate::provider_database::{
     Database, InnerFilterItem, InnerFilterItemCollections, ProviderContext, SortablePresetId,
 };
 use crate::{
-    FiledBasedPotPresetKind, FilterInput, InnerBuildInput, InnerPresetId, PersistentDatabaseId,
-    PersistentInnerPresetId, PersistentPresetId, PipeEscaped, PluginId, PotPreset, PotPresetCommon,
-    PotPresetKind, SearchInput,
+    Fil, FiledBasedPotPresetKind, FilterInput, FilterItem, FilterItemId, InnerBuildInput,
+    InnerPresetId, PersistentDatabaseId, PersistentInnerPresetId, PersistentPresetId, PipeEscaped,
+    PluginId, PotPreset, PotPresetCommon, PotPresetKind, PotPresetMetaData, SearchInput,
 };
 use std::borrow::Cow;
 
-use crate::plugins::{PluginCore, PluginDatabase};
-use base::hash_util::{NonCryptoHashSet, NonCryptoIndexMap, PersistentHash, PersistentHasher};
+use crate::index_cache::IndexCache;
+use crate::plugins::{is_plugin_rxml_line, PluginCore, PluginDatabase};
+use base::hash_util::{
+    NonCryptoHashSet, NonCryptoIndexMap, NonCryptoIndexSet, PersistentHash, PersistentHasher,
+};
+use base::{blocking_read_lock, blocking_write_lock};
 use camino::Utf8PathBuf;
 use either::Either;
 use enumset::{enum_set, EnumSet};
 use helgobox_api::persistence::PotFilterKind;
 use itertools::Itertools;
 use std::error::Error;
-use std::ffi::OsStr;
+use std::ffi::{OsStr, OsString};
+use std::fs;
 use std::fs::File;
 use std::hash::Hasher;
 use std::io::{BufRead, BufReader};
 use std::iter;
 use std::path::Path;
+use std::sync::{Arc, RwLock};
 use walkdir::WalkDir;
 
 pub struct DirectoryDatabase {
@@ -30,7 +36,9 @@ pub struct DirectoryDatabase {
     valid_extensions: NonCryptoHashSet<&'static OsStr>,
     name: &'static str,
     description: &'static str,
+    content_parser: Option<ContentParser>,
     entries: Vec<PresetEntry>,
+    metadata_values: Arc<DirectoryMetadataValues>,
 }
 
 pub struct DirectoryDbConfig {
@@ -39,8 +47,26 @@ pub struct DirectoryDbConfig {
     pub valid_extensions: &'static [&'static str],
     pub name: &'static str,
     pub description: &'static str,
+    /// Makes additional names contained in the preset files searchable.
+    pub content_parser: Option<ContentParser>,
+    /// Should be the same for all directory databases.
+    pub metadata_values: Arc<DirectoryMetadataValues>,
 }
 
+/// Distinct metadata values found in the sidecar files of all directory databases. The index
+/// serves as filter value.
+///
+/// Shared by all directory databases so that the same value (e.g. category "Synth") has the same
+/// filter value in each of them. Values are only added, never removed, because another database
+/// might still refer to them.
+#[derive(Default)]
+pub struct DirectoryMetadataValues(RwLock<NonCryptoIndexSet<MetadataValue>>);
+
+/// Extracts names of things contained in a preset file (e.g. track names) from its text.
+///
+/// The names of the used plug-ins are searchable anyway, so they don't need to be extracted.
+pub type ContentParser = fn(&str) -> Vec<String>;
+
 impl DirectoryDatabase {
     pub fn open(config: DirectoryDbConfig) -> Result<Self, Box<dyn Error>> {
         if !config.root_dir.try_exists()? {
@@ -50,9 +76,11 @@ impl DirectoryDatabase {
             persistent_id: config.persistent_id,
             name: config.name,
             entries: Default::default(),
+            metadata_values: config.metadata_values,
             root_dir: config.root_dir,
             valid_extensions: config.valid_extensions.iter().map(OsStr::new).collect(),
             description: config.description,
+            content_parser: config.content_parser,
         };
         Ok(db)
     }
@@ -67,21 +95,138 @@ impl DirectoryDatabase {
         }
         let iter = self.entries.iter().enumerate().filter(|(id, e)| {
             let id = InnerPresetId(*id as _);
-            e.plugin_cores
-                .values()
-                .any(|core| filter_input.everything_matches(Some(core), id))
+            let is_favorite = e.metadata.favorite || filter_input.db_favorites.contains(&id);
+            e.plugin_cores.values().any(|core| {
+                filter_input.everything_matches_with_favorite_status(Some(core), is_favorite)
+            }) && METADATA_FILTER_KINDS.iter().all(|kind| {
+                filter_input
+                    .filters
+                    .matches_optional(kind, e.metadata.filter_value(kind))
+            })
         });
         Either::Right(iter)
     }
+
+    fn build_metadata_filter_item(&self, fil: Fil) -> Option<FilterItem> {
+        let Fil::DirectoryMetadata(index) = fil else {
+            return None;
+        };
+        let metadata_values =
+            blocking_read_lock(&self.metadata_values.0, "directory db metadata values");
+        let value = metadata_values.get_index(index as usize)?;
+        let item = FilterItem {
+            persistent_id: "".to_string(),
+            id: FilterItemId(Some(fil)),
+            parent_name: value.parent_name.clone(),
+            name: value.name.clone(),
+            icon: None,
+            more_info: None,
+        };
+        Some(item)
+    }
 }
 
 struct PresetEntry {
     preset_name: String,
     relative_path: String,
     plugin_cores: NonCryptoIndexMap<PluginId, PluginCore>,
+    /// Names of the contained plug-ins and of whatever the content parser extracted.
+    contents: Vec<String>,
     content_hash: PersistentHash,
+    metadata: PresetMetadata,
 }
 
+/// Preset metadata taken from the optional sidecar file.
+#[derive(Default)]
+struct PresetMetadata {
+    /// Index into the metadata values shared by all directory databases.
+    sub_bank: Option<u32>,
+    category: Option<u32>,
+    sub_category: Option<u32>,
+    mode: Option<u32>,
+    favorite: bool,
+    author: Option<String>,
+    vendor: Option<String>,
+    comment: Option<String>,
+}
+
+impl PresetMetadata {
+    fn filter_value(&self, kind: PotFilterKind) -> Option<Fil> {
+        let value_index = match kind {
+            PotFilterKind::SubBank => self.sub_bank,
+            PotFilterKind::Category => self.category,
+            PotFilterKind::SubCategory => self.sub_category,
+            PotFilterKind::Mode => self.mode,
+            _ => None,
+        };
+        value_index.map(Fil::DirectoryMetadata)
+    }
+}
+
+/// Filter kinds which are fed by sidecar metadata.
+///
+/// "Bank" is not among them because in Pot, it represents the product, which is derived from the
+/// plug-ins used in the preset file. That keeps the bank filter consistent across all databases
+/// and lets the bank filter narrow down the sub banks. The bank given in the sidecar file is only
+/// used as parent of the sub bank (which needs a bank to be meaningful).
+const METADATA_FILTER_KINDS: EnumSet<PotFilterKind> = enum_set!(
+    PotFilterKind::SubBank
+        | PotFilterKind::Category
+        | PotFilterKind::SubCategory
+        | PotFilterKind::Mode
+);
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+struct MetadataValue {
+    /// Only set for values of sub filters (name of the bank or category).
+    parent_name: Option<String>,
+    name: Option<String>,
+}
+
+/// Content of the optional sidecar file next to a preset file (format documented in the Pot Browser
+/// section of the Helgobox docs).
+///
+/// For the preset file `Lead.RfxChain`, the sidecar file is `Lead.RfxChain.json`,
+/// `Lead.RfxChain.yaml` or `Lead.RfxChain.yml`.
+#[derive(Default, serde::Deserialize)]
+#[serde(default)]
+struct Sidecar {
+    bank: Option<String>,
+    sub_bank: Option<String>,
+    category: Option<String>,
+    sub_category: Option<String>,
+    mode: Option<String>,
+    /// Alternative name for `mode`.
+    character: Option<String>,
+    favorite: bool,
+    author: Option<String>,
+    vendor: Option<String>,
+    comment: Option<String>,
+}
+
+impl Sidecar {
+    /// Returns descriptions of values which are ignored because they contradict each other or
+    /// lack context.
+    fn problems(&self) -> Vec<&'static str> {
+        let mut problems = vec![];
+        if self.mode.is_some() && self.character.is_some() {
+            problems.push("both \"mode\" and \"character\" given, ignoring \"character\"");
+        }
+        if self.sub_bank.is_some() && self.bank.is_none() {
+            problems.push("\"sub_bank\" given without \"bank\", ignoring it");
+        }
+        if self.sub_category.is_some() && self.category.is_none() {
+            problems.push("\"sub_category\" given without \"category\", ignoring it");
+        }
+        problems
+    }
+}
+
+const SIDECAR_EXTENSIONS: [&str; 3] = ["json", "yaml", "yml"];
+
+/// Increase whenever [`FileProcessingOutput`] changes.
+const INDEX_CACHE_FORMAT_VERSION: u32 = 2;
+
 impl Database for DirectoryDatabase {
     fn persistent_id(&self) -> &PersistentDatabaseId {
         &self.persistent_id
@@ -96,10 +241,15 @@ impl Database for DirectoryDatabase {
     }
 
     fn supported_advanced_filter_kinds(&self) -> EnumSet<PotFilterKind> {
-        enum_set!(PotFilterKind::Bank)
+        METADATA_FILTER_KINDS | PotFilterKind::Bank
     }
 
     fn refresh(&mut self, ctx: &ProviderContext) -> Result<(), Box<dyn Error>> {
+        let mut index_cache = IndexCache::load(
+            ctx.index_cache_dir,
+            &self.persistent_id,
+            INDEX_CACHE_FORMAT_VERSION,
+        );
         self.entries = WalkDir::new(&self.root_dir)
             .follow_links(true)
             .into_iter()
@@ -115,16 +265,49 @@ impl Database for DirectoryDatabase {
                 let relative_path = entry.path().strip_prefix(&self.root_dir).ok()?;
                 // Immediately exclude relative paths that can't be represented as valid UTF-8.
                 // Otherwise we will potentially open a can of worms (regarding persistence etc.).
-                let processing_output = process_file(entry.path(), ctx.plugin_db).ok()?;
+                let relative_path = relative_path.to_str()?.to_string();
+                let metadata = entry.metadata().ok()?;
+                let processing_output =
+                    index_cache.get_or_scan(&relative_path, &metadata, || {
+                        process_file(entry.path(), self.content_parser).ok()
+                    })?;
+                // Sidecar files are small and can change independently of the preset file, so
+                // we don't cache them.
+                let sidecar = read_sidecar(entry.path()).unwrap_or_default();
+                let plugin_cores = processing_output.used_plugins(ctx.plugin_db);
+                let plugin_names = plugin_cores.values().filter_map(|core| {
+                    let plugin = ctx.plugin_db.find_plugin_by_id(&core.id)?;
+                    Some(plugin.common.to_string())
+                });
+                let contents = processing_output
+                    .content_names
+                    .iter()
+                    .cloned()
+                    .chain(plugin_names)
+                    .collect();
                 let preset_entry = PresetEntry {
                     preset_name: entry.path().file_stem()?.to_str()?.to_string(),
-                    relative_path: relative_path.to_str()?.to_string(),
-                    plugin_cores: processing_output.used_plugins,
+                    plugin_cores,
+                    contents,
                     content_hash: processing_output.content_hash,
+                    relative_path,
+                    metadata: build_preset_metadata(
+                        sidecar,
+                        &mut blocking_write_lock(
+                            &self.metadata_values.0,
+                            "directory db refresh metadata values",
+                        ),
+                    ),
                 };
                 Some(preset_entry)
             })
             .collect();
+        if let Err(e) = index_cache.save() {
+            tracing::warn!(
+                "Couldn't save index cache of Pot database {}: {e}",
+                self.name
+            );
+        }
         Ok(())
     }
 
@@ -132,18 +315,37 @@ impl Database for DirectoryDatabase {
         &self,
         _: &ProviderContext,
         input: InnerBuildInput,
-        _: EnumSet<PotFilterKind>,
+        affected_kinds: EnumSet<PotFilterKind>,
     ) -> Result<InnerFilterItemCollections, Box<dyn Error>> {
-        let mut new_filters = *input.filter_input.filters;
-        new_filters.clear_this_and_dependent_filters(PotFilterKind::Bank);
-        let product_items = self
-            .query_presets_internal(&input.filter_input.with_filters(&new_filters))
-            .flat_map(|(_, entry)| entry.plugin_cores.values().map(|core| core.product_id))
-            .unique()
-            .map(InnerFilterItem::Product)
-            .collect();
         let mut collections = InnerFilterItemCollections::empty();
-        collections.set(PotFilterKind::Bank, product_items);
+        if affected_kinds.contains(PotFilterKind::Bank) {
+            let mut new_filters = *input.filter_input.filters;
+            new_filters.clear_this_and_dependent_filters(PotFilterKind::Bank);
+            let product_items = self
+                .query_presets_internal(&input.filter_input.with_filters(&new_filters))
+                .flat_map(|(_, entry)| entry.plugin_cores.values().map(|core| core.product_id))
+                .unique()
+                .map(InnerFilterItem::Product)
+                .collect();
+            collections.set(PotFilterKind::Bank, product_items);
+        }
+        for kind in affected_kinds.intersection(METADATA_FILTER_KINDS) {
+            let mut new_filters = *input.filter_input.filters;
+            new_filters.clear_this_and_dependent_filters(kind);
+            let items = self
+                .query_presets_internal(&input.filter_input.with_filters(&new_filters))
+                .map(|(_, entry)| entry.metadata.filter_value(kind))
+                .unique()
+                .filter_map(|fil| {
+                    let item = match fil {
+                        None => FilterItem::none(),
+                        Some(fil) => self.build_metadata_filter_item(fil)?,
+                    };
+                    Some(InnerFilterItem::Shared(item))
+                })
+                .collect();
+            collections.set(kind, items);
+        }
         Ok(collections)
     }
 
@@ -193,7 +395,12 @@ impl Database for DirectoryDatabase {
                 db_specific_preview_file: None,
                 is_supported: true,
                 is_available: !preset_entry.plugin_cores.is_empty(),
-                metadata: Default::default(),
+                metadata: PotPresetMetaData {
+                    author: preset_entry.metadata.author.clone(),
+                    vendor: preset_entry.metadata.vendor.clone(),
+                    comment: preset_entry.metadata.comment.clone(),
+                    ..Default::default()
+                },
             },
             kind: PotPresetKind::FileBased(FiledBasedPotPresetKind {
                 file_ext: get_file_extension(&preset_entry.relative_path).to_string(),
@@ -204,12 +411,32 @@ impl Database for DirectoryDatabase {
     }
 }
 
+/// Result of scanning a preset file. Stored in the index cache.
+#[derive(serde::Serialize, serde::Deserialize)]
 struct FileProcessingOutput {
     content_hash: PersistentHash,
-    used_plugins: NonCryptoIndexMap<PluginId, PluginCore>,
+    /// Lines which declare a plug-in. They are resolved against the plug-in database on each
+    /// refresh (and not when scanning) because the installed plug-ins might change independently
+    /// of the preset file.
+    plugin_lines: Vec<String>,
+    /// Names extracted by the content parser.
+    content_names: Vec<String>,
+}
+
+impl FileProcessingOutput {
+    fn used_plugins(&self, plugin_db: &PluginDatabase) -> NonCryptoIndexMap<PluginId, PluginCore> {
+        self.plugin_lines
+            .iter()
+            .filter_map(|line| {
+                let plugin = plugin_db.detect_plugin_from_rxml_line(line)?;
+                Some((plugin.common.core.id, plugin.common.core))
+            })
+            .collect()
+    }
 }
 
-/// Finds used plug-ins in a REAPER-XML-like text file (e.g. RPP, RfxChain, RTrackTemplate).
+/// Finds lines declaring plug-ins in a REAPER-XML-like text file (e.g. RPP, RfxChain,
+/// RTrackTemplate) and passes the text to the content parser, if any.
 ///
 /// Examples entries:
 ///
@@ -220,10 +447,12 @@ struct FileProcessingOutput {
 /// ```
 fn process_file(
     path: &Path,
-    plugin_db: &PluginDatabase,
+    content_parser: Option<ContentParser>,
 ) -> Result<FileProcessingOutput, Box<dyn Error>> {
     let file = File::open(path)?;
-    let mut used_plugins = NonCryptoIndexMap::default();
+    let mut plugin_lines = vec![];
+    // Only collected if there's a content parser
+    let mut text = content_parser.map(|_| String::new());
     let mut buffer = String::new();
     let mut reader = BufReader::new(&file);
     let mut hasher = PersistentHasher::new();
@@ -233,19 +462,91 @@ fn process_file(
             break;
         }
         hasher.write(buffer.as_bytes());
+        if let Some(text) = &mut text {
+            text.push_str(&buffer);
+        }
         let line = buffer.trim();
-        if let Some(plugin) = plugin_db.detect_plugin_from_rxml_line(line) {
-            used_plugins.insert(plugin.common.core.id, plugin.common.core);
+        if is_plugin_rxml_line(line) {
+            plugin_lines.push(line.to_string());
         }
         buffer.clear();
     }
+    let content_names = match (content_parser, text) {
+        (Some(parse), Some(text)) => parse(&text),
+        _ => vec![],
+    };
     let output = FileProcessingOutput {
         content_hash: hasher.digest_128(),
-        used_plugins,
+        plugin_lines,
+        content_names,
     };
     Ok(output)
 }
 
+fn read_sidecar(preset_path: &Path) -> Option<Sidecar> {
+    SIDECAR_EXTENSIONS.iter().find_map(|ext| {
+        let mut sidecar_path = OsString::from(preset_path);
+        sidecar_path.push(".");
+        sidecar_path.push(ext);
+        let content = fs::read_to_string(&sidecar_path).ok()?;
+        match parse_sidecar(&content, ext) {
+            Ok(sidecar) => {
+                for problem in sidecar.problems() {
+                    tracing::warn!("Preset sidecar file {sidecar_path:?}: {problem}");
+                }
+                Some(sidecar)
+            }
+            Err(e) => {
+                tracing::warn!("Couldn't read preset sidecar file {sidecar_path:?}: {e}");
+                None
+            }
+        }
+    })
+}
+
+fn parse_sidecar(content: &str, extension: &str) -> Result<Sidecar, Box<dyn Error>> {
+    let sidecar = if extension == "json" {
+        serde_json::from_str(content)?
+    } else {
+        serde_yaml::from_str(content)?
+    };
+    Ok(sidecar)
+}
+
+fn build_preset_metadata(
+    sidecar: Sidecar,
+    metadata_values: &mut NonCryptoIndexSet<MetadataValue>,
+) -> PresetMetadata {
+    let mut intern = |parent_name: Option<String>, name: Option<String>| {
+        let value = MetadataValue { parent_name, name };
+        metadata_values.insert_full(value).0 as u32
+    };
+    let sub_bank = sidecar
+        .bank
+        .map(|bank| intern(Some(bank), sidecar.sub_bank));
+    let category = sidecar
+        .category
+        .clone()
+        .map(|category| intern(None, Some(category)));
+    let sub_category = sidecar
+        .category
+        .map(|category| intern(Some(category), sidecar.sub_category));
+    let mode = sidecar
+        .mode
+        .or(sidecar.character)
+        .map(|mode| intern(None, Some(mode)));
+    PresetMetadata {
+        sub_bank,
+        category,
+        sub_category,
+        mode,
+        favorite: sidecar.favorite,
+        author: sidecar.author,
+        vendor: sidecar.vendor,
+        comment: sidecar.comment,
+    }
+}
+
 /// Example: `Synths/Lead.RTrackTemplate`
 fn create_persistent_inner_id(preset_entry: &PresetEntry) -> PersistentInnerPresetId {
     let escaped_path = PipeEscaped(preset_entry.relative_path.as_str());
@@ -270,6 +571,10 @@ impl SearchInput for DirectorySearchInput<'_> {
     fn file_extension(&self) -> Option<&str> {
         Some(get_file_extension(&self.preset_entry.relative_path))
     }
+
+    fn contents(&self) -> &[String] {
+        &self.preset_entry.contents
+    }
 }
 
 fn build_product_name(ctx: &ProviderContext, preset_entry: &PresetEntry) -> Option<String> {
@@ -291,3 +596,168 @@ fn get_file_extension(relative_path: &str) -> &str {
         .to_str()
         .unwrap()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::track_templates::parse_track_template;
+
+    #[test]
+    fn parse_json_sidecar() {
+        // Given
+        let content = r#"{
+            "bank": "Factory",
+            "sub_bank": "Leads",
+            "category": "Synth",
+            "character": "Bright",
+            "favorite": true,
+            "author": "Jane"
+        }"#;
+        // When
+        let sidecar = parse_sidecar(content, "json").unwrap();
+        let mut metadata_values = NonCryptoIndexSet::default();
+        let metadata = build_preset_metadata(sidecar, &mut metadata_values);
+        // Then
+        let value = |index: Option<u32>| metadata_values.get_index(index.unwrap() as usize);
+        assert_eq!(
+            value(metadata.sub_bank),
+            Some(&MetadataValue {
+                parent_name: Some("Factory".to_string()),
+                name: Some("Leads".to_string())
+            })
+        );
+        assert_eq!(
+            value(metadata.mode),
+            Some(&MetadataValue {
+                parent_name: None,
+                name: Some("Bright".to_string())
+            })
+        );
+        assert!(metadata.favorite);
+        assert_eq!(metadata.author.as_deref(), Some("Jane"));
+    }
+
+    #[test]
+    fn parse_yaml_sidecar_with_mode_and_character() {
+        // Given
+        let content = "mode: Dark\ncharacter: Bright\n";
+        // When
+        let sidecar = parse_sidecar(content, "yaml").unwrap();
+        // Then
+        assert_eq!(sidecar.problems().len(), 1);
+        let mut metadata_values = NonCryptoIndexSet::default();
+        let metadata = build_preset_metadata(sidecar, &mut metadata_values);
+        let mode = metadata_values.get_index(metadata.mode.unwrap() as usize);
+        assert_eq!(mode.unwrap().name.as_deref(), Some("Dark"));
+    }
+
+    #[test]
+    fn parse_sidecar_with_sub_bank_without_bank() {
+        // Given
+        let content = r#"{ "sub_bank": "Leads", "sub_category": "Mono" }"#;
+        // When
+        let sidecar = parse_sidecar(content, "json").unwrap();
+        // Then
+        assert_eq!(sidecar.problems().len(), 2);
+        let metadata = build_preset_metadata(sidecar, &mut NonCryptoIndexSet::default());
+        assert_eq!(metadata.sub_bank, None);
+        assert_eq!(metadata.sub_category, None);
+    }
+
+    #[test]
+    fn reject_invalid_sidecar() {
+        assert!(parse_sidecar(r#"{ "favorite": "yes" }"#, "json").is_err());
+    }
+
+    #[test]
+    fn share_metadata_values_between_databases() {
+        // Given
+        let temp_dir = tempfile::tempdir().unwrap();
+        let index_cache_dir = Utf8PathBuf::from_path_buf(temp_dir.path().into()).unwrap();
+        let plugin_db = PluginDatabase::default();
+        let ctx = ProviderContext::new(&plugin_db, &index_cache_dir);
+        let metadata_values = Arc::new(DirectoryMetadataValues::default());
+        let (_fx_chains_dir, mut fx_chains_db) = create_test_db(
+            "fx-chains",
+            &["RfxChain"],
+            None,
+            &[
+                ("Lead", Some(r#"{ "category": "Synth" }"#)),
+                ("Pad", Some(r#"{ "category": "Pad" }"#)),
+            ],
+            metadata_values.clone(),
+        );
+        let (_track_templates_dir, mut track_templates_db) = create_test_db(
+            "track-templates",
+            &["RTrackTemplate"],
+            Some(parse_track_template),
+            &[
+                ("Bass", Some(r#"{ "category": "Synth" }"#)),
+                ("Drums", None),
+            ],
+            metadata_values,
+        );
+        // When
+        fx_chains_db.refresh(&ctx).unwrap();
+        track_templates_db.refresh(&ctx).unwrap();
+        // Then
+        let category = |db: &DirectoryDatabase, preset_name: &str| {
+            let entry = db
+                .entries
+                .iter()
+                .find(|e| e.preset_name == preset_name)
+                .unwrap();
+            entry.metadata.filter_value(PotFilterKind::Category)
+        };
+        let synth = category(&fx_chains_db, "Lead").unwrap();
+        assert_eq!(category(&track_templates_db, "Bass"), Some(synth));
+        assert_ne!(category(&fx_chains_db, "Pad"), Some(synth));
+        assert_eq!(category(&track_templates_db, "Drums"), None);
+        let synth_item = track_templates_db
+            .build_metadata_filter_item(synth)
+            .unwrap();
+        assert_eq!(synth_item.name.as_deref(), Some("Synth"));
+        let pad = category(&fx_chains_db, "Pad").unwrap();
+        let pad_item = track_templates_db.build_metadata_filter_item(pad).unwrap();
+        assert_eq!(pad_item.name.as_deref(), Some("Pad"));
+        // Sharing metadata values doesn't interfere with parsing the track templates
+        let bass = track_templates_db
+            .entries
+            .iter()
+            .find(|e| e.preset_name == "Bass")
+            .unwrap();
+        assert_eq!(bass.contents, vec!["Bass"]);
+    }
+
+    /// Creates a directory database whose root directory contains the given presets, each one
+    /// with an optional JSON sidecar file.
+    fn create_test_db(
+        persistent_id: &str,
+        valid_extensions: &'static [&'static str],
+        content_parser: Option<ContentParser>,
+        presets: &[(&str, Option<&str>)],
+        metadata_values: Arc<DirectoryMetadataValues>,
+    ) -> (tempfile::TempDir, DirectoryDatabase) {
+        let root_dir = tempfile::tempdir().unwrap();
+        for (name, sidecar) in presets {
+            let preset_path = root_dir
+                .path()
+                .join(format!("{name}.{}", valid_extensions[0]));
+            fs::write(&preset_path, format!("<TRACK\n  NAME {name}\n>\n")).unwrap();
+            if let Some(sidecar) = sidecar {
+                fs::write(format!("{}.json", preset_path.display()), sidecar).unwrap();
+            }
+        }
+        let config = DirectoryDbConfig {
+            persistent_id: PersistentDatabaseId::new(persistent_id.to_string()),
+            root_dir: Utf8PathBuf::from_path_buf(root_dir.path().into()).unwrap(),
+            valid_extensions,
+            name: "Test",
+            description: "",
+            content_parser,
+            metadata_values,
+        };
+        let db = DirectoryDatabase::open(config).unwrap();
+        (root_dir, db)
+    }
+}
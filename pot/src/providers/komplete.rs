@@ -240,7 +240,8 @@ impl KompleteDatabase {
             })
             .map(InnerFilterItem::Unique);
         if non_empty_filter.has_non_associated_presets {
-            iter::once(InnerFilterItem::Unique(FilterItem::none()))
+            // "<None>" means the same in all databases
+            iter::once(InnerFilterItem::Shared(FilterItem::none()))
                 .chain(existing_filter_items)
                 .collect()
         } else {
@@ -1136,6 +1137,10 @@ impl PresetDb {
                             conjunction += "i.file_ext LIKE ?";
                             sql.add_param(search_expression);
                         }
+                        SearchField::Contents => {
+                            // Komplete doesn't tell us about contents
+                            conjunction += "0";
+                        }
                     }
                 }
                 conjunction += ")";
@@ -3,3 +3,4 @@ pub mod directory;
 pub mod ini;
 pub mod komplete;
 pub mod projects;
+pub mod track_templates;
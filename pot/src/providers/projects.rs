@@ -8,7 +8,8 @@ use crate::{
 };
 use std::borrow::Cow;
 
-use crate::plugins::{PluginCore, PluginDatabase};
+use crate::index_cache::IndexCache;
+use crate::plugins::{is_plugin_rxml_line, PluginCore, PluginDatabase};
 use base::hash_util::{
     calculate_persistent_non_crypto_hash_one_shot, NonCryptoIndexMap, PersistentHash,
 };
@@ -110,6 +111,12 @@ impl Database for ProjectDatabase {
     }
 
     fn refresh(&mut self, ctx: &ProviderContext) -> Result<(), Box<dyn Error>> {
+        let mut index_cache = IndexCache::load(
+            ctx.index_cache_dir,
+            &self.persistent_id,
+            INDEX_CACHE_FORMAT_VERSION,
+        );
+        self.projects.clear();
         self.preset_entries = WalkDir::new(&self.root_dir)
             .follow_links(true)
             .into_iter()
@@ -130,12 +137,33 @@ impl Database for ProjectDatabase {
                     name: stem.to_str()?.to_string(),
                     relative_path_to_rpp: relative_path.to_str()?.to_string(),
                 };
+                let metadata = entry.metadata().ok()?;
+                let scanned_presets =
+                    index_cache.get_or_scan(&project.relative_path_to_rpp, &metadata, || {
+                        process_file(entry.path()).ok()
+                    })?;
+                let project_id = ProjectId(self.projects.len() as u32);
+                let preset_entries: Vec<_> = scanned_presets
+                    .iter()
+                    .filter_map(|p| {
+                        let preset_entry = PresetEntry {
+                            project_id,
+                            track_preset: p.resolve(ctx.plugin_db)?,
+                        };
+                        Some(preset_entry)
+                    })
+                    .collect();
                 self.projects.push(project);
-                let project_id = ProjectId(self.projects.len() as u32 - 1);
-                process_file(entry.path(), ctx.plugin_db, project_id).ok()
+                Some(preset_entries)
             })
             .flatten()
             .collect();
+        if let Err(e) = index_cache.save() {
+            tracing::warn!(
+                "Couldn't save index cache of Pot database {}: {e}",
+                self.name
+            );
+        }
         Ok(())
     }
 
@@ -256,13 +284,50 @@ struct TrackPreset {
     content_hash: PersistentHash,
 }
 
-fn process_file(
-    path: &Path,
-    plugin_db: &PluginDatabase,
-    project_id: ProjectId,
-) -> Result<Vec<PresetEntry>, Box<dyn Error>> {
+/// Track preset as found in the project file, before resolving the plug-ins.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct ScannedTrackPreset {
+    preset_name: String,
+    track_id: String,
+    fx_chain_range: Range<usize>,
+    /// Lines which declare a plug-in. They are resolved against the plug-in database on each
+    /// refresh (and not when scanning) because the installed plug-ins might change independently
+    /// of the project file.
+    plugin_lines: Vec<String>,
+    content_hash: PersistentHash,
+}
+
+impl ScannedTrackPreset {
+    /// Returns `None` if none of the plug-ins is known.
+    fn resolve(&self, plugin_db: &PluginDatabase) -> Option<TrackPreset> {
+        let used_plugins: NonCryptoIndexMap<_, _> = self
+            .plugin_lines
+            .iter()
+            .filter_map(|line| {
+                let plugin = plugin_db.detect_plugin_from_rxml_line(line)?;
+                Some((plugin.common.core.id, plugin.common.core))
+            })
+            .collect();
+        if used_plugins.is_empty() {
+            return None;
+        }
+        let track_preset = TrackPreset {
+            preset_name: self.preset_name.clone(),
+            track_id: self.track_id.clone(),
+            fx_chain_range: self.fx_chain_range.clone(),
+            used_plugins,
+            content_hash: self.content_hash,
+        };
+        Some(track_preset)
+    }
+}
+
+/// Increase whenever [`ScannedTrackPreset`] changes.
+const INDEX_CACHE_FORMAT_VERSION: u32 = 1;
+
+fn process_file(path: &Path) -> Result<Vec<ScannedTrackPreset>, Box<dyn Error>> {
     let rppxml = fs::read_to_string(path)?;
-    Ok(extract_presets(&rppxml, plugin_db, project_id))
+    Ok(extract_presets(&rppxml))
 }
 
 /// Example: `maojiao/2023-02-03-ben/2023-02-03-ben.RPP|0FF9F738-7CF6-8A49-9AEA-A9AF26DF9C46`
@@ -275,11 +340,7 @@ fn create_persistent_inner_id(
     PersistentInnerPresetId::new(id)
 }
 
-fn extract_presets(
-    rppxml: &str,
-    plugin_db: &PluginDatabase,
-    project_id: ProjectId,
-) -> Vec<PresetEntry> {
+fn extract_presets(rppxml: &str) -> Vec<ScannedTrackPreset> {
     use rppxml_parser::*;
     let parser = OneShotParser::new(rppxml);
     #[derive(Debug, Default)]
@@ -288,7 +349,7 @@ fn extract_presets(
         name: Option<&'a str>,
         rfx_chain_start: Option<usize>,
         rfx_chain_end: Option<usize>,
-        used_plugins: NonCryptoIndexMap<PluginId, PluginCore>,
+        plugin_lines: Vec<String>,
     }
     impl<'a> P<'a> {
         pub fn new(track_id: &'a str) -> Self {
@@ -297,7 +358,7 @@ fn extract_presets(
                 name: None,
                 rfx_chain_start: None,
                 rfx_chain_end: None,
-                used_plugins: Default::default(),
+                plugin_lines: vec![],
             }
         }
     }
@@ -316,11 +377,9 @@ fn extract_presets(
                     }
                     ["REAPER_PROJECT", "TRACK", "FXCHAIN", _] => {
                         if let Some(p) = &mut preset {
-                            if let Some(plugin) =
-                                plugin_db.detect_plugin_from_rxml_line(line.trim())
-                            {
-                                p.used_plugins
-                                    .insert(plugin.common.core.id, plugin.common.core);
+                            let line = line.trim();
+                            if is_plugin_rxml_line(line) {
+                                p.plugin_lines.push(line.to_string());
                             }
                         }
                     }
@@ -370,7 +429,7 @@ fn extract_presets(
     presets
         .into_iter()
         .filter_map(|p| {
-            if p.used_plugins.is_empty() {
+            if p.plugin_lines.is_empty() {
                 return None;
             }
             let fx_chain_range = p.rfx_chain_start?..p.rfx_chain_end?;
@@ -379,18 +438,14 @@ fn extract_presets(
             let content_hash = calculate_persistent_non_crypto_hash_one_shot(
                 rppxml[fx_chain_range.clone()].as_bytes(),
             );
-            let track_preset = TrackPreset {
+            let scanned_preset = ScannedTrackPreset {
                 preset_name,
                 track_id,
                 fx_chain_range,
-                used_plugins: p.used_plugins,
+                plugin_lines: p.plugin_lines,
                 content_hash,
             };
-            let preset_entry = PresetEntry {
-                project_id,
-                track_preset,
-            };
-            Some(preset_entry)
+            Some(scanned_preset)
         })
         .collect()
 }
@@ -0,0 +1,55 @@
+//! Content parser which makes the contents of REAPER track templates searchable.
+//!
+//! Track templates are provided by a [`DirectoryDatabase`](super::directory::DirectoryDatabase).
+//! Like all directory databases, it shares the metadata values from sidecar files with the FX chain
+//! database, so their filter items don't collide.
+
+/// Collects the names of all tracks in the given track template.
+pub fn parse_track_template(rppxml: &str) -> Vec<String> {
+    use rppxml_parser::*;
+    let parser = OneShotParser::new(rppxml);
+    let mut stack: Vec<&str> = Vec::with_capacity(10);
+    let mut track_names = vec![];
+    for e in parser.events() {
+        match e.item {
+            Item::StartTag(el) => {
+                stack.push(el.name());
+            }
+            Item::EndTag => {
+                stack.pop();
+            }
+            Item::Attribute(el) => {
+                if *stack.as_slice() == ["TRACK"] && el.name() == "NAME" {
+                    let name = el.into_values().next().unwrap_or_default();
+                    if !name.is_empty() {
+                        track_names.push(name.to_string());
+                    }
+                }
+            }
+            Item::Content(_) => {}
+            Item::Empty => {}
+        }
+    }
+    track_names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_track_names() {
+        // Given
+        let rppxml = include_str!("test_data/Lead.RTrackTemplate");
+        // When
+        let track_names = parse_track_template(rppxml);
+        // Then
+        // The unnamed track and the names of FX and items are not included
+        assert_eq!(track_names, vec!["Lead", "Lead FX return"]);
+    }
+
+    #[test]
+    fn parse_invalid_track_template() {
+        assert!(parse_track_template("no track template").is_empty());
+    }
+}
@@ -0,0 +1,332 @@
+use helgobox_api::persistence::{
+    Compartment, Group, Mapping, Source, Target, VirtualControlElementCharacter,
+    VirtualControlElementId,
+};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Finding {
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Finding {
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            message: message.into(),
+        }
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let label = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{label}: {}", self.message)
+    }
+}
+
+/// A virtual control element as provided by a controller preset.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct VirtualElement {
+    pub id: VirtualControlElementId,
+    pub character: VirtualControlElementCharacter,
+}
+
+impl Display for VirtualElement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let character = match self.character {
+            VirtualControlElementCharacter::Multi => "multi",
+            VirtualControlElementCharacter::Button => "button",
+        };
+        match &self.id {
+            VirtualControlElementId::Indexed(i) => write!(f, "{character} {i}"),
+            VirtualControlElementId::Named(n) => write!(f, "{character} \"{n}\""),
+        }
+    }
+}
+
+/// Checks the given compartment.
+///
+/// - `raw`: The compartment before deserialization, for detecting ignored properties.
+/// - `controller_elements`: Virtual control elements provided by the controller presets, for
+///   checking virtual sources. `None` skips this check.
+pub fn lint_compartment(
+    compartment: &Compartment,
+    raw: Option<&Value>,
+    controller_elements: Option<&[VirtualElement]>,
+) -> Vec<Finding> {
+    let mut findings = vec![];
+    check_unknown_props(compartment, raw, &mut findings);
+    check_duplicate_ids(compartment, &mut findings);
+    check_group_references(compartment, &mut findings);
+    if let Some(elements) = controller_elements {
+        check_virtual_sources(compartment, elements, &mut findings);
+    }
+    findings
+}
+
+/// Returns all virtual control elements that the given (controller) compartment maps to.
+pub fn collect_virtual_targets(compartment: &Compartment) -> Vec<VirtualElement> {
+    mappings(compartment)
+        .filter_map(|m| match m.target.as_ref()? {
+            Target::Virtual(t) => Some(VirtualElement {
+                id: t.id.clone(),
+                character: t.character.unwrap_or_default(),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+fn check_unknown_props(
+    compartment: &Compartment,
+    raw: Option<&Value>,
+    findings: &mut Vec<Finding>,
+) {
+    // Unknown top-level properties are collected by the compartment itself
+    if let Some(props) = &compartment.unknown_props {
+        for key in props.keys() {
+            findings.push(Finding::warning(format!("unknown property `{key}`")));
+        }
+    }
+    // Everything else is detected by comparing the raw value with the deserialized one. Properties
+    // which don't survive the round trip have been ignored.
+    let Some(raw) = raw else {
+        return;
+    };
+    let Ok(round_tripped) = serde_json::to_value(compartment) else {
+        return;
+    };
+    let mut paths = vec![];
+    collect_ignored_props(raw, &round_tripped, "", &mut paths);
+    for path in paths {
+        findings.push(Finding::warning(format!(
+            "unknown or deprecated property `{path}` is ignored"
+        )));
+    }
+}
+
+fn collect_ignored_props(raw: &Value, round_tripped: &Value, path: &str, paths: &mut Vec<String>) {
+    match (raw, round_tripped) {
+        (Value::Object(raw), Value::Object(round_tripped)) => {
+            for (key, raw_value) in raw {
+                let child_path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                match round_tripped.get(key) {
+                    None if !raw_value.is_null() => paths.push(child_path),
+                    None => {}
+                    Some(v) => collect_ignored_props(raw_value, v, &child_path, paths),
+                }
+            }
+        }
+        (Value::Array(raw), Value::Array(round_tripped)) => {
+            for (i, (raw_value, v)) in raw.iter().zip(round_tripped).enumerate() {
+                collect_ignored_props(raw_value, v, &format!("{path}[{i}]"), paths);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn check_duplicate_ids(compartment: &Compartment, findings: &mut Vec<Finding>) {
+    let mapping_ids = mappings(compartment).filter_map(|m| m.id.as_deref());
+    check_duplicates("mapping", mapping_ids, findings);
+    let group_ids = groups(compartment).filter_map(|g| g.id.as_deref());
+    check_duplicates("group", group_ids, findings);
+    let param_ids = compartment
+        .parameters
+        .iter()
+        .flatten()
+        .filter_map(|p| p.id.as_deref());
+    check_duplicates("parameter", param_ids, findings);
+}
+
+fn check_duplicates<'a>(
+    label: &str,
+    ids: impl Iterator<Item = &'a str>,
+    findings: &mut Vec<Finding>,
+) {
+    let mut seen = HashSet::new();
+    let mut reported = HashSet::new();
+    for id in ids {
+        if !seen.insert(id) && reported.insert(id) {
+            findings.push(Finding::error(format!("duplicate {label} ID \"{id}\"")));
+        }
+    }
+}
+
+fn check_group_references(compartment: &Compartment, findings: &mut Vec<Finding>) {
+    let group_ids: HashSet<_> = groups(compartment)
+        .filter_map(|g| g.id.as_deref())
+        .collect();
+    for (i, m) in mappings(compartment).enumerate() {
+        let Some(group) = m.group.as_deref() else {
+            continue;
+        };
+        if !group_ids.contains(group) {
+            findings.push(Finding::error(format!(
+                "{} refers to non-existing group \"{group}\"",
+                describe_mapping(m, i)
+            )));
+        }
+    }
+}
+
+fn check_virtual_sources(
+    compartment: &Compartment,
+    controller_elements: &[VirtualElement],
+    findings: &mut Vec<Finding>,
+) {
+    for (i, m) in mappings(compartment).enumerate() {
+        let Some(Source::Virtual(s)) = &m.source else {
+            continue;
+        };
+        let element = VirtualElement {
+            id: s.id.clone(),
+            character: s.character.unwrap_or_default(),
+        };
+        if !controller_elements.contains(&element) {
+            findings.push(Finding::warning(format!(
+                "{} uses virtual control element {element}, which no controller mapping provides",
+                describe_mapping(m, i)
+            )));
+        }
+    }
+}
+
+fn mappings(compartment: &Compartment) -> impl Iterator<Item = &Mapping> {
+    compartment.mappings.iter().flatten()
+}
+
+fn groups(compartment: &Compartment) -> impl Iterator<Item = &Group> {
+    compartment.groups.iter().flatten()
+}
+
+fn describe_mapping(mapping: &Mapping, index: usize) -> String {
+    match (&mapping.id, &mapping.name) {
+        (Some(id), _) => format!("mapping \"{id}\""),
+        (None, Some(name)) => format!("mapping {} (\"{name}\")", index + 1),
+        (None, None) => format!("mapping {}", index + 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn lint_json(raw: Value, controller_elements: Option<&[VirtualElement]>) -> Vec<Finding> {
+        let compartment: Compartment = serde_json::from_value(raw.clone()).unwrap();
+        lint_compartment(&compartment, Some(&raw), controller_elements)
+    }
+
+    #[test]
+    fn valid() {
+        // Given
+        let raw = json!({
+            "groups": [{ "id": "faders" }],
+            "mappings": [
+                { "id": "m1", "group": "faders" },
+                { "id": "m2" }
+            ]
+        });
+        // When
+        let findings = lint_json(raw, None);
+        // Then
+        assert_eq!(findings, vec![]);
+    }
+
+    #[test]
+    fn unknown_props() {
+        // Given
+        let raw = json!({
+            "foo": 5,
+            "mappings": [
+                { "id": "m1", "nmae": "Typo" }
+            ]
+        });
+        // When
+        let findings = lint_json(raw, None);
+        // Then
+        assert_eq!(
+            findings,
+            vec![
+                Finding::warning("unknown property `foo`"),
+                Finding::warning("unknown or deprecated property `mappings[0].nmae` is ignored"),
+            ]
+        );
+    }
+
+    #[test]
+    fn duplicate_ids_and_dangling_groups() {
+        // Given
+        let raw = json!({
+            "mappings": [
+                { "id": "m1" },
+                { "id": "m1", "group": "nope" },
+                { "id": "m1" }
+            ]
+        });
+        // When
+        let findings = lint_json(raw, None);
+        // Then
+        assert_eq!(
+            findings,
+            vec![
+                Finding::error("duplicate mapping ID \"m1\""),
+                Finding::error("mapping \"m1\" refers to non-existing group \"nope\""),
+            ]
+        );
+    }
+
+    #[test]
+    fn unmatched_virtual_sources() {
+        // Given
+        let controller = serde_json::from_value(json!({
+            "mappings": [
+                { "target": { "kind": "Virtual", "id": "play", "character": "Button" } },
+                { "target": { "kind": "Virtual", "id": 0 } }
+            ]
+        }))
+        .unwrap();
+        let controller_elements = collect_virtual_targets(&controller);
+        let raw = json!({
+            "mappings": [
+                { "id": "play", "source": { "kind": "Virtual", "id": "play", "character": "Button" } },
+                { "id": "stop", "source": { "kind": "Virtual", "id": "stop", "character": "Button" } },
+                { "id": "fader", "source": { "kind": "Virtual", "id": 0 } }
+            ]
+        });
+        // When
+        let findings = lint_json(raw, Some(&controller_elements));
+        // Then
+        assert_eq!(
+            findings,
+            vec![Finding::warning(
+                "mapping \"stop\" uses virtual control element button \"stop\", which no controller mapping provides"
+            )]
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use anyhow::{Context, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+use helgobox_api::lua::{FsDirLuaModuleFinder, LuaModuleContainer, SafeLua};
+use helgobox_api::persistence::Compartment;
+use helgobox_api::preset_data::InternalCompartmentPresetData;
+use std::fs;
+
+pub struct LoadedPreset {
+    pub compartment: Compartment,
+    /// The preset content before deserialization, used for detecting ignored properties.
+    ///
+    /// `None` if the Lua value couldn't be represented as JSON or if the preset is in ReaLearn's
+    /// internal data format.
+    pub raw: Option<serde_json::Value>,
+}
+
+/// Loads a JSON or Lua preset.
+///
+/// Lua presets are executed in the same sandbox as within ReaLearn. Modules required via
+/// `require` are resolved relative to the given module root. If there's no module root, using
+/// `require` fails with the given message.
+pub fn load_preset(
+    file: &Utf8Path,
+    module_root: Result<&Utf8Path, &'static str>,
+) -> Result<LoadedPreset> {
+    let content = fs::read_to_string(file).with_context(|| format!("couldn't read {file}"))?;
+    if file.extension() == Some("json") {
+        load_json_preset(&content)
+    } else {
+        load_lua_preset(file, module_root, &content)
+    }
+}
+
+fn load_json_preset(content: &str) -> Result<LoadedPreset> {
+    let raw: serde_json::Value = serde_json::from_str(content).context("invalid JSON")?;
+    // ReaLearn saves JSON presets in its internal data format, which is different from the API
+    // format. We can only check its outline.
+    if raw.get("name").is_some() {
+        let data: InternalCompartmentPresetData =
+            serde_json::from_value(raw).context("invalid preset content")?;
+        let compartment = data
+            .into_compartment_outline()
+            .map_err(anyhow::Error::msg)
+            .context("invalid preset content")?;
+        let preset = LoadedPreset {
+            compartment,
+            raw: None,
+        };
+        return Ok(preset);
+    }
+    let compartment = serde_json::from_value(raw.clone()).context("invalid preset content")?;
+    let preset = LoadedPreset {
+        compartment,
+        raw: Some(raw),
+    };
+    Ok(preset)
+}
+
+fn load_lua_preset(
+    file: &Utf8Path,
+    module_root: Result<&Utf8Path, &'static str>,
+    code: &str,
+) -> Result<LoadedPreset> {
+    let lua = SafeLua::new()?;
+    lua.start_execution_time_limit_countdown();
+    let module_finder = module_root.map(|root| FsDirLuaModuleFinder::new(root.to_path_buf()));
+    let module_container = LuaModuleContainer::new(module_finder);
+    let normalized_path = module_root
+        .ok()
+        .and_then(|root| file.strip_prefix(root).ok())
+        .map(|p| p.as_str().replace('\\', "/"));
+    let value = module_container.execute_as_module(
+        lua.as_ref(),
+        normalized_path,
+        file.to_string(),
+        code,
+    )?;
+    let raw = SafeLua::from_value(value.clone()).ok();
+    let compartment = SafeLua::from_value(value).context("invalid preset content")?;
+    let preset = LoadedPreset { compartment, raw };
+    Ok(preset)
+}
+
+/// Finds all preset files in the given directory, using the same file name conventions as
+/// ReaLearn.
+pub fn find_preset_files(dir: &Utf8Path) -> Result<Vec<Utf8PathBuf>> {
+    let mut files = vec![];
+    for entry in walkdir::WalkDir::new(dir).sort_by_file_name() {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let Ok(path) = Utf8PathBuf::from_path_buf(entry.into_path()) else {
+            continue;
+        };
+        let name = path.file_name().unwrap_or_default();
+        if name.ends_with(".json") || name.ends_with(".preset.luau") {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
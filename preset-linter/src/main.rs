@@ -0,0 +1,181 @@
+use crate::lint::{collect_virtual_targets, lint_compartment, Finding, Severity, VirtualElement};
+use crate::load::{find_preset_files, load_preset};
+use anyhow::{bail, Context, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+use helgobox_api::lua::user_preset_lua_module_root;
+use std::process::ExitCode;
+
+mod lint;
+mod load;
+
+const USAGE: &str = "\
+Usage: helgobox-preset-linter [OPTIONS] <PRESET_FILE_OR_DIR>...
+
+Checks ReaLearn controller and main presets (JSON or Lua) without having to start REAPER.
+
+If a directory is given, all presets in it are checked recursively. As in ReaLearn's preset
+folder, each subfolder serves as namespace and root for resolving Lua modules via \"require\".
+
+Options:
+  --module-root <DIR>      Root directory for resolving Lua modules of all linted presets
+                           (default: the namespace subfolder for presets in a given directory,
+                           the directory containing the preset for presets given as files)
+  --controller <FILE>      Controller preset whose virtual targets should be used to check the
+                           virtual sources of the linted presets (can be given multiple times)
+  --controller-module-root <DIR>
+                           Root directory for resolving Lua modules of controller presets
+                           (default: the directory containing the controller preset)
+  --deny-warnings          Exit with non-zero code on warnings, too
+  -h, --help               Print this help";
+
+struct Args {
+    inputs: Vec<Utf8PathBuf>,
+    module_root: Option<Utf8PathBuf>,
+    controller_presets: Vec<Utf8PathBuf>,
+    controller_module_root: Option<Utf8PathBuf>,
+    deny_warnings: bool,
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("error: {e:#}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Returns `false` if the linted presets contain problems that should fail the check.
+fn run() -> Result<bool> {
+    let Some(args) = parse_args()? else {
+        println!("{USAGE}");
+        return Ok(true);
+    };
+    // Collect virtual control elements provided by the given controller presets
+    let controller_elements = if args.controller_presets.is_empty() {
+        None
+    } else {
+        let mut elements = vec![];
+        for file in &args.controller_presets {
+            let module_root = args
+                .controller_module_root
+                .as_deref()
+                .unwrap_or(parent_dir(file));
+            let preset = load_preset(file, Ok(module_root))
+                .with_context(|| format!("couldn't load controller preset {file}"))?;
+            elements.extend(collect_virtual_targets(&preset.compartment));
+        }
+        Some(elements)
+    };
+    // Lint
+    let mut error_count = 0;
+    let mut warning_count = 0;
+    for input in &args.inputs {
+        for (file, module_root) in resolve_input(input, args.module_root.as_deref())? {
+            let findings = lint_file(
+                &file,
+                module_root.as_deref().map_err(|e| *e),
+                controller_elements.as_deref(),
+            );
+            for finding in findings {
+                match finding.severity {
+                    Severity::Error => error_count += 1,
+                    Severity::Warning => warning_count += 1,
+                }
+                println!("{file}: {finding}");
+            }
+        }
+    }
+    println!("{error_count} error(s), {warning_count} warning(s)");
+    let success = error_count == 0 && (!args.deny_warnings || warning_count == 0);
+    Ok(success)
+}
+
+fn lint_file(
+    file: &Utf8Path,
+    module_root: Result<&Utf8Path, &'static str>,
+    controller_elements: Option<&[VirtualElement]>,
+) -> Vec<Finding> {
+    match load_preset(file, module_root) {
+        Ok(preset) => lint_compartment(
+            &preset.compartment,
+            preset.raw.as_ref(),
+            controller_elements,
+        ),
+        Err(e) => vec![Finding::error(format!("{e:#}"))],
+    }
+}
+
+/// Returns the preset files denoted by the given input, each one with its module root.
+///
+/// The module root is an error message if the preset is not allowed to use `require`.
+fn resolve_input(
+    input: &Utf8Path,
+    module_root: Option<&Utf8Path>,
+) -> Result<Vec<(Utf8PathBuf, Result<Utf8PathBuf, &'static str>)>> {
+    if input.is_dir() {
+        let files = find_preset_files(input)?
+            .into_iter()
+            .map(|file| {
+                let file_module_root = match module_root {
+                    Some(r) => Ok(r.to_path_buf()),
+                    None => {
+                        let relative_path = file.strip_prefix(input).unwrap_or(&file);
+                        user_preset_lua_module_root(input, relative_path)
+                    }
+                };
+                (file, file_module_root)
+            })
+            .collect();
+        Ok(files)
+    } else if input.is_file() {
+        let module_root = module_root.unwrap_or(parent_dir(input)).to_path_buf();
+        Ok(vec![(input.to_path_buf(), Ok(module_root))])
+    } else {
+        bail!("{input} doesn't exist");
+    }
+}
+
+fn parent_dir(file: &Utf8Path) -> &Utf8Path {
+    file.parent().unwrap_or(Utf8Path::new("."))
+}
+
+/// Returns `None` if help was requested.
+fn parse_args() -> Result<Option<Args>> {
+    let mut args = Args {
+        inputs: vec![],
+        module_root: None,
+        controller_presets: vec![],
+        controller_module_root: None,
+        deny_warnings: false,
+    };
+    let mut raw_args = std::env::args().skip(1);
+    while let Some(arg) = raw_args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--deny-warnings" => args.deny_warnings = true,
+            "--module-root" => {
+                let dir = raw_args.next().context("--module-root needs a directory")?;
+                args.module_root = Some(dir.into());
+            }
+            "--controller" => {
+                let file = raw_args.next().context("--controller needs a file")?;
+                args.controller_presets.push(file.into());
+            }
+            "--controller-module-root" => {
+                let dir = raw_args
+                    .next()
+                    .context("--controller-module-root needs a directory")?;
+                args.controller_module_root = Some(dir.into());
+            }
+            a if a.starts_with('-') => bail!("unknown option {a}\n\n{USAGE}"),
+            _ => args.inputs.push(arg.into()),
+        }
+    }
+    if args.inputs.is_empty() {
+        bail!("no presets given\n\n{USAGE}");
+    }
+    Ok(Some(args))
+}